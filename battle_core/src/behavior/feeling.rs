@@ -13,6 +13,22 @@ pub const UNDER_FIRE_MAX: u32 = 200;
 pub const UNDER_FIRE_DANGER: u32 = 150;
 pub const UNDER_FIRE_WARNING: u32 = 100;
 
+/// Under fire values from which soldier feel in warning and in danger (depend on soldier traits)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnderFireThresholds {
+    pub warning: u32,
+    pub danger: u32,
+}
+
+impl Default for UnderFireThresholds {
+    fn default() -> Self {
+        Self {
+            warning: UNDER_FIRE_WARNING,
+            danger: UNDER_FIRE_DANGER,
+        }
+    }
+}
+
 impl Feeling {
     pub fn blast_increase_value(distance: Distance) -> u32 {
         if distance.meters() < 5 {
@@ -52,15 +68,15 @@ impl Feeling {
         }
     }
 
-    pub fn is_warning(&self) -> bool {
+    pub fn is_warning(&self, thresholds: &UnderFireThresholds) -> bool {
         match self {
-            Feeling::UnderFire(value) => *value >= UNDER_FIRE_WARNING && *value < UNDER_FIRE_DANGER,
+            Feeling::UnderFire(value) => *value >= thresholds.warning && *value < thresholds.danger,
         }
     }
 
-    pub fn is_danger(&self) -> bool {
+    pub fn is_danger(&self, thresholds: &UnderFireThresholds) -> bool {
         match self {
-            Feeling::UnderFire(value) => *value >= UNDER_FIRE_DANGER && *value < UNDER_FIRE_MAX,
+            Feeling::UnderFire(value) => *value >= thresholds.danger && *value < UNDER_FIRE_MAX,
         }
     }
 
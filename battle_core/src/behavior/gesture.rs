@@ -13,6 +13,10 @@ pub enum Gesture {
     Reloading(u64, WeaponClass),
     Aiming(u64, WeaponClass),
     Firing(u64, WeaponClass),
+    ClearingJam(u64, WeaponClass),
+    ChangingBarrel(u64, WeaponClass),
+    SettingUp(u64, WeaponClass),
+    TearingDown(u64, WeaponClass),
     // Firing,
     // ReloadingOwn,
     // ReloadingAsAssistant,
@@ -21,7 +25,13 @@ impl Gesture {
     pub fn next(&self, frame_i: u64, next: Gesture) -> Gesture {
         match self {
             Gesture::Idle => next,
-            Gesture::Reloading(end, _) | Gesture::Aiming(end, _) | Gesture::Firing(end, _) => {
+            Gesture::Reloading(end, _)
+            | Gesture::Aiming(end, _)
+            | Gesture::Firing(end, _)
+            | Gesture::ClearingJam(end, _)
+            | Gesture::ChangingBarrel(end, _)
+            | Gesture::SettingUp(end, _)
+            | Gesture::TearingDown(end, _) => {
                 if end <= &frame_i {
                     next
                 } else {
@@ -79,11 +79,12 @@ impl Behavior {
             Order::MoveTo(path, _) => Behavior::MoveTo(path.clone()),
             Order::MoveFastTo(path, _) => Behavior::MoveFastTo(path.clone()),
             Order::SneakTo(path, _) => Behavior::SneakTo(path.clone()),
-            Order::Defend(angle) => Behavior::Defend(*angle),
+            Order::Defend(angle, _) => Behavior::Defend(*angle),
             Order::Hide(angle) => Behavior::Hide(*angle),
-            // default_behavior should never be called for EngageSquad & SuppressFire
+            // default_behavior should never be called for EngageSquad, SuppressFire & AreaFire
             Order::EngageSquad(_squad_id) => unreachable!(),
             Order::SuppressFire(_point) => unreachable!(),
+            Order::AreaFire(_point, _radius) => unreachable!(),
         }
     }
 
@@ -0,0 +1,1158 @@
+use std::{collections::HashMap, fs, io, path::Path, str::FromStr};
+
+use crate::{
+    behavior::{Behavior, Body},
+    game::explosive::ExplosiveType,
+    map::terrain::TileType,
+    types::Distance,
+};
+use rules::RuleSet;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use thiserror::Error;
+use value::{
+    server_config_values, ConfigGroup, ConfigUnit, ConfigValue, ConfigValueError, ConfigValueMeta,
+    OutOfRangeValue,
+};
+
+pub mod rules;
+pub mod value;
+
+pub const DEFAULT_SERVER_REP_ADDRESS: &str = "tcp://0.0.0.0:4255";
+pub const DEFAULT_SERVER_PUB_ADDRESS: &str = "tcp://0.0.0.0:4256";
+//
+pub const TARGET_CYCLE_DURATION_US: u64 = 16666;
+pub const TARGET_FPS: u64 = 60;
+pub const SOLDIER_UPDATE_FREQ: u64 = 1;
+pub const FLAGS_UPDATE_FREQ: u64 = 120;
+pub const SOLDIER_ANIMATE_FREQ: u64 = 20;
+pub const SQUAD_LEADERS_UPDATE_FREQ: u64 = 120;
+pub const INTERIORS_UPDATE_FREQ: u64 = 60;
+pub const VISIBILITY_UPDATE_FREQ: u64 = 60;
+pub const MORALE_UPDATE_FREQ: u64 = 300;
+pub const VICTORY_UPDATE_FREQ: u64 = 300;
+pub const FEELING_DECREASING_FREQ: u64 = 60;
+pub const PHYSICS_UPDATE_FREQ: u64 = 1;
+pub const WEAPON_COOLING_FREQ: u64 = 60;
+pub const CREW_SERVED_WEAPONS_UPDATE_FREQ: u64 = 30;
+// A side lose the battle when its morale fall under this value (0 : never)
+pub const END_MORALE: f32 = 0.2;
+// Squad is considered routed (battle event) when its able soldiers ratio fall under this value
+pub const SQUAD_ROUT_MORALE: f32 = 0.5;
+//
+pub const VISIBILITY_IDLE_STANDUP_MODIFIER: f32 = 0.5;
+pub const VISIBILITY_IDLE_CROUCH_MODIFIER: f32 = 0.5;
+pub const VISIBILITY_IDLE_LYING_MODIFIER: f32 = -0.9;
+pub const VISIBILITY_MOVE_TO_MODIFIER: f32 = 1.0;
+pub const VISIBILITY_MOVE_FAST_TO_MODIFIER: f32 = 2.0;
+pub const VISIBILITY_SNEAK_TO_MODIFIER: f32 = VISIBILITY_IDLE_LYING_MODIFIER;
+pub const VISIBILITY_DEFEND_MODIFIER: f32 = VISIBILITY_IDLE_LYING_MODIFIER;
+pub const VISIBILITY_HIDE_MODIFIER: f32 = VISIBILITY_IDLE_LYING_MODIFIER;
+pub const VISIBILITY_IN_VEHICLE_MODIFIER: f32 = 0.;
+pub const VISIBILITY_SUPPRESS_FIRE_MODIFIER: f32 = 0.5;
+pub const VISIBILITY_ENGAGE_MODIFIER: f32 = 0.5;
+pub const VISIBILITY_DEAD_MODIFIER: f32 = 0.0;
+pub const VISIBILITY_UNCONSCIOUS_MODIFIER: f32 = 0.0;
+//
+pub const TILE_TYPE_OPACITY_SHORT_GRASS: f32 = 0.0;
+pub const TILE_TYPE_OPACITY_MIDDLE_GRASS: f32 = 0.002;
+pub const TILE_TYPE_OPACITY_HIGH_GRASS: f32 = 0.1;
+pub const TILE_TYPE_OPACITY_DIRT: f32 = 0.0;
+pub const TILE_TYPE_OPACITY_CONCRETE: f32 = 0.0;
+pub const TILE_TYPE_OPACITY_MUD: f32 = 0.0;
+pub const TILE_TYPE_OPACITY_BRICK_WALL: f32 = 1.0;
+pub const TILE_TYPE_OPACITY_TRUNK: f32 = 0.2;
+pub const TILE_TYPE_OPACITY_WATER: f32 = 0.0;
+pub const TILE_TYPE_OPACITY_DEEP_WATER: f32 = 0.0;
+pub const TILE_TYPE_OPACITY_UNDERBRUSH: f32 = 0.015;
+pub const TILE_TYPE_OPACITY_LIGHT_UNDERBRUSH: f32 = 0.010;
+pub const TILE_TYPE_OPACITY_MIDDLE_WOOD_LOGS: f32 = 0.15;
+pub const TILE_TYPE_OPACITY_HEDGE: f32 = 0.25;
+pub const TILE_TYPE_OPACITY_MIDDLE_ROCK: f32 = 0.15;
+pub const TILE_TYPE_OPACITY_CRATER: f32 = 0.0;
+pub const TILE_TYPE_OPACITY_ROAD: f32 = 0.0;
+pub const TILE_TYPE_OPACITY_WHEAT_FIELD: f32 = 0.08;
+pub const TILE_TYPE_OPACITY_BARBED_WIRE: f32 = 0.0;
+pub const TILE_TYPE_OPACITY_TRENCH: f32 = 0.0;
+pub const TILE_TYPE_OPACITY_FOXHOLE: f32 = 0.0;
+//
+pub const VISIBILITY_BY_LAST_FRAME_SHOOT: u64 = TARGET_FPS * 15;
+pub const VISIBILITY_BY_LAST_FRAME_SHOOT_DISTANCE: usize = 4;
+
+// Target FPS
+// Velocity of move vector
+pub const MOVE_VELOCITY: f32 = 5.0 / TARGET_FPS as f32;
+// Velocity of move fast vector
+pub const MOVE_FAST_VELOCITY: f32 = 10.0 / TARGET_FPS as f32;
+// Velocity of move hide vector
+pub const MOVE_HIDE_VELOCITY: f32 = 1.5 / TARGET_FPS as f32;
+// Selection square size of selected soldier
+pub const DEFAULT_SELECTED_SQUARE_SIDE: f32 = 14.0;
+// Half selection square size of selected soldier
+pub const DEFAULT_SELECTED_SQUARE_SIDE_HALF: f32 = DEFAULT_SELECTED_SQUARE_SIDE / 2.0;
+// Selection square size of selectable zone (click)
+pub const SOLDIER_SELECTABLE_SQUARE_SIDE: f32 = 14.0;
+// Half selection square size of selectable zone (click)
+pub const SOLDIER_SELECTABLE_SQUARE_SIDE_HALF: f32 = SOLDIER_SELECTABLE_SQUARE_SIDE / 2.0;
+// Frames to wait to draw the pending order path finding
+pub const PENDING_ORDER_PATH_FINDING_DRAW_FRAMES: u64 = TARGET_FPS / 3;
+
+pub const VEHICLE_DRIVE_ORIENTATION_TARGET_TOLERANCE_COEFFICIENT: f32 = 100.;
+pub const VEHICLE_DRIVE_ORIENTATION_ADVANCE_TOLERANCE_COEFFICIENT: f32 = 100.;
+pub const VEHICLE_DRIVE_ORIENTATION_ADVANCE_TOLERANCE_DIFF: f32 = 50.;
+
+// Grid distance to search cover point
+pub const COVER_DISTANCE: i32 = 6;
+
+// Default space (pixels) between two squad members in formation
+pub const FORMATION_SPACING: f32 = 10.;
+pub const FORMATION_SPACING_MIN_FACTOR: f32 = 0.5;
+pub const FORMATION_SPACING_MAX_FACTOR: f32 = 1.5;
+// Grid distance around formation point considered to scale formation spacing
+pub const FORMATION_TERRAIN_DISTANCE: i32 = 4;
+
+// Visibility computing must consider firsts tiles differently
+pub const VISIBILITY_FIRSTS: usize = 6;
+pub const VISIBLE_STARTS_AT: f32 = 0.5;
+pub const TARGET_ALTERATION_BY_OPACITY_FACTOR: f32 = 8.;
+pub const INACCURATE_FIRE_FACTOR_BY_METER: f32 = 0.075;
+// When compute visibility, configure here each pixels step of line which me considered
+pub const VISIBILITY_PIXEL_STEPS: usize = 5;
+// When compute coverage, configure here each pixels step of line which me considered
+pub const COVERAGE_PIXEL_STEPS: usize = 5;
+// When compute coverage, configure here how many tile to consider starting from soldier
+pub const COVERAGE_TILE_STEPS: usize = 3;
+// How many meters maximum soldier hide before shoot
+pub const HIDE_MAXIMUM_RAYON: i64 = 50;
+// How many frames after last proximity shoot needed before soldier go from lying to crouch when idle
+pub const CAN_CROUCH_AFTER: u64 = TARGET_FPS * 60 * 5;
+// How many frames after last proximity shoot needed before soldier go from crouch to standup when idle
+pub const CAN_STANDUP_AFTER: u64 = TARGET_FPS * 60 * 10;
+
+// Adjust distance (computed by pixels) according to tile cost
+pub const PATH_FINDING_HEURISTIC_COEFFICIENT: f32 = 10.;
+// Pedestrian path finding cost added by each climbed meter
+pub const UPHILL_COST_BY_METER: f32 = 4.;
+// Eyes height (meters) of soldier when compute line of sight over elevation
+pub const SIGHT_HEIGHT: f32 = 1.5;
+// Fire inaccuracy reduction factor by meter of height advantage
+pub const ELEVATION_ACCURACY_BONUS_BY_METER: f32 = 0.05;
+// Maximum fire inaccuracy reduction factor given by height advantage
+pub const ELEVATION_ACCURACY_BONUS_MAX: f32 = 0.4;
+// Coverage chance by meter of height advantage against a shooter below
+pub const ELEVATION_COVERAGE_BY_METER: f32 = 0.04;
+// Maximum coverage chance given by height advantage
+pub const ELEVATION_COVERAGE_MAX: f32 = 0.3;
+// Opacity given to a target hidden behind ground elevation
+pub const ELEVATION_HIDDEN_OPACITY: f32 = 999.;
+// Height (meters) of a building floor
+pub const FLOOR_HEIGHT: f32 = 3.;
+// Terrain opacity factor applied for each floor above ground of looking soldier
+pub const UPPER_FLOOR_OPACITY_FACTOR: f32 = 0.5;
+// Coverage chance of a soldier behind a window or on building upper floor
+pub const WINDOW_COVERAGE: f32 = 0.75;
+// How many meters maximum between squad leader and soldier to consider soldier in command
+pub const COMMAND_RADIUS: i64 = 40;
+// How many frames needed by soldier outside command radius to react to a new order
+pub const OUT_OF_COMMAND_ORDER_DELAY: u64 = TARGET_FPS * 3;
+// How many frames needed by a new squad leader (previous one dead) before his orders are executed
+pub const LEADER_REPLACEMENT_ORDER_DELAY: u64 = TARGET_FPS * 10;
+// How many frames a suppress fire order last before squad continue with its queued orders
+pub const QUEUED_FIRE_DURATION: u64 = TARGET_FPS * 15;
+// How many meters maximum between an ambush squad and a visible enemy to open fire
+pub const AMBUSH_DISTANCE: i64 = 40;
+// How many frames after being under fire a squad in return fire discipline can fire
+pub const RETURN_FIRE_DURATION: u64 = TARGET_FPS * 20;
+// Radius (meters) of zone suppressed by an area fire order
+pub const AREA_FIRE_RADIUS: i64 = 10;
+// How many frames soldier fire on the same point of area fire zone before choosing another one
+pub const AREA_FIRE_SHIFT_DURATION: u64 = TARGET_FPS * 4;
+// Minimum range (meters) of a defend order fire arc (cursor too near of squad)
+pub const FIRE_ARC_MINIMUM_RANGE: i64 = 20;
+// Squad members ordered to move follow a flow field shared by soldiers going to the same place
+pub const FLOW_FIELD_MOVEMENT: bool = false;
+// Distance (meters) under which moving soldiers steer away from each other (flow field movement)
+pub const FLOW_FIELD_STEERING_RADIUS: i64 = 2;
+// Weight of steering away from near moving soldiers against following path (flow field movement)
+pub const FLOW_FIELD_STEERING_FACTOR: f32 = 0.5;
+// Factor applied to weapons aiming duration
+pub const WEAPON_AIMING_FRAMES_FACTOR: f32 = 1.;
+// Factor applied to weapons reloading duration
+pub const WEAPON_RELOADING_FRAMES_FACTOR: f32 = 1.;
+// Factor applied to weapons firing duration
+pub const WEAPON_FIRING_FRAMES_FACTOR: f32 = 1.;
+// Factor applied to dispersion of bullets following the first one of a burst
+pub const WEAPON_BURST_DISPERSION_FACTOR: f32 = 1.;
+// Squad member under this distance (meters) of a crew-served weapon gunner assist him
+pub const ASSISTANT_GUNNER_DISTANCE: i64 = 3;
+// Factor applied to crew-served weapons reloading duration when gunner is assisted
+pub const ASSISTANT_RELOADING_FACTOR: f32 = 0.5;
+// Factor applied to crew-served weapons firing duration (pause between bursts) without assistant
+pub const UNASSISTED_FIRING_FRAMES_FACTOR: f32 = 2.;
+// Squad member under this distance (meters) of a fallen gunner go to take over its crew-served
+// weapon
+pub const WEAPON_TAKE_OVER_DISTANCE: i64 = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    #[serde(skip)]
+    pub send_debug_points: bool,
+    pub flow_field_movement: bool,
+    pub target_cycle_duration_us: u64,
+    pub flags_update_freq: u64,
+    pub soldier_update_freq: u64,
+    pub soldier_animate_freq: u64,
+    pub squad_leaders_update_freq: u64,
+    pub interiors_update_freq: u64,
+    pub visibility_update_freq: u64,
+    pub morale_update_freq: u64,
+    pub victory_update_freq: u64,
+    pub physics_update_freq: u64,
+    pub feeling_decreasing_freq: u64,
+    pub weapon_cooling_freq: u64,
+    pub crew_served_weapons_update_freq: u64,
+    pub visibility_firsts: usize,
+    pub visible_starts_at: f32,
+    pub target_alteration_by_opacity_factor: f32,
+    pub inaccurate_fire_factor_by_meter: f32,
+    pub visibility_idle_standup_modifier: f32,
+    pub visibility_idle_crouch_modifier: f32,
+    pub visibility_idle_lying_modifier: f32,
+    pub visibility_move_to_modifier: f32,
+    pub visibility_move_fast_to_modifier: f32,
+    pub visibility_sneak_to_modifier: f32,
+    pub visibility_defend_modifier: f32,
+    pub visibility_hide_modifier: f32,
+    pub visibility_in_vehicle_modifier: f32,
+    pub visibility_suppress_fire_modifier: f32,
+    pub visibility_engage_modifier: f32,
+    pub visibility_dead_modifier: f32,
+    pub visibility_unconscious_modifier: f32,
+    pub tile_type_opacity_short_grass: f32,
+    pub tile_type_opacity_middle_grass: f32,
+    pub tile_type_opacity_high_grass: f32,
+    pub tile_type_opacity_dirt: f32,
+    pub tile_type_opacity_concrete: f32,
+    pub tile_type_opacity_mud: f32,
+    pub tile_type_opacity_brick_wall: f32,
+    pub tile_type_opacity_trunk: f32,
+    pub tile_type_opacity_water: f32,
+    pub tile_type_opacity_deep_water: f32,
+    pub tile_type_opacity_underbrush: f32,
+    pub tile_type_opacity_light_underbrush: f32,
+    pub tile_type_opacity_middle_wood_logs: f32,
+    pub tile_type_opacity_hedge: f32,
+    pub tile_type_opacity_middle_rock: f32,
+    pub tile_type_opacity_crater: f32,
+    pub tile_type_opacity_road: f32,
+    pub tile_type_opacity_wheat_field: f32,
+    pub tile_type_opacity_barbed_wire: f32,
+    pub tile_type_opacity_trench: f32,
+    pub tile_type_opacity_foxhole: f32,
+    pub visibility_by_last_frame_shoot: u64,
+    pub visibility_by_last_frame_shoot_distance: usize,
+    #[serde(with = "explosive_distances")]
+    pub explosive_direct_death_rayon: HashMap<ExplosiveType, Distance>,
+    #[serde(with = "explosive_distances")]
+    pub explosive_regressive_death_rayon: HashMap<ExplosiveType, Distance>,
+    #[serde(with = "explosive_distances")]
+    pub explosive_regressive_injured_rayon: HashMap<ExplosiveType, Distance>,
+    pub hide_maximum_rayon: Distance,
+    pub path_finding_heuristic_coefficient: f32,
+    pub command_radius: Distance,
+    pub out_of_command_order_delay: u64,
+    pub leader_replacement_order_delay: u64,
+    pub queued_fire_duration: u64,
+    pub ambush_distance: Distance,
+    pub return_fire_duration: u64,
+    pub area_fire_radius: Distance,
+    pub area_fire_shift_duration: u64,
+    pub fire_arc_minimum_range: Distance,
+    pub sight_height: f32,
+    pub elevation_hidden_opacity: f32,
+    pub elevation_accuracy_bonus_by_meter: f32,
+    pub elevation_accuracy_bonus_max: f32,
+    pub elevation_coverage_by_meter: f32,
+    pub elevation_coverage_max: f32,
+    pub flow_field_steering_radius: Distance,
+    pub flow_field_steering_factor: f32,
+    #[serde(skip)]
+    pub rule_set: RuleSet,
+    // Rule set given on command line : deployment one is ignored
+    #[serde(skip)]
+    pub rule_set_locked: bool,
+    // Values replaced by current rule set, as they were before it was applied
+    #[serde(skip)]
+    pub rule_set_replaced: Vec<(&'static str, ConfigValue)>,
+    pub end_morale: f32,
+    pub squad_rout_morale: f32,
+    pub weapon_aiming_frames_factor: f32,
+    pub weapon_reloading_frames_factor: f32,
+    pub weapon_firing_frames_factor: f32,
+    pub weapon_burst_dispersion_factor: f32,
+    pub assistant_gunner_distance: Distance,
+    pub assistant_reloading_factor: f32,
+    pub unassisted_firing_frames_factor: f32,
+    pub weapon_take_over_distance: Distance,
+}
+
+// Adding a tunable only requires a `ServerConfig` field and its line here : debug gui
+// widgets, live change and file validation rely on it
+#[rustfmt::skip]
+server_config_values! {
+    (target_cycle_duration_us, U64, Server, Microseconds, 1, TARGET_CYCLE_DURATION_US * 5),
+    (flags_update_freq, U64, Server, Frames, 1, 600),
+    (soldier_update_freq, U64, Server, Frames, 1, 600),
+    (soldier_animate_freq, U64, Server, Frames, 1, 600),
+    (squad_leaders_update_freq, U64, Server, Frames, 1, 600),
+    (interiors_update_freq, U64, Server, Frames, 1, 600),
+    (visibility_update_freq, U64, Server, Frames, 1, 600),
+    (morale_update_freq, U64, Server, Frames, 1, 600),
+    (victory_update_freq, U64, Server, Frames, 1, 600),
+    (physics_update_freq, U64, Server, Frames, 1, 600),
+    (feeling_decreasing_freq, U64, Server, Frames, 1, 600),
+    (weapon_cooling_freq, U64, Server, Frames, 1, 600),
+    (crew_served_weapons_update_freq, U64, Server, Frames, 1, 600),
+    (path_finding_heuristic_coefficient, F32, Server, Unitless, 0, 100),
+    (flow_field_movement, Bool, Server, Unitless, 0, 1),
+    (flow_field_steering_radius, Distance, Server, Meters, 0, 10),
+    (flow_field_steering_factor, F32, Server, Unitless, 0, 1),
+    (visibility_firsts, Usize, Visibility, Tiles, 0, 10),
+    (visible_starts_at, F32, Visibility, Unitless, 0, 1),
+    (visibility_idle_standup_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_idle_crouch_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_idle_lying_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_move_to_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_move_fast_to_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_sneak_to_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_defend_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_hide_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_in_vehicle_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_suppress_fire_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_engage_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_dead_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_unconscious_modifier, F32, Visibility, Unitless, -10, 10),
+    (visibility_by_last_frame_shoot, U64, Visibility, Frames, 0, 3600),
+    (visibility_by_last_frame_shoot_distance, Usize, Visibility, Tiles, 0, 30),
+    (tile_type_opacity_short_grass, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_middle_grass, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_high_grass, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_dirt, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_concrete, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_mud, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_brick_wall, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_trunk, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_water, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_deep_water, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_underbrush, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_light_underbrush, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_middle_wood_logs, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_hedge, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_middle_rock, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_crater, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_road, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_wheat_field, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_barbed_wire, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_trench, F32, Opacity, Unitless, 0, 1),
+    (tile_type_opacity_foxhole, F32, Opacity, Unitless, 0, 1),
+    (target_alteration_by_opacity_factor, F32, Fight, Unitless, 0, 50),
+    (inaccurate_fire_factor_by_meter, F32, Fight, Unitless, 0, 1),
+    (hide_maximum_rayon, Distance, Fight, Meters, 0, 200),
+    (command_radius, Distance, Fight, Meters, 0, 200),
+    (out_of_command_order_delay, U64, Fight, Frames, 0, 3600),
+    (leader_replacement_order_delay, U64, Fight, Frames, 0, 3600),
+    (ambush_distance, Distance, Fight, Meters, 0, 200),
+    (return_fire_duration, U64, Fight, Frames, 0, 3600),
+    (area_fire_radius, Distance, Fight, Meters, 0, 200),
+    (area_fire_shift_duration, U64, Fight, Frames, 0, 3600),
+    (queued_fire_duration, U64, Fight, Frames, 0, 3600),
+    (fire_arc_minimum_range, Distance, Fight, Meters, 0, 200),
+    (sight_height, F32, Visibility, Meters, 0, 10),
+    (elevation_hidden_opacity, F32, Visibility, Unitless, 0, 999),
+    (elevation_accuracy_bonus_by_meter, F32, Fight, Unitless, 0, 1),
+    (elevation_accuracy_bonus_max, F32, Fight, Unitless, 0, 1),
+    (elevation_coverage_by_meter, F32, Fight, Unitless, 0, 1),
+    (elevation_coverage_max, F32, Fight, Unitless, 0, 1),
+    (end_morale, F32, Rules, Unitless, 0, 1),
+    (squad_rout_morale, F32, Fight, Unitless, 0, 1),
+    (weapon_aiming_frames_factor, F32, Rules, Unitless, 0, 5),
+    (weapon_reloading_frames_factor, F32, Rules, Unitless, 0, 5),
+    (weapon_firing_frames_factor, F32, Rules, Unitless, 0, 5),
+    (weapon_burst_dispersion_factor, F32, Rules, Unitless, 0, 5),
+    (assistant_gunner_distance, Distance, Fight, Meters, 0, 20),
+    (assistant_reloading_factor, F32, Fight, Unitless, 0, 1),
+    (unassisted_firing_frames_factor, F32, Fight, Unitless, 1, 5),
+    (weapon_take_over_distance, Distance, Fight, Meters, 0, 50),
+}
+
+/// Explosive distances values. Their keys are suffixed by explosive name, like
+/// `explosive_direct_death_rayon.FA19241927` (see `explosive_distance_key`).
+pub const EXPLOSIVE_DISTANCES_VALUES: &[ConfigValueMeta] = &[
+    ConfigValueMeta {
+        key: "explosive_direct_death_rayon",
+        group: ConfigGroup::Fight,
+        unit: ConfigUnit::Meters,
+        min: 0.,
+        max: 100.,
+    },
+    ConfigValueMeta {
+        key: "explosive_regressive_death_rayon",
+        group: ConfigGroup::Fight,
+        unit: ConfigUnit::Meters,
+        min: 0.,
+        max: 100.,
+    },
+    ConfigValueMeta {
+        key: "explosive_regressive_injured_rayon",
+        group: ConfigGroup::Fight,
+        unit: ConfigUnit::Meters,
+        min: 0.,
+        max: 100.,
+    },
+];
+
+/// Key of given explosive distance value (`name` being one of `EXPLOSIVE_DISTANCES_VALUES`)
+pub fn explosive_distance_key(name: &str, explosive: &ExplosiveType) -> String {
+    format!("{}.{}", name, explosive)
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        let mut explosive_direct_death_rayon = HashMap::new();
+        let mut explosive_regressive_death_rayon = HashMap::new();
+        let mut explosive_regressive_injured_rayon = HashMap::new();
+
+        for explosive in ExplosiveType::iter() {
+            explosive_direct_death_rayon.insert(explosive.clone(), explosive.direct_death_rayon());
+            explosive_regressive_death_rayon
+                .insert(explosive.clone(), explosive.regressive_death_rayon());
+            explosive_regressive_injured_rayon
+                .insert(explosive.clone(), explosive.regressive_injured_rayon());
+        }
+
+        Self {
+            send_debug_points: false,
+            flow_field_movement: FLOW_FIELD_MOVEMENT,
+            target_cycle_duration_us: TARGET_CYCLE_DURATION_US,
+            // Frequency of flags update
+            flags_update_freq: FLAGS_UPDATE_FREQ,
+            // Frequency of soldier update :
+            //  - World pixel point according to movement
+            //  - ...
+            soldier_update_freq: SOLDIER_UPDATE_FREQ,
+            // Frequency of soldier animation :
+            //  - Compute visibility with other soldiers
+            //  - Compute behavior against physics (explosions, gunfires, ...)
+            soldier_animate_freq: SOLDIER_ANIMATE_FREQ,
+            //
+            squad_leaders_update_freq: SQUAD_LEADERS_UPDATE_FREQ,
+            // Frequency of update buildings interiors visibility
+            interiors_update_freq: INTERIORS_UPDATE_FREQ,
+            // Frequency of update visibility between soldiers
+            visibility_update_freq: VISIBILITY_UPDATE_FREQ,
+            //
+            morale_update_freq: MORALE_UPDATE_FREQ,
+            //
+            victory_update_freq: VICTORY_UPDATE_FREQ,
+            //
+            physics_update_freq: PHYSICS_UPDATE_FREQ,
+            // Frequency of decreasing feelings
+            feeling_decreasing_freq: FEELING_DECREASING_FREQ,
+            // Frequency of weapons barrel cooling
+            weapon_cooling_freq: WEAPON_COOLING_FREQ,
+            // Frequency of crew-served weapons assistants designation and take over
+            crew_served_weapons_update_freq: CREW_SERVED_WEAPONS_UPDATE_FREQ,
+            //
+            visibility_firsts: VISIBILITY_FIRSTS,
+            visible_starts_at: VISIBLE_STARTS_AT,
+            target_alteration_by_opacity_factor: TARGET_ALTERATION_BY_OPACITY_FACTOR,
+            inaccurate_fire_factor_by_meter: INACCURATE_FIRE_FACTOR_BY_METER,
+
+            visibility_idle_standup_modifier: VISIBILITY_IDLE_STANDUP_MODIFIER,
+            visibility_idle_crouch_modifier: VISIBILITY_IDLE_CROUCH_MODIFIER,
+            visibility_idle_lying_modifier: VISIBILITY_IDLE_LYING_MODIFIER,
+            visibility_move_to_modifier: VISIBILITY_MOVE_TO_MODIFIER,
+            visibility_move_fast_to_modifier: VISIBILITY_MOVE_FAST_TO_MODIFIER,
+            visibility_sneak_to_modifier: VISIBILITY_SNEAK_TO_MODIFIER,
+            visibility_defend_modifier: VISIBILITY_DEFEND_MODIFIER,
+            visibility_hide_modifier: VISIBILITY_HIDE_MODIFIER,
+            visibility_in_vehicle_modifier: VISIBILITY_IN_VEHICLE_MODIFIER,
+            visibility_suppress_fire_modifier: VISIBILITY_SUPPRESS_FIRE_MODIFIER,
+            visibility_engage_modifier: VISIBILITY_ENGAGE_MODIFIER,
+            visibility_dead_modifier: VISIBILITY_DEAD_MODIFIER,
+            visibility_unconscious_modifier: VISIBILITY_UNCONSCIOUS_MODIFIER,
+            visibility_by_last_frame_shoot: VISIBILITY_BY_LAST_FRAME_SHOOT,
+            visibility_by_last_frame_shoot_distance: VISIBILITY_BY_LAST_FRAME_SHOOT_DISTANCE,
+
+            tile_type_opacity_short_grass: TILE_TYPE_OPACITY_SHORT_GRASS,
+            tile_type_opacity_middle_grass: TILE_TYPE_OPACITY_MIDDLE_GRASS,
+            tile_type_opacity_high_grass: TILE_TYPE_OPACITY_HIGH_GRASS,
+            tile_type_opacity_dirt: TILE_TYPE_OPACITY_DIRT,
+            tile_type_opacity_concrete: TILE_TYPE_OPACITY_CONCRETE,
+            tile_type_opacity_mud: TILE_TYPE_OPACITY_MUD,
+            tile_type_opacity_brick_wall: TILE_TYPE_OPACITY_BRICK_WALL,
+            tile_type_opacity_trunk: TILE_TYPE_OPACITY_TRUNK,
+            tile_type_opacity_water: TILE_TYPE_OPACITY_WATER,
+            tile_type_opacity_deep_water: TILE_TYPE_OPACITY_DEEP_WATER,
+            tile_type_opacity_underbrush: TILE_TYPE_OPACITY_UNDERBRUSH,
+            tile_type_opacity_light_underbrush: TILE_TYPE_OPACITY_LIGHT_UNDERBRUSH,
+            tile_type_opacity_middle_wood_logs: TILE_TYPE_OPACITY_MIDDLE_WOOD_LOGS,
+            tile_type_opacity_hedge: TILE_TYPE_OPACITY_HEDGE,
+            tile_type_opacity_middle_rock: TILE_TYPE_OPACITY_MIDDLE_ROCK,
+            tile_type_opacity_crater: TILE_TYPE_OPACITY_CRATER,
+            tile_type_opacity_road: TILE_TYPE_OPACITY_ROAD,
+            tile_type_opacity_wheat_field: TILE_TYPE_OPACITY_WHEAT_FIELD,
+            tile_type_opacity_barbed_wire: TILE_TYPE_OPACITY_BARBED_WIRE,
+            tile_type_opacity_trench: TILE_TYPE_OPACITY_TRENCH,
+            tile_type_opacity_foxhole: TILE_TYPE_OPACITY_FOXHOLE,
+
+            explosive_direct_death_rayon,
+            explosive_regressive_death_rayon,
+            explosive_regressive_injured_rayon,
+
+            hide_maximum_rayon: Distance::from_meters(HIDE_MAXIMUM_RAYON),
+            path_finding_heuristic_coefficient: PATH_FINDING_HEURISTIC_COEFFICIENT,
+
+            command_radius: Distance::from_meters(COMMAND_RADIUS),
+            out_of_command_order_delay: OUT_OF_COMMAND_ORDER_DELAY,
+            leader_replacement_order_delay: LEADER_REPLACEMENT_ORDER_DELAY,
+            queued_fire_duration: QUEUED_FIRE_DURATION,
+
+            ambush_distance: Distance::from_meters(AMBUSH_DISTANCE),
+            return_fire_duration: RETURN_FIRE_DURATION,
+            area_fire_radius: Distance::from_meters(AREA_FIRE_RADIUS),
+            area_fire_shift_duration: AREA_FIRE_SHIFT_DURATION,
+            fire_arc_minimum_range: Distance::from_meters(FIRE_ARC_MINIMUM_RANGE),
+            sight_height: SIGHT_HEIGHT,
+            elevation_hidden_opacity: ELEVATION_HIDDEN_OPACITY,
+            elevation_accuracy_bonus_by_meter: ELEVATION_ACCURACY_BONUS_BY_METER,
+            elevation_accuracy_bonus_max: ELEVATION_ACCURACY_BONUS_MAX,
+            elevation_coverage_by_meter: ELEVATION_COVERAGE_BY_METER,
+            elevation_coverage_max: ELEVATION_COVERAGE_MAX,
+            flow_field_steering_radius: Distance::from_meters(FLOW_FIELD_STEERING_RADIUS),
+            flow_field_steering_factor: FLOW_FIELD_STEERING_FACTOR,
+            rule_set: RuleSet::default(),
+            rule_set_locked: false,
+            rule_set_replaced: vec![],
+            end_morale: END_MORALE,
+            squad_rout_morale: SQUAD_ROUT_MORALE,
+            weapon_aiming_frames_factor: WEAPON_AIMING_FRAMES_FACTOR,
+            weapon_reloading_frames_factor: WEAPON_RELOADING_FRAMES_FACTOR,
+            weapon_firing_frames_factor: WEAPON_FIRING_FRAMES_FACTOR,
+            weapon_burst_dispersion_factor: WEAPON_BURST_DISPERSION_FACTOR,
+            assistant_gunner_distance: Distance::from_meters(ASSISTANT_GUNNER_DISTANCE),
+            assistant_reloading_factor: ASSISTANT_RELOADING_FACTOR,
+            unassisted_firing_frames_factor: UNASSISTED_FIRING_FRAMES_FACTOR,
+            weapon_take_over_distance: Distance::from_meters(WEAPON_TAKE_OVER_DISTANCE),
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Read config from a TOML or JSON file (according to file extension). Values missing
+    /// from file keep their default value.
+    pub fn from_file(path: &Path) -> Result<Self, ServerConfigError> {
+        let content = fs::read_to_string(path)?;
+        let mut config: Self = match ServerConfigFormat::from_path(path)? {
+            ServerConfigFormat::Toml => toml::from_str(&content)?,
+            ServerConfigFormat::Json => serde_json::from_str(&content)?,
+        };
+        config.fill_missing_explosives();
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /// Write config into a TOML or JSON file (according to file extension)
+    pub fn write(&self, path: &Path) -> Result<(), ServerConfigError> {
+        let content = match ServerConfigFormat::from_path(path)? {
+            // Toml value is used to write tables after simple values
+            ServerConfigFormat::Toml => toml::to_string_pretty(&toml::Value::try_from(self)?)?,
+            ServerConfigFormat::Json => serde_json::to_string_pretty(self)?,
+        };
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+
+    fn fill_missing_explosives(&mut self) {
+        for explosive in ExplosiveType::iter() {
+            self.explosive_direct_death_rayon
+                .entry(explosive.clone())
+                .or_insert(explosive.direct_death_rayon());
+            self.explosive_regressive_death_rayon
+                .entry(explosive.clone())
+                .or_insert(explosive.regressive_death_rayon());
+            self.explosive_regressive_injured_rayon
+                .entry(explosive.clone())
+                .or_insert(explosive.regressive_injured_rayon());
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ServerConfigError> {
+        let errors = self.out_of_range_values();
+        if !errors.is_empty() {
+            return Err(ServerConfigError::OutOfRange(errors));
+        }
+
+        Ok(())
+    }
+
+    /// Values which would break or make no sense in the simulation
+    pub fn out_of_range_values(&self) -> Vec<OutOfRangeValue> {
+        let mut errors = vec![];
+
+        for meta in SERVER_CONFIG_VALUES {
+            if let Some(value) = self.value(meta.key) {
+                if let Err(error) = meta.check(&value) {
+                    errors.push(error);
+                }
+            }
+        }
+
+        for meta in EXPLOSIVE_DISTANCES_VALUES {
+            for (explosive, distance) in self.explosive_distances(meta.key) {
+                if let Err(error) = meta.check(&ConfigValue::Distance(*distance)) {
+                    errors.push(OutOfRangeValue {
+                        name: explosive_distance_key(meta.key, explosive),
+                        ..error
+                    });
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Change value of given key, refused if unknown key, wrong type or out of range value
+    pub fn set_value(&mut self, key: &str, value: ConfigValue) -> Result<(), ConfigValueError> {
+        let meta = SERVER_CONFIG_VALUES
+            .iter()
+            .find(|meta| meta.key == key)
+            .or_else(|| Self::explosive_distance_meta(key).map(|(meta, _)| meta))
+            .ok_or(ConfigValueError::UnknownKey(key.to_string()))?;
+        meta.check(&value).map_err(|error| {
+            ConfigValueError::OutOfRange(OutOfRangeValue {
+                name: key.to_string(),
+                ..error
+            })
+        })?;
+
+        self.set_value_unchecked(key, value)
+    }
+
+    /// Metadata and explosive of given explosive distance key
+    fn explosive_distance_meta(key: &str) -> Option<(&'static ConfigValueMeta, ExplosiveType)> {
+        let (name, explosive) = key.split_once('.')?;
+        let meta = EXPLOSIVE_DISTANCES_VALUES
+            .iter()
+            .find(|meta| meta.key == name)?;
+        Some((meta, ExplosiveType::from_str(explosive).ok()?))
+    }
+
+    fn explosive_distances(&self, name: &str) -> &HashMap<ExplosiveType, Distance> {
+        match name {
+            "explosive_direct_death_rayon" => &self.explosive_direct_death_rayon,
+            "explosive_regressive_death_rayon" => &self.explosive_regressive_death_rayon,
+            "explosive_regressive_injured_rayon" => &self.explosive_regressive_injured_rayon,
+            _ => unreachable!("Explosive distances name must be one of EXPLOSIVE_DISTANCES_VALUES"),
+        }
+    }
+
+    fn explosive_distances_mut(&mut self, name: &str) -> &mut HashMap<ExplosiveType, Distance> {
+        match name {
+            "explosive_direct_death_rayon" => &mut self.explosive_direct_death_rayon,
+            "explosive_regressive_death_rayon" => &mut self.explosive_regressive_death_rayon,
+            "explosive_regressive_injured_rayon" => &mut self.explosive_regressive_injured_rayon,
+            _ => unreachable!("Explosive distances name must be one of EXPLOSIVE_DISTANCES_VALUES"),
+        }
+    }
+
+    fn explosive_distance(&self, key: &str) -> Option<Distance> {
+        let (meta, explosive) = Self::explosive_distance_meta(key)?;
+        self.explosive_distances(meta.key).get(&explosive).copied()
+    }
+
+    /// Change explosive distance of given key, false if key is not an explosive distance one
+    fn set_explosive_distance(&mut self, key: &str, distance: Distance) -> bool {
+        match Self::explosive_distance_meta(key) {
+            Some((meta, explosive)) => {
+                self.explosive_distances_mut(meta.key)
+                    .insert(explosive, distance);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replace current rule set overrides (values are restored to the ones they had before it,
+    /// like values loaded from config file) by the given rule set ones
+    pub fn apply_rule_set(&mut self, rule_set: RuleSet) {
+        for (key, value) in std::mem::take(&mut self.rule_set_replaced) {
+            self.set_value_unchecked(key, value)
+                .expect("Rule set keys must be valid config keys");
+        }
+
+        for (key, value) in rule_set.overrides() {
+            let previous = self.value(key);
+            match (self.set_value(key, value), previous) {
+                (Ok(_), Some(previous)) => self.rule_set_replaced.push((key, previous)),
+                (Ok(_), None) => {}
+                (Err(error), _) => {
+                    eprintln!("Rule set {} value ignored : {}", rule_set, error)
+                }
+            }
+        }
+        self.rule_set = rule_set;
+    }
+
+    pub fn soldier_update_freq(&self) -> u64 {
+        self.soldier_update_freq
+    }
+
+    pub fn flags_update_freq(&self) -> u64 {
+        self.flags_update_freq
+    }
+
+    pub fn soldier_animate_freq(&self) -> u64 {
+        self.soldier_animate_freq
+    }
+
+    pub fn squad_leaders_update_freq(&self) -> u64 {
+        self.squad_leaders_update_freq
+    }
+
+    pub fn interiors_update_freq(&self) -> u64 {
+        self.interiors_update_freq
+    }
+
+    pub fn visibility_update_freq(&self) -> u64 {
+        self.visibility_update_freq
+    }
+
+    pub fn morale_update_freq(&self) -> u64 {
+        self.morale_update_freq
+    }
+
+    pub fn victory_update_freq(&self) -> u64 {
+        self.victory_update_freq
+    }
+
+    pub fn physics_update_freq(&self) -> u64 {
+        self.physics_update_freq
+    }
+
+    pub fn feeling_decreasing_freq(&self) -> u64 {
+        self.feeling_decreasing_freq
+    }
+
+    pub fn weapon_cooling_freq(&self) -> u64 {
+        self.weapon_cooling_freq
+    }
+
+    pub fn crew_served_weapons_update_freq(&self) -> u64 {
+        self.crew_served_weapons_update_freq
+    }
+
+    pub fn visibility_behavior_modifier(&self, behavior: &Behavior) -> f32 {
+        match behavior {
+            Behavior::Idle(Body::StandUp) => self.visibility_idle_standup_modifier,
+            Behavior::Idle(Body::Crouched) => self.visibility_idle_standup_modifier,
+            Behavior::Idle(Body::Lying) => self.visibility_idle_standup_modifier,
+            Behavior::Hide(_) => self.visibility_hide_modifier,
+            Behavior::Defend(_) => self.visibility_defend_modifier,
+            Behavior::MoveTo(_) => self.visibility_move_to_modifier,
+            Behavior::MoveFastTo(_) => self.visibility_move_fast_to_modifier,
+            Behavior::SneakTo(_) => self.visibility_sneak_to_modifier,
+            Behavior::DriveTo(_) => self.visibility_in_vehicle_modifier,
+            Behavior::RotateTo(_) => self.visibility_in_vehicle_modifier,
+            Behavior::SuppressFire(_) => self.visibility_suppress_fire_modifier,
+            Behavior::EngageSoldier(_) => self.visibility_engage_modifier,
+            Behavior::Dead => self.visibility_dead_modifier,
+            Behavior::Unconscious => self.visibility_unconscious_modifier,
+        }
+    }
+
+    pub fn behavior_velocity(&self, behavior: &Behavior) -> Option<f32> {
+        match behavior {
+            Behavior::Idle(_) => None,
+            Behavior::MoveTo(_) => Some(MOVE_VELOCITY),
+            Behavior::MoveFastTo(_) => Some(MOVE_FAST_VELOCITY),
+            Behavior::SneakTo(_) => Some(MOVE_HIDE_VELOCITY),
+            Behavior::Defend(_) => None,
+            Behavior::Hide(_) => None,
+            Behavior::DriveTo(_) => None,
+            Behavior::RotateTo(_) => None,
+            Behavior::Dead => None,
+            Behavior::Unconscious => None,
+            Behavior::SuppressFire(_) => None,
+            Behavior::EngageSoldier(_) => None,
+        }
+    }
+
+    pub fn terrain_tile_opacity(&self, tile_type: &TileType) -> f32 {
+        match tile_type {
+            TileType::ShortGrass => self.tile_type_opacity_short_grass,
+            TileType::MiddleGrass => self.tile_type_opacity_middle_grass,
+            TileType::HighGrass => self.tile_type_opacity_high_grass,
+            TileType::Dirt => self.tile_type_opacity_dirt,
+            TileType::Concrete => self.tile_type_opacity_concrete,
+            TileType::Mud => self.tile_type_opacity_mud,
+            TileType::BrickWall => self.tile_type_opacity_brick_wall,
+            TileType::Trunk => self.tile_type_opacity_trunk,
+            TileType::Water => self.tile_type_opacity_water,
+            TileType::DeepWater => self.tile_type_opacity_deep_water,
+            TileType::Underbrush => self.tile_type_opacity_underbrush,
+            TileType::LightUnderbrush => self.tile_type_opacity_light_underbrush,
+            TileType::MiddleWoodLogs => self.tile_type_opacity_middle_wood_logs,
+            TileType::Hedge => self.tile_type_opacity_hedge,
+            TileType::MiddleRock => self.tile_type_opacity_middle_rock,
+            TileType::Crater => self.tile_type_opacity_crater,
+            TileType::Road => self.tile_type_opacity_road,
+            TileType::WheatField => self.tile_type_opacity_wheat_field,
+            TileType::BarbedWire => self.tile_type_opacity_barbed_wire,
+            TileType::Trench => self.tile_type_opacity_trench,
+            TileType::Foxhole => self.tile_type_opacity_foxhole,
+            // Custom tile types opacity is given by their tile properties
+            TileType::Custom(_) => 0.,
+        }
+    }
+
+    pub fn react(&mut self, message: &ChangeConfigMessage) {
+        match message {
+            ChangeConfigMessage::SendDebugPoints(v) => self.send_debug_points = *v,
+            ChangeConfigMessage::RuleSet(rule_set) => self.apply_rule_set(*rule_set),
+            ChangeConfigMessage::Set(key, value) => {
+                if let Err(error) = self.set_value(key, *value) {
+                    eprintln!("Config change refused : {}", error)
+                }
+            }
+        }
+    }
+}
+
+/// Explosive distances are written with explosive name as key because some formats (TOML)
+/// can't read enum map keys
+mod explosive_distances {
+    use std::{
+        collections::{BTreeMap, HashMap},
+        str::FromStr,
+    };
+
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{game::explosive::ExplosiveType, types::Distance};
+
+    pub fn serialize<S: Serializer>(
+        distances: &HashMap<ExplosiveType, Distance>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        distances
+            .iter()
+            .map(|(explosive, distance)| (explosive.to_string(), *distance))
+            .collect::<BTreeMap<String, Distance>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<ExplosiveType, Distance>, D::Error> {
+        HashMap::<String, Distance>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, distance)| {
+                ExplosiveType::from_str(&name)
+                    .map(|explosive| (explosive, distance))
+                    .map_err(|_| D::Error::custom(format!("Unknown explosive '{}'", name)))
+            })
+            .collect()
+    }
+}
+
+enum ServerConfigFormat {
+    Toml,
+    Json,
+}
+
+impl ServerConfigFormat {
+    fn from_path(path: &Path) -> Result<Self, ServerConfigError> {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => Ok(Self::Toml),
+            Some("json") => Ok(Self::Json),
+            _ => Err(ServerConfigError::UnknownFormat(path.display().to_string())),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ServerConfigError {
+    #[error("Error during file read or write : {0}")]
+    Io(#[from] io::Error),
+    #[error("Unknown config file format (expected .toml or .json) : {0}")]
+    UnknownFormat(String),
+    #[error("TOML read error : {0}")]
+    TomlRead(#[from] toml::de::Error),
+    #[error("TOML write error : {0}")]
+    TomlWrite(#[from] toml::ser::Error),
+    #[error("JSON error : {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Out of range values : {}", .0.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(", "))]
+    OutOfRange(Vec<OutOfRangeValue>),
+}
+
+pub struct GuiConfig {
+    pub target_fps: u32,
+    pub interiors_update_freq: u64,
+}
+
+impl Default for GuiConfig {
+    fn default() -> Self {
+        Self {
+            target_fps: TARGET_FPS as u32,
+            interiors_update_freq: INTERIORS_UPDATE_FREQ,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum ChangeConfigMessage {
+    SendDebugPoints(bool),
+    /// Change value by its key (see `SERVER_CONFIG_VALUES` and `EXPLOSIVE_DISTANCES_VALUES`)
+    Set(String, ConfigValue),
+    RuleSet(RuleSet),
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use rstest::*;
+
+    use super::*;
+
+    /// Temporary config file path with given extension
+    fn config_path(name: &str, extension: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "oc_config_{}_{}.{}",
+            name,
+            std::process::id(),
+            extension
+        ))
+    }
+
+    /// Read config from a temporary file with given content
+    fn read_config(
+        name: &str,
+        extension: &str,
+        content: &str,
+    ) -> Result<ServerConfig, ServerConfigError> {
+        let path = config_path(name, extension);
+        fs::write(&path, content).unwrap();
+        let config = ServerConfig::from_file(&path);
+        fs::remove_file(&path).unwrap();
+        config
+    }
+
+    #[rstest]
+    #[case("toml")]
+    #[case("json")]
+    fn config_file_round_trip(#[case] extension: &str) {
+        let mut config = ServerConfig {
+            visible_starts_at: 0.25,
+            visibility_by_last_frame_shoot: 42,
+            hide_maximum_rayon: Distance::from_millimeters(12345),
+            ..Default::default()
+        };
+        config
+            .explosive_regressive_injured_rayon
+            .insert(ExplosiveType::FA19241927, Distance::from_meters(33));
+        let path = config_path("round_trip", extension);
+
+        config.write(&path).unwrap();
+        let read = ServerConfig::from_file(&path);
+        fs::remove_file(&path).unwrap();
+        let read = read.unwrap();
+
+        for meta in SERVER_CONFIG_VALUES {
+            assert_eq!(read.value(meta.key), config.value(meta.key), "{}", meta.key);
+        }
+        assert_eq!(
+            read.explosive_direct_death_rayon,
+            config.explosive_direct_death_rayon
+        );
+        assert_eq!(
+            read.explosive_regressive_death_rayon,
+            config.explosive_regressive_death_rayon
+        );
+        assert_eq!(
+            read.explosive_regressive_injured_rayon,
+            config.explosive_regressive_injured_rayon
+        );
+    }
+
+    #[rstest]
+    #[case("toml", "visible_starts_at = 0.2")]
+    #[case("json", r#"{"visible_starts_at": 0.2}"#)]
+    fn config_file_missing_values_keep_default(#[case] extension: &str, #[case] content: &str) {
+        let config = read_config("missing", extension, content).unwrap();
+
+        assert_eq!(config.visible_starts_at, 0.2);
+        assert_eq!(config.visibility_firsts, VISIBILITY_FIRSTS);
+        for explosive in ExplosiveType::iter() {
+            assert_eq!(
+                config.explosive_direct_death_rayon.get(&explosive),
+                Some(&explosive.direct_death_rayon())
+            );
+        }
+    }
+
+    #[rstest]
+    #[case("toml", "visible_starts_at = 2.0", "visible_starts_at")]
+    #[case("json", r#"{"visible_starts_at": -1.0}"#, "visible_starts_at")]
+    #[case("toml", "[hide_maximum_rayon]\nmillimeters = -1", "hide_maximum_rayon")]
+    #[case(
+        "toml",
+        "[explosive_direct_death_rayon.FA19241927]\nmillimeters = -1",
+        "explosive_direct_death_rayon.FA19241927"
+    )]
+    fn config_file_out_of_range_values_are_rejected(
+        #[case] extension: &str,
+        #[case] content: &str,
+        #[case] expected: &str,
+    ) {
+        match read_config("out_of_range", extension, content) {
+            Err(ServerConfigError::OutOfRange(values)) => {
+                assert_eq!(
+                    values
+                        .iter()
+                        .map(|value| value.name.as_str())
+                        .collect::<Vec<_>>(),
+                    vec![expected]
+                );
+            }
+            other => panic!("Expected out of range error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn set_value_change_value_of_given_key() {
+        let mut config = ServerConfig::default();
+
+        config
+            .set_value("end_morale", ConfigValue::F32(0.5))
+            .unwrap();
+        assert_eq!(config.end_morale, 0.5);
+        assert_eq!(config.value("end_morale"), Some(ConfigValue::F32(0.5)));
+
+        let key =
+            explosive_distance_key("explosive_direct_death_rayon", &ExplosiveType::FA19241927);
+        config
+            .set_value(&key, ConfigValue::Distance(Distance::from_meters(5)))
+            .unwrap();
+        assert_eq!(
+            config.explosive_direct_death_rayon[&ExplosiveType::FA19241927],
+            Distance::from_meters(5)
+        );
+        assert_eq!(
+            config.value(&key),
+            Some(ConfigValue::Distance(Distance::from_meters(5)))
+        );
+    }
+
+    #[rstest]
+    #[case("unknown", ConfigValue::F32(0.5))]
+    #[case(
+        "explosive_direct_death_rayon",
+        ConfigValue::Distance(Distance::from_meters(1))
+    )]
+    #[case(
+        "explosive_direct_death_rayon.Unknown",
+        ConfigValue::Distance(Distance::from_meters(1))
+    )]
+    fn set_value_refuse_unknown_key(#[case] key: &str, #[case] value: ConfigValue) {
+        let mut config = ServerConfig::default();
+
+        assert!(matches!(
+            config.set_value(key, value),
+            Err(ConfigValueError::UnknownKey(_))
+        ));
+    }
+
+    #[rstest]
+    #[case("end_morale", ConfigValue::U64(1))]
+    #[case("hide_maximum_rayon", ConfigValue::F32(1.))]
+    #[case("explosive_direct_death_rayon.FA19241927", ConfigValue::F32(1.))]
+    fn set_value_refuse_wrong_type(#[case] key: &str, #[case] value: ConfigValue) {
+        let mut config = ServerConfig::default();
+        let before = config.value(key);
+
+        assert!(matches!(
+            config.set_value(key, value),
+            Err(ConfigValueError::WrongType(_, _))
+        ));
+        assert_eq!(config.value(key), before);
+    }
+
+    #[rstest]
+    #[case("end_morale", ConfigValue::F32(1.5))]
+    #[case("end_morale", ConfigValue::F32(f32::NAN))]
+    #[case("hide_maximum_rayon", ConfigValue::Distance(Distance::from_meters(-1)))]
+    #[case(
+        "explosive_regressive_injured_rayon.FA19241927",
+        ConfigValue::Distance(Distance::from_meters(101))
+    )]
+    fn set_value_refuse_out_of_range_value(#[case] key: &str, #[case] value: ConfigValue) {
+        let mut config = ServerConfig::default();
+        let before = config.value(key);
+
+        match config.set_value(key, value) {
+            Err(ConfigValueError::OutOfRange(error)) => assert_eq!(error.name, key),
+            other => panic!("Expected out of range error, got {:?}", other),
+        }
+        assert_eq!(config.value(key), before);
+    }
+
+    #[test]
+    fn default_config_is_in_range() {
+        assert!(ServerConfig::default().out_of_range_values().is_empty());
+        assert!(ServerConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn config_file_format_is_given_by_extension() {
+        let path = config_path("format", "yaml");
+
+        assert!(matches!(
+            ServerConfig::default().write(&path),
+            Err(ServerConfigError::UnknownFormat(_))
+        ));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn rule_set_restore_replaced_values() {
+        // Like a value loaded from config file
+        let mut config = ServerConfig {
+            end_morale: 0.15,
+            ..Default::default()
+        };
+
+        config.apply_rule_set(RuleSet::Realistic);
+        assert_eq!(config.end_morale, 0.3);
+        config.apply_rule_set(RuleSet::Arcade);
+        assert_eq!(config.end_morale, 0.1);
+        config.apply_rule_set(RuleSet::Standard);
+        assert_eq!(config.end_morale, 0.15);
+        assert_eq!(
+            config.weapon_firing_frames_factor,
+            WEAPON_FIRING_FRAMES_FACTOR
+        );
+    }
+
+    #[test]
+    fn rule_set_names() {
+        assert_eq!(RuleSet::Realistic.to_string(), "Realistic");
+        assert_eq!("realistic".parse::<RuleSet>().unwrap(), RuleSet::Realistic);
+        assert_eq!("Realistic".parse::<RuleSet>().unwrap(), RuleSet::Realistic);
+    }
+}
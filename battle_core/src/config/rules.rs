@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use super::{value::ConfigValue, TARGET_FPS};
+
+/// Named gameplay rules of a battle. Each one overrides some server config values
+/// (morale threshold, weapon handling, command delays, ...) for the whole battle.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumIter,
+    EnumString,
+    Display,
+)]
+#[strum(serialize_all = "title_case", ascii_case_insensitive)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSet {
+    // No override : default config values
+    #[default]
+    Standard,
+    // Faster and more forgiving battles
+    Arcade,
+    // Slower weapon handling, command delays and earlier rout
+    Realistic,
+    // Battle never ends by morale, quick weapon handling
+    Training,
+}
+
+impl RuleSet {
+    /// Server config values (by key) overridden by this rule set
+    pub fn overrides(&self) -> Vec<(&'static str, ConfigValue)> {
+        match self {
+            RuleSet::Standard => vec![],
+            RuleSet::Arcade => vec![
+                ("end_morale", ConfigValue::F32(0.1)),
+                ("weapon_aiming_frames_factor", ConfigValue::F32(0.5)),
+                ("weapon_reloading_frames_factor", ConfigValue::F32(0.5)),
+                ("weapon_burst_dispersion_factor", ConfigValue::F32(0.8)),
+                ("out_of_command_order_delay", ConfigValue::U64(0)),
+                (
+                    "leader_replacement_order_delay",
+                    ConfigValue::U64(TARGET_FPS * 2),
+                ),
+            ],
+            RuleSet::Realistic => vec![
+                ("end_morale", ConfigValue::F32(0.3)),
+                ("weapon_aiming_frames_factor", ConfigValue::F32(1.5)),
+                ("weapon_reloading_frames_factor", ConfigValue::F32(1.5)),
+                ("weapon_firing_frames_factor", ConfigValue::F32(1.2)),
+                ("weapon_burst_dispersion_factor", ConfigValue::F32(1.2)),
+                (
+                    "out_of_command_order_delay",
+                    ConfigValue::U64(TARGET_FPS * 6),
+                ),
+                (
+                    "leader_replacement_order_delay",
+                    ConfigValue::U64(TARGET_FPS * 20),
+                ),
+            ],
+            RuleSet::Training => vec![
+                ("end_morale", ConfigValue::F32(0.)),
+                ("weapon_aiming_frames_factor", ConfigValue::F32(0.5)),
+                ("weapon_reloading_frames_factor", ConfigValue::F32(0.25)),
+            ],
+        }
+    }
+}
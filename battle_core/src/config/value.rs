@@ -0,0 +1,221 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+use thiserror::Error;
+
+use crate::types::Distance;
+
+/// A server config value, used to read, change or validate config values by their key
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ConfigValue {
+    Bool(bool),
+    U64(u64),
+    Usize(usize),
+    F32(f32),
+    Distance(Distance),
+}
+
+impl ConfigValue {
+    /// Value in unit of its metadata range (meters for distances), None for booleans
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            ConfigValue::Bool(_) => None,
+            ConfigValue::U64(value) => Some(*value as f64),
+            ConfigValue::Usize(value) => Some(*value as f64),
+            ConfigValue::F32(value) => Some(*value as f64),
+            ConfigValue::Distance(distance) => Some(distance.millimeters() as f64 / 1000.),
+        }
+    }
+}
+
+impl Display for ConfigValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigValue::Bool(value) => write!(f, "{}", value),
+            ConfigValue::U64(value) => write!(f, "{}", value),
+            ConfigValue::Usize(value) => write!(f, "{}", value),
+            ConfigValue::F32(value) => write!(f, "{}", value),
+            ConfigValue::Distance(distance) => {
+                write!(f, "{}", distance.millimeters() as f32 / 1000.)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, Display)]
+pub enum ConfigGroup {
+    Server,
+    Visibility,
+    Opacity,
+    Fight,
+    Rules,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigUnit {
+    Unitless,
+    Microseconds,
+    Frames,
+    Tiles,
+    Meters,
+}
+
+impl ConfigUnit {
+    pub fn suffix(&self) -> &str {
+        match self {
+            ConfigUnit::Unitless => "",
+            ConfigUnit::Microseconds => "µs",
+            ConfigUnit::Frames => "frames",
+            ConfigUnit::Tiles => "tiles",
+            ConfigUnit::Meters => "m",
+        }
+    }
+}
+
+/// Description of a server config value. Key is the `ServerConfig` field name (also used
+/// in config files).
+#[derive(Debug, Clone)]
+pub struct ConfigValueMeta {
+    pub key: &'static str,
+    pub group: ConfigGroup,
+    pub unit: ConfigUnit,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ConfigValueMeta {
+    /// Name as displayed (like matching constant name)
+    pub fn name(&self) -> String {
+        self.key.to_uppercase()
+    }
+
+    pub fn check(&self, value: &ConfigValue) -> Result<(), OutOfRangeValue> {
+        if let Some(value_) = value.as_f64() {
+            // Written as negation to refuse NaN
+            if !(value_ >= self.min && value_ <= self.max) {
+                return Err(OutOfRangeValue {
+                    name: self.key.to_string(),
+                    value: value.to_string(),
+                    expected: format!("{}..={}", self.min, self.max),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OutOfRangeValue {
+    pub name: String,
+    pub value: String,
+    pub expected: String,
+}
+
+impl Display for OutOfRangeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} = {} (expected {})",
+            self.name, self.value, self.expected
+        )
+    }
+}
+
+#[derive(Error, Debug, Clone)]
+pub enum ConfigValueError {
+    #[error("Unknown config key '{0}'")]
+    UnknownKey(String),
+    #[error("Wrong value type for '{0}' : {1:?}")]
+    WrongType(String, ConfigValue),
+    #[error("Out of range value : {0}")]
+    OutOfRange(OutOfRangeValue),
+}
+
+/// Declare server config values metadata and generate their access by key (explosive
+/// distances keys included)
+macro_rules! server_config_values {
+    ($(($key:ident, $type_:ident, $group:ident, $unit:ident, $min:expr, $max:expr)),* $(,)?) => {
+        pub const SERVER_CONFIG_VALUES: &[ConfigValueMeta] = &[
+            $(ConfigValueMeta {
+                key: stringify!($key),
+                group: ConfigGroup::$group,
+                unit: ConfigUnit::$unit,
+                min: $min as f64,
+                max: $max as f64,
+            },)*
+        ];
+
+        impl ServerConfig {
+            pub fn value(&self, key: &str) -> Option<ConfigValue> {
+                match key {
+                    $(stringify!($key) => Some(ConfigValue::$type_(self.$key)),)*
+                    _ => self.explosive_distance(key).map(ConfigValue::Distance),
+                }
+            }
+
+            fn set_value_unchecked(
+                &mut self,
+                key: &str,
+                value: ConfigValue,
+            ) -> Result<(), ConfigValueError> {
+                match (key, value) {
+                    $((stringify!($key), ConfigValue::$type_(value)) => self.$key = value,)*
+                    (key, value) => {
+                        if self.value(key).is_none() {
+                            return Err(ConfigValueError::UnknownKey(key.to_string()));
+                        }
+                        if let ConfigValue::Distance(distance) = value {
+                            if self.set_explosive_distance(key, distance) {
+                                return Ok(());
+                            }
+                        }
+                        return Err(ConfigValueError::WrongType(key.to_string(), value));
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+pub(crate) use server_config_values;
+
+#[cfg(test)]
+mod test {
+    use rstest::*;
+
+    use super::*;
+
+    const META: ConfigValueMeta = ConfigValueMeta {
+        key: "value",
+        group: ConfigGroup::Fight,
+        unit: ConfigUnit::Meters,
+        min: 1.,
+        max: 10.,
+    };
+
+    #[rstest]
+    #[case(ConfigValue::U64(1), true)]
+    #[case(ConfigValue::Usize(10), true)]
+    #[case(ConfigValue::F32(0.99), false)]
+    #[case(ConfigValue::F32(f32::NAN), false)]
+    #[case(ConfigValue::U64(11), false)]
+    // Distances are checked in meters
+    #[case(ConfigValue::Distance(Distance::from_millimeters(10000)), true)]
+    #[case(ConfigValue::Distance(Distance::from_millimeters(10001)), false)]
+    // Booleans have no range
+    #[case(ConfigValue::Bool(false), true)]
+    fn check_value_range(#[case] value: ConfigValue, #[case] expected: bool) {
+        assert_eq!(META.check(&value).is_ok(), expected);
+    }
+
+    #[test]
+    fn out_of_range_value_describe_expected_range() {
+        let error = META.check(&ConfigValue::F32(20.)).unwrap_err();
+
+        assert_eq!(error.to_string(), "value = 20 (expected 1..=10)");
+    }
+}
@@ -6,12 +6,17 @@ use thiserror::Error;
 
 use crate::{
     behavior::Behavior,
+    config::rules::RuleSet,
     entity::{
         soldier::Soldier,
         vehicle::{Vehicle, VehicleType},
     },
     game::{
-        weapon::{Magazine, Weapon},
+        traits::{SoldierTraits, SoldierTraitsError},
+        weapon::{
+            definition::{WeaponDefinitions, WeaponDefinitionsError},
+            Magazine, Weapon,
+        },
         Side,
     },
     order::Order,
@@ -27,6 +32,9 @@ pub struct Deployment {
     vehicles: Vec<VehicleDeployment>,
     boards: SoldiersOnBoard,
     squad_types: SquadTypes,
+    // Rule set to play this deployment with, if any
+    #[serde(default)]
+    rule_set: Option<RuleSet>,
 }
 
 impl Deployment {
@@ -41,6 +49,7 @@ impl Deployment {
             vehicles,
             boards,
             squad_types,
+            rule_set: None,
         }
     }
 
@@ -50,6 +59,7 @@ impl Deployment {
             vehicles: vec![],
             boards: HashMap::new(),
             squad_types: HashMap::new(),
+            rule_set: None,
         }
     }
 
@@ -67,7 +77,7 @@ impl Deployment {
         let squad_types: SquadTypes = battle_state
             .squads()
             .iter()
-            .map(|s| (*s.0, s.1.type_().clone()))
+            .map(|s| (*s.0, *s.1.type_()))
             .collect();
 
         Self {
@@ -75,6 +85,7 @@ impl Deployment {
             vehicles,
             boards: battle_state.soldier_on_board().clone(),
             squad_types,
+            rule_set: Some(battle_state.rule_set()),
         }
     }
 
@@ -93,6 +104,39 @@ impl Deployment {
     pub fn squad_types(&self) -> &SquadTypes {
         &self.squad_types
     }
+
+    /// Ensure all weapons and magazines are known by given definitions
+    pub fn validate_weapons(
+        &self,
+        definitions: &WeaponDefinitions,
+    ) -> Result<(), WeaponDefinitionsError> {
+        for soldier in &self.soldiers {
+            definitions.validate_equipment(soldier.main_weapon(), soldier.magazines())?;
+            definitions.validate_equipment(soldier.secondary_weapon(), &[])?;
+        }
+
+        Ok(())
+    }
+
+    /// Ensure all soldiers traits are in their valid range
+    pub fn validate_traits(&self) -> Result<(), DeploymentTraitsError> {
+        for soldier in &self.soldiers {
+            soldier
+                .traits()
+                .validate()
+                .map_err(|error| DeploymentTraitsError(soldier.uuid(), error))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn rule_set(&self) -> Option<RuleSet> {
+        self.rule_set
+    }
+
+    pub fn set_rule_set(&mut self, rule_set: Option<RuleSet>) {
+        self.rule_set = rule_set
+    }
 }
 
 impl
@@ -116,6 +160,7 @@ impl
             vehicles: value.1,
             boards: value.2,
             squad_types: value.3,
+            rule_set: None,
         }
     }
 }
@@ -125,6 +170,8 @@ pub struct DeploymentReader;
 impl DeploymentReader {
     pub fn from_file(path: &PathBuf) -> Result<Deployment, DeploymentReaderError> {
         let deployment: Deployment = serde_json::from_str(&fs::read_to_string(path)?)?;
+        deployment.validate_weapons(WeaponDefinitions::get())?;
+        deployment.validate_traits()?;
         Ok(deployment)
     }
 }
@@ -135,22 +182,37 @@ pub enum DeploymentReaderError {
     Read(#[from] io::Error),
     #[error("Data format error")]
     Format(#[from] serde_json::Error),
+    #[error("Weapon error : {0}")]
+    Weapon(#[from] WeaponDefinitionsError),
+    #[error("Traits error : {0}")]
+    Traits(#[from] DeploymentTraitsError),
 }
 
+#[derive(Error, Debug, PartialEq)]
+#[error("Soldier {0} : {1}")]
+pub struct DeploymentTraitsError(pub SoldierIndex, pub SoldierTraitsError);
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SoldierDeployment {
     uuid: SoldierIndex,
+    // Absent from deployment files written before soldier types
+    #[serde(default)]
     type_: SoldierType,
     side: Side,
     world_point: WorldPoint,
     squad_uuid: SquadUuid,
     main_weapon: Option<Weapon>,
+    #[serde(default)]
+    secondary_weapon: Option<Weapon>,
     magazines: Vec<Magazine>,
     order: Order,
     behavior: Behavior,
+    #[serde(default)]
+    traits: SoldierTraits,
 }
 
 impl SoldierDeployment {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         uuid: SoldierIndex,
         type_: SoldierType,
@@ -161,6 +223,7 @@ impl SoldierDeployment {
         magazines: Vec<Magazine>,
         order: Order,
         behavior: Behavior,
+        traits: SoldierTraits,
     ) -> Self {
         Self {
             uuid,
@@ -169,9 +232,11 @@ impl SoldierDeployment {
             world_point,
             squad_uuid,
             main_weapon,
+            secondary_weapon: None,
             magazines,
             order,
             behavior,
+            traits,
         }
     }
 
@@ -195,6 +260,10 @@ impl SoldierDeployment {
         self.main_weapon.as_ref()
     }
 
+    pub fn secondary_weapon(&self) -> Option<&Weapon> {
+        self.secondary_weapon.as_ref()
+    }
+
     pub fn magazines(&self) -> &[Magazine] {
         self.magazines.as_ref()
     }
@@ -210,6 +279,10 @@ impl SoldierDeployment {
     pub fn type_(&self) -> &SoldierType {
         &self.type_
     }
+
+    pub fn traits(&self) -> &SoldierTraits {
+        &self.traits
+    }
 }
 
 impl From<&Soldier> for SoldierDeployment {
@@ -221,9 +294,11 @@ impl From<&Soldier> for SoldierDeployment {
             world_point: soldier.world_point(),
             squad_uuid: soldier.squad_uuid(),
             main_weapon: soldier.main_weapon().clone(),
+            secondary_weapon: soldier.secondary_weapon().clone(),
             magazines: soldier.magazines().clone(),
             order: soldier.order().clone(),
             behavior: soldier.behavior().clone(),
+            traits: soldier.traits().clone(),
         }
     }
 }
@@ -266,3 +341,82 @@ impl From<&Vehicle> for VehicleDeployment {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use rstest::*;
+
+    use crate::{
+        map::{
+            decor::Decor,
+            terrain::{TerrainTile, TileType},
+            Map,
+        },
+        types::Offset,
+    };
+
+    use super::*;
+
+    #[rstest]
+    #[case("demo1_deployment.json")]
+    #[case("demo2_deployment.json")]
+    #[case("demo2_deployment2.json")]
+    #[case("map1_deployment.json")]
+    fn read_bundled_deployment(#[case] file_name: &str) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../assets")
+            .join(file_name);
+        let deployment = DeploymentReader::from_file(&path).unwrap();
+        assert!(!deployment.soldiers().is_empty());
+    }
+
+    #[test]
+    fn keep_battle_rule_set() {
+        let tiles = (0..4)
+            .flat_map(|y| {
+                (0..4).map(move |x| {
+                    TerrainTile::new(TileType::ShortGrass, 10, 10, 1., 1., x, y, 0, 0)
+                })
+            })
+            .collect();
+        let map = Map::new(
+            "TestMap".to_string(),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            vec![],
+            vec![],
+            4,
+            4,
+            tiles,
+            10,
+            10,
+            Decor::new(vec![], vec![], Offset::new(0., 0.)),
+            vec![],
+        );
+        let mut battle_state = BattleState::empty(&map);
+        battle_state.set_rule_set(RuleSet::Training);
+        // Like a battle state received by a client
+        let battle_state = BattleState::from_copy(&battle_state.copy(), &map);
+
+        let deployment = Deployment::from_battle_state(&battle_state);
+        assert_eq!(deployment.rule_set(), Some(RuleSet::Training));
+    }
+
+    #[test]
+    fn refuse_out_of_range_traits() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../assets/map1_deployment.json");
+        let mut value: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        value["soldiers"][1]["traits"] = serde_json::json!({"courage": 1.5});
+        let deployment: Deployment = serde_json::from_value(value).unwrap();
+
+        assert_eq!(
+            deployment.validate_traits(),
+            Err(DeploymentTraitsError(
+                deployment.soldiers()[1].uuid(),
+                SoldierTraitsError::Courage(1.5)
+            ))
+        );
+    }
+}
@@ -2,6 +2,7 @@ use crate::{
     behavior::{feeling::Feeling, gesture::Gesture, Behavior, Body},
     deployment::SoldierDeployment,
     game::{
+        traits::SoldierTraits,
         weapon::{Magazine, Shot, Weapon},
         Side,
     },
@@ -20,6 +21,9 @@ pub struct Soldier {
     world_point: WorldPoint,
     squad_uuid: SquadUuid,
     order: Order,
+    // Frame when current order has been given
+    #[serde(default)]
+    order_frame_i: u64,
     behavior: Behavior,
     gesture: Gesture,
     looking_direction: Angle,
@@ -27,9 +31,21 @@ pub struct Soldier {
     unconscious: bool,
     under_fire: Feeling,
     main_weapon: Option<Weapon>,
+    // Carried weapon, like the one left to take over a crew-served weapon
+    #[serde(default)]
+    secondary_weapon: Option<Weapon>,
     magazines: Vec<Magazine>,
     last_shoot_frame_i: u64,
     last_shot_frame_i: u64,
+    delayed_order: Option<(Order, u64)>,
+    // Building floor where soldier is (0 is ground floor)
+    #[serde(default)]
+    floor: u8,
+    // Rounds fired since battle start, for after-action report
+    #[serde(default)]
+    ammunition_spent: usize,
+    #[serde(default)]
+    traits: SoldierTraits,
 }
 
 impl Soldier {
@@ -49,6 +65,7 @@ impl Soldier {
             world_point,
             squad_uuid,
             order: Order::Idle,
+            order_frame_i: 0,
             behavior: Behavior::Idle(Body::StandUp),
             gesture: Gesture::Idle,
             looking_direction: Angle(0.0),
@@ -56,14 +73,19 @@ impl Soldier {
             unconscious: false,
             under_fire: Feeling::UnderFire(0),
             main_weapon,
+            secondary_weapon: None,
             magazines,
             last_shot_frame_i: 0,
             last_shoot_frame_i: 0,
+            delayed_order: None,
+            floor: 0,
+            ammunition_spent: 0,
+            traits: SoldierTraits::default(),
         }
     }
 
     pub fn from_soldier(soldier: &Soldier) -> Self {
-        Self::new(
+        let mut copy = Self::new(
             soldier.uuid(),
             *soldier.type_(),
             soldier.world_point(),
@@ -71,7 +93,10 @@ impl Soldier {
             *soldier.side(),
             soldier.main_weapon().clone(),
             soldier.magazines().clone(),
-        )
+        );
+        copy.secondary_weapon = soldier.secondary_weapon().clone();
+        copy.traits = soldier.traits().clone();
+        copy
     }
 
     pub fn uuid(&self) -> SoldierIndex {
@@ -126,6 +151,31 @@ impl Soldier {
         self.order = order
     }
 
+    pub fn order_frame_i(&self) -> u64 {
+        self.order_frame_i
+    }
+
+    pub fn set_order_frame_i(&mut self, frame_i: u64) {
+        self.order_frame_i = frame_i
+    }
+
+    pub fn delayed_order(&self) -> &Option<(Order, u64)> {
+        &self.delayed_order
+    }
+
+    pub fn set_delayed_order(&mut self, order: Order, frame_i: u64) {
+        // Soldier keep the first expected reaction frame, but with the last given order
+        let frame_i = match &self.delayed_order {
+            Some((_, previous_frame_i)) => frame_i.min(*previous_frame_i),
+            None => frame_i,
+        };
+        self.delayed_order = Some((order, frame_i))
+    }
+
+    pub fn clear_delayed_order(&mut self) {
+        self.delayed_order = None
+    }
+
     pub fn get_looking_direction(&self) -> Angle {
         self.looking_direction
     }
@@ -138,6 +188,14 @@ impl Soldier {
         &self.main_weapon
     }
 
+    pub fn secondary_weapon(&self) -> &Option<Weapon> {
+        &self.secondary_weapon
+    }
+
+    pub fn traits(&self) -> &SoldierTraits {
+        &self.traits
+    }
+
     pub fn magazines(&self) -> &Vec<Magazine> {
         &self.magazines
     }
@@ -214,6 +272,14 @@ impl Soldier {
         self.under_fire.decrease()
     }
 
+    pub fn floor(&self) -> u8 {
+        self.floor
+    }
+
+    pub fn set_floor(&mut self, value: u8) {
+        self.floor = value
+    }
+
     pub fn set_last_shoot_frame_i(&mut self, value: u64) {
         self.last_shoot_frame_i = value
     }
@@ -233,12 +299,14 @@ impl Soldier {
     pub fn weapon(&self, class: &WeaponClass) -> &Option<Weapon> {
         match class {
             WeaponClass::Main => &self.main_weapon,
+            WeaponClass::Secondary => &self.secondary_weapon,
         }
     }
 
     pub fn weapon_mut(&mut self, class: &WeaponClass) -> &mut Option<Weapon> {
         match class {
             WeaponClass::Main => &mut self.main_weapon,
+            WeaponClass::Secondary => &mut self.secondary_weapon,
         }
     }
 
@@ -265,9 +333,61 @@ impl Soldier {
     pub fn weapon_shot(&mut self, class: &WeaponClass, shot: &Shot) {
         if let Some(weapon) = self.weapon_mut(class) {
             weapon.shot(shot);
+            self.ammunition_spent += shot.count();
+        }
+    }
+
+    pub fn ammunition_spent(&self) -> usize {
+        self.ammunition_spent
+    }
+
+    pub fn set_weapon(&mut self, class: &WeaponClass, weapon: Option<Weapon>) {
+        *self.weapon_mut(class) = weapon
+    }
+
+    pub fn set_magazines(&mut self, magazines: Vec<Magazine>) {
+        self.magazines = magazines
+    }
+
+    pub fn set_weapon_deployed(&mut self, class: &WeaponClass, value: bool) {
+        if let Some(weapon) = self.weapon_mut(class) {
+            weapon.set_deployed(value);
+        }
+    }
+
+    pub fn jam_weapon(&mut self, class: &WeaponClass) {
+        if let Some(weapon) = self.weapon_mut(class) {
+            weapon.jam();
+        }
+    }
+
+    pub fn clear_weapon_jam(&mut self, class: &WeaponClass) {
+        if let Some(weapon) = self.weapon_mut(class) {
+            weapon.clear_jam();
+        }
+    }
+
+    pub fn change_weapon_barrel(&mut self, class: &WeaponClass) {
+        if let Some(weapon) = self.weapon_mut(class) {
+            weapon.change_barrel();
         }
     }
 
+    pub fn cool_weapons(&mut self, seconds: f32) {
+        for class in [WeaponClass::Main, WeaponClass::Secondary] {
+            if let Some(weapon) = self.weapon_mut(&class) {
+                weapon.cool(seconds);
+            }
+        }
+    }
+
+    /// Soldier carry a weapon which is not cold
+    pub fn hot_weapon(&self) -> bool {
+        [WeaponClass::Main, WeaponClass::Secondary]
+            .iter()
+            .any(|class| self.weapon(class).as_ref().is_some_and(|w| w.heat() > 0.))
+    }
+
     pub fn alive(&self) -> bool {
         self.alive
     }
@@ -341,6 +461,8 @@ impl From<&SoldierDeployment> for Soldier {
         );
         soldier.order = deployment.order().clone();
         soldier.behavior = deployment.behavior().clone();
+        soldier.secondary_weapon = deployment.secondary_weapon().cloned();
+        soldier.traits = deployment.traits().clone();
         soldier
     }
 }
@@ -348,4 +470,5 @@ impl From<&SoldierDeployment> for Soldier {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum WeaponClass {
     Main,
+    Secondary,
 }
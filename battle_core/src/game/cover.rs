@@ -4,12 +4,10 @@ use crate::{
     map::{find_arbitrary_cover_grid_point, find_arbitrary_cover_grid_points},
     physics::visibility::Visibility,
     state::battle::BattleState,
-    types::{GridPoint, SoldierIndex, SquadComposition, WorldPoint},
+    types::{GridPoint, SoldierIndex, WorldPoint},
     utils::NewDebugPoint,
 };
 
-use super::squad::{squad_positions, Formation};
-
 pub struct CoverFinder<'a> {
     battle_state: &'a BattleState,
     config: &'a ServerConfig,
@@ -39,7 +37,6 @@ impl<'a> CoverFinder<'a> {
 
     pub fn find_arbitrary_cover_points(
         &self,
-        squad: &SquadComposition,
         leader: &Soldier,
     ) -> (
         Vec<(SoldierIndex, WorldPoint, WorldPoint)>,
@@ -49,9 +46,11 @@ impl<'a> CoverFinder<'a> {
         let mut already_used_cover_grid_points: Vec<GridPoint> = self.exclude_grid_points.clone();
         let mut debug_points = vec![];
 
-        for (member_id, formation_position) in
-            squad_positions(squad, Formation::Line, leader, self.point)
-        {
+        for (member_id, formation_position) in self.battle_state.squad_formation_positions(
+            self.config,
+            &leader.squad_uuid(),
+            Some(self.point.unwrap_or(leader.world_point())),
+        ) {
             let soldier = self.battle_state.soldier(member_id);
             let grid_point = self
                 .battle_state
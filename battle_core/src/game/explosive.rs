@@ -2,6 +2,7 @@ use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 use strum_macros::Display;
 use strum_macros::EnumIter;
+use strum_macros::EnumString;
 
 use crate::{
     audio::Sound,
@@ -9,7 +10,9 @@ use crate::{
     types::Distance,
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize, EnumIter, PartialEq, Eq, Hash, Display)]
+#[derive(
+    Debug, Clone, Serialize, Deserialize, EnumIter, EnumString, PartialEq, Eq, Hash, Display,
+)]
 pub enum ExplosiveType {
     FA19241927,
 }
@@ -45,6 +48,13 @@ impl ExplosiveType {
         }
     }
 
+    /// Rayon where explosion destroy terrain (vegetation, walls, etc)
+    pub fn destruction_rayon(&self) -> Distance {
+        match self {
+            ExplosiveType::FA19241927 => Distance::from_meters(2),
+        }
+    }
+
     pub fn regressive_injured_rayon(&self) -> Distance {
         match self {
             ExplosiveType::FA19241927 => Distance::from_meters(6),
@@ -1,7 +1,11 @@
 use oc_core::spawn::SpawnZoneName;
 use serde::{Deserialize, Serialize};
 
-use crate::{map::Map, types::WorldPoint, utils::WorldShape};
+use crate::{
+    map::Map,
+    types::WorldPoint,
+    utils::{Rect, WorldPolygon},
+};
 
 use super::control::MapControl;
 
@@ -11,6 +15,7 @@ pub struct FlagName(pub String);
 #[derive(Clone)]
 pub struct Flag {
     name: FlagName,
+    polygon: WorldPolygon,
     x: f32,
     y: f32,
     width: f32,
@@ -19,8 +24,23 @@ pub struct Flag {
 
 impl Flag {
     pub fn new(name: FlagName, x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self::from_polygon(
+            name,
+            WorldPolygon::from_rect(&Rect::new(x, y, width, height)),
+        )
+    }
+
+    /// Position and size are the polygon bounding rect
+    pub fn from_polygon(name: FlagName, polygon: WorldPolygon) -> Self {
+        let Rect {
+            x,
+            y,
+            w: width,
+            h: height,
+        } = polygon.bounding_rect();
         Self {
             name,
+            polygon,
             x,
             y,
             width,
@@ -48,13 +68,8 @@ impl Flag {
         self.height
     }
 
-    pub fn shape(&self) -> WorldShape {
-        WorldShape {
-            top_left: WorldPoint::new(self.x, self.y),
-            top_right: WorldPoint::new(self.x + self.width, self.y),
-            bottom_right: WorldPoint::new(self.x + self.width, self.y + self.height),
-            bottom_left: WorldPoint::new(self.x, self.y + self.height),
-        }
+    pub fn polygon(&self) -> &WorldPolygon {
+        &self.polygon
     }
 
     pub fn position(&self) -> WorldPoint {
@@ -196,4 +211,45 @@ pub mod test {
             }
         )
     }
+
+    #[cfg(test)]
+    #[fixture]
+    fn triangle_spawn_zones() -> Vec<SpawnZone> {
+        vec![SpawnZone::from_polygon(
+            SpawnZoneName::North,
+            WorldPolygon::new(vec![
+                WorldPoint::new(0., 0.),
+                WorldPoint::new(10., 0.),
+                WorldPoint::new(0., 10.),
+            ]),
+            10.,
+            10.,
+        )]
+    }
+
+    #[rstest]
+    #[case(Flag::new(FlagName("FlagName".to_string()), 1., 1., 2., 2.), FlagOwnership::A)]
+    #[case(Flag::new(FlagName("FlagName".to_string()), 6., 6., 2., 2.), FlagOwnership::Nobody)]
+    #[case(Flag::from_polygon(FlagName("FlagName".to_string()), WorldPolygon::from_ellipse(&Rect::new(1., 1., 2., 2.))), FlagOwnership::A)]
+    fn flag_in_polygon_spawn_zone(
+        triangle_spawn_zones: Vec<SpawnZone>,
+        #[case] flag: Flag,
+        #[case] ownership: FlagOwnership,
+    ) {
+        // Given
+        let map = map(triangle_spawn_zones, vec![flag]);
+        let a_control = MapControl::new(vec![SpawnZoneName::North]);
+        let b_control = MapControl::new(vec![]);
+
+        // When
+        let flags_ownership = FlagsOwnership::from_control(&map, &a_control, &b_control);
+
+        // Then
+        assert_eq!(
+            flags_ownership,
+            FlagsOwnership {
+                ownerships: vec![(FlagName("FlagName".to_string()), ownership)]
+            }
+        )
+    }
 }
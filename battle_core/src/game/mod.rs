@@ -7,6 +7,7 @@ pub mod flag;
 pub mod health;
 pub mod posture;
 pub mod squad;
+pub mod traits;
 pub mod weapon;
 
 #[derive(Debug, Copy, Serialize, Deserialize, Clone, PartialEq)]
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Display};
 
 use oc_core::{
     game::{soldier::SoldierType, squad::SquadType},
@@ -7,9 +7,16 @@ use oc_core::{
     morale::Morale,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
     behavior::{feeling::UNDER_FIRE_MAX, gesture::Gesture, Behavior},
+    config::{
+        ServerConfig, FORMATION_SPACING, FORMATION_SPACING_MAX_FACTOR,
+        FORMATION_SPACING_MIN_FACTOR, FORMATION_TERRAIN_DISTANCE,
+    },
     entity::soldier::Soldier,
+    map::{find_arbitrary_cover_grid_points, Map},
     state::battle::BattleState,
     types::{SoldierIndex, SquadComposition, SquadUuid, WorldPoint},
     utils::apply_angle_on_point,
@@ -17,55 +24,141 @@ use crate::{
 
 use super::{
     health::SoldierHealthBuilder,
-    weapon::{Magazine, Weapon},
+    posture::Posture,
+    traits::SoldierTraits,
+    weapon::{Magazine, Weapon, WeaponStatus},
     Side,
 };
 
+// Frame when squad leader has been replaced (previous one dead, unconscious, etc)
+pub type SquadLeadersReplaced = HashMap<SquadUuid, u64>;
+
+pub type SquadFormations = HashMap<SquadUuid, Formation>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Formation {
+    #[default]
     Line,
+    Column,
+    Wedge,
+    Echelon,
+    File,
+}
+
+impl Formation {
+    pub fn next(&self) -> Self {
+        match self {
+            Formation::Line => Formation::Column,
+            Formation::Column => Formation::Wedge,
+            Formation::Wedge => Formation::Echelon,
+            Formation::Echelon => Formation::File,
+            Formation::File => Formation::Line,
+        }
+    }
+}
+
+impl Display for Formation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Formation::Line => f.write_str("Line"),
+            Formation::Column => f.write_str("Column"),
+            Formation::Wedge => f.write_str("Wedge"),
+            Formation::Echelon => f.write_str("Echelon"),
+            Formation::File => f.write_str("File"),
+        }
+    }
+}
+
+pub type SquadFireDisciplines = HashMap<SquadUuid, FireDiscipline>;
+
+/// When squad members are allowed to open fire by themselves. Engage and suppress fire orders
+/// are always executed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FireDiscipline {
+    #[default]
+    FireAtWill,
+    // Fire only when squad recently came under fire
+    ReturnFire,
+    HoldFire,
+    // Hold fire until an enemy is visible near the squad, then fire at will
+    Ambush,
+}
+
+impl FireDiscipline {
+    pub fn next(&self) -> Self {
+        match self {
+            FireDiscipline::FireAtWill => FireDiscipline::ReturnFire,
+            FireDiscipline::ReturnFire => FireDiscipline::HoldFire,
+            FireDiscipline::HoldFire => FireDiscipline::Ambush,
+            FireDiscipline::Ambush => FireDiscipline::FireAtWill,
+        }
+    }
+}
+
+impl Display for FireDiscipline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FireDiscipline::FireAtWill => f.write_str("Fire at will"),
+            FireDiscipline::ReturnFire => f.write_str("Return fire"),
+            FireDiscipline::HoldFire => f.write_str("Hold fire"),
+            FireDiscipline::Ambush => f.write_str("Ambush"),
+        }
+    }
+}
+
+/// Spacing (in pixels) between squad members around given point. Open terrain spread
+/// the squad, dense terrain (opacity) or available covers tighten it.
+pub fn formation_spacing(config: &ServerConfig, map: &Map, point: &WorldPoint) -> f32 {
+    let grid_point = map.grid_point_from_world_point(point);
+    let tiles =
+        find_arbitrary_cover_grid_points(config, &grid_point, map, FORMATION_TERRAIN_DISTANCE);
+    if tiles.is_empty() {
+        return FORMATION_SPACING;
+    }
+
+    let opacity = tiles
+        .iter()
+        .map(|(_, tile)| tile.opacity(config))
+        .sum::<f32>()
+        / tiles.len() as f32;
+    let covers = tiles
+        .iter()
+        .filter(|(_, tile)| tile.coverage(&Posture::Flat).is_some())
+        .count() as f32
+        / tiles.len() as f32;
+
+    let factor = (FORMATION_SPACING_MAX_FACTOR - opacity * 2. - covers)
+        .clamp(FORMATION_SPACING_MIN_FACTOR, FORMATION_SPACING_MAX_FACTOR);
+    FORMATION_SPACING * factor
 }
 
 pub fn squad_positions(
     squad: &SquadComposition,
-    formation: Formation,
+    formation: &Formation,
     leader: &Soldier,
     point: Option<WorldPoint>,
+    spacing: f32,
 ) -> HashMap<SoldierIndex, WorldPoint> {
     let mut positions = HashMap::new();
     let ref_point = point.unwrap_or(leader.world_point());
     let ref_angle = leader.get_looking_direction();
 
-    match formation {
-        Formation::Line => {
-            let mut x_offset: f32 = 0.0;
-            let mut y_offset: f32 = 0.0;
-            let mut counter: u8 = 0;
-
-            for (i, soldier_index) in squad.members().iter().enumerate() {
-                // Don't return position for leader
-                if *soldier_index == squad.leader() {
-                    continue;
-                }
-
-                if counter % 2 == 0 {
-                    x_offset += 10.0;
-                    y_offset += 0.0;
-                }
-                counter += 1;
-
-                let (x_offset_, y_offset_) = if i % 2 == 0 {
-                    (x_offset, y_offset)
-                } else {
-                    (-x_offset, -y_offset)
-                };
-
-                let member_scene_point =
-                    WorldPoint::new(ref_point.x + x_offset_, ref_point.y + y_offset_);
-                let member_scene_point =
-                    apply_angle_on_point(&member_scene_point, &ref_point, &ref_angle);
-                positions.insert(*soldier_index, member_scene_point);
-            }
-        }
+    // Offsets are relative to leader, squad looking "up" before rotation (y > 0 is behind leader)
+    for (i, soldier_index) in squad.subordinates().iter().enumerate() {
+        let rank = (i / 2 + 1) as f32;
+        let side = if i % 2 == 0 { 1. } else { -1. };
+
+        let (x_offset, y_offset) = match formation {
+            Formation::Line => (side * rank * spacing, 0.),
+            Formation::Column => (side * spacing / 2., rank * spacing),
+            Formation::Wedge => (side * rank * spacing, rank * spacing),
+            Formation::Echelon => ((i + 1) as f32 * spacing, (i + 1) as f32 * spacing),
+            Formation::File => (0., (i + 1) as f32 * spacing),
+        };
+
+        let member_scene_point = WorldPoint::new(ref_point.x + x_offset, ref_point.y + y_offset);
+        let member_scene_point = apply_angle_on_point(&member_scene_point, &ref_point, &ref_angle);
+        positions.insert(**soldier_index, member_scene_point);
     }
 
     positions
@@ -76,13 +169,17 @@ pub struct SquadStatusesResume {
 }
 
 impl SquadStatusesResume {
-    pub fn from_battle_state(side: &Side, battle_state: &BattleState) -> Self {
+    pub fn from_battle_state(
+        side: &Side,
+        battle_state: &BattleState,
+        config: &ServerConfig,
+    ) -> Self {
         Self {
             squads: battle_state
                 .squads()
                 .iter()
                 .filter(|(_, squad)| battle_state.soldier(squad.leader()).side() == side)
-                .map(|(squad_id, _)| SquadStatusResume::from_squad(battle_state, squad_id))
+                .map(|(squad_id, _)| SquadStatusResume::from_squad(battle_state, config, squad_id))
                 .collect(),
         }
     }
@@ -98,10 +195,19 @@ pub struct SquadStatusResume {
     squad_type: SquadType,
     health: SquadHealth,
     members: Vec<SquadMemberStatus>,
+    command_disrupted: bool,
+    formation: Formation,
+    fire_discipline: FireDiscipline,
+    // Order waiting for its trigger, with trigger description
+    triggered_order: Option<String>,
 }
 
 impl SquadStatusResume {
-    pub fn from_squad(battle_state: &BattleState, squad_id: &SquadUuid) -> Self {
+    pub fn from_squad(
+        battle_state: &BattleState,
+        config: &ServerConfig,
+        squad_id: &SquadUuid,
+    ) -> Self {
         let squad = battle_state.squad(*squad_id);
         Self {
             squad_id: *squad_id,
@@ -113,11 +219,22 @@ impl SquadStatusResume {
                 .map(|soldier_index| {
                     SquadMemberStatus::from_soldier(
                         battle_state,
+                        config,
                         squad,
                         battle_state.soldier(*soldier_index),
                     )
                 })
                 .collect(),
+            command_disrupted: battle_state
+                .squad_command_disrupted_until(config, squad_id)
+                .is_some(),
+            formation: battle_state.squad_formation(squad_id),
+            fire_discipline: battle_state.squad_fire_discipline(squad_id),
+            triggered_order: battle_state.squad_triggered_order(squad_id).map(
+                |(trigger, order)| {
+                    format!("{} {}", order, battle_state.order_trigger_display(trigger))
+                },
+            ),
         }
     }
 
@@ -136,6 +253,22 @@ impl SquadStatusResume {
     pub fn squad_type(&self) -> &SquadType {
         &self.squad_type
     }
+
+    pub fn command_disrupted(&self) -> bool {
+        self.command_disrupted
+    }
+
+    pub fn formation(&self) -> &Formation {
+        &self.formation
+    }
+
+    pub fn fire_discipline(&self) -> &FireDiscipline {
+        &self.fire_discipline
+    }
+
+    pub fn triggered_order(&self) -> &Option<String> {
+        &self.triggered_order
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -173,6 +306,8 @@ pub struct SquadMemberStatus {
     under_fire_coefficient: f32,
     current: CurrentAction,
     leader: bool,
+    in_command_radius: bool,
+    traits: SoldierTraits,
 }
 
 // FIXME : this func is here because AmmunitionReserveStatus, Soldier, etc will have to move
@@ -200,6 +335,7 @@ fn ammunition_reserve_status(soldier: &Soldier) -> AmmunitionReserveStatus {
 impl SquadMemberStatus {
     pub fn from_soldier(
         battle_state: &BattleState,
+        config: &ServerConfig,
         squad: &SquadComposition,
         soldier: &Soldier,
     ) -> Self {
@@ -213,6 +349,8 @@ impl SquadMemberStatus {
             under_fire_coefficient: (*soldier.under_fire().value() as f32 / UNDER_FIRE_MAX as f32),
             current: CurrentAction::from_soldier(battle_state, squad, soldier),
             leader: battle_state.squad(soldier.squad_uuid()).leader() == soldier.uuid(),
+            in_command_radius: battle_state.soldier_is_in_command_radius(config, soldier),
+            traits: soldier.traits().clone(),
         }
     }
 
@@ -220,10 +358,22 @@ impl SquadMemberStatus {
         self.main_weapon.as_ref()
     }
 
+    /// Main weapon status, if it needs attention
+    pub fn weapon_status(&self) -> Option<WeaponStatus> {
+        self.main_weapon
+            .as_ref()
+            .map(|weapon| weapon.status())
+            .filter(|status| status != &WeaponStatus::Ok)
+    }
+
     pub fn health(&self) -> &Health {
         &self.health
     }
 
+    pub fn traits(&self) -> &SoldierTraits {
+        &self.traits
+    }
+
     pub fn magazines(&self) -> &[Magazine] {
         self.magazines.as_ref()
     }
@@ -244,6 +394,10 @@ impl SquadMemberStatus {
         self.leader
     }
 
+    pub fn in_command_radius(&self) -> bool {
+        self.in_command_radius
+    }
+
     pub fn soldier_index(&self) -> SoldierIndex {
         self.soldier_index
     }
@@ -264,6 +418,10 @@ pub enum CurrentAction {
     SuppressFiring,
     Aiming,
     Reloading,
+    ClearingJam,
+    ChangingBarrel,
+    SettingUp,
+    TearingDown,
     Defending,
     Hiding,
     Driving,
@@ -278,6 +436,16 @@ impl CurrentAction {
         soldier: &Soldier,
     ) -> Self {
         match soldier.behavior() {
+            Behavior::MoveTo(_) | Behavior::MoveFastTo(_) | Behavior::SneakTo(_)
+                if matches!(soldier.gesture(), Gesture::TearingDown(_, _)) =>
+            {
+                Self::TearingDown
+            }
+            Behavior::Defend(_) | Behavior::Hide(_)
+                if matches!(soldier.gesture(), Gesture::SettingUp(_, _)) =>
+            {
+                Self::SettingUp
+            }
             Behavior::MoveTo(_) => Self::Walking,
             Behavior::MoveFastTo(_) => Self::Running,
             Behavior::SneakTo(_) => Self::Crawling,
@@ -290,12 +458,20 @@ impl CurrentAction {
                 Gesture::Reloading(_, _) => Self::Reloading,
                 Gesture::Aiming(_, _) => Self::Aiming,
                 Gesture::Firing(_, _) => Self::SuppressFiring,
+                Gesture::ClearingJam(_, _) => Self::ClearingJam,
+                Gesture::ChangingBarrel(_, _) => Self::ChangingBarrel,
+                Gesture::SettingUp(_, _) => Self::SettingUp,
+                Gesture::TearingDown(_, _) => Self::TearingDown,
             },
             Behavior::EngageSoldier(_) => match soldier.gesture() {
                 Gesture::Idle => Self::Idle,
                 Gesture::Reloading(_, _) => Self::Reloading,
                 Gesture::Aiming(_, _) => Self::Aiming,
                 Gesture::Firing(_, _) => Self::TargetFiring,
+                Gesture::ClearingJam(_, _) => Self::ClearingJam,
+                Gesture::ChangingBarrel(_, _) => Self::ChangingBarrel,
+                Gesture::SettingUp(_, _) => Self::SettingUp,
+                Gesture::TearingDown(_, _) => Self::TearingDown,
             },
             Behavior::Idle(_) | Behavior::Dead | Behavior::Unconscious => Self::Idle,
         }
@@ -311,6 +487,10 @@ impl CurrentAction {
             CurrentAction::SuppressFiring => "suppress firing",
             CurrentAction::Aiming => "aiming",
             CurrentAction::Reloading => "reloading",
+            CurrentAction::ClearingJam => "clearing jam",
+            CurrentAction::ChangingBarrel => "changing barrel",
+            CurrentAction::SettingUp => "setting up",
+            CurrentAction::TearingDown => "tearing down",
             CurrentAction::Defending => "defending",
             CurrentAction::Hiding => "hiding",
             CurrentAction::Driving => "driving",
@@ -318,3 +498,99 @@ impl CurrentAction {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::PI;
+
+    use rstest::*;
+
+    use crate::types::Angle;
+
+    use super::*;
+
+    /// Squad of a leader (0) and four subordinates
+    fn squad() -> SquadComposition {
+        SquadComposition::new(
+            SoldierIndex(0),
+            SquadType::Type1,
+            (0..5).map(SoldierIndex).collect(),
+            None,
+        )
+    }
+
+    fn leader(looking_direction: f32) -> Soldier {
+        let mut leader = Soldier::new(
+            SoldierIndex(0),
+            SoldierType::Type1,
+            WorldPoint::new(100., 100.),
+            SquadUuid(0),
+            Side::A,
+            None,
+            vec![],
+        );
+        leader.set_looking_direction(Angle(looking_direction));
+        leader
+    }
+
+    /// Subordinates offsets from leader, rounded to avoid rotation float errors
+    fn offsets(positions: &HashMap<SoldierIndex, WorldPoint>, leader: &Soldier) -> Vec<(f32, f32)> {
+        (1..5)
+            .map(|i| {
+                let position = positions[&SoldierIndex(i)];
+                (
+                    (position.x - leader.world_point().x).round(),
+                    (position.y - leader.world_point().y).round(),
+                )
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case(Formation::Line, vec![(10., 0.), (-10., 0.), (20., 0.), (-20., 0.)])]
+    #[case(Formation::Column, vec![(5., 10.), (-5., 10.), (5., 20.), (-5., 20.)])]
+    #[case(Formation::Wedge, vec![(10., 10.), (-10., 10.), (20., 20.), (-20., 20.)])]
+    #[case(Formation::Echelon, vec![(10., 10.), (20., 20.), (30., 30.), (40., 40.)])]
+    #[case(Formation::File, vec![(0., 10.), (0., 20.), (0., 30.), (0., 40.)])]
+    fn formation_place_members_around_leader(
+        #[case] formation: Formation,
+        #[case] expected: Vec<(f32, f32)>,
+    ) {
+        let leader = leader(0.);
+
+        let positions = squad_positions(&squad(), &formation, &leader, None, 10.);
+
+        assert_eq!(positions.len(), 4);
+        assert_eq!(offsets(&positions, &leader), expected);
+    }
+
+    #[rstest]
+    #[case(PI / 2., vec![(0., 10.), (0., -10.), (0., 20.), (0., -20.)])]
+    #[case(PI, vec![(-10., 0.), (10., 0.), (-20., 0.), (20., 0.)])]
+    fn formation_follow_leader_looking_direction(
+        #[case] looking_direction: f32,
+        #[case] expected: Vec<(f32, f32)>,
+    ) {
+        let leader = leader(looking_direction);
+
+        let positions = squad_positions(&squad(), &Formation::Line, &leader, None, 10.);
+
+        assert_eq!(offsets(&positions, &leader), expected);
+    }
+
+    #[test]
+    fn formation_is_placed_around_given_point() {
+        let leader = leader(0.);
+
+        let positions = squad_positions(
+            &squad(),
+            &Formation::File,
+            &leader,
+            Some(WorldPoint::new(200., 50.)),
+            10.,
+        );
+
+        assert_eq!(positions[&SoldierIndex(1)], WorldPoint::new(200., 60.));
+        assert_eq!(positions[&SoldierIndex(4)], WorldPoint::new(200., 90.));
+    }
+}
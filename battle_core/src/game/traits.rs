@@ -0,0 +1,208 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::behavior::feeling::{UnderFireThresholds, UNDER_FIRE_MAX};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum Experience {
+    Green,
+    #[default]
+    Regular,
+    Veteran,
+}
+
+impl Experience {
+    pub fn short_name(&self) -> &str {
+        match self {
+            Experience::Green => "Grn",
+            Experience::Regular => "Reg",
+            Experience::Veteran => "Vet",
+        }
+    }
+
+    // Factor applied to delays before soldier react (aiming)
+    fn reaction_factor(&self) -> f32 {
+        match self {
+            Experience::Green => 1.5,
+            Experience::Regular => 1.0,
+            Experience::Veteran => 0.6,
+        }
+    }
+
+    // Factor applied to weapon reloading duration
+    fn reloading_factor(&self) -> f32 {
+        match self {
+            Experience::Green => 1.3,
+            Experience::Regular => 1.0,
+            Experience::Veteran => 0.8,
+        }
+    }
+
+    // Factor applied to aim alteration
+    fn aim_factor(&self) -> f32 {
+        match self {
+            Experience::Green => 1.3,
+            Experience::Regular => 1.0,
+            Experience::Veteran => 0.8,
+        }
+    }
+
+    // Factor applied to under fire thresholds
+    fn nerve_factor(&self) -> f32 {
+        match self {
+            Experience::Green => 0.85,
+            Experience::Regular => 1.0,
+            Experience::Veteran => 1.15,
+        }
+    }
+}
+
+/// Individual soldier attributes, given at deployment. Marksmanship and courage are from 0.0 to
+/// 1.0, 0.5 being an average soldier.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SoldierTraits {
+    pub experience: Experience,
+    pub marksmanship: f32,
+    pub courage: f32,
+}
+
+impl Default for SoldierTraits {
+    fn default() -> Self {
+        Self {
+            experience: Experience::Regular,
+            marksmanship: 0.5,
+            courage: 0.5,
+        }
+    }
+}
+
+impl SoldierTraits {
+    pub fn new(experience: Experience, marksmanship: f32, courage: f32) -> Self {
+        Self {
+            experience,
+            marksmanship,
+            courage,
+        }
+    }
+
+    /// Ensure marksmanship and courage are in their 0.0 to 1.0 range
+    pub fn validate(&self) -> Result<(), SoldierTraitsError> {
+        if !(0.0..=1.0).contains(&self.marksmanship) {
+            return Err(SoldierTraitsError::Marksmanship(self.marksmanship));
+        }
+        if !(0.0..=1.0).contains(&self.courage) {
+            return Err(SoldierTraitsError::Courage(self.courage));
+        }
+
+        Ok(())
+    }
+
+    /// Factor applied to target point alteration (from 0.5 for best marksman to 1.5 for worst)
+    pub fn aim_alteration_factor(&self) -> f32 {
+        (1.5 - self.marksmanship.clamp(0., 1.)) * self.experience.aim_factor()
+    }
+
+    pub fn reaction_factor(&self) -> f32 {
+        self.experience.reaction_factor()
+    }
+
+    pub fn reloading_factor(&self) -> f32 {
+        self.experience.reloading_factor()
+    }
+
+    /// Brave and experienced soldiers need more fire before feeling in warning or danger
+    pub fn under_fire_thresholds(&self) -> UnderFireThresholds {
+        let factor = (0.75 + self.courage.clamp(0., 1.) * 0.5) * self.experience.nerve_factor();
+        let default = UnderFireThresholds::default();
+        let scale = |value: u32| ((value as f32 * factor) as u32).min(UNDER_FIRE_MAX - 1);
+
+        UnderFireThresholds {
+            warning: scale(default.warning),
+            danger: scale(default.danger),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SoldierTraitsError {
+    #[error("Marksmanship must be between 0.0 and 1.0 (got {0})")]
+    Marksmanship(f32),
+    #[error("Courage must be between 0.0 and 1.0 (got {0})")]
+    Courage(f32),
+}
+
+impl Display for SoldierTraits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!(
+            "{} M{} C{}",
+            self.experience.short_name(),
+            (self.marksmanship * 100.).round(),
+            (self.courage * 100.).round()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Experience::Regular, 0.5, 1.0)]
+    #[case(Experience::Regular, 1.0, 0.5)]
+    #[case(Experience::Regular, 0.0, 1.5)]
+    #[case(Experience::Green, 0.0, 1.95)]
+    #[case(Experience::Veteran, 1.0, 0.4)]
+    // Out of range values are clamped
+    #[case(Experience::Regular, 2.0, 0.5)]
+    #[case(Experience::Regular, -1.0, 1.5)]
+    fn test_aim_alteration_factor(
+        #[case] experience: Experience,
+        #[case] marksmanship: f32,
+        #[case] expected: f32,
+    ) {
+        let traits = SoldierTraits::new(experience, marksmanship, 0.5);
+        assert!((traits.aim_alteration_factor() - expected).abs() < 0.0001);
+    }
+
+    #[rstest]
+    #[case(Experience::Regular, 0.5, 100, 150)]
+    #[case(Experience::Regular, 0.0, 75, 112)]
+    #[case(Experience::Regular, 1.0, 125, 187)]
+    #[case(Experience::Green, 0.0, 63, 95)]
+    // Danger threshold is capped below under fire max
+    #[case(Experience::Veteran, 1.0, 143, UNDER_FIRE_MAX - 1)]
+    // Out of range values are clamped
+    #[case(Experience::Regular, 2.0, 125, 187)]
+    #[case(Experience::Regular, -1.0, 75, 112)]
+    fn test_under_fire_thresholds(
+        #[case] experience: Experience,
+        #[case] courage: f32,
+        #[case] warning: u32,
+        #[case] danger: u32,
+    ) {
+        let traits = SoldierTraits::new(experience, 0.5, courage);
+        assert_eq!(
+            traits.under_fire_thresholds(),
+            UnderFireThresholds { warning, danger }
+        );
+    }
+
+    #[rstest]
+    #[case(0.5, 0.5, Ok(()))]
+    #[case(0.0, 1.0, Ok(()))]
+    #[case(1.2, 0.5, Err(SoldierTraitsError::Marksmanship(1.2)))]
+    #[case(0.5, -0.1, Err(SoldierTraitsError::Courage(-0.1)))]
+    fn test_validate(
+        #[case] marksmanship: f32,
+        #[case] courage: f32,
+        #[case] expected: Result<(), SoldierTraitsError>,
+    ) {
+        let traits = SoldierTraits::new(Experience::Regular, marksmanship, courage);
+        assert_eq!(traits.validate(), expected);
+    }
+}
@@ -0,0 +1,635 @@
+use std::{collections::HashMap, fs, io, path::Path, sync::OnceLock};
+
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{audio::Sound, config::TARGET_FPS};
+
+use super::{Ammunition, Magazine, MagazineType, Shot, Weapon, WeaponSprite, WeaponType};
+
+pub const WEAPONS_FILE_NAME: &str = "weapons.toml";
+// Used when no definitions file has been loaded (tools, examples, etc)
+const DEFAULT_DEFINITIONS: &str = include_str!("../../../../resources/weapons.toml");
+
+static DEFINITIONS: OnceLock<WeaponDefinitions> = OnceLock::new();
+
+/// How weapon is fed : bolt weapons chamber a bullet from their magazine after each shot,
+/// automatic ones fire bursts directly from their magazine
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum WeaponAction {
+    Bolt,
+    Automatic,
+}
+
+/// What weapon is effective against : anti-tank weapons only harm vehicles (and their crew)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum WeaponTarget {
+    #[default]
+    Infantry,
+    Vehicle,
+}
+
+/// Burst sizes by count of opponents around target : none, 1-2, 3-4, 5+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Bursts {
+    pub short: usize,
+    pub medium: usize,
+    pub long: usize,
+    pub very_long: usize,
+}
+
+impl Bursts {
+    pub fn size(&self, opponents_count: usize) -> usize {
+        match opponents_count {
+            5.. => self.very_long,
+            3..=4 => self.long,
+            1..=2 => self.medium,
+            _ => self.short,
+        }
+    }
+
+    fn sizes(&self) -> [usize; 4] {
+        [self.short, self.medium, self.long, self.very_long]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MagazineDefinition {
+    #[serde(rename = "type")]
+    pub type_: MagazineType,
+    pub name: String,
+    pub ammunition: Ammunition,
+    pub capacity: usize,
+    // Replace weapon bursts when this magazine is used (bigger belts, etc)
+    #[serde(default)]
+    pub bursts: Option<Bursts>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponDefinition {
+    #[serde(rename = "type")]
+    pub type_: WeaponType,
+    pub name: String,
+    pub action: WeaponAction,
+    #[serde(default)]
+    pub target: WeaponTarget,
+    // Ammunition when weapon have no magazine
+    pub ammunition: Ammunition,
+    pub magazines: Vec<MagazineType>,
+    // Under this count of accepted magazines, soldier ammunition reserve is low
+    pub ok_magazines: usize,
+    // Rounds per minute, used to space bullets of a burst
+    #[serde(default)]
+    pub rate_of_fire: Option<f32>,
+    #[serde(default)]
+    pub bursts: Option<Bursts>,
+    #[serde(default = "default_factor")]
+    pub burst_dispersion: f32,
+    // Meters, soldiers don't open fire beyond this distance
+    #[serde(default)]
+    pub range: Option<f32>,
+    // Seconds
+    pub reloading: f32,
+    pub aiming: f32,
+    pub firing: f32,
+    // (distance in meters, inaccuracy factor) points
+    #[serde(default)]
+    pub accuracy: Vec<(f32, f32)>,
+    // Inaccuracy factor applied when soldier hold a defend order (steady position, scope)
+    #[serde(default = "default_factor")]
+    pub defend_accuracy: f32,
+    // Barrel heat (1.0 is overheated, weapon can't fire) gained by each bullet
+    #[serde(default)]
+    pub heat_by_shot: f32,
+    // Barrel heat lost by second
+    #[serde(default)]
+    pub cooling: f32,
+    // Jam chance by burst with cold barrel, raising to `overheated_jam_chance` with heat
+    #[serde(default)]
+    pub jam_chance: f32,
+    #[serde(default)]
+    pub overheated_jam_chance: f32,
+    // Seconds
+    #[serde(default = "default_jam_clearing")]
+    pub jam_clearing: f32,
+    // Seconds, weapon can only cool down if None
+    #[serde(default)]
+    pub barrel_change: Option<f32>,
+    // Served by a gunner and an assistant feeding it (faster reloading)
+    #[serde(default)]
+    pub crew_served: bool,
+    // Seconds, crew-served weapon must be set up before firing and torn down before moving
+    #[serde(default)]
+    pub setup: f32,
+    // Seconds, setup when engaging while moving (on unprepared position), `setup` if None
+    #[serde(default)]
+    pub hasty_setup: Option<f32>,
+    #[serde(default)]
+    pub teardown: f32,
+    pub sprite: WeaponSprite,
+    pub fire_sounds: Vec<Sound>,
+    // Sound by burst size (first is for one bullet), fire sounds are used if empty
+    #[serde(default)]
+    pub burst_sounds: Vec<Sound>,
+    pub reload_sounds: Vec<Sound>,
+}
+
+fn default_factor() -> f32 {
+    1.0
+}
+
+fn default_jam_clearing() -> f32 {
+    3.0
+}
+
+impl WeaponDefinition {
+    pub fn reloading_frames(&self) -> u64 {
+        seconds_to_frames(self.reloading)
+    }
+
+    pub fn aiming_frames(&self) -> u64 {
+        seconds_to_frames(self.aiming)
+    }
+
+    pub fn firing_frames(&self) -> u64 {
+        seconds_to_frames(self.firing)
+    }
+
+    pub fn setup_frames(&self) -> u64 {
+        seconds_to_frames(self.setup)
+    }
+
+    pub fn hasty_setup_frames(&self) -> u64 {
+        seconds_to_frames(self.hasty_setup.unwrap_or(self.setup))
+    }
+
+    pub fn teardown_frames(&self) -> u64 {
+        seconds_to_frames(self.teardown)
+    }
+
+    pub fn jam_clearing_frames(&self) -> u64 {
+        seconds_to_frames(self.jam_clearing)
+    }
+
+    pub fn barrel_change_frames(&self) -> Option<u64> {
+        self.barrel_change.map(seconds_to_frames)
+    }
+
+    /// Jam chance of a burst fired with given heat
+    pub fn jam_chance(&self, heat: f32) -> f32 {
+        self.jam_chance + (self.overheated_jam_chance - self.jam_chance) * heat.clamp(0., 1.)
+    }
+
+    pub fn frame_offset_on_burst(&self) -> u64 {
+        match self.rate_of_fire {
+            Some(rate_of_fire) if rate_of_fire > 0. => {
+                (TARGET_FPS as f32 / (rate_of_fire / 60.)) as u64
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn in_range(&self, meters: f32) -> bool {
+        self.range.map(|range| meters <= range).unwrap_or(true)
+    }
+
+    /// Inaccuracy factor at given distance (1.0 if no accuracy curve)
+    pub fn accuracy_factor(&self, meters: f32) -> f32 {
+        let (first, last) = match (self.accuracy.first(), self.accuracy.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return 1.0,
+        };
+        if meters <= first.0 {
+            return first.1;
+        }
+
+        for window in self.accuracy.windows(2) {
+            let ((from_meters, from_factor), (to_meters, to_factor)) = (window[0], window[1]);
+            if meters <= to_meters {
+                let progress = (meters - from_meters) / (to_meters - from_meters);
+                return from_factor + (to_factor - from_factor) * progress;
+            }
+        }
+
+        last.1
+    }
+
+    pub fn fire_sounds(&self, shot: &Shot) -> Vec<Sound> {
+        let sound = match self.burst_sounds.get(shot.count() - 1) {
+            Some(sound) => Some(sound),
+            None => self.fire_sounds.choose(&mut rand::thread_rng()),
+        };
+
+        sound.into_iter().cloned().collect()
+    }
+
+    pub fn reload_sounds(&self) -> Vec<Sound> {
+        self.reload_sounds
+            .choose(&mut rand::thread_rng())
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+}
+
+fn seconds_to_frames(seconds: f32) -> u64 {
+    (seconds * TARGET_FPS as f32).round() as u64
+}
+
+#[derive(Debug, Deserialize)]
+struct WeaponDefinitionsFile {
+    #[serde(default)]
+    magazine: Vec<MagazineDefinition>,
+    #[serde(default)]
+    weapon: Vec<WeaponDefinition>,
+}
+
+/// All known weapons and magazines. Loaded once (from `resources/weapons.toml`) and then
+/// reachable from weapon and magazine types.
+#[derive(Debug, Clone)]
+pub struct WeaponDefinitions {
+    magazines: HashMap<MagazineType, MagazineDefinition>,
+    weapons: HashMap<WeaponType, WeaponDefinition>,
+}
+
+impl WeaponDefinitions {
+    pub fn from_file(path: &Path) -> Result<Self, WeaponDefinitionsError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, WeaponDefinitionsError> {
+        let file: WeaponDefinitionsFile = toml::from_str(content)?;
+        let definitions = Self {
+            magazines: file
+                .magazine
+                .into_iter()
+                .map(|magazine| (magazine.type_.clone(), magazine))
+                .collect(),
+            weapons: file
+                .weapon
+                .into_iter()
+                .map(|weapon| (weapon.type_.clone(), weapon))
+                .collect(),
+        };
+        definitions.validate()?;
+
+        Ok(definitions)
+    }
+
+    fn validate(&self) -> Result<(), WeaponDefinitionsError> {
+        for magazine in self.magazines.values() {
+            if let Some(bursts) = &magazine.bursts {
+                validate_bursts(&magazine.type_.0, bursts)?;
+            }
+        }
+
+        for weapon in self.weapons.values() {
+            let name = &weapon.type_.0;
+            for magazine in &weapon.magazines {
+                if !self.magazines.contains_key(magazine) {
+                    return Err(WeaponDefinitionsError::UnknownMagazine(magazine.0.clone()));
+                }
+            }
+            if let Some(bursts) = &weapon.bursts {
+                validate_bursts(name, bursts)?;
+            }
+            if weapon.action == WeaponAction::Automatic && weapon.bursts.is_none() {
+                return Err(WeaponDefinitionsError::Invalid(
+                    name.clone(),
+                    "automatic weapon require bursts".to_string(),
+                ));
+            }
+            if weapon.fire_sounds.is_empty() || weapon.reload_sounds.is_empty() {
+                return Err(WeaponDefinitionsError::Invalid(
+                    name.clone(),
+                    "fire and reload sounds are required".to_string(),
+                ));
+            }
+            if weapon.range.is_some_and(|range| range <= 0.) {
+                return Err(WeaponDefinitionsError::Invalid(
+                    name.clone(),
+                    "range must be positive".to_string(),
+                ));
+            }
+            if [weapon.jam_chance, weapon.overheated_jam_chance]
+                .iter()
+                .any(|chance| !(0.0..=1.0).contains(chance))
+            {
+                return Err(WeaponDefinitionsError::Invalid(
+                    name.clone(),
+                    "jam chances must be between 0 and 1".to_string(),
+                ));
+            }
+            if (weapon.setup > 0. || weapon.teardown > 0. || weapon.hasty_setup.is_some())
+                && !weapon.crew_served
+            {
+                return Err(WeaponDefinitionsError::Invalid(
+                    name.clone(),
+                    "only crew-served weapons have setup and teardown".to_string(),
+                ));
+            }
+            if weapon
+                .hasty_setup
+                .is_some_and(|hasty_setup| hasty_setup < weapon.setup)
+            {
+                return Err(WeaponDefinitionsError::Invalid(
+                    name.clone(),
+                    "hasty setup can't be quicker than setup".to_string(),
+                ));
+            }
+            if weapon.heat_by_shot < 0. || weapon.cooling < 0. {
+                return Err(WeaponDefinitionsError::Invalid(
+                    name.clone(),
+                    "heat by shot and cooling can't be negative".to_string(),
+                ));
+            }
+            if weapon
+                .accuracy
+                .windows(2)
+                .any(|window| window[1].0 <= window[0].0)
+            {
+                return Err(WeaponDefinitionsError::Invalid(
+                    name.clone(),
+                    "accuracy distances must be increasing".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Make these definitions the ones used by weapons. Can be done only once, before any use.
+    pub fn install(self) -> Result<(), WeaponDefinitionsError> {
+        DEFINITIONS
+            .set(self)
+            .map_err(|_| WeaponDefinitionsError::AlreadyInstalled)
+    }
+
+    pub fn get() -> &'static Self {
+        DEFINITIONS.get_or_init(|| {
+            Self::from_toml(DEFAULT_DEFINITIONS).expect("Default weapon definitions must be valid")
+        })
+    }
+
+    pub fn weapon(&self, type_: &WeaponType) -> Option<&WeaponDefinition> {
+        self.weapons.get(type_)
+    }
+
+    pub fn magazine(&self, type_: &MagazineType) -> Option<&MagazineDefinition> {
+        self.magazines.get(type_)
+    }
+
+    /// Check soldier equipment (coming from files or network) only use known weapons and
+    /// magazines, as using unknown ones is not possible
+    pub fn validate_equipment(
+        &self,
+        weapon: Option<&Weapon>,
+        magazines: &[Magazine],
+    ) -> Result<(), WeaponDefinitionsError> {
+        if let Some(weapon) = weapon {
+            if self.weapon(weapon.type_()).is_none() {
+                return Err(WeaponDefinitionsError::UnknownWeapon(
+                    weapon.type_().0.clone(),
+                ));
+            }
+        }
+
+        for magazine in magazines
+            .iter()
+            .chain(weapon.and_then(|weapon| weapon.magazine().as_ref()))
+        {
+            if self.magazine(magazine.type_()).is_none() {
+                return Err(WeaponDefinitionsError::UnknownMagazine(
+                    magazine.type_().0.clone(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn weapons(&self) -> Vec<&WeaponDefinition> {
+        let mut weapons = self.weapons.values().collect::<Vec<_>>();
+        weapons.sort_by(|a, b| a.type_.0.cmp(&b.type_.0));
+        weapons
+    }
+}
+
+fn validate_bursts(name: &str, bursts: &Bursts) -> Result<(), WeaponDefinitionsError> {
+    if bursts
+        .sizes()
+        .iter()
+        .any(|size| Shot::try_from(*size).is_err())
+    {
+        return Err(WeaponDefinitionsError::Invalid(
+            name.to_string(),
+            "burst sizes must be between 1 and 16".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum WeaponDefinitionsError {
+    #[error("Error during file read : {0}")]
+    Io(#[from] io::Error),
+    #[error("TOML read error : {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Unknown magazine : {0}")]
+    UnknownMagazine(String),
+    #[error("Unknown weapon : {0}")]
+    UnknownWeapon(String),
+    #[error("Invalid definition of {0} : {1}")]
+    Invalid(String, String),
+    #[error("Weapon definitions are already installed")]
+    AlreadyInstalled,
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::*;
+
+    use super::*;
+
+    const MAGAZINES: &str = r#"
+[[magazine]]
+type = "Belt"
+name = "Belt"
+ammunition = "7.92×57mm"
+capacity = 50
+"#;
+
+    fn definitions(weapon: &str) -> Result<WeaponDefinitions, WeaponDefinitionsError> {
+        WeaponDefinitions::from_toml(&format!(
+            r#"{}
+[[weapon]]
+type = "Gun"
+name = "Gun"
+ammunition = "7.92×57mm"
+magazines = ["Belt"]
+ok_magazines = 2
+reloading = 1.0
+aiming = 1.0
+firing = 1.0
+sprite = "Riffle"
+fire_sounds = ["MauserRiffleFire1"]
+reload_sounds = ["ReloadGeneric1"]
+{}
+"#,
+            MAGAZINES, weapon
+        ))
+    }
+
+    #[test]
+    fn default_definitions_are_valid() {
+        let definitions = WeaponDefinitions::from_toml(DEFAULT_DEFINITIONS).unwrap();
+        assert!(definitions
+            .weapon(&WeaponType::new("MosinNagantM1924"))
+            .is_some());
+        assert!(definitions
+            .magazine(&MagazineType::new("MosinNagant"))
+            .is_some());
+    }
+
+    #[rstest]
+    #[case(0., 1.0)]
+    #[case(50., 1.0)]
+    #[case(100., 1.0)]
+    #[case(150., 1.5)]
+    #[case(200., 2.0)]
+    #[case(400., 4.0)]
+    #[case(1000., 4.0)]
+    fn accuracy_curve(#[case] meters: f32, #[case] expected: f32) {
+        let definitions = definitions(
+            r#"action = "Bolt"
+accuracy = [[100.0, 1.0], [200.0, 2.0], [400.0, 4.0]]"#,
+        )
+        .unwrap();
+        let weapon = definitions.weapon(&WeaponType::new("Gun")).unwrap();
+        assert_eq!(weapon.accuracy_factor(meters), expected);
+    }
+
+    #[test]
+    fn no_accuracy_curve() {
+        let definitions = definitions(r#"action = "Bolt""#).unwrap();
+        let weapon = definitions.weapon(&WeaponType::new("Gun")).unwrap();
+        assert_eq!(weapon.accuracy_factor(500.), 1.0);
+    }
+
+    #[rstest]
+    #[case(0, 2)]
+    #[case(2, 4)]
+    #[case(4, 8)]
+    #[case(12, 16)]
+    fn burst_size(#[case] opponents_count: usize, #[case] expected: usize) {
+        let definitions = definitions(
+            r#"action = "Automatic"
+bursts = { short = 2, medium = 4, long = 8, very_long = 16 }"#,
+        )
+        .unwrap();
+        let weapon = definitions.weapon(&WeaponType::new("Gun")).unwrap();
+        assert_eq!(weapon.bursts.unwrap().size(opponents_count), expected);
+    }
+
+    #[rstest]
+    #[case(r#"action = "Automatic""#)]
+    #[case(
+        r#"action = "Automatic"
+bursts = { short = 0, medium = 4, long = 8, very_long = 16 }"#
+    )]
+    #[case(
+        r#"action = "Automatic"
+bursts = { short = 2, medium = 4, long = 8, very_long = 17 }"#
+    )]
+    #[case(
+        r#"action = "Bolt"
+accuracy = [[200.0, 2.0], [100.0, 1.0]]"#
+    )]
+    #[case(
+        r#"action = "Bolt"
+range = 0.0"#
+    )]
+    #[case(
+        r#"action = "Bolt"
+jam_chance = 1.5"#
+    )]
+    #[case(
+        r#"action = "Bolt"
+setup = 2.0"#
+    )]
+    #[case(
+        r#"action = "Bolt"
+crew_served = true
+setup = 4.0
+hasty_setup = 2.0"#
+    )]
+    fn invalid_definition(#[case] weapon: &str) {
+        assert!(matches!(
+            definitions(weapon),
+            Err(WeaponDefinitionsError::Invalid(_, _))
+        ));
+    }
+
+    #[rstest]
+    #[case("setup = 4.0", 4., 4.)]
+    #[case("setup = 4.0\nhasty_setup = 7.0", 4., 7.)]
+    fn setup_duration(#[case] setup: &str, #[case] expected: f32, #[case] expected_hasty: f32) {
+        let definitions =
+            definitions(&format!("action = \"Bolt\"\ncrew_served = true\n{}", setup)).unwrap();
+        let weapon = definitions.weapon(&WeaponType::new("Gun")).unwrap();
+        assert_eq!(weapon.setup_frames(), seconds_to_frames(expected));
+        assert_eq!(
+            weapon.hasty_setup_frames(),
+            seconds_to_frames(expected_hasty)
+        );
+    }
+
+    #[test]
+    fn unknown_magazine() {
+        let definitions = WeaponDefinitions::from_toml(
+            r#"[[weapon]]
+type = "Gun"
+name = "Gun"
+action = "Bolt"
+ammunition = "7.92×57mm"
+magazines = ["Unknown"]
+ok_magazines = 2
+reloading = 1.0
+aiming = 1.0
+firing = 1.0
+sprite = "Riffle"
+fire_sounds = ["MauserRiffleFire1"]
+reload_sounds = ["ReloadGeneric1"]
+"#,
+        );
+        assert!(matches!(
+            definitions,
+            Err(WeaponDefinitionsError::UnknownMagazine(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_equipment() {
+        let definitions = definitions(r#"action = "Bolt""#).unwrap();
+        let belt = Magazine::new(MagazineType::new("Belt"), 50);
+        let unknown = Magazine::new(MagazineType::new("Unknown"), 5);
+        let gun = Weapon::new(WeaponType::new("Gun"), false, None);
+
+        assert!(definitions
+            .validate_equipment(Some(&gun), std::slice::from_ref(&belt))
+            .is_ok());
+        assert!(matches!(
+            definitions.validate_equipment(Some(&gun), &[belt, unknown]),
+            Err(WeaponDefinitionsError::UnknownMagazine(_))
+        ));
+        assert!(matches!(
+            definitions.validate_equipment(
+                Some(&Weapon::new(WeaponType::new("Unknown"), false, None)),
+                &[]
+            ),
+            Err(WeaponDefinitionsError::UnknownWeapon(_))
+        ));
+    }
+}
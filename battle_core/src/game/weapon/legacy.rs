@@ -0,0 +1,141 @@
+//! Before weapon definitions file, weapons and magazines were enums and written (in deployment
+//! files) like `{"MosinNagantM1924": [false, {"MosinNagant": 5}]}` or
+//! `{"BrenMark2": {"BrenCurved30": 30}}`. Human readable formats accept both shapes, binary ones
+//! (network, saves) only the current one.
+use std::collections::HashMap;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Magazine, MagazineType, Weapon, WeaponType};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MagazineFormat {
+    Current(#[serde(with = "Magazine")] Magazine),
+    // Magazine type with its bullets count
+    Legacy(HashMap<MagazineType, usize>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LegacyWeaponState {
+    // Bullet chambered, magazine (bolt weapons)
+    Bolt(bool, Option<Magazine>),
+    // Magazine (automatic weapons)
+    Automatic(Option<Magazine>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WeaponFormat {
+    Current(#[serde(with = "Weapon")] Weapon),
+    Legacy(HashMap<WeaponType, LegacyWeaponState>),
+}
+
+fn single<K, V>(map: HashMap<K, V>) -> Option<(K, V)> {
+    if map.len() != 1 {
+        return None;
+    }
+
+    map.into_iter().next()
+}
+
+impl Serialize for Magazine {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Magazine::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Magazine {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            return Magazine::deserialize(deserializer);
+        }
+
+        match MagazineFormat::deserialize(deserializer)? {
+            MagazineFormat::Current(magazine) => Ok(magazine),
+            MagazineFormat::Legacy(legacy) => single(legacy)
+                .map(|(type_, count)| Magazine::new(type_, count))
+                .ok_or_else(|| D::Error::custom("legacy magazine must have exactly one type")),
+        }
+    }
+}
+
+impl Serialize for Weapon {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Weapon::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Weapon {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            return Weapon::deserialize(deserializer);
+        }
+
+        match WeaponFormat::deserialize(deserializer)? {
+            WeaponFormat::Current(weapon) => Ok(weapon),
+            WeaponFormat::Legacy(legacy) => single(legacy)
+                .map(|(type_, state)| match state {
+                    LegacyWeaponState::Bolt(ready, magazine) => Weapon::new(type_, ready, magazine),
+                    LegacyWeaponState::Automatic(magazine) => Weapon::new(type_, false, magazine),
+                })
+                .ok_or_else(|| D::Error::custom("legacy weapon must have exactly one type")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case(r#"{"MosinNagantM1924": [false, {"MosinNagant": 5}]}"#, "MosinNagantM1924", false, Some(("MosinNagant", 5)))]
+    #[case(r#"{"MauserG41": [true, null]}"#, "MauserG41", true, None)]
+    #[case(r#"{"BrenMark2": {"BrenCurved30": 30}}"#, "BrenMark2", false, Some(("BrenCurved30", 30)))]
+    #[case(r#"{"Mg34": null}"#, "Mg34", false, None)]
+    #[case(r#"{"type_": "Mg34", "ready": false, "magazine": {"type_": "Patronengurtx792x57s50", "count": 12}}"#, "Mg34", false, Some(("Patronengurtx792x57s50", 12)))]
+    fn weapon_from_json(
+        #[case] json: &str,
+        #[case] type_: &str,
+        #[case] ready: bool,
+        #[case] magazine: Option<(&str, usize)>,
+    ) {
+        let expected = Weapon::new(
+            WeaponType::new(type_),
+            ready,
+            magazine.map(|(type_, count)| Magazine::new(MagazineType::new(type_), count)),
+        );
+        assert_eq!(serde_json::from_str::<Weapon>(json).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(r#"{"MosinNagant": 5}"#, "MosinNagant", 5)]
+    #[case(r#"{"type_": "Mauser", "count": 3}"#, "Mauser", 3)]
+    fn magazine_from_json(#[case] json: &str, #[case] type_: &str, #[case] count: usize) {
+        assert_eq!(
+            serde_json::from_str::<Magazine>(json).unwrap(),
+            Magazine::new(MagazineType::new(type_), count)
+        );
+    }
+
+    #[rstest]
+    #[case(r#"{}"#)]
+    #[case(r#"{"MosinNagant": 5, "Mauser": 5}"#)]
+    fn invalid_magazine_from_json(#[case] json: &str) {
+        assert!(serde_json::from_str::<Magazine>(json).is_err());
+    }
+
+    #[test]
+    fn weapon_binary_round_trip() {
+        let weapon = Weapon::new(
+            WeaponType::new("BrenMark2"),
+            false,
+            Some(Magazine::new(MagazineType::new("BrenCurved30"), 30)),
+        );
+        let bytes = bincode::serialize(&weapon).unwrap();
+        assert_eq!(bincode::deserialize::<Weapon>(&bytes).unwrap(), weapon);
+    }
+}
@@ -0,0 +1,608 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audio::Sound,
+    graphics::{cannon_blast::CannonBlastAnimationType, Sprite},
+};
+
+use self::definition::{
+    MagazineDefinition, WeaponAction, WeaponDefinition, WeaponDefinitions, WeaponTarget,
+};
+
+pub mod definition;
+mod legacy;
+
+// From this heat, weapon is displayed as hot
+pub const HOT_WEAPON_HEAT: f32 = 0.5;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+pub struct Ammunition(pub String);
+
+impl Display for Ammunition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Magazine type, as named in weapon definitions file
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+pub struct MagazineType(pub String);
+
+impl MagazineType {
+    pub fn new(type_: &str) -> Self {
+        Self(type_.to_string())
+    }
+
+    pub fn definition(&self) -> &'static MagazineDefinition {
+        WeaponDefinitions::get()
+            .magazine(self)
+            .unwrap_or_else(|| panic!("Unknown magazine type {}", self.0))
+    }
+}
+
+/// Weapon type, as named in weapon definitions file
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+pub struct WeaponType(pub String);
+
+impl WeaponType {
+    pub fn new(type_: &str) -> Self {
+        Self(type_.to_string())
+    }
+
+    pub fn definition(&self) -> &'static WeaponDefinition {
+        WeaponDefinitions::get()
+            .weapon(self)
+            .unwrap_or_else(|| panic!("Unknown weapon type {}", self.0))
+    }
+}
+
+// Deserialize is implemented in `legacy` to accept files written before weapon definitions file
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(remote = "Self")]
+pub struct Magazine {
+    type_: MagazineType,
+    count: usize,
+}
+
+impl Magazine {
+    pub fn new(type_: MagazineType, count: usize) -> Self {
+        Self { type_, count }
+    }
+
+    pub fn full(type_: MagazineType) -> Self {
+        let capacity = type_.definition().capacity;
+        Self::new(type_, capacity)
+    }
+
+    pub fn type_(&self) -> &MagazineType {
+        &self.type_
+    }
+
+    pub fn definition(&self) -> &'static MagazineDefinition {
+        self.type_.definition()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.definition().name
+    }
+
+    pub fn ammunition(&self) -> Ammunition {
+        self.definition().ammunition.clone()
+    }
+
+    pub fn filled(&self) -> bool {
+        self.count > 0
+    }
+
+    fn remove(&mut self, count: usize) {
+        if self.count < count {
+            eprintln!(
+                "Tried to remove {} bullet from {} magazine with {} ammo ",
+                count,
+                self.name(),
+                self.count
+            )
+        }
+
+        self.count = self.count.saturating_sub(count);
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum WeaponSprite {
+    Riffle,
+}
+
+impl WeaponSprite {
+    pub fn prefix(&self) -> &str {
+        match self {
+            WeaponSprite::Riffle => "/weapon_riffle",
+        }
+    }
+
+    pub fn sprite(&self) -> Box<dyn Sprite> {
+        let animation_type = match self {
+            WeaponSprite::Riffle => CannonBlastAnimationType::RiffleOneShotOnLying,
+        };
+        Box::new(animation_type)
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Shot {
+    x1,
+    x2,
+    x3,
+    x4,
+    x5,
+    x6,
+    x7,
+    x8,
+    x9,
+    x10,
+    x11,
+    x12,
+    x13,
+    x14,
+    x15,
+    x16,
+}
+impl Shot {
+    pub fn count(&self) -> usize {
+        match self {
+            Shot::x1 => 1,
+            Shot::x2 => 2,
+            Shot::x3 => 3,
+            Shot::x4 => 4,
+            Shot::x5 => 5,
+            Shot::x6 => 6,
+            Shot::x7 => 7,
+            Shot::x8 => 8,
+            Shot::x9 => 9,
+            Shot::x10 => 10,
+            Shot::x11 => 11,
+            Shot::x12 => 12,
+            Shot::x13 => 13,
+            Shot::x14 => 14,
+            Shot::x15 => 15,
+            Shot::x16 => 16,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ShotFromIntError(usize);
+
+impl TryFrom<usize> for Shot {
+    type Error = ShotFromIntError;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Shot::x1),
+            2 => Ok(Shot::x2),
+            3 => Ok(Shot::x3),
+            4 => Ok(Shot::x4),
+            5 => Ok(Shot::x5),
+            6 => Ok(Shot::x6),
+            7 => Ok(Shot::x7),
+            8 => Ok(Shot::x8),
+            9 => Ok(Shot::x9),
+            10 => Ok(Shot::x10),
+            11 => Ok(Shot::x11),
+            12 => Ok(Shot::x12),
+            13 => Ok(Shot::x13),
+            14 => Ok(Shot::x14),
+            15 => Ok(Shot::x15),
+            16 => Ok(Shot::x16),
+            _ => Err(ShotFromIntError(value)),
+        }
+    }
+}
+
+/// Weapon condition, as displayed to player
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeaponStatus {
+    Ok,
+    // Heat percentage
+    Hot(u8),
+    Overheated,
+    Jammed,
+}
+
+impl Display for WeaponStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeaponStatus::Ok => f.write_str("ok"),
+            WeaponStatus::Hot(percent) => f.write_str(&format!("hot {}%", percent)),
+            WeaponStatus::Overheated => f.write_str("overheated"),
+            WeaponStatus::Jammed => f.write_str("jammed"),
+        }
+    }
+}
+
+// Deserialize is implemented in `legacy` to accept files written before weapon definitions file
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(remote = "Self")]
+pub struct Weapon {
+    type_: WeaponType,
+    // Bullet chambered (bolt weapons)
+    ready: bool,
+    magazine: Option<Magazine>,
+    // Barrel heat, from 0.0 (cold) to 1.0 (overheated)
+    #[serde(default)]
+    heat: f32,
+    #[serde(default)]
+    jammed: bool,
+    // Set up (crew-served weapons)
+    #[serde(default)]
+    deployed: bool,
+}
+
+impl Weapon {
+    pub fn new(type_: WeaponType, ready: bool, magazine: Option<Magazine>) -> Self {
+        Self {
+            type_,
+            ready,
+            magazine,
+            heat: 0.,
+            jammed: false,
+            deployed: false,
+        }
+    }
+
+    pub fn type_(&self) -> &WeaponType {
+        &self.type_
+    }
+
+    pub fn definition(&self) -> &'static WeaponDefinition {
+        self.type_.definition()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.definition().name
+    }
+
+    pub fn reload_sounds(&self) -> Vec<Sound> {
+        self.definition().reload_sounds()
+    }
+
+    pub fn magazine(&self) -> &Option<Magazine> {
+        &self.magazine
+    }
+
+    pub fn accepted_magazine(&self, magazine: &Magazine) -> bool {
+        self.definition().magazines.contains(magazine.type_())
+    }
+
+    pub fn ammunition(&self) -> Ammunition {
+        if let Some(magazine) = self.magazine() {
+            return magazine.ammunition();
+        }
+
+        // Default value
+        self.definition().ammunition.clone()
+    }
+
+    pub fn can_fire(&self) -> bool {
+        if self.jammed || self.overheated() {
+            return false;
+        }
+
+        match self.definition().action {
+            WeaponAction::Bolt => self.ready,
+            WeaponAction::Automatic => self
+                .magazine
+                .as_ref()
+                .map(|magazine| magazine.filled())
+                .unwrap_or(false),
+        }
+    }
+
+    // TODO: not working for all weapon rifle/machine guns
+    pub fn can_reload(&self) -> bool {
+        match self.definition().action {
+            WeaponAction::Bolt => {
+                if let Some(magazine) = &self.magazine {
+                    return magazine.filled();
+                }
+            }
+            // No bullet reload
+            WeaponAction::Automatic => {}
+        }
+
+        false
+    }
+
+    // TODO: not working for all weapon rifle/machine guns
+    pub fn reload(&mut self) {
+        match self.definition().action {
+            WeaponAction::Bolt => {
+                if !self.ready {
+                    if let Some(magazine) = &mut self.magazine {
+                        if magazine.filled() {
+                            magazine.remove(1);
+                            self.ready = true;
+                        }
+
+                        if !magazine.filled() {
+                            self.magazine = None;
+                        }
+                    }
+                }
+            }
+            WeaponAction::Automatic => {}
+        }
+    }
+
+    pub fn shot(&mut self, shot: &Shot) {
+        self.heat = (self.heat + self.definition().heat_by_shot * shot.count() as f32).min(1.);
+
+        match self.definition().action {
+            WeaponAction::Bolt => self.ready = false,
+            WeaponAction::Automatic => {
+                if let Some(magazine) = &mut self.magazine {
+                    magazine.remove(shot.count())
+                }
+            }
+        }
+
+        if let Some(magazine) = &self.magazine {
+            if !magazine.filled() {
+                self.magazine = None
+            }
+        }
+    }
+
+    pub fn set_magazine(&mut self, new_magazine: Magazine) {
+        self.magazine = Some(new_magazine)
+    }
+
+    pub fn heat(&self) -> f32 {
+        self.heat
+    }
+
+    pub fn overheated(&self) -> bool {
+        self.heat >= 1.
+    }
+
+    pub fn jammed(&self) -> bool {
+        self.jammed
+    }
+
+    /// Chance of the next burst to jam weapon
+    pub fn jam_chance(&self) -> f32 {
+        self.definition().jam_chance(self.heat)
+    }
+
+    pub fn jam(&mut self) {
+        self.jammed = true
+    }
+
+    pub fn clear_jam(&mut self) {
+        self.jammed = false
+    }
+
+    pub fn can_change_barrel(&self) -> bool {
+        self.definition().barrel_change.is_some()
+    }
+
+    pub fn change_barrel(&mut self) {
+        self.heat = 0.
+    }
+
+    pub fn cool(&mut self, seconds: f32) {
+        self.heat = (self.heat - self.definition().cooling * seconds).max(0.)
+    }
+
+    pub fn crew_served(&self) -> bool {
+        self.definition().crew_served
+    }
+
+    /// Weapon must be set up before firing
+    pub fn need_setup(&self) -> bool {
+        self.crew_served() && self.definition().setup > 0. && !self.deployed
+    }
+
+    pub fn deployed(&self) -> bool {
+        self.deployed
+    }
+
+    pub fn set_deployed(&mut self, value: bool) {
+        self.deployed = value
+    }
+
+    pub fn status(&self) -> WeaponStatus {
+        if self.jammed {
+            WeaponStatus::Jammed
+        } else if self.overheated() {
+            WeaponStatus::Overheated
+        } else if self.heat >= HOT_WEAPON_HEAT {
+            WeaponStatus::Hot((self.heat * 100.) as u8)
+        } else {
+            WeaponStatus::Ok
+        }
+    }
+
+    // TODO: according to magazine type (mixed ?)
+    pub fn ok_count_magazines(&self) -> usize {
+        self.definition().ok_magazines
+    }
+
+    pub fn sprite_type(&self) -> WeaponSprite {
+        self.definition().sprite.clone()
+    }
+
+    pub fn shot_type(&self, opponents_count: usize) -> Shot {
+        let magazine = match &self.magazine {
+            Some(magazine) => magazine,
+            None => {
+                eprintln!("Tried to determine shot on weapon without magazine");
+                return Shot::x1;
+            }
+        };
+        let definition = self.definition();
+        if definition.action == WeaponAction::Bolt {
+            return Shot::x1;
+        }
+
+        let size = match magazine.definition().bursts.or(definition.bursts) {
+            Some(bursts) => bursts.size(opponents_count).min(magazine.count()),
+            None => 1,
+        };
+        match Shot::try_from(size) {
+            Ok(shot) => shot,
+            Err(err) => {
+                eprintln!(
+                    "Tried to find shot on {} but not shot for value {}",
+                    self.name(),
+                    err.0
+                );
+                Shot::x1
+            }
+        }
+    }
+
+    pub fn frame_offset_on_burst(&self) -> u64 {
+        self.definition().frame_offset_on_burst()
+    }
+
+    pub fn range_on_burst(&self) -> f32 {
+        // FIXME: depend on experience, tiredness, etc
+        self.definition().burst_dispersion
+    }
+
+    pub fn target(&self) -> WeaponTarget {
+        self.definition().target
+    }
+
+    pub fn in_range(&self, meters: f32) -> bool {
+        self.definition().in_range(meters)
+    }
+
+    /// Inaccuracy factor of this weapon at given distance
+    pub fn accuracy_factor(&self, meters: f32) -> f32 {
+        self.definition().accuracy_factor(meters)
+    }
+
+    pub fn defend_accuracy_factor(&self) -> f32 {
+        self.definition().defend_accuracy
+    }
+
+    pub fn reloading_frames(&self) -> u64 {
+        self.definition().reloading_frames()
+    }
+
+    pub fn aiming_frames(&self) -> u64 {
+        self.definition().aiming_frames()
+    }
+
+    pub fn firing_frames(&self) -> u64 {
+        self.definition().firing_frames()
+    }
+
+    pub fn setup_frames(&self) -> u64 {
+        self.definition().setup_frames()
+    }
+
+    pub fn hasty_setup_frames(&self) -> u64 {
+        self.definition().hasty_setup_frames()
+    }
+
+    pub fn teardown_frames(&self) -> u64 {
+        self.definition().teardown_frames()
+    }
+
+    pub fn jam_clearing_frames(&self) -> u64 {
+        self.definition().jam_clearing_frames()
+    }
+
+    pub fn barrel_change_frames(&self) -> u64 {
+        self.definition().barrel_change_frames().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BELT: &str = "Patronengurtx792x57s250";
+
+    fn mg34() -> Weapon {
+        Weapon::new(
+            WeaponType::new("Mg34"),
+            true,
+            Some(Magazine::full(MagazineType::new(BELT))),
+        )
+    }
+
+    /// Fire long bursts (with a full belt each time) until weapon is overheated
+    fn overheat(weapon: &mut Weapon) {
+        while !weapon.overheated() {
+            weapon.set_magazine(Magazine::full(MagazineType::new(BELT)));
+            weapon.shot(&Shot::x16);
+        }
+        weapon.set_magazine(Magazine::full(MagazineType::new(BELT)));
+    }
+
+    #[test]
+    fn shots_heat_weapon_until_overheated() {
+        let mut weapon = mg34();
+        weapon.shot(&Shot::x10);
+        assert!(weapon.heat() > 0.);
+        assert_eq!(weapon.status(), WeaponStatus::Ok);
+
+        overheat(&mut weapon);
+        assert_eq!(weapon.heat(), 1.);
+        assert_eq!(weapon.status(), WeaponStatus::Overheated);
+        assert!(!weapon.can_fire());
+        assert_eq!(
+            weapon.jam_chance(),
+            weapon.definition().overheated_jam_chance
+        );
+    }
+
+    #[test]
+    fn overheated_weapon_cool_down() {
+        let mut weapon = mg34();
+        overheat(&mut weapon);
+
+        weapon.cool(10.);
+        assert!(weapon.can_fire());
+        assert!(matches!(weapon.status(), WeaponStatus::Hot(_)));
+
+        weapon.cool(1000.);
+        assert_eq!(weapon.heat(), 0.);
+        assert_eq!(weapon.status(), WeaponStatus::Ok);
+        assert_eq!(weapon.jam_chance(), weapon.definition().jam_chance);
+    }
+
+    #[test]
+    fn barrel_change_cool_weapon() {
+        let mut weapon = mg34();
+        overheat(&mut weapon);
+        assert!(weapon.can_change_barrel());
+
+        weapon.change_barrel();
+        assert_eq!(weapon.heat(), 0.);
+        assert!(weapon.can_fire());
+    }
+
+    #[test]
+    fn jammed_weapon_must_be_cleared() {
+        let mut weapon = mg34();
+        weapon.jam();
+        assert_eq!(weapon.status(), WeaponStatus::Jammed);
+        assert!(!weapon.can_fire());
+
+        weapon.clear_jam();
+        assert_eq!(weapon.status(), WeaponStatus::Ok);
+        assert!(weapon.can_fire());
+    }
+}
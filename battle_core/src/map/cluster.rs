@@ -0,0 +1,310 @@
+use std::collections::{HashSet, VecDeque};
+
+use glam::Vec2;
+use pathfinding::prelude::astar;
+
+use crate::{
+    physics::path::{Direction, PathMode},
+    types::GridPoint,
+};
+
+use super::Map;
+
+// Width and height (in tiles) of clusters
+pub const CLUSTER_SIZE: i32 = 16;
+
+/// Map tiles grouped by square clusters, each cluster being split in its connected parts (named
+/// nodes). A node is linked to the nodes of neighbor clusters which can be reached from it. This
+/// graph is a lot smaller than the tiles one : a path is first searched on it, then tiles path is
+/// only searched through the found nodes.
+#[derive(Debug)]
+pub struct Clusters {
+    // Node of each tile (None if tile can't be entered with this path mode)
+    tiles: Vec<Option<usize>>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug)]
+struct Node {
+    // Mean position of node tiles
+    center: Vec2,
+    // Mean cost to enter one of node tiles
+    cost: f32,
+    neighbors: HashSet<usize>,
+}
+
+/// Nodes crossed by a path found on clusters graph
+pub struct Corridor<'a> {
+    clusters: &'a Clusters,
+    nodes: HashSet<usize>,
+}
+
+impl Clusters {
+    pub fn new(map: &Map, path_mode: &PathMode) -> Self {
+        let mut tiles = vec![None; map.terrain_tiles().len()];
+        let mut nodes = vec![];
+        // Successors leaving their cluster, linked when all nodes are known
+        let mut exits = vec![];
+
+        for (i, tile) in map.terrain_tiles().iter().enumerate() {
+            let point = GridPoint::new(tile.x as i32, tile.y as i32);
+            if tiles[i].is_some() || !map.can_enter(&point, tile, path_mode) {
+                continue;
+            }
+
+            let node = nodes.len();
+            let cluster = cluster_of(&point);
+            let mut sum = Vec2::ZERO;
+            let mut cost = 0;
+            let mut count = 0;
+
+            tiles[i] = Some(node);
+            let mut queue = VecDeque::from([point]);
+            while let Some(point) = queue.pop_front() {
+                sum += point.to_vec2();
+                cost += tile_cost(map, &point, path_mode);
+                count += 1;
+
+                // Successors costs don't matter, so any direction can be used
+                for ((next, _), _) in map.successors(&(point, Direction::North), path_mode) {
+                    if cluster_of(&next) != cluster {
+                        exits.push((node, next));
+                        continue;
+                    }
+
+                    let j = (next.y * map.width() as i32 + next.x) as usize;
+                    if tiles[j].is_none() {
+                        tiles[j] = Some(node);
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            nodes.push(Node {
+                center: sum / count as f32,
+                cost: cost as f32 / count as f32,
+                neighbors: HashSet::new(),
+            });
+        }
+
+        for (node, next) in exits {
+            if let Some(neighbor) = tiles[(next.y * map.width() as i32 + next.x) as usize] {
+                nodes[node].neighbors.insert(neighbor);
+            }
+        }
+
+        Self { tiles, nodes }
+    }
+
+    /// Node of given point, None if it can't be entered
+    pub fn node(&self, map: &Map, point: &GridPoint) -> Option<usize> {
+        if !map.contains(point) {
+            return None;
+        }
+
+        self.tiles
+            .get((point.y * map.width() as i32 + point.x) as usize)
+            .cloned()
+            .flatten()
+    }
+
+    /// Points of given node tiles
+    pub fn node_points(&self, map: &Map, node: usize) -> Vec<GridPoint> {
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, node_)| **node_ == Some(node))
+            .map(|(i, _)| {
+                GridPoint::new(
+                    (i % map.width() as usize) as i32,
+                    (i / map.width() as usize) as i32,
+                )
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Nodes to cross to go from a point to another. None if one of these points can't be
+    /// entered or if there is no way between them.
+    pub fn corridor(
+        &self,
+        map: &Map,
+        from: &GridPoint,
+        to: &GridPoint,
+        heuristic_coefficient: f32,
+    ) -> Option<Corridor<'_>> {
+        let from = self.node(map, from)?;
+        let to = self.node(map, to)?;
+        let destination = self.nodes[to].center;
+
+        let (nodes, _) = astar(
+            &from,
+            |node| {
+                let node_ = &self.nodes[*node];
+                node_
+                    .neighbors
+                    .iter()
+                    .map(|neighbor| {
+                        let neighbor_ = &self.nodes[*neighbor];
+                        // Pedestrian and vehicle moves cost the same in diagonal
+                        let offset = (neighbor_.center - node_.center).abs();
+                        let cost = offset.max_element() * (node_.cost + neighbor_.cost) / 2.;
+                        (*neighbor, cost as i32)
+                    })
+                    .collect::<Vec<_>>()
+            },
+            |node| (self.nodes[*node].center.distance(destination) * heuristic_coefficient) as i32,
+            |node| *node == to,
+        )?;
+
+        Some(Corridor {
+            clusters: self,
+            nodes: nodes.into_iter().collect(),
+        })
+    }
+}
+
+impl<'a> Corridor<'a> {
+    pub fn contains(&self, map: &Map, point: &GridPoint) -> bool {
+        self.clusters
+            .node(map, point)
+            .is_some_and(|node| self.nodes.contains(&node))
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+fn cluster_of(point: &GridPoint) -> (i32, i32) {
+    (point.x / CLUSTER_SIZE, point.y / CLUSTER_SIZE)
+}
+
+fn tile_cost(map: &Map, point: &GridPoint, path_mode: &PathMode) -> i32 {
+    let tile = map
+        .terrain_tiles()
+        .get((point.y * map.width() as i32 + point.x) as usize)
+        .expect("Node tiles must be in map");
+    match path_mode {
+        PathMode::Walk | PathMode::Sneak => tile.pedestrian_cost(),
+        PathMode::Drive(_) => tile.type_().vehicle_cost(),
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::path::PathBuf;
+
+    use crate::{
+        map::{decor::Decor, terrain::TerrainTile},
+        types::Offset,
+    };
+
+    use super::*;
+    use crate::map::terrain::TileType;
+
+    /// Square map of grass with barbed wire (which can't be sneaked) on given points
+    pub fn map_with_wires(size: u32, wires: &[GridPoint]) -> Map {
+        let tiles = wires
+            .iter()
+            .map(|point| (*point, TileType::BarbedWire))
+            .collect::<Vec<_>>();
+        map_with_tiles(size, &tiles)
+    }
+
+    /// Square map of grass with given tile types on given points
+    pub fn map_with_tiles(size: u32, types: &[(GridPoint, TileType)]) -> Map {
+        let tiles = (0..size * size)
+            .map(|i| {
+                let (x, y) = (i % size, i / size);
+                let type_ = types
+                    .iter()
+                    .find(|(point, _)| point == &GridPoint::new(x as i32, y as i32))
+                    .map(|(_, type_)| type_.clone())
+                    .unwrap_or(TileType::ShortGrass);
+                TerrainTile::new(type_, 5, 5, 1., 1., x, y, 0, 0)
+            })
+            .collect();
+
+        Map::new(
+            "TestMap".to_string(),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            vec![],
+            vec![],
+            size,
+            size,
+            tiles,
+            5,
+            5,
+            Decor::new(vec![], vec![], Offset::new(0., 0.)),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn nodes_split_by_clusters_and_obstacles() {
+        // Wire column through the first cluster column, except at its bottom
+        let wires = (0..40).map(|y| GridPoint::new(8, y)).collect::<Vec<_>>();
+        let map = map_with_wires(48, &wires);
+
+        let clusters = Clusters::new(&map, &PathMode::Sneak);
+
+        // 3x3 clusters, two of them split in two by the wire
+        assert_eq!(clusters.len(), 11);
+        let left = clusters.node(&map, &GridPoint::new(2, 2));
+        let right = clusters.node(&map, &GridPoint::new(12, 2));
+        assert!(left.is_some());
+        assert_ne!(left, right);
+        assert_eq!(clusters.node(&map, &GridPoint::new(8, 2)), None);
+        // Wire stops in third cluster row, so it has only one node in its first cluster
+        assert_eq!(
+            clusters.node(&map, &GridPoint::new(2, 45)),
+            clusters.node(&map, &GridPoint::new(12, 45))
+        );
+    }
+
+    #[test]
+    fn corridor_go_around_obstacle() {
+        let wires = (0..40).map(|y| GridPoint::new(8, y)).collect::<Vec<_>>();
+        let map = map_with_wires(48, &wires);
+        let clusters = Clusters::new(&map, &PathMode::Sneak);
+
+        let corridor = clusters
+            .corridor(&map, &GridPoint::new(2, 2), &GridPoint::new(12, 2), 10.)
+            .unwrap();
+
+        // Both sides of the wire in the three clusters of first column
+        assert_eq!(corridor.len(), 5);
+        assert!(corridor.contains(&map, &GridPoint::new(2, 20)));
+        assert!(corridor.contains(&map, &GridPoint::new(5, 45)));
+        assert!(corridor.contains(&map, &GridPoint::new(12, 20)));
+        assert!(!corridor.contains(&map, &GridPoint::new(20, 2)));
+    }
+
+    #[test]
+    fn no_corridor_through_closed_obstacle() {
+        let wires = (0..48).map(|y| GridPoint::new(8, y)).collect::<Vec<_>>();
+        let map = map_with_wires(48, &wires);
+        let clusters = Clusters::new(&map, &PathMode::Sneak);
+
+        assert!(clusters
+            .corridor(&map, &GridPoint::new(2, 2), &GridPoint::new(12, 2), 10.)
+            .is_none());
+        assert!(clusters
+            .corridor(&map, &GridPoint::new(2, 2), &GridPoint::new(2, 40), 10.)
+            .is_some());
+    }
+}
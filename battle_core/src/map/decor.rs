@@ -69,4 +69,8 @@ impl Decor {
     pub fn offset(&self) -> Offset {
         self.offset
     }
+
+    pub fn remove_tiles_at(&mut self, x: u32, y: u32) {
+        self.tiles.retain(|tile| tile.x != x || tile.y != y)
+    }
 }
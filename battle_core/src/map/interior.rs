@@ -1,5 +1,16 @@
+use crate::{
+    types::WorldPoint,
+    utils::{Rect, WorldPolygon},
+};
+
+// Height (in pixels) of the horizontal strips revealing the interior polygon
+const REVEAL_STRIP_HEIGHT: f32 = 2.;
+
 #[derive(Clone)]
 pub struct Interior {
+    polygon: WorldPolygon,
+    reveal_parts: Vec<(Rect, Rect)>,
+    floors: u8,
     x: f32,
     relative_x: f32,
     y: f32,
@@ -16,10 +27,47 @@ impl Interior {
         y: f32,
         width: f32,
         height: f32,
+        floors: u8,
+        image_width: f32,
+        image_height: f32,
+    ) -> Self {
+        Self::from_polygon(
+            WorldPolygon::from_rect(&Rect::new(x, y, width, height)),
+            floors,
+            image_width,
+            image_height,
+        )
+    }
+
+    /// Interior image part is the polygon bounding rect, revealed by polygon strips
+    pub fn from_polygon(
+        polygon: WorldPolygon,
+        floors: u8,
         image_width: f32,
         image_height: f32,
     ) -> Self {
+        let Rect {
+            x,
+            y,
+            w: width,
+            h: height,
+        } = polygon.bounding_rect();
+        let reveal_parts = Self::reveal_strips(&polygon)
+            .into_iter()
+            .map(|strip| {
+                let relative = Rect::new(
+                    strip.x / image_width,
+                    strip.y / image_height,
+                    strip.w / image_width,
+                    strip.h / image_height,
+                );
+                (strip, relative)
+            })
+            .collect();
         Self {
+            polygon,
+            reveal_parts,
+            floors: floors.max(1),
             x,
             relative_x: x / image_width,
             y,
@@ -31,6 +79,23 @@ impl Interior {
         }
     }
 
+    /// Horizontal strips covering the polygon (and not its whole bounding rect, which is
+    /// larger for concave or rotated polygons)
+    fn reveal_strips(polygon: &WorldPolygon) -> Vec<Rect> {
+        let bounds = polygon.bounding_rect();
+        let mut strips = vec![];
+        let mut y = bounds.y;
+        while y < bounds.y + bounds.h {
+            let height = REVEAL_STRIP_HEIGHT.min(bounds.y + bounds.h - y);
+            for (start, end) in polygon.horizontal_spans(y + height / 2.) {
+                strips.push(Rect::new(start, y, end - start, height));
+            }
+            y += REVEAL_STRIP_HEIGHT;
+        }
+
+        strips
+    }
+
     pub fn x(&self) -> f32 {
         self.x
     }
@@ -62,4 +127,59 @@ impl Interior {
     pub fn relative_height(&self) -> f32 {
         self.relative_height
     }
+
+    pub fn polygon(&self) -> &WorldPolygon {
+        &self.polygon
+    }
+
+    /// Parts of the interior to reveal, as world rects and their matching interiors image
+    /// relative rects
+    pub fn reveal_parts(&self) -> &[(Rect, Rect)] {
+        &self.reveal_parts
+    }
+
+    /// Number of floors, ground floor included
+    pub fn floors(&self) -> u8 {
+        self.floors
+    }
+
+    pub fn contains(&self, point: &WorldPoint) -> bool {
+        self.polygon.contains(point)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn concave_interior_reveal_only_its_polygon() {
+        // L-shaped building : 20x20 square without its 10x10 top right quarter
+        let polygon = WorldPolygon::new(
+            [
+                (0., 0.),
+                (10., 0.),
+                (10., 10.),
+                (20., 10.),
+                (20., 20.),
+                (0., 20.),
+            ]
+            .iter()
+            .map(|(x, y)| WorldPoint::new(*x, *y))
+            .collect(),
+        );
+        let interior = Interior::from_polygon(polygon, 1, 100., 50.);
+        let parts = interior.reveal_parts();
+
+        let area: f32 = parts.iter().map(|(world, _)| world.w * world.h).sum();
+        assert_eq!(area, 300.);
+        for (world, relative) in parts {
+            // Nothing revealed in the missing quarter
+            assert!(world.y >= 10. || world.x + world.w <= 10.);
+            assert_eq!(relative.x, world.x / 100.);
+            assert_eq!(relative.y, world.y / 50.);
+            assert_eq!(relative.w, world.w / 100.);
+            assert_eq!(relative.h, world.h / 50.);
+        }
+    }
 }
@@ -1,22 +1,32 @@
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
-use self::{decor::Decor, interior::Interior, spawn::SpawnZone, terrain::TerrainTile};
+use self::{
+    decor::Decor,
+    interior::Interior,
+    spawn::SpawnZone,
+    terrain::{Opening, TerrainChange, TerrainTile, TileProperties, TileType},
+};
 use crate::{
-    config::ServerConfig,
+    config::{ServerConfig, UPHILL_COST_BY_METER},
     game::{
         control::MapControl,
         flag::{Flag, FlagName},
     },
-    physics::path::{Direction, PathMode},
+    physics::path::{Direction, PathCache, PathMode},
     types::{GridPoint, VehicleSize, WorldPoint},
     utils::grid_points_for_square,
 };
 use oc_core::spawn::SpawnZoneName;
 use strum::IntoEnumIterator;
 
+pub mod cluster;
 pub mod decor;
 pub mod interior;
 pub mod reader;
+pub mod region;
 pub mod spawn;
 pub mod terrain;
 
@@ -37,6 +47,13 @@ pub struct Map {
     tile_height: u32,
     decor: Decor,
     flags: Vec<Flag>,
+    // Interior index of each terrain tile
+    tiles_interiors: Vec<Option<usize>>,
+    // Interiors with at least one door, which can be entered only by their doors
+    doored_interiors: HashSet<usize>,
+    // Properties of tile types used by this map (which can be overridden by tileset)
+    tile_types_properties: HashMap<TileType, TileProperties>,
+    path_cache: PathCache,
 }
 
 impl Map {
@@ -56,6 +73,31 @@ impl Map {
         decor: Decor,
         flags: Vec<Flag>,
     ) -> Self {
+        let tiles_interiors: Vec<Option<usize>> = terrain_tiles
+            .iter()
+            .map(|tile| {
+                let point = WorldPoint::new(
+                    (tile.x * tile_width + tile_width / 2) as f32,
+                    (tile.y * tile_height + tile_height / 2) as f32,
+                );
+                interiors
+                    .iter()
+                    .position(|interior| interior.contains(&point))
+            })
+            .collect();
+        let doored_interiors = terrain_tiles
+            .iter()
+            .zip(&tiles_interiors)
+            .filter(|(tile, _)| tile.opening() == Some(&Opening::Door))
+            .filter_map(|(_, interior_index)| *interior_index)
+            .collect();
+        let mut tile_types_properties = HashMap::new();
+        for tile in &terrain_tiles {
+            tile_types_properties
+                .entry(tile.type_().clone())
+                .or_insert_with(|| tile.properties().clone());
+        }
+
         Self {
             name,
             background_image_path,
@@ -72,6 +114,10 @@ impl Map {
             tile_height,
             decor,
             flags,
+            tiles_interiors,
+            doored_interiors,
+            tile_types_properties,
+            path_cache: PathCache::default(),
         }
     }
 
@@ -127,6 +173,40 @@ impl Map {
         &self.terrain_tiles
     }
 
+    pub fn apply_terrain_change(&mut self, change: &TerrainChange) {
+        if !self.contains(&change.grid_point) {
+            return;
+        }
+
+        let i = (change.grid_point.y * self.width() as i32 + change.grid_point.x) as usize;
+        if let Some(tile) = self.terrain_tiles.get_mut(i) {
+            // Breached wall is a new entry point of its building, which can then only be
+            // entered by its doors (tiles interiors don't depend on tile types)
+            if tile.type_.breachable() && !change.type_.breachable() {
+                tile.opening = Some(Opening::Door);
+                if let Some(Some(interior_index)) = self.tiles_interiors.get(i) {
+                    self.doored_interiors.insert(*interior_index);
+                }
+            }
+            tile.type_ = change.type_.clone();
+            tile.properties = self
+                .tile_types_properties
+                .get(&change.type_)
+                .cloned()
+                .unwrap_or_else(|| change.type_.default_properties());
+            self.path_cache.clear();
+        }
+
+        if change.remove_decor {
+            self.decor
+                .remove_tiles_at(change.grid_point.x as u32, change.grid_point.y as u32);
+        }
+    }
+
+    pub fn path_cache(&self) -> &PathCache {
+        &self.path_cache
+    }
+
     pub fn flags(&self) -> &Vec<Flag> {
         &self.flags
     }
@@ -139,6 +219,10 @@ impl Map {
             .expect("Flags ownership and map flag must be consistent")
     }
 
+    pub fn spawn_zones(&self) -> &Vec<SpawnZone> {
+        &self.spawn_zones
+    }
+
     pub fn find_spawn_zones(&self, names: &[SpawnZoneName]) -> Vec<&SpawnZone> {
         self.spawn_zones
             .iter()
@@ -153,10 +237,10 @@ impl Map {
     ) -> bool {
         for spawn_zone_name in spawn_zone_names {
             // FIXME BS NOW : algo moche ?!
-            let found = self.find_spawn_zones(&[spawn_zone_name.clone()]);
+            let found = self.find_spawn_zones(std::slice::from_ref(spawn_zone_name));
             let spawn_zone = found.first().unwrap(); // FIXME BS NOW : manage error
 
-            if spawn_zone.contains(&flag.shape()) {
+            if spawn_zone.contains(flag.polygon()) {
                 return true;
             }
         }
@@ -177,28 +261,30 @@ impl Map {
             let new_y = from.0.y + mod_y;
 
             // Don't care ifd outside map
-            if new_x < 0 || new_y < 0 || new_x > self.width as i32 || new_y > self.height as i32 {
+            if new_x < 0 || new_y < 0 || new_x >= self.width as i32 || new_y >= self.height as i32 {
                 continue;
             }
 
             // If in map
             let i = (new_y * self.width() as i32 + new_x) as usize;
             if let Some(next_tile) = self.terrain_tiles.get(i) {
-                if path_mode.include_vehicles() {
-                    if next_tile.type_().block_vehicle() {
-                        continue;
-                    }
+                if !self.can_enter(&GridPoint::new(new_x, new_y), next_tile, path_mode) {
+                    continue;
+                }
 
-                    if let PathMode::Drive(size) = path_mode {
-                        if !self.point_allow_vehicle(&GridPoint::new(new_x, new_y), size) {
-                            continue;
-                        }
+                if let PathMode::Walk | PathMode::Sneak = path_mode {
+                    if !self.can_walk_between(&from.0, &GridPoint::new(new_x, new_y)) {
+                        continue;
                     }
                 }
 
                 let cost = match path_mode {
-                    PathMode::Walk => next_tile.type_().pedestrian_cost(),
-                    PathMode::Drive(_size) => from.1.angle_cost(&direction),
+                    PathMode::Walk | PathMode::Sneak => {
+                        next_tile.pedestrian_cost() + self.uphill_cost(&from.0, next_tile)
+                    }
+                    PathMode::Drive(_size) => {
+                        from.1.angle_cost(&direction) + next_tile.type_().vehicle_cost()
+                    }
                 };
 
                 successors.push(((GridPoint::new(new_x, new_y), direction), cost))
@@ -208,6 +294,98 @@ impl Map {
         successors
     }
 
+    /// Given tile can be entered with given path mode (from any of its neighbors)
+    pub fn can_enter(&self, point: &GridPoint, tile: &TerrainTile, path_mode: &PathMode) -> bool {
+        if path_mode.include_vehicles() {
+            if tile.block_vehicle() {
+                return false;
+            }
+
+            if let PathMode::Drive(size) = path_mode {
+                if !self.point_allow_vehicle(point, size) {
+                    return false;
+                }
+            }
+        }
+
+        if let PathMode::Sneak = path_mode {
+            if tile.type_().block_sneak() {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Buildings with doors can only be entered or left by their doors
+    fn can_walk_between(&self, from: &GridPoint, to: &GridPoint) -> bool {
+        let from_interior = self.interior_index_at(from);
+        let to_interior = self.interior_index_at(to);
+        if from_interior == to_interior {
+            return true;
+        }
+
+        let doored = [from_interior, to_interior]
+            .iter()
+            .flatten()
+            .any(|index| self.doored_interiors.contains(index));
+        !doored
+            || self.opening_at(from) == Some(&Opening::Door)
+            || self.opening_at(to) == Some(&Opening::Door)
+    }
+
+    fn interior_index_at(&self, grid_point: &GridPoint) -> Option<usize> {
+        if !self.contains(grid_point) {
+            return None;
+        }
+
+        self.tiles_interiors
+            .get((grid_point.y * self.width() as i32 + grid_point.x) as usize)
+            .cloned()
+            .flatten()
+    }
+
+    pub fn interior_at(&self, grid_point: &GridPoint) -> Option<&Interior> {
+        self.interior_index_at(grid_point)
+            .and_then(|index| self.interiors.get(index))
+    }
+
+    pub fn terrain_tile_at(&self, grid_point: &GridPoint) -> Option<&TerrainTile> {
+        if !self.contains(grid_point) {
+            return None;
+        }
+
+        self.terrain_tiles
+            .get((grid_point.y * self.width() as i32 + grid_point.x) as usize)
+    }
+
+    pub fn opening_at(&self, grid_point: &GridPoint) -> Option<&Opening> {
+        self.terrain_tile_at(grid_point)
+            .and_then(|tile| tile.opening())
+    }
+
+    pub fn uphill_cost(&self, from: &GridPoint, next_tile: &TerrainTile) -> i32 {
+        let climb = next_tile.elevation() - self.elevation_at(from);
+        if climb > 0. {
+            (climb * UPHILL_COST_BY_METER) as i32
+        } else {
+            0
+        }
+    }
+
+    /// Ground elevation (meters) at given grid point, 0 if outside map
+    pub fn elevation_at(&self, grid_point: &GridPoint) -> f32 {
+        self.terrain_tile_at(grid_point)
+            .map(|tile| tile.elevation())
+            .unwrap_or(0.)
+    }
+
+    /// Height (meters) of `from` point above `to` point, negative if `from` is lower
+    pub fn height_advantage(&self, from: &WorldPoint, to: &WorldPoint) -> f32 {
+        self.elevation_at(&self.grid_point_from_world_point(from))
+            - self.elevation_at(&self.grid_point_from_world_point(to))
+    }
+
     pub fn contains(&self, grid_point: &GridPoint) -> bool {
         grid_point.x >= 0
             && grid_point.y >= 0
@@ -240,7 +418,7 @@ impl Map {
                     .terrain_tiles
                     .get((y * self.width() as i32 + x) as usize)
                 {
-                    if tile.type_().block_vehicle() {
+                    if tile.block_vehicle() {
                         return false;
                     }
                 }
@@ -262,10 +440,7 @@ impl Map {
 
         for spawn_zone in &self.spawn_zones {
             if allowed_zone_names.contains_spawn_zone(spawn_zone.name())
-                && point.x >= spawn_zone.x()
-                && point.x <= spawn_zone.x() + spawn_zone.width()
-                && point.y >= spawn_zone.y()
-                && point.y <= spawn_zone.y() + spawn_zone.height()
+                && spawn_zone.contains_point(point)
             {
                 return true;
             }
@@ -319,12 +494,154 @@ pub fn find_arbitrary_cover_grid_points<'a>(
         }
     }
     tiles.sort_by(|(_, tile_a), (_, tile_b)| {
-        config
-            .terrain_tile_opacity(&tile_a.type_)
-            .partial_cmp(&config.terrain_tile_opacity(&tile_b.type_))
+        tile_a
+            .opacity(config)
+            .partial_cmp(&tile_b.opacity(config))
             // TODO : manage this unwrap
             .unwrap()
     });
 
     tiles
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::types::Offset;
+
+    use super::{decor::Decor, interior::Interior, terrain::TileType, *};
+
+    // One row : outside, wall, door and floor tiles, the three last ones being a building
+    fn building_map() -> Map {
+        let types = [
+            TileType::ShortGrass,
+            TileType::BrickWall,
+            TileType::Concrete,
+            TileType::Concrete,
+        ];
+        let tiles = types
+            .into_iter()
+            .enumerate()
+            .map(|(x, type_)| {
+                let mut tile = TerrainTile::new(type_, 10, 10, 1., 1., x as u32, 0, 0, 0);
+                if x == 2 {
+                    tile.opening = Some(Opening::Door);
+                }
+                tile
+            })
+            .collect();
+
+        Map::new(
+            "TestMap".to_string(),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            vec![Interior::new(10., 0., 30., 10., 0, 40., 10.)],
+            vec![],
+            4,
+            1,
+            tiles,
+            10,
+            10,
+            Decor::new(vec![], vec![], Offset::new(0., 0.)),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn breached_wall_is_an_entry() {
+        let mut map = building_map();
+        let outside = GridPoint::new(0, 0);
+        let wall = GridPoint::new(1, 0);
+        assert!(!map.can_walk_between(&outside, &wall));
+
+        map.apply_terrain_change(&TerrainChange::new(wall, TileType::MiddleRock, false));
+        assert!(map.can_walk_between(&outside, &wall));
+        assert!(map.can_walk_between(&wall, &GridPoint::new(2, 0)));
+    }
+
+    #[test]
+    fn breached_building_is_entered_by_its_breach() {
+        // Doorless building on first row, outside on second one
+        let tiles = (0..2)
+            .flat_map(|y| {
+                (0..4).map(move |x| {
+                    let type_ = match (x, y) {
+                        (1, 0) => TileType::BrickWall,
+                        (2, 0) | (3, 0) => TileType::Concrete,
+                        _ => TileType::ShortGrass,
+                    };
+                    TerrainTile::new(type_, 10, 10, 1., 1., x, y, 0, 0)
+                })
+            })
+            .collect();
+        let mut map = Map::new(
+            "TestMap".to_string(),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            vec![Interior::new(10., 0., 30., 10., 0, 40., 20.)],
+            vec![],
+            4,
+            2,
+            tiles,
+            10,
+            10,
+            Decor::new(vec![], vec![], Offset::new(0., 0.)),
+            vec![],
+        );
+        let outside = GridPoint::new(2, 1);
+        let floor = GridPoint::new(2, 0);
+        assert!(map.can_walk_between(&outside, &floor));
+
+        let wall = GridPoint::new(1, 0);
+        map.apply_terrain_change(&TerrainChange::new(wall, TileType::MiddleRock, false));
+        assert!(!map.can_walk_between(&outside, &floor));
+        assert!(map.can_walk_between(&GridPoint::new(0, 0), &wall));
+    }
+
+    #[test]
+    fn doored_building_is_only_entered_by_its_doors() {
+        // Building with a door on first row, outside on second one
+        let tiles = (0..2)
+            .flat_map(|y| {
+                (0..4).map(move |x| {
+                    let type_ = match (x, y) {
+                        (1, 0) => TileType::BrickWall,
+                        (2, 0) | (3, 0) => TileType::Concrete,
+                        _ => TileType::ShortGrass,
+                    };
+                    let mut tile = TerrainTile::new(type_, 10, 10, 1., 1., x, y, 0, 0);
+                    if (x, y) == (2, 0) {
+                        tile.opening = Some(Opening::Door);
+                    }
+                    tile
+                })
+            })
+            .collect();
+        let map = Map::new(
+            "TestMap".to_string(),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            vec![Interior::new(10., 0., 30., 10., 0, 40., 20.)],
+            vec![],
+            4,
+            2,
+            tiles,
+            10,
+            10,
+            Decor::new(vec![], vec![], Offset::new(0., 0.)),
+            vec![],
+        );
+        let door = GridPoint::new(2, 0);
+        let floor = GridPoint::new(3, 0);
+
+        assert!(map.can_walk_between(&GridPoint::new(2, 1), &door));
+        assert!(map.can_walk_between(&door, &GridPoint::new(2, 1)));
+        assert!(!map.can_walk_between(&GridPoint::new(3, 1), &floor));
+        assert!(!map.can_walk_between(&floor, &GridPoint::new(3, 1)));
+        assert!(map.can_walk_between(&door, &floor));
+    }
+}
@@ -8,20 +8,21 @@ use std::{
 
 use oc_core::spawn::{ParseOriginDirectionError, SpawnZoneName};
 use tiled::{
-    FiniteTileLayer, Image, ImageLayer, Layer, LayerType, Loader, Map as TiledMap, ObjectLayer,
-    TileLayer, Tileset,
+    FiniteTileLayer, Image, ImageLayer, Layer, LayerType, Loader, Map as TiledMap, ObjectData,
+    ObjectLayer, ObjectShape, TileLayer, Tileset,
 };
 
 use crate::{
     game::flag::{Flag, FlagName},
-    types::Offset,
+    types::{Angle, Coverage, Offset, WorldPoint},
+    utils::{Rect, WorldPolygon},
 };
 
 use super::{
     decor::{Decor, DecorTile},
     interior::Interior,
     spawn::SpawnZone,
-    terrain::{TerrainTile, TerrainTileError},
+    terrain::{Opening, TerrainTile, TerrainTileError, TileProperties, TileType},
     Map,
 };
 
@@ -36,6 +37,22 @@ const DECOR_LAYER_NAME: &str = "decor";
 const TERRAIN_LAYER_NAME: &str = "terrain";
 const TERRAIN_TILESET_NAME: &str = "terrain";
 const TILE_ID_PROPERTY_KEY: &str = "ID";
+const ELEVATION_LAYER_NAME: &str = "elevation";
+const TILE_ELEVATION_PROPERTY_KEY: &str = "ELEVATION";
+const OPENINGS_LAYER_NAME: &str = "openings";
+const TILE_OPENING_PROPERTY_KEY: &str = "OPENING";
+const INTERIOR_FLOORS_PROPERTY_KEY: &str = "FLOORS";
+const TILE_PEDESTRIAN_COST_PROPERTY_KEY: &str = "PEDESTRIAN_COST";
+const TILE_BLOCK_VEHICLE_PROPERTY_KEY: &str = "BLOCK_VEHICLE";
+const TILE_COVERAGE_STANDUP_PROPERTY_KEY: &str = "COVERAGE_STANDUP";
+const TILE_COVERAGE_FLAT_PROPERTY_KEY: &str = "COVERAGE_FLAT";
+const TILE_OPACITY_PROPERTY_KEY: &str = "OPACITY";
+// Properties which must be given by tileset for tile types unknown by the game
+const CUSTOM_TILE_REQUIRED_PROPERTY_KEYS: [&str; 3] = [
+    TILE_PEDESTRIAN_COST_PROPERTY_KEY,
+    TILE_BLOCK_VEHICLE_PROPERTY_KEY,
+    TILE_OPACITY_PROPERTY_KEY,
+];
 
 #[derive(Debug)]
 pub enum MapReaderError {
@@ -114,7 +131,7 @@ impl MapReader {
         })
     }
 
-    fn layer(&self, name: &str) -> Result<Layer, MapReaderError> {
+    fn layer(&self, name: &str) -> Result<Layer<'_>, MapReaderError> {
         match self
             .map
             .layers()
@@ -130,7 +147,7 @@ impl MapReader {
         }
     }
 
-    fn background_image_layer(&self) -> Result<ImageLayer, MapReaderError> {
+    fn background_image_layer(&self) -> Result<ImageLayer<'_>, MapReaderError> {
         match self.layer(BACKGROUND_IMAGE_LAYER_NAME)?.layer_type() {
             LayerType::ImageLayer(layer) => Ok(layer),
             _ => Result::Err(MapReaderError::InvalidLayer(format!(
@@ -150,7 +167,7 @@ impl MapReader {
         }
     }
 
-    fn interiors_image_layer(&self) -> Result<ImageLayer, MapReaderError> {
+    fn interiors_image_layer(&self) -> Result<ImageLayer<'_>, MapReaderError> {
         match self.layer(INTERIORS_IMAGE_LAYER_NAME)?.layer_type() {
             LayerType::ImageLayer(layer) => Ok(layer),
             _ => Result::Err(MapReaderError::InvalidLayer(format!(
@@ -170,7 +187,7 @@ impl MapReader {
         }
     }
 
-    fn interiors_zones_layer(&self) -> Result<ObjectLayer, MapReaderError> {
+    fn interiors_zones_layer(&self) -> Result<ObjectLayer<'_>, MapReaderError> {
         match self.layer(INTERIORS_ZONES_LAYER_NAME)?.layer_type() {
             LayerType::ObjectLayer(layer) => Ok(layer),
             _ => Result::Err(MapReaderError::InvalidLayer(format!(
@@ -180,7 +197,7 @@ impl MapReader {
         }
     }
 
-    fn spawn_zones_layer(&self) -> Result<ObjectLayer, MapReaderError> {
+    fn spawn_zones_layer(&self) -> Result<ObjectLayer<'_>, MapReaderError> {
         match self.layer(SPAWN_ZONES_LAYER_NAME)?.layer_type() {
             LayerType::ObjectLayer(layer) => Ok(layer),
             _ => Result::Err(MapReaderError::InvalidLayer(format!(
@@ -190,7 +207,7 @@ impl MapReader {
         }
     }
 
-    fn flags_layer(&self) -> Result<ObjectLayer, MapReaderError> {
+    fn flags_layer(&self) -> Result<ObjectLayer<'_>, MapReaderError> {
         match self.layer(FLAGS_LAYER_NAME)?.layer_type() {
             LayerType::ObjectLayer(layer) => Ok(layer),
             _ => Result::Err(MapReaderError::InvalidLayer(format!(
@@ -205,27 +222,33 @@ impl MapReader {
         let mut interiors = vec![];
 
         for object in self.interiors_zones_layer()?.objects() {
-            interiors.push(match object.shape {
-                tiled::ObjectShape::Rect { width, height } => Interior::new(
-                    object.x,
-                    object.y,
-                    width,
-                    height,
-                    interiors_image.width as f32,
-                    interiors_image.height as f32,
-                ),
-                _ => {
-                    return Result::Err(MapReaderError::InvalidLayer(format!(
-                        "Layer '{}' in map {} contains non Rect shapes, this is not supported now",
-                        INTERIORS_ZONES_LAYER_NAME, self.name,
-                    )))
-                }
-            })
+            interiors.push(Interior::from_polygon(
+                self.object_polygon(&object, INTERIORS_ZONES_LAYER_NAME)?,
+                self.interior_floors(&object)?,
+                interiors_image.width as f32,
+                interiors_image.height as f32,
+            ))
         }
 
         Ok(interiors)
     }
 
+    /// Interior without floors property is a single floor building
+    fn interior_floors(&self, object: &ObjectData) -> Result<u8, MapReaderError> {
+        match object.properties.get(INTERIOR_FLOORS_PROPERTY_KEY) {
+            Some(tiled::PropertyValue::IntValue(floors))
+                if *floors >= 1 && *floors <= u8::MAX as i32 =>
+            {
+                Ok(*floors as u8)
+            }
+            Some(_) => Result::Err(MapReaderError::InvalidLayer(format!(
+                "Object '{}' in layer '{}' in map {} should contains {} positive int property but it is not",
+                object.name, INTERIORS_ZONES_LAYER_NAME, self.name, INTERIOR_FLOORS_PROPERTY_KEY,
+            ))),
+            None => Ok(1),
+        }
+    }
+
     fn spawn_zones(&self) -> Result<Vec<SpawnZone>, MapReaderError> {
         let background_image = self.background_image()?;
         let mut spawn_zones = vec![];
@@ -239,23 +262,12 @@ impl MapReader {
                 )));
             }
 
-            spawn_zones.push(match object.shape {
-                tiled::ObjectShape::Rect { width, height } => SpawnZone::new(
-                    spawn_zone_name,
-                    object.x,
-                    object.y,
-                    width,
-                    height,
-                    background_image.width as f32,
-                    background_image.height as f32,
-                ),
-                _ => {
-                    return Result::Err(MapReaderError::InvalidLayer(format!(
-                        "Layer '{}' in map {} contains non Rect shapes, this is not supported now",
-                        SPAWN_ZONES_LAYER_NAME, self.name,
-                    )))
-                }
-            })
+            spawn_zones.push(SpawnZone::from_polygon(
+                spawn_zone_name,
+                self.object_polygon(&object, SPAWN_ZONES_LAYER_NAME)?,
+                background_image.width as f32,
+                background_image.height as f32,
+            ))
         }
 
         Ok(spawn_zones)
@@ -267,23 +279,53 @@ impl MapReader {
         for object in self.flags_layer()?.objects() {
             let flag_name = FlagName(object.name.clone());
 
-            flags.push(match object.shape {
-                tiled::ObjectShape::Rect { width, height } => {
-                    Flag::new(flag_name, object.x, object.y, width, height)
-                }
-                _ => {
-                    return Result::Err(MapReaderError::InvalidLayer(format!(
-                        "Layer '{}' in map {} contains non Rect shapes, this is not supported now",
-                        FLAGS_LAYER_NAME, self.name,
-                    )))
-                }
-            })
+            flags.push(Flag::from_polygon(
+                flag_name,
+                self.object_polygon(&object, FLAGS_LAYER_NAME)?,
+            ))
         }
 
         Ok(flags)
     }
 
-    fn terrain_layer(&self) -> Result<FiniteTileLayer, MapReaderError> {
+    /// Rect, ellipse and polygon objects as world polygon, rotated like object
+    fn object_polygon(
+        &self,
+        object: &ObjectData,
+        layer_name: &str,
+    ) -> Result<WorldPolygon, MapReaderError> {
+        let polygon = match &object.shape {
+            ObjectShape::Rect { width, height } => Ok(WorldPolygon::from_rect(&Rect::new(
+                object.x, object.y, *width, *height,
+            ))),
+            ObjectShape::Ellipse { width, height } => Ok(WorldPolygon::from_ellipse(
+                &Rect::new(object.x, object.y, *width, *height),
+            )),
+            // Polygon points are relative to object position
+            ObjectShape::Polygon { points } if points.len() >= 3 => Ok(WorldPolygon::new(
+                points
+                    .iter()
+                    .map(|(x, y)| WorldPoint::new(object.x + x, object.y + y))
+                    .collect(),
+            )),
+            _ => Result::Err(MapReaderError::InvalidLayer(format!(
+                "Layer '{}' in map {} contains shapes other than Rect, Ellipse or Polygon (object '{}'), this is not supported now",
+                layer_name, self.name, object.name,
+            ))),
+        }?;
+
+        // Tiled rotation is in degrees, clockwise, around object position
+        if object.rotation != 0. {
+            return Ok(polygon.rotated(
+                &WorldPoint::new(object.x, object.y),
+                &Angle(object.rotation.to_radians()),
+            ));
+        }
+
+        Ok(polygon)
+    }
+
+    fn terrain_layer(&self) -> Result<FiniteTileLayer<'_>, MapReaderError> {
         match self.layer(TERRAIN_LAYER_NAME)?.layer_type() {
             LayerType::TileLayer(layer) => match layer{
                 TileLayer::Finite(layer) => Ok(layer),
@@ -299,7 +341,122 @@ impl MapReader {
         }
     }
 
-    fn decor_layer(&self) -> Result<(Layer, FiniteTileLayer), MapReaderError> {
+    fn optional_tile_layer(
+        &self,
+        name: &str,
+    ) -> Result<Option<FiniteTileLayer<'_>>, MapReaderError> {
+        let layer = match self.layer(name) {
+            Ok(layer) => layer,
+            Err(MapReaderError::LayerNotFound(_)) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+
+        match layer.layer_type() {
+            LayerType::TileLayer(layer) => match layer{
+                TileLayer::Finite(layer) => Ok(Some(layer)),
+                TileLayer::Infinite(_) => Result::Err(MapReaderError::InvalidLayer(format!(
+                    "Layer '{}' in map {} is an infinite tile layer, but on finite layer is supported",
+                    name, self.name,
+                ))),
+            },
+            _ => Result::Err(MapReaderError::InvalidLayer(format!(
+                "Layer '{}' in map {} is not an tile layer",
+                name, self.name,
+            ))),
+        }
+    }
+
+    /// Elevation layer is optional : map without it is flat
+    fn elevation_layer(&self) -> Result<Option<FiniteTileLayer<'_>>, MapReaderError> {
+        self.optional_tile_layer(ELEVATION_LAYER_NAME)
+    }
+
+    /// Openings layer is optional : map without it have no doors and windows
+    fn openings_layer(&self) -> Result<Option<FiniteTileLayer<'_>>, MapReaderError> {
+        self.optional_tile_layer(OPENINGS_LAYER_NAME)
+    }
+
+    fn elevation(
+        &self,
+        layer: &FiniteTileLayer<'_>,
+        x: u32,
+        y: u32,
+    ) -> Result<f32, MapReaderError> {
+        // No elevation tile means ground level
+        let tile = match layer.get_tile(x as i32, y as i32) {
+            Some(tile) => tile,
+            None => return Ok(0.),
+        };
+        let tile_data = match tile.get_tile() {
+            Some(tile_data) => tile_data,
+            None => {
+                return Result::Err(MapReaderError::TileError(format!(
+                    "Tile '{}' in elevation layer in map {} is not found in tilesets",
+                    tile.id(),
+                    self.name,
+                )))
+            }
+        };
+
+        match tile_data.properties.get(TILE_ELEVATION_PROPERTY_KEY) {
+            Some(tiled::PropertyValue::FloatValue(elevation)) => Ok(*elevation),
+            Some(tiled::PropertyValue::IntValue(elevation)) => Ok(*elevation as f32),
+            Some(_) => Result::Err(MapReaderError::TileError(format!(
+                "Tile '{}' in elevation layer in map {} should contains {} number property but it is not",
+                tile.id(),
+                self.name,
+                TILE_ELEVATION_PROPERTY_KEY,
+            ))),
+            None => Result::Err(MapReaderError::TileError(format!(
+                "Tile '{}' in elevation layer in map {} should contains {} property",
+                tile.id(),
+                self.name,
+                TILE_ELEVATION_PROPERTY_KEY,
+            ))),
+        }
+    }
+
+    fn opening(
+        &self,
+        layer: &FiniteTileLayer<'_>,
+        x: u32,
+        y: u32,
+    ) -> Result<Option<Opening>, MapReaderError> {
+        let tile = match layer.get_tile(x as i32, y as i32) {
+            Some(tile) => tile,
+            None => return Ok(None),
+        };
+        let tile_data = match tile.get_tile() {
+            Some(tile_data) => tile_data,
+            None => {
+                return Result::Err(MapReaderError::TileError(format!(
+                    "Tile '{}' in openings layer in map {} is not found in tilesets",
+                    tile.id(),
+                    self.name,
+                )))
+            }
+        };
+
+        match tile_data.properties.get(TILE_OPENING_PROPERTY_KEY) {
+            Some(tiled::PropertyValue::StringValue(opening)) => {
+                Ok(Some(Opening::from_str(opening)?))
+            }
+            Some(_) => Result::Err(MapReaderError::TileError(format!(
+                "Tile '{}' in openings layer in map {} should contains {} string property but it is not",
+                tile.id(),
+                self.name,
+                TILE_OPENING_PROPERTY_KEY,
+            ))),
+            None => Result::Err(MapReaderError::TileError(format!(
+                "Tile '{}' in openings layer in map {} should contains {} property",
+                tile.id(),
+                self.name,
+                TILE_OPENING_PROPERTY_KEY,
+            ))),
+        }
+    }
+
+    fn decor_layer(&self) -> Result<(Layer<'_>, FiniteTileLayer<'_>), MapReaderError> {
         let decor_layer = self.layer(DECOR_LAYER_NAME)?;
         match decor_layer.layer_type() {
             LayerType::TileLayer(layer) => match layer{
@@ -359,10 +516,142 @@ impl MapReader {
         }
     }
 
+    /// Tile type of given terrain tileset tile and its properties : tile type defaults,
+    /// overridden by tile custom properties. Unknown tile type ids are accepted as custom
+    /// tile types if they give all required properties.
+    fn terrain_tile_type_and_properties(
+        &self,
+        tile_id: u32,
+        id: &str,
+        properties: &tiled::Properties,
+    ) -> Result<(TileType, TileProperties), MapReaderError> {
+        let type_ = match TileType::from_str(id) {
+            Ok(type_) => type_,
+            Err(error) => {
+                let missing = CUSTOM_TILE_REQUIRED_PROPERTY_KEYS
+                    .iter()
+                    .filter(|key| !properties.contains_key(**key))
+                    .map(|key| key.to_string())
+                    .collect::<Vec<String>>();
+                if !missing.is_empty() {
+                    return Result::Err(MapReaderError::TileError(format!(
+                        "Tile '{}' in terrain tileset in map {} : {}, custom tile type must contains {} properties",
+                        tile_id,
+                        self.name,
+                        error,
+                        missing.join(", "),
+                    )));
+                }
+                TileType::Custom(id.to_string())
+            }
+        };
+        let mut tile_properties = type_.default_properties();
+
+        if let Some(pedestrian_cost) =
+            self.tile_int_property(tile_id, properties, TILE_PEDESTRIAN_COST_PROPERTY_KEY)?
+        {
+            if pedestrian_cost <= 0 {
+                return Result::Err(MapReaderError::TileError(format!(
+                    "Tile '{}' in terrain tileset in map {} should contains positive {} property",
+                    tile_id, self.name, TILE_PEDESTRIAN_COST_PROPERTY_KEY,
+                )));
+            }
+            tile_properties.pedestrian_cost = pedestrian_cost;
+        }
+        if let Some(block_vehicle) =
+            self.tile_bool_property(tile_id, properties, TILE_BLOCK_VEHICLE_PROPERTY_KEY)?
+        {
+            tile_properties.block_vehicle = block_vehicle;
+        }
+        // Zero coverage means the tile give no coverage
+        if let Some(coverage) =
+            self.tile_ratio_property(tile_id, properties, TILE_COVERAGE_STANDUP_PROPERTY_KEY)?
+        {
+            tile_properties.coverage_standup = (coverage > 0.).then_some(Coverage(coverage));
+        }
+        if let Some(coverage) =
+            self.tile_ratio_property(tile_id, properties, TILE_COVERAGE_FLAT_PROPERTY_KEY)?
+        {
+            tile_properties.coverage_flat = (coverage > 0.).then_some(Coverage(coverage));
+        }
+        if let Some(opacity) =
+            self.tile_ratio_property(tile_id, properties, TILE_OPACITY_PROPERTY_KEY)?
+        {
+            tile_properties.opacity = Some(opacity);
+        }
+
+        Ok((type_, tile_properties))
+    }
+
+    fn tile_int_property(
+        &self,
+        tile_id: u32,
+        properties: &tiled::Properties,
+        key: &str,
+    ) -> Result<Option<i32>, MapReaderError> {
+        match properties.get(key) {
+            Some(tiled::PropertyValue::IntValue(value)) => Ok(Some(*value)),
+            Some(_) => Result::Err(MapReaderError::TileError(format!(
+                "Tile '{}' in terrain tileset in map {} should contains {} int property but it is not",
+                tile_id, self.name, key,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    fn tile_bool_property(
+        &self,
+        tile_id: u32,
+        properties: &tiled::Properties,
+        key: &str,
+    ) -> Result<Option<bool>, MapReaderError> {
+        match properties.get(key) {
+            Some(tiled::PropertyValue::BoolValue(value)) => Ok(Some(*value)),
+            Some(_) => Result::Err(MapReaderError::TileError(format!(
+                "Tile '{}' in terrain tileset in map {} should contains {} bool property but it is not",
+                tile_id, self.name, key,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Number property which must be between 0.0 and 1.0
+    fn tile_ratio_property(
+        &self,
+        tile_id: u32,
+        properties: &tiled::Properties,
+        key: &str,
+    ) -> Result<Option<f32>, MapReaderError> {
+        let value = match properties.get(key) {
+            Some(tiled::PropertyValue::FloatValue(value)) => *value,
+            Some(tiled::PropertyValue::IntValue(value)) => *value as f32,
+            Some(_) => {
+                return Result::Err(MapReaderError::TileError(format!(
+                    "Tile '{}' in terrain tileset in map {} should contains {} number property but it is not",
+                    tile_id, self.name, key,
+                )))
+            }
+            None => return Ok(None),
+        };
+
+        if !(0.0..=1.0).contains(&value) {
+            return Result::Err(MapReaderError::TileError(format!(
+                "Tile '{}' in terrain tileset in map {} should contains {} property between 0.0 and 1.0 but it is {}",
+                tile_id, self.name, key, value,
+            )));
+        }
+
+        Ok(Some(value))
+    }
+
     fn terrain_tiles(&self) -> Result<Vec<TerrainTile>, MapReaderError> {
         let layer = self.terrain_layer()?;
         let terrain_tileset = self.terrain_tileset()?;
         let terrain_image = self.terrain_image()?;
+        let elevation_layer = self.elevation_layer()?;
+        let openings_layer = self.openings_layer()?;
+        let mut tiles_types_and_properties: HashMap<u32, (TileType, TileProperties)> =
+            HashMap::new();
         let mut tiles = vec![];
 
         for y in 0..layer.height() {
@@ -417,8 +706,20 @@ impl MapReader {
                 let tile_y = tile_id / terrain_tileset.columns;
                 let tile_x = tile_id - (tile_y * terrain_tileset.columns);
 
-                let terrain_tile = TerrainTile::from_str_id(
-                    id,
+                let (type_, properties) = match tiles_types_and_properties.get(&tile_id) {
+                    Some(type_and_properties) => type_and_properties.clone(),
+                    None => {
+                        let type_and_properties = self.terrain_tile_type_and_properties(
+                            tile_id,
+                            id,
+                            &tile_data.properties,
+                        )?;
+                        tiles_types_and_properties.insert(tile_id, type_and_properties.clone());
+                        type_and_properties
+                    }
+                };
+                let mut terrain_tile = TerrainTile::new(
+                    type_,
                     tile_width,
                     tile_height,
                     relative_tile_width,
@@ -427,7 +728,14 @@ impl MapReader {
                     y,
                     tile_x,
                     tile_y,
-                )?;
+                );
+                terrain_tile.properties = properties;
+                if let Some(elevation_layer) = &elevation_layer {
+                    terrain_tile.elevation = self.elevation(elevation_layer, x, y)?;
+                }
+                if let Some(openings_layer) = &openings_layer {
+                    terrain_tile.opening = self.opening(openings_layer, x, y)?;
+                }
 
                 tiles.push(terrain_tile)
             }
@@ -542,6 +850,23 @@ impl MapReader {
         Ok(Decor::new(image_paths, tiles, offset))
     }
 
+    /// Errors of all required layers and tilesets (where `build` stop at the first one)
+    pub fn check_layers(&self) -> Vec<MapReaderError> {
+        [
+            self.background_image().map(|_| ()),
+            self.interiors_image().map(|_| ()),
+            self.interiors_zones_layer().map(|_| ()),
+            self.spawn_zones_layer().map(|_| ()),
+            self.flags_layer().map(|_| ()),
+            self.terrain_layer().map(|_| ()),
+            self.terrain_image().map(|_| ()),
+            self.decor_layer().map(|_| ()),
+        ]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect()
+    }
+
     pub fn build(&self) -> Result<Map, MapReaderError> {
         let background_image_path = PathBuf::from("/").join(
             self.background_image()?
@@ -589,3 +914,150 @@ impl MapReader {
         ))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use rstest::*;
+
+    use super::*;
+
+    const TMX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="right-down" width="1" height="1" tilewidth="5" tileheight="5" infinite="0" nextlayerid="1" nextobjectid="1">
+</map>"#;
+
+    fn reader() -> MapReader {
+        let map = Loader::new()
+            .load_tmx_map_from(TMX.as_bytes(), "test.tmx")
+            .expect("Test map must be valid");
+        MapReader {
+            resources: PathBuf::from("."),
+            name: "test".to_string(),
+            map,
+        }
+    }
+
+    /// Properties of the single tile of a terrain tileset made with given properties xml
+    fn properties(properties: &str) -> tiled::Properties {
+        let tsx = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" name="terrain" tilewidth="5" tileheight="5" tilecount="1" columns="1">
+ <tile id="0">
+  <properties>
+   {}
+  </properties>
+ </tile>
+</tileset>"#,
+            properties
+        );
+        let tileset = Loader::new()
+            .load_tsx_tileset_from(tsx.as_bytes(), "terrain.tsx")
+            .expect("Test tileset must be valid");
+        tileset
+            .get_tile(0)
+            .expect("Test tileset must contain tile")
+            .properties
+            .clone()
+    }
+
+    #[test]
+    fn known_tile_type_use_its_default_properties() {
+        let (type_, properties) = reader()
+            .terrain_tile_type_and_properties(0, "BrickWall", &properties(""))
+            .unwrap();
+        let defaults = TileType::BrickWall.default_properties();
+
+        assert_eq!(type_, TileType::BrickWall);
+        assert_eq!(properties.pedestrian_cost, defaults.pedestrian_cost);
+        assert_eq!(properties.block_vehicle, defaults.block_vehicle);
+        assert_eq!(
+            properties.coverage_standup.map(|c| c.0),
+            defaults.coverage_standup.map(|c| c.0)
+        );
+        assert_eq!(
+            properties.coverage_flat.map(|c| c.0),
+            defaults.coverage_flat.map(|c| c.0)
+        );
+        assert_eq!(properties.opacity, None);
+    }
+
+    #[test]
+    fn tile_properties_override_tile_type_defaults() {
+        let (type_, properties) = reader()
+            .terrain_tile_type_and_properties(
+                0,
+                "BrickWall",
+                &properties(
+                    r#"<property name="PEDESTRIAN_COST" type="int" value="3"/>
+   <property name="BLOCK_VEHICLE" type="bool" value="false"/>
+   <property name="COVERAGE_STANDUP" type="int" value="0"/>
+   <property name="COVERAGE_FLAT" type="float" value="0.5"/>
+   <property name="OPACITY" type="int" value="1"/>"#,
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(type_, TileType::BrickWall);
+        assert_eq!(properties.pedestrian_cost, 3);
+        assert!(!properties.block_vehicle);
+        // Zero coverage means no coverage
+        assert!(properties.coverage_standup.is_none());
+        assert_eq!(properties.coverage_flat.map(|c| c.0), Some(0.5));
+        assert_eq!(properties.opacity, Some(1.));
+    }
+
+    #[test]
+    fn unknown_tile_type_is_custom() {
+        let (type_, properties) = reader()
+            .terrain_tile_type_and_properties(
+                0,
+                "Marsh",
+                &properties(
+                    r#"<property name="PEDESTRIAN_COST" type="int" value="40"/>
+   <property name="BLOCK_VEHICLE" type="bool" value="true"/>
+   <property name="OPACITY" type="float" value="0.1"/>"#,
+                ),
+            )
+            .unwrap();
+
+        assert_eq!(type_, TileType::Custom("Marsh".to_string()));
+        assert_eq!(properties.pedestrian_cost, 40);
+        assert!(properties.block_vehicle);
+        assert_eq!(properties.opacity, Some(0.1));
+    }
+
+    #[rstest]
+    // Custom tile type without all required properties
+    #[case(
+        "Marsh",
+        r#"<property name="PEDESTRIAN_COST" type="int" value="40"/>
+   <property name="BLOCK_VEHICLE" type="bool" value="true"/>"#
+    )]
+    // Not positive pedestrian cost
+    #[case(
+        "ShortGrass",
+        r#"<property name="PEDESTRIAN_COST" type="int" value="0"/>"#
+    )]
+    // Not int pedestrian cost
+    #[case(
+        "ShortGrass",
+        r#"<property name="PEDESTRIAN_COST" type="float" value="2.5"/>"#
+    )]
+    // Not bool block vehicle
+    #[case(
+        "ShortGrass",
+        r#"<property name="BLOCK_VEHICLE" type="int" value="1"/>"#
+    )]
+    // Out of range ratios
+    #[case("ShortGrass", r#"<property name="OPACITY" type="float" value="1.5"/>"#)]
+    #[case(
+        "ShortGrass",
+        r#"<property name="COVERAGE_FLAT" type="int" value="-1"/>"#
+    )]
+    // Not number ratio
+    #[case("ShortGrass", r#"<property name="COVERAGE_STANDUP" value="high"/>"#)]
+    fn invalid_tile_properties_are_rejected(#[case] id: &str, #[case] properties_xml: &str) {
+        let result = reader().terrain_tile_type_and_properties(0, id, &properties(properties_xml));
+
+        assert!(matches!(result, Err(MapReaderError::TileError(_))));
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::VecDeque;
+
+use crate::{
+    physics::path::{Direction, PathMode},
+    types::GridPoint,
+};
+
+use super::Map;
+
+/// Connected regions of map tiles for a path mode. There is no path between two tiles of
+/// different regions, so it is useless to search it.
+#[derive(Debug)]
+pub struct Regions {
+    // Region of each tile (None if tile can't be entered with this path mode)
+    tiles: Vec<Option<u32>>,
+}
+
+impl Regions {
+    pub fn new(map: &Map, path_mode: &PathMode) -> Self {
+        let mut tiles = vec![None; map.terrain_tiles().len()];
+        let mut region = 0;
+
+        for (i, tile) in map.terrain_tiles().iter().enumerate() {
+            let point = GridPoint::new(tile.x as i32, tile.y as i32);
+            if tiles[i].is_some() || !map.can_enter(&point, tile, path_mode) {
+                continue;
+            }
+
+            // Successors costs don't matter, so any direction can be used
+            tiles[i] = Some(region);
+            let mut queue = VecDeque::from([point]);
+            while let Some(point) = queue.pop_front() {
+                for ((next, _), _) in map.successors(&(point, Direction::North), path_mode) {
+                    let j = (next.y * map.width() as i32 + next.x) as usize;
+                    if tiles[j].is_none() {
+                        tiles[j] = Some(region);
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            region += 1;
+        }
+
+        Self { tiles }
+    }
+
+    fn region(&self, map: &Map, point: &GridPoint) -> Option<u32> {
+        if !map.contains(point) {
+            return None;
+        }
+
+        self.tiles
+            .get((point.y * map.width() as i32 + point.x) as usize)
+            .cloned()
+            .flatten()
+    }
+
+    /// A path can exist between given points
+    pub fn connected(
+        &self,
+        map: &Map,
+        from: &GridPoint,
+        to: &GridPoint,
+        path_mode: &PathMode,
+    ) -> bool {
+        let to_region = match self.region(map, to) {
+            Some(region) => region,
+            None => return false,
+        };
+
+        // Start point can be a not enterable one (ex. vehicle stuck), then look at its exits
+        match self.region(map, from) {
+            Some(region) => region == to_region,
+            None => map
+                .successors(&(*from, Direction::North), path_mode)
+                .iter()
+                .any(|((next, _), _)| self.region(map, next) == Some(to_region)),
+        }
+    }
+}
@@ -1,10 +1,14 @@
 use oc_core::spawn::SpawnZoneName;
 
-use crate::{types::WorldPoint, utils::WorldShape};
+use crate::{
+    types::WorldPoint,
+    utils::{Rect, WorldPolygon},
+};
 
 #[derive(Clone)]
 pub struct SpawnZone {
     name: SpawnZoneName,
+    polygon: WorldPolygon,
     x: f32,
     relative_x: f32,
     y: f32,
@@ -25,8 +29,30 @@ impl SpawnZone {
         map_width: f32,
         map_height: f32,
     ) -> Self {
+        Self::from_polygon(
+            name,
+            WorldPolygon::from_rect(&Rect::new(x, y, width, height)),
+            map_width,
+            map_height,
+        )
+    }
+
+    /// Position and size are the polygon bounding rect
+    pub fn from_polygon(
+        name: SpawnZoneName,
+        polygon: WorldPolygon,
+        map_width: f32,
+        map_height: f32,
+    ) -> Self {
+        let Rect {
+            x,
+            y,
+            w: width,
+            h: height,
+        } = polygon.bounding_rect();
         Self {
             name,
+            polygon,
             x,
             relative_x: x / map_width,
             y,
@@ -74,20 +100,15 @@ impl SpawnZone {
         self.relative_height
     }
 
-    pub fn shape(&self) -> WorldShape {
-        WorldShape {
-            top_left: WorldPoint::new(self.x, self.y),
-            top_right: WorldPoint::new(self.x + self.width, self.y),
-            bottom_right: WorldPoint::new(self.x + self.width, self.y + self.height),
-            bottom_left: WorldPoint::new(self.x, self.y + self.height),
-        }
+    pub fn polygon(&self) -> &WorldPolygon {
+        &self.polygon
+    }
+
+    pub fn contains_point(&self, point: &WorldPoint) -> bool {
+        self.polygon.contains(point)
     }
 
-    pub fn contains(&self, shape: &WorldShape) -> bool {
-        let this = self.shape();
-        this.top_left.x <= shape.top_left.x
-            && this.top_left.y <= shape.top_left.y
-            && this.bottom_right.x >= shape.bottom_right.x
-            && this.bottom_right.y >= shape.bottom_right.y
+    pub fn contains(&self, polygon: &WorldPolygon) -> bool {
+        self.polygon.contains_polygon(polygon)
     }
 }
@@ -1,8 +1,14 @@
 use std::{fmt::Display, str::FromStr};
 
-use crate::{game::posture::Posture, types::Coverage};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug)]
+use crate::{
+    config::ServerConfig,
+    game::posture::Posture,
+    types::{Coverage, GridPoint},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TileType {
     ShortGrass,
     MiddleGrass,
@@ -19,6 +25,15 @@ pub enum TileType {
     MiddleWoodLogs,
     Hedge,
     MiddleRock,
+    Crater,
+    Road,
+    WheatField,
+    BarbedWire,
+    Trench,
+    Foxhole,
+    /// Terrain type defined by map maker in tileset, its properties must be fully given
+    /// by tileset custom properties
+    Custom(String),
 }
 
 impl FromStr for TileType {
@@ -41,6 +56,12 @@ impl FromStr for TileType {
             "MiddleWoodLogs" => Ok(Self::MiddleWoodLogs),
             "Hedge" => Ok(Self::Hedge),
             "MiddleRock" => Ok(Self::MiddleRock),
+            "Crater" => Ok(Self::Crater),
+            "Road" => Ok(Self::Road),
+            "WheatField" => Ok(Self::WheatField),
+            "BarbedWire" => Ok(Self::BarbedWire),
+            "Trench" => Ok(Self::Trench),
+            "Foxhole" => Ok(Self::Foxhole),
             _ => Result::Err(TerrainTileError::UnknownId(s.to_string())),
         }
     }
@@ -64,6 +85,46 @@ impl TileType {
             TileType::MiddleWoodLogs => 30,
             TileType::Hedge => 20,
             TileType::MiddleRock => 25,
+            TileType::Crater => 14,
+            TileType::Road => 8,
+            TileType::WheatField => 12,
+            TileType::BarbedWire => 60,
+            TileType::Trench => 30,
+            TileType::Foxhole => 25,
+            TileType::Custom(_) => 10,
+        }
+    }
+
+    /// Path finding cost added to vehicle moves (which prefer roads)
+    pub fn vehicle_cost(&self) -> i32 {
+        match self {
+            TileType::Road => 0,
+            _ => 5,
+        }
+    }
+
+    /// Factor applied to soldiers velocity when moving on this tile type
+    pub fn velocity_factor(&self) -> f32 {
+        match self {
+            TileType::Road => 1.2,
+            TileType::WheatField => 0.9,
+            TileType::BarbedWire => 0.3,
+            TileType::Trench | TileType::Foxhole => 0.7,
+            _ => 1.0,
+        }
+    }
+
+    /// Sneaking soldiers can't cross this tile type
+    pub fn block_sneak(&self) -> bool {
+        matches!(self, TileType::BarbedWire)
+    }
+
+    /// Part of explosion effects absorbed by soldier position (from 0.0 to less than 1.0)
+    pub fn explosion_protection(&self) -> f32 {
+        match self {
+            TileType::Trench => 0.5,
+            TileType::Foxhole => 0.4,
+            _ => 0.0,
         }
     }
 
@@ -79,10 +140,18 @@ impl TileType {
             | TileType::Underbrush
             | TileType::LightUnderbrush
             | TileType::MiddleWoodLogs
-            | TileType::Hedge => false,
-            TileType::BrickWall | TileType::Trunk | TileType::DeepWater | TileType::MiddleRock => {
-                true
-            }
+            | TileType::Hedge
+            | TileType::Crater
+            | TileType::Road
+            | TileType::WheatField
+            | TileType::BarbedWire
+            | TileType::Foxhole
+            | TileType::Custom(_) => false,
+            TileType::BrickWall
+            | TileType::Trunk
+            | TileType::DeepWater
+            | TileType::MiddleRock
+            | TileType::Trench => true,
         }
     }
 
@@ -104,6 +173,13 @@ impl TileType {
                 TileType::MiddleWoodLogs => Some(Coverage(0.2)),
                 TileType::Hedge => Some(Coverage(0.15)),
                 TileType::MiddleRock => Some(Coverage(0.2)),
+                TileType::Crater => Some(Coverage(0.1)),
+                TileType::Road => None,
+                TileType::WheatField => None,
+                TileType::BarbedWire => None,
+                TileType::Trench => Some(Coverage(0.6)),
+                TileType::Foxhole => Some(Coverage(0.4)),
+                TileType::Custom(_) => None,
             },
             Posture::Flat => match self {
                 TileType::ShortGrass => None,
@@ -121,10 +197,92 @@ impl TileType {
                 TileType::MiddleWoodLogs => Some(Coverage(0.7)),
                 TileType::Hedge => Some(Coverage(0.15)),
                 TileType::MiddleRock => Some(Coverage(0.75)),
+                TileType::Crater => Some(Coverage(0.6)),
+                TileType::Road => None,
+                TileType::WheatField => None,
+                TileType::BarbedWire => None,
+                TileType::Trench => Some(Coverage(0.9)),
+                TileType::Foxhole => Some(Coverage(0.85)),
+                TileType::Custom(_) => None,
             },
         }
     }
 
+    /// Tile type after being hit by an explosion, if it can be destroyed
+    pub fn destroyed(&self) -> Option<TileType> {
+        match self {
+            TileType::Hedge
+            | TileType::Underbrush
+            | TileType::LightUnderbrush
+            | TileType::Trunk
+            | TileType::MiddleWoodLogs
+            | TileType::BarbedWire => Some(TileType::Dirt),
+            // Breached wall leave rubble
+            TileType::BrickWall => Some(TileType::MiddleRock),
+            TileType::ShortGrass
+            | TileType::MiddleGrass
+            | TileType::HighGrass
+            | TileType::Dirt
+            | TileType::Mud
+            | TileType::Concrete
+            | TileType::Water
+            | TileType::DeepWater
+            | TileType::MiddleRock
+            | TileType::Crater
+            | TileType::Road
+            | TileType::WheatField
+            | TileType::Trench
+            | TileType::Foxhole
+            | TileType::Custom(_) => None,
+        }
+    }
+
+    /// Building wall which, once destroyed, become a way to enter (or leave) the building
+    pub fn breachable(&self) -> bool {
+        matches!(self, TileType::BrickWall)
+    }
+
+    /// Explosion on this tile type dig a crater
+    pub fn can_be_cratered(&self) -> bool {
+        match self {
+            TileType::ShortGrass
+            | TileType::MiddleGrass
+            | TileType::HighGrass
+            | TileType::Dirt
+            | TileType::Mud
+            | TileType::Road
+            | TileType::WheatField => true,
+            TileType::Concrete
+            | TileType::BrickWall
+            | TileType::Trunk
+            | TileType::Water
+            | TileType::DeepWater
+            | TileType::Underbrush
+            | TileType::LightUnderbrush
+            | TileType::MiddleWoodLogs
+            | TileType::Hedge
+            | TileType::MiddleRock
+            | TileType::Crater
+            | TileType::BarbedWire
+            | TileType::Trench
+            | TileType::Foxhole
+            | TileType::Custom(_) => false,
+        }
+    }
+
+    /// Destroying this tile type destroy its decor (foliage, etc)
+    pub fn destroy_decor(&self) -> bool {
+        matches!(
+            self,
+            TileType::Hedge
+                | TileType::Underbrush
+                | TileType::LightUnderbrush
+                | TileType::Trunk
+                | TileType::MiddleWoodLogs
+                | TileType::BarbedWire
+        )
+    }
+
     pub fn block_bullet(&self) -> bool {
         match self {
             TileType::ShortGrass => false,
@@ -142,6 +300,72 @@ impl TileType {
             TileType::MiddleWoodLogs => false, // true ?
             TileType::Hedge => false,
             TileType::MiddleRock => false, // true ?
+            TileType::Crater => false,
+            TileType::Road => false,
+            TileType::WheatField => false,
+            TileType::BarbedWire => false,
+            TileType::Trench => false,
+            TileType::Foxhole => false,
+            TileType::Custom(_) => false,
+        }
+    }
+
+    pub fn default_properties(&self) -> TileProperties {
+        TileProperties {
+            pedestrian_cost: self.pedestrian_cost(),
+            block_vehicle: self.block_vehicle(),
+            coverage_standup: self.coverage(&Posture::StandUp),
+            coverage_flat: self.coverage(&Posture::Flat),
+            opacity: None,
+        }
+    }
+}
+
+/// Terrain properties of a tile. Built from tile type defaults and overridden by terrain
+/// tileset custom properties.
+#[derive(Debug, Clone)]
+pub struct TileProperties {
+    pub pedestrian_cost: i32,
+    pub block_vehicle: bool,
+    pub coverage_standup: Option<Coverage>,
+    pub coverage_flat: Option<Coverage>,
+    /// If None, opacity is given by server config (which can be changed during the battle)
+    pub opacity: Option<f32>,
+}
+
+/// Building wall part allowing to enter (door) or to look and fire out (window)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opening {
+    Door,
+    Window,
+}
+
+impl FromStr for Opening {
+    type Err = TerrainTileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Door" => Ok(Self::Door),
+            "Window" => Ok(Self::Window),
+            _ => Result::Err(TerrainTileError::UnknownOpening(s.to_string())),
+        }
+    }
+}
+
+/// Runtime modification of a map tile (explosion craters, destroyed vegetation, etc)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerrainChange {
+    pub grid_point: GridPoint,
+    pub type_: TileType,
+    pub remove_decor: bool,
+}
+
+impl TerrainChange {
+    pub fn new(grid_point: GridPoint, type_: TileType, remove_decor: bool) -> Self {
+        Self {
+            grid_point,
+            type_,
+            remove_decor,
         }
     }
 }
@@ -149,12 +373,16 @@ impl TileType {
 #[derive(Debug)]
 pub enum TerrainTileError {
     UnknownId(String),
+    UnknownOpening(String),
 }
 
 impl Display for TerrainTileError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TerrainTileError::UnknownId(id) => f.write_str(&format!("Unknown id : {}", id)),
+            TerrainTileError::UnknownOpening(opening) => {
+                f.write_str(&format!("Unknown opening : {}", opening))
+            }
         }
     }
 }
@@ -169,6 +397,10 @@ pub struct TerrainTile {
     pub y: u32,
     pub tile_x: u32,
     pub tile_y: u32,
+    /// Ground height, in meters
+    pub elevation: f32,
+    pub opening: Option<Opening>,
+    pub properties: TileProperties,
 }
 
 impl TerrainTile {
@@ -185,6 +417,7 @@ impl TerrainTile {
         tile_y: u32,
     ) -> Self {
         Self {
+            properties: type_.default_properties(),
             type_,
             tile_width,
             tile_height,
@@ -194,6 +427,8 @@ impl TerrainTile {
             y,
             tile_x,
             tile_y,
+            elevation: 0.,
+            opening: None,
         }
     }
 
@@ -225,4 +460,76 @@ impl TerrainTile {
     pub fn type_(&self) -> &TileType {
         &self.type_
     }
+
+    pub fn elevation(&self) -> f32 {
+        self.elevation
+    }
+
+    pub fn opening(&self) -> Option<&Opening> {
+        self.opening.as_ref()
+    }
+
+    pub fn properties(&self) -> &TileProperties {
+        &self.properties
+    }
+
+    pub fn pedestrian_cost(&self) -> i32 {
+        self.properties.pedestrian_cost
+    }
+
+    pub fn block_vehicle(&self) -> bool {
+        self.properties.block_vehicle
+    }
+
+    pub fn coverage(&self, posture: &Posture) -> Option<Coverage> {
+        match posture {
+            Posture::StandUp => self.properties.coverage_standup.clone(),
+            Posture::Flat => self.properties.coverage_flat.clone(),
+        }
+    }
+
+    pub fn opacity(&self, config: &ServerConfig) -> f32 {
+        self.properties
+            .opacity
+            .unwrap_or_else(|| config.terrain_tile_opacity(&self.type_))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::*;
+
+    use super::*;
+
+    #[rstest]
+    #[case("Road", TileType::Road)]
+    #[case("WheatField", TileType::WheatField)]
+    #[case("BarbedWire", TileType::BarbedWire)]
+    #[case("Trench", TileType::Trench)]
+    #[case("Foxhole", TileType::Foxhole)]
+    fn test_tile_type_from_str(#[case] id: &str, #[case] expected: TileType) {
+        assert_eq!(TileType::from_str(id).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case(TileType::ShortGrass, 1.0, false, 0.0, 5, false)]
+    #[case(TileType::Road, 1.2, false, 0.0, 0, false)]
+    #[case(TileType::WheatField, 0.9, false, 0.0, 5, false)]
+    #[case(TileType::BarbedWire, 0.3, true, 0.0, 5, false)]
+    #[case(TileType::Trench, 0.7, false, 0.5, 5, true)]
+    #[case(TileType::Foxhole, 0.7, false, 0.4, 5, false)]
+    fn test_tile_type_movement_properties(
+        #[case] type_: TileType,
+        #[case] velocity_factor: f32,
+        #[case] block_sneak: bool,
+        #[case] explosion_protection: f32,
+        #[case] vehicle_cost: i32,
+        #[case] block_vehicle: bool,
+    ) {
+        assert_eq!(type_.velocity_factor(), velocity_factor);
+        assert_eq!(type_.block_sneak(), block_sneak);
+        assert_eq!(type_.explosion_protection(), explosion_protection);
+        assert_eq!(type_.vehicle_cost(), vehicle_cost);
+        assert_eq!(type_.block_vehicle(), block_vehicle);
+    }
 }
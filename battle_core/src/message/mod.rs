@@ -24,14 +24,14 @@ pub enum InputMessage {
     LoadDeployment(Deployment),
     LoadControl((MapControl, MapControl)),
     RequireCompleteSync,
-    SetBattleState(BattleStateCopy),
+    SetBattleState(Box<BattleStateCopy>),
     BattleState(BattleStateMessage),
     ChangeConfig(ChangeConfigMessage),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum OutputMessage {
-    LoadFromCopy(BattleStateCopy),
+    LoadFromCopy(Box<BattleStateCopy>),
     BattleState(BattleStateMessage),
     ClientState(ClientStateMessage),
     ChangeConfig(ChangeConfigMessage),
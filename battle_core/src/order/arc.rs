@@ -0,0 +1,88 @@
+use std::{f32::consts::PI, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    physics::utils::distance_between_points,
+    types::{Angle, Distance, WorldPoint},
+    utils::angle,
+};
+
+/// Sector where a defending squad is allowed to engage. Sector goes clockwise from left to
+/// right angle.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct FireArc {
+    pub left: Angle,
+    pub right: Angle,
+    pub range: Distance,
+}
+
+impl FireArc {
+    pub fn new(left: Angle, right: Angle, range: Distance) -> Self {
+        Self { left, right, range }
+    }
+
+    pub fn centered(angle: Angle, width: Angle, range: Distance) -> Self {
+        Self::new(
+            Angle(angle.0 - width.0 / 2.),
+            Angle(angle.0 + width.0 / 2.),
+            range,
+        )
+    }
+
+    /// Sector width, in radians
+    pub fn width(&self) -> f32 {
+        normalize(self.right.0 - self.left.0)
+    }
+
+    pub fn contains(&self, from: &WorldPoint, point: &WorldPoint) -> bool {
+        if distance_between_points(from, point) > self.range {
+            return false;
+        }
+
+        normalize(angle(point, from).0 - self.left.0) <= self.width()
+    }
+}
+
+fn normalize(radians: f32) -> f32 {
+    radians.rem_euclid(PI * 2.)
+}
+
+/// Width of fire arc to apply on next given defend orders
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FireArcMode {
+    #[default]
+    Free,
+    Narrow,
+    Medium,
+    Wide,
+}
+
+impl FireArcMode {
+    pub fn next(&self) -> Self {
+        match self {
+            FireArcMode::Free => FireArcMode::Narrow,
+            FireArcMode::Narrow => FireArcMode::Medium,
+            FireArcMode::Medium => FireArcMode::Wide,
+            FireArcMode::Wide => FireArcMode::Free,
+        }
+    }
+
+    pub fn width(&self) -> Option<Angle> {
+        match self {
+            FireArcMode::Free => None,
+            FireArcMode::Narrow => Some(Angle(PI / 4.)),
+            FireArcMode::Medium => Some(Angle(PI / 2.)),
+            FireArcMode::Wide => Some(Angle(PI * 3. / 4.)),
+        }
+    }
+}
+
+impl Display for FireArcMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.width() {
+            Some(width) => f.write_str(&format!("{}°", width.0.to_degrees().round())),
+            None => f.write_str("Free"),
+        }
+    }
+}
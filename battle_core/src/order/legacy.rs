@@ -0,0 +1,89 @@
+//! Before fire arcs, defend orders were only a facing angle and written (in deployment files)
+//! like `{"Defend": 4.16}`. Human readable formats accept both shapes, binary ones (network,
+//! saves) only the current one.
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::Angle;
+
+use super::Order;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OrderFormat {
+    Current(#[serde(with = "Order")] Order),
+    Legacy(LegacyOrder),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+enum LegacyOrder {
+    Defend(Angle),
+}
+
+impl Serialize for Order {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Order::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Order {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            return Order::deserialize(deserializer);
+        }
+
+        match OrderFormat::deserialize(deserializer)? {
+            OrderFormat::Current(order) => Ok(order),
+            OrderFormat::Legacy(LegacyOrder::Defend(angle)) => Ok(Order::Defend(angle, None)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::*;
+
+    use crate::{
+        order::arc::FireArc,
+        types::{Distance, WorldPath, WorldPaths, WorldPoint},
+    };
+
+    use super::*;
+
+    fn move_then_defend() -> Order {
+        Order::MoveTo(
+            WorldPaths::new(vec![WorldPath::new(vec![WorldPoint::new(1., 2.)])]),
+            Some(Box::new(Order::Defend(Angle(1.5), None))),
+        )
+    }
+
+    #[rstest]
+    #[case(r#"{"Defend": 4.16}"#, Order::Defend(Angle(4.16), None))]
+    #[case(r#"{"Defend": [4.16, null]}"#, Order::Defend(Angle(4.16), None))]
+    #[case(r#"{"Hide": 1.0}"#, Order::Hide(Angle(1.0)))]
+    #[case(r#""Idle""#, Order::Idle)]
+    #[case(
+        r#"{"MoveTo": [{"paths": [{"points": [{"x": 1.0, "y": 2.0}]}]}, {"Defend": 1.5}]}"#,
+        move_then_defend()
+    )]
+    fn order_from_json(#[case] json: &str, #[case] expected: Order) {
+        assert_eq!(serde_json::from_str::<Order>(json).unwrap(), expected);
+    }
+
+    #[test]
+    fn order_binary_round_trip() {
+        let order = Order::MoveTo(
+            WorldPaths::new(vec![WorldPath::new(vec![WorldPoint::new(1., 2.)])]),
+            Some(Box::new(Order::Defend(
+                Angle(1.5),
+                Some(FireArc::centered(
+                    Angle(1.5),
+                    Angle(1.),
+                    Distance::from_meters(50),
+                )),
+            ))),
+        );
+        let bytes = bincode::serialize(&order).unwrap();
+        assert_eq!(bincode::deserialize::<Order>(&bytes).unwrap(), order);
+    }
+}
@@ -22,6 +22,7 @@ pub enum OrderMarker {
     Hide,
     EngageSquad,
     SuppressFire,
+    AreaFire,
 }
 
 impl OrderMarker {
@@ -31,6 +32,7 @@ impl OrderMarker {
             | OrderMarker::MoveFastTo
             | OrderMarker::SneakTo
             | OrderMarker::SuppressFire
+            | OrderMarker::AreaFire
             | OrderMarker::EngageSquad => Offset::new(1.0, 1.0),
             OrderMarker::Defend | OrderMarker::Hide => Offset::new(1.0, 0.33),
         }
@@ -70,7 +72,8 @@ impl OrderMarker {
                 half_width: ORDER_MARKER_WIDTH / 2.0,
                 half_height: ORDER_MARKER_HEIGHT / 2.0,
             },
-            OrderMarker::SuppressFire => OrderMarkerSpriteInfo {
+            // Area fire zone is drawn around suppress fire marker
+            OrderMarker::SuppressFire | OrderMarker::AreaFire => OrderMarkerSpriteInfo {
                 relative_start_x: (ORDER_MARKER_START_X + ORDER_MARKER_WIDTH)
                     / UI_SPRITE_SHEET_WIDTH,
                 relative_start_y: (ORDER_MARKER_START_Y + (ORDER_MARKER_HEIGHT * 3.0))
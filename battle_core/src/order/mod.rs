@@ -1,11 +1,14 @@
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display};
 
 use crate::types::*;
 use serde::{Deserialize, Serialize};
 
-use self::marker::OrderMarker;
+use self::{arc::FireArc, marker::OrderMarker};
 
+pub mod arc;
+mod legacy;
 pub mod marker;
+pub mod trigger;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum PendingOrder {
@@ -87,16 +90,58 @@ impl Display for PendingOrder {
     }
 }
 
+pub type SquadOrderQueues = HashMap<SquadUuid, SquadOrderQueue>;
+
+/// Identify a queued order leg, whatever legs given (and removed) before it
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct OrderLegId(pub u64);
+
+/// Orders to give to a squad after its current one
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SquadOrderQueue {
+    legs: Vec<(OrderLegId, Order)>,
+    next_id: u64,
+}
+
+impl SquadOrderQueue {
+    pub fn legs(&self) -> &[(OrderLegId, Order)] {
+        &self.legs
+    }
+
+    pub fn push(&mut self, order: Order) {
+        self.legs.push((OrderLegId(self.next_id), order));
+        self.next_id += 1;
+    }
+
+    pub fn replace(&mut self, id: &OrderLegId, order: Order) {
+        if let Some((_, queued)) = self.legs.iter_mut().find(|(id_, _)| id_ == id) {
+            *queued = order
+        }
+    }
+
+    pub fn remove(&mut self, id: &OrderLegId) {
+        self.legs.retain(|(id_, _)| id_ != id)
+    }
+
+    // Ids are not reused, so a message about a removed leg can't alter a new one
+    pub fn clear(&mut self) {
+        self.legs.clear()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(remote = "Self")]
 pub enum Order {
     Idle,
     MoveTo(WorldPaths, Option<Box<Order>>),
     MoveFastTo(WorldPaths, Option<Box<Order>>),
     SneakTo(WorldPaths, Option<Box<Order>>),
-    Defend(Angle),
+    Defend(Angle, Option<FireArc>),
     Hide(Angle),
     EngageSquad(SquadUuid),
     SuppressFire(WorldPoint),
+    // Suppress fire spread over the zone (center and radius)
+    AreaFire(WorldPoint, Distance),
 }
 
 impl Order {
@@ -105,10 +150,11 @@ impl Order {
             Order::MoveTo(_, _) => Some(OrderMarker::MoveTo),
             Order::MoveFastTo(_, _) => Some(OrderMarker::MoveFastTo),
             Order::SneakTo(_, _) => Some(OrderMarker::SneakTo),
-            Order::Defend(_) => Some(OrderMarker::Defend),
+            Order::Defend(_, _) => Some(OrderMarker::Defend),
             Order::Hide(_) => Some(OrderMarker::Hide),
             Order::EngageSquad(_) => Some(OrderMarker::EngageSquad),
             Order::SuppressFire(_) => Some(OrderMarker::SuppressFire),
+            Order::AreaFire(_, _) => Some(OrderMarker::AreaFire),
             Order::Idle => None,
         }
     }
@@ -116,9 +162,9 @@ impl Order {
     pub fn angle(&self) -> Option<Angle> {
         match self {
             Order::MoveTo(_, _) | Order::MoveFastTo(_, _) | Order::SneakTo(_, _) => None,
-            Order::Defend(angle) => Some(*angle),
+            Order::Defend(angle, _) => Some(*angle),
             Order::Hide(angle) => Some(*angle),
-            Order::SuppressFire(_) => None,
+            Order::SuppressFire(_) | Order::AreaFire(_, _) => None,
             Order::EngageSquad(_) => None,
             Order::Idle => None,
         }
@@ -135,16 +181,34 @@ impl Order {
                     return true;
                 }
             }
-            Order::Defend(_) => {}
+            Order::Defend(_, _) => {}
             Order::Hide(_) => {}
             Order::Idle => {}
             Order::EngageSquad(_) => {}
             Order::SuppressFire(_) => {}
+            Order::AreaFire(_, _) => {}
         }
 
         false
     }
 
+    /// Point where the squad leader will be when this order is finished (only for move orders)
+    pub fn end_point(&self) -> Option<WorldPoint> {
+        match self {
+            Order::MoveTo(paths, _) | Order::MoveFastTo(paths, _) | Order::SneakTo(paths, _) => {
+                paths.paths.last().and_then(|path| path.last_point())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn fire_arc(&self) -> Option<&FireArc> {
+        match self {
+            Order::Defend(_, fire_arc) => fire_arc.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn then(&self) -> Option<Order> {
         match self {
             Self::MoveTo(_, then) => then,
@@ -163,11 +227,12 @@ impl Display for Order {
             Order::MoveTo(_, _) => f.write_str("MoveTo"),
             Order::MoveFastTo(_, _) => f.write_str("MoveFastTo"),
             Order::SneakTo(_, _) => f.write_str("SneakTo"),
-            Order::Defend(_) => f.write_str("Defend"),
+            Order::Defend(_, _) => f.write_str("Defend"),
             Order::Hide(_) => f.write_str("Hide"),
             Order::Idle => f.write_str("Idle"),
             Order::EngageSquad(_) => f.write_str("Engage"),
             Order::SuppressFire(_) => f.write_str("SuppressFire"),
+            Order::AreaFire(_, _) => f.write_str("AreaFire"),
         }
     }
 }
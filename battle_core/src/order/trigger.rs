@@ -0,0 +1,93 @@
+use std::{collections::HashMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{config::TARGET_FPS, types::*};
+
+use super::Order;
+
+pub type SquadTriggeredOrders = HashMap<SquadUuid, (OrderTrigger, Order)>;
+// Frame when squad reached its move destination
+pub type SquadDestinationsReached = HashMap<SquadUuid, u64>;
+
+/// Condition to give an order to a squad
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum OrderTrigger {
+    /// Battle reached this frame
+    Frame(u64),
+    /// Given squad reached its destination after given frame
+    SquadReachedDestination(SquadUuid, u64),
+    /// Given squad came under fire after given frame
+    SquadUnderFire(SquadUuid, u64),
+}
+
+impl OrderTrigger {
+    /// Squad which condition depends on
+    pub fn reference_squad(&self) -> Option<&SquadUuid> {
+        match self {
+            OrderTrigger::Frame(_) => None,
+            OrderTrigger::SquadReachedDestination(squad_uuid, _)
+            | OrderTrigger::SquadUnderFire(squad_uuid, _) => Some(squad_uuid),
+        }
+    }
+}
+
+/// Trigger chosen by player for the next orders
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderTriggerMode {
+    #[default]
+    Immediate,
+    // Delay in seconds
+    Delay(u64),
+    SquadReachedDestination,
+    SquadUnderFire,
+}
+
+impl OrderTriggerMode {
+    pub fn next(&self) -> Self {
+        match self {
+            OrderTriggerMode::Immediate => OrderTriggerMode::Delay(30),
+            OrderTriggerMode::Delay(30) => OrderTriggerMode::Delay(60),
+            OrderTriggerMode::Delay(60) => OrderTriggerMode::Delay(120),
+            OrderTriggerMode::Delay(_) => OrderTriggerMode::SquadReachedDestination,
+            OrderTriggerMode::SquadReachedDestination => OrderTriggerMode::SquadUnderFire,
+            OrderTriggerMode::SquadUnderFire => OrderTriggerMode::Immediate,
+        }
+    }
+
+    pub fn expect_squad(&self) -> bool {
+        matches!(
+            self,
+            OrderTriggerMode::SquadReachedDestination | OrderTriggerMode::SquadUnderFire
+        )
+    }
+
+    /// Produce the trigger, `squad_uuid` is required when mode expect a squad
+    pub fn trigger(&self, frame_i: u64, squad_uuid: Option<SquadUuid>) -> Option<OrderTrigger> {
+        match (self, squad_uuid) {
+            (OrderTriggerMode::Immediate, _) => None,
+            (OrderTriggerMode::Delay(seconds), _) => {
+                Some(OrderTrigger::Frame(frame_i + seconds * TARGET_FPS))
+            }
+            (OrderTriggerMode::SquadReachedDestination, Some(squad_uuid)) => {
+                Some(OrderTrigger::SquadReachedDestination(squad_uuid, frame_i))
+            }
+            (OrderTriggerMode::SquadUnderFire, Some(squad_uuid)) => {
+                Some(OrderTrigger::SquadUnderFire(squad_uuid, frame_i))
+            }
+            (OrderTriggerMode::SquadReachedDestination, None)
+            | (OrderTriggerMode::SquadUnderFire, None) => None,
+        }
+    }
+}
+
+impl Display for OrderTriggerMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrderTriggerMode::Immediate => f.write_str("Immediate"),
+            OrderTriggerMode::Delay(seconds) => f.write_str(&format!("In {}s", seconds)),
+            OrderTriggerMode::SquadReachedDestination => f.write_str("Squad arrived"),
+            OrderTriggerMode::SquadUnderFire => f.write_str("Squad under fire"),
+        }
+    }
+}
@@ -2,23 +2,30 @@ use bresenham::Bresenham;
 use rand::Rng;
 
 use crate::{
-    config::{COVERAGE_PIXEL_STEPS, COVERAGE_TILE_STEPS},
+    config::{ServerConfig, COVERAGE_PIXEL_STEPS, COVERAGE_TILE_STEPS, WINDOW_COVERAGE},
     entity::soldier::Soldier,
-    map::Map,
+    map::{terrain::Opening, Map},
     types::WorldPoint,
 };
 
 use super::event::bullet::BulletFire;
 
 pub struct SoldierCovered<'a> {
+    config: &'a ServerConfig,
     map: &'a Map,
     bullet_fire: &'a BulletFire,
     soldier: &'a Soldier,
 }
 
 impl<'a> SoldierCovered<'a> {
-    pub fn new(map: &'a Map, bullet_fire: &'a BulletFire, soldier: &'a Soldier) -> Self {
+    pub fn new(
+        config: &'a ServerConfig,
+        map: &'a Map,
+        bullet_fire: &'a BulletFire,
+        soldier: &'a Soldier,
+    ) -> Self {
         Self {
+            config,
             map,
             bullet_fire,
             soldier,
@@ -38,6 +45,35 @@ impl<'a> SoldierCovered<'a> {
             ),
         );
 
+        // Soldier on higher ground than shooter is harder to hit
+        let height_advantage = self
+            .map
+            .height_advantage(&self.soldier.world_point(), self.bullet_fire.from());
+        let elevation_coverage = (height_advantage * self.config.elevation_coverage_by_meter)
+            .clamp(0., self.config.elevation_coverage_max);
+        if elevation_coverage > 0. {
+            let mut rng = rand::thread_rng();
+            let value: f32 = rng.gen();
+            if value <= elevation_coverage {
+                return true;
+            }
+        }
+
+        // Soldier behind a window or on building upper floor is mostly covered by walls, if
+        // not, terrain between him and shooter can still cover him
+        let soldier_grid_point = self
+            .map
+            .grid_point_from_world_point(&self.soldier.world_point());
+        if self.soldier.floor() > 0
+            || self.map.opening_at(&soldier_grid_point) == Some(&Opening::Window)
+        {
+            let mut rng = rand::thread_rng();
+            let value: f32 = rng.gen();
+            if value <= WINDOW_COVERAGE {
+                return true;
+            }
+        }
+
         if force_target_tile {
             let target_grid_point = self
                 .map
@@ -48,7 +84,7 @@ impl<'a> SoldierCovered<'a> {
                 .terrain_tiles()
                 .get((target_grid_point.y * self.map.width() as i32 + target_grid_point.x) as usize)
             {
-                if let Some(coverage) = tile.type_().coverage(&self.soldier.behavior().posture()) {
+                if let Some(coverage) = tile.coverage(&self.soldier.behavior().posture()) {
                     let mut rng = rand::thread_rng();
                     let value: f32 = rng.gen();
                     return value <= coverage.0;
@@ -66,7 +102,7 @@ impl<'a> SoldierCovered<'a> {
                 .terrain_tiles()
                 .get((grid_point.y * self.map.width() as i32 + grid_point.x) as usize)
             {
-                if let Some(coverage) = tile.type_().coverage(&self.soldier.behavior().posture()) {
+                if let Some(coverage) = tile.coverage(&self.soldier.behavior().posture()) {
                     let mut rng = rand::thread_rng();
                     let value: f32 = rng.gen();
                     return value <= coverage.0;
@@ -82,3 +118,163 @@ impl<'a> SoldierCovered<'a> {
         false
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use oc_core::game::soldier::SoldierType;
+    use rstest::*;
+
+    use crate::{
+        game::{
+            weapon::{definition::WeaponTarget, Ammunition, Shot},
+            Side,
+        },
+        map::{
+            decor::Decor,
+            terrain::{TerrainTile, TileType},
+        },
+        types::{Offset, SoldierIndex, SquadUuid},
+    };
+
+    use super::*;
+
+    /// Short grass map (which never covers) with a 10 meters hill on its east half
+    fn hill_map() -> Map {
+        grass_map(|tile| {
+            if tile.x >= 10 {
+                tile.elevation = 10.;
+            }
+        })
+    }
+
+    /// Flat short grass map with a window at (15, 10)
+    fn window_map() -> Map {
+        grass_map(|tile| {
+            if (tile.x, tile.y) == (15, 10) {
+                tile.opening = Some(Opening::Window);
+            }
+        })
+    }
+
+    fn grass_map(setup: impl Fn(&mut TerrainTile)) -> Map {
+        let size = 20;
+        let tiles = (0..size * size)
+            .map(|i| {
+                let (x, y) = (i % size, i / size);
+                let mut tile = TerrainTile::new(TileType::ShortGrass, 5, 5, 1., 1., x, y, 0, 0);
+                setup(&mut tile);
+                tile
+            })
+            .collect();
+
+        Map::new(
+            "TestMap".to_string(),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            vec![],
+            vec![],
+            size,
+            size,
+            tiles,
+            5,
+            5,
+            Decor::new(vec![], vec![], Offset::new(0., 0.)),
+            vec![],
+        )
+    }
+
+    #[rstest]
+    #[case(1., 1., true)]
+    #[case(1., 0., false)]
+    #[case(0., 1., false)]
+    fn elevation_coverage_follow_config(
+        #[case] by_meter: f32,
+        #[case] max: f32,
+        #[case] expected: bool,
+    ) {
+        let config = ServerConfig {
+            elevation_coverage_by_meter: by_meter,
+            elevation_coverage_max: max,
+            ..Default::default()
+        };
+        let map = hill_map();
+        let soldier = Soldier::new(
+            SoldierIndex(0),
+            SoldierType::Type1,
+            WorldPoint::new(75., 50.),
+            SquadUuid(0),
+            Side::A,
+            None,
+            vec![],
+        );
+        let bullet_fire = BulletFire::new(
+            0,
+            WorldPoint::new(10., 50.),
+            soldier.world_point(),
+            None,
+            Ammunition("7.92×57mm".to_string()),
+            None,
+            Shot::x1,
+            WeaponTarget::Infantry,
+            None,
+        );
+
+        let covered = SoldierCovered::new(&config, &map, &bullet_fire, &soldier);
+        for _ in 0..20 {
+            assert_eq!(covered.compute(false), expected);
+        }
+    }
+
+    #[rstest]
+    // On grass, in front of the window
+    #[case(WorldPoint::new(72., 52.), 0, false)]
+    // Behind the window
+    #[case(WorldPoint::new(77., 52.), 0, true)]
+    // On a building upper floor
+    #[case(WorldPoint::new(72., 52.), 1, true)]
+    fn window_and_upper_floor_mostly_cover(
+        #[case] point: WorldPoint,
+        #[case] floor: u8,
+        #[case] expected: bool,
+    ) {
+        let config = ServerConfig::default();
+        let map = window_map();
+        let mut soldier = Soldier::new(
+            SoldierIndex(0),
+            SoldierType::Type1,
+            point,
+            SquadUuid(0),
+            Side::A,
+            None,
+            vec![],
+        );
+        soldier.set_floor(floor);
+        let bullet_fire = BulletFire::new(
+            0,
+            WorldPoint::new(10., 52.),
+            soldier.world_point(),
+            None,
+            Ammunition("7.92×57mm".to_string()),
+            None,
+            Shot::x1,
+            WeaponTarget::Infantry,
+            None,
+        );
+
+        let covered = SoldierCovered::new(&config, &map, &bullet_fire, &soldier);
+        let covered_count = (0..200).filter(|_| covered.compute(false)).count();
+        if expected {
+            // Covered by WINDOW_COVERAGE (75%) of the time, but not always
+            assert!(
+                (100..200).contains(&covered_count),
+                "{} covered shots",
+                covered_count
+            );
+        } else {
+            assert_eq!(covered_count, 0);
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    game::weapon::{Ammunition, GunFireSoundType, Shot},
+    game::weapon::{definition::WeaponTarget, Ammunition, Shot, WeaponType},
     types::{Precision, SoldierIndex, WorldPoint},
 };
 use serde::{Deserialize, Serialize};
@@ -12,12 +12,17 @@ pub struct BulletFire {
     to: WorldPoint,
     target: Option<(SoldierIndex, Precision)>,
     ammunition: Ammunition,
-    gun_fire_sound_type: Option<GunFireSoundType>,
+    // Weapon which fire sound must be played
+    gun_fire_sound: Option<WeaponType>,
     // FIXME BS NOW : remove ?
     shot: Shot,
+    effective_against: WeaponTarget,
+    // Soldier which fired and its weapon name (for battle events)
+    shooter: Option<(SoldierIndex, String)>,
 }
 
 impl BulletFire {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         // Used as offset (machine gun)
         start: u64,
@@ -25,8 +30,10 @@ impl BulletFire {
         to: WorldPoint,
         target: Option<(SoldierIndex, Precision)>,
         ammunition: Ammunition,
-        gun_fire_sound_type: Option<GunFireSoundType>,
+        gun_fire_sound: Option<WeaponType>,
         shot: Shot,
+        effective_against: WeaponTarget,
+        shooter: Option<(SoldierIndex, String)>,
     ) -> Self {
         Self {
             start,
@@ -35,8 +42,10 @@ impl BulletFire {
             to,
             target,
             ammunition,
-            gun_fire_sound_type,
+            gun_fire_sound,
             shot,
+            effective_against,
+            shooter,
         }
     }
 
@@ -50,6 +59,10 @@ impl BulletFire {
         &self.to
     }
 
+    pub fn shooter(&self) -> &Option<(SoldierIndex, String)> {
+        &self.shooter
+    }
+
     pub fn ammunition(&self) -> &Ammunition {
         &self.ammunition
     }
@@ -78,11 +91,15 @@ impl BulletFire {
         self.end
     }
 
-    pub fn gun_fire_sound_type(&self) -> &Option<GunFireSoundType> {
-        &self.gun_fire_sound_type
+    pub fn gun_fire_sound(&self) -> &Option<WeaponType> {
+        &self.gun_fire_sound
     }
 
     pub fn shots(&self) -> &Shot {
         &self.shot
     }
+
+    pub fn effective_against(&self) -> &WeaponTarget {
+        &self.effective_against
+    }
 }
@@ -1,4 +1,8 @@
-use crate::{config::TARGET_FPS, game::explosive::ExplosiveType, types::WorldPoint};
+use crate::{
+    config::TARGET_FPS,
+    game::explosive::ExplosiveType,
+    types::{SoldierIndex, WorldPoint},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,15 +11,18 @@ pub struct Explosion {
     end: u64,
     point: WorldPoint,
     explosive_type: ExplosiveType,
+    // None when not produced by a soldier (debug explosion, etc)
+    thrower: Option<SoldierIndex>,
 }
 
 impl Explosion {
-    pub fn new(point: WorldPoint, type_: ExplosiveType) -> Self {
+    pub fn new(point: WorldPoint, type_: ExplosiveType, thrower: Option<SoldierIndex>) -> Self {
         Self {
             start: 0,
             end: 0,
             point,
             explosive_type: type_,
+            thrower,
         }
     }
 
@@ -52,4 +59,8 @@ impl Explosion {
     pub fn explosive_type(&self) -> &ExplosiveType {
         &self.explosive_type
     }
+
+    pub fn thrower(&self) -> Option<SoldierIndex> {
+        self.thrower
+    }
 }
@@ -0,0 +1,255 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::{
+    config::ServerConfig,
+    map::Map,
+    physics::path::{find_path, Direction, PathMode},
+    types::GridPoint,
+};
+
+// Cost added to a tile for each soldier already going through it
+pub const FLOW_FIELD_SEPARATION_COST: i32 = 15;
+// Distance (in tiles) from its formation position where a soldier leave the flow field
+pub const FLOW_FIELD_SLOT_DISTANCE: f32 = 6.;
+// Search radius (in tiles) of a free formation position when it is already taken
+const FLOW_FIELD_FREE_SLOT_RADIUS: i32 = 4;
+
+/// Cost (pedestrian tile costs) to reach a destination area (the tiles of a cluster node) from
+/// each map tile. Soldiers moving to the same area follow it instead of computing their own path.
+#[derive(Debug)]
+pub struct FlowField {
+    costs: Vec<Option<i32>>,
+}
+
+impl FlowField {
+    pub fn new(map: &Map, destinations: &[GridPoint], path_mode: &PathMode) -> Self {
+        let index = |point: &GridPoint| (point.y * map.width() as i32 + point.x) as usize;
+        let mut costs = vec![None; map.terrain_tiles().len()];
+        let mut heap = BinaryHeap::new();
+
+        for destination in destinations.iter().filter(|point| map.contains(point)) {
+            costs[index(destination)] = Some(0);
+            heap.push(Reverse((0, destination.x, destination.y)));
+        }
+
+        // Reverse Dijkstra : neighbors are symmetric, cost is the one to enter current tile
+        while let Some(Reverse((cost, x, y))) = heap.pop() {
+            let point = GridPoint::new(x, y);
+            if matches!(costs[index(&point)], Some(known) if known < cost) {
+                continue;
+            }
+            let tile = match map.terrain_tile_at(&point) {
+                Some(tile) => tile,
+                None => continue,
+            };
+
+            for ((previous, _), _) in map.successors(&(point, Direction::North), path_mode) {
+                let previous_cost =
+                    cost + tile.pedestrian_cost() + map.uphill_cost(&previous, tile);
+                let i = index(&previous);
+                if costs[i].is_none_or(|known| previous_cost < known) {
+                    costs[i] = Some(previous_cost);
+                    heap.push(Reverse((previous_cost, previous.x, previous.y)));
+                }
+            }
+        }
+
+        Self { costs }
+    }
+
+    /// Cost to reach destination from given point, None if unreachable
+    pub fn cost(&self, map: &Map, point: &GridPoint) -> Option<i32> {
+        if !map.contains(point) {
+            return None;
+        }
+
+        self.costs
+            .get((point.y * map.width() as i32 + point.x) as usize)
+            .cloned()
+            .flatten()
+    }
+
+    /// Path from given point to its formation position (slot) : follow the field while the slot
+    /// is further along it, preferring tiles with less soldiers going through (`occupied`),
+    /// then join the slot. Used tiles are added to `occupied`.
+    pub fn path(
+        &self,
+        config: &ServerConfig,
+        map: &Map,
+        from: &GridPoint,
+        slot: &GridPoint,
+        path_mode: &PathMode,
+        occupied: &mut HashMap<GridPoint, i32>,
+    ) -> Option<Vec<GridPoint>> {
+        let mut current_cost = self.cost(map, from)?;
+        // Slot can be behind soldier (ex. leader far behind), then field is useless
+        let slot_cost = self.cost(map, slot).unwrap_or(0);
+        let mut current = *from;
+        let mut path = vec![];
+
+        while current_cost > slot_cost
+            && current.to_vec2().distance(slot.to_vec2()) > FLOW_FIELD_SLOT_DISTANCE
+        {
+            // Only descending tiles, so the path always progress to destination
+            let next = map
+                .successors(&(current, Direction::North), path_mode)
+                .into_iter()
+                .filter_map(|((next, _), _)| {
+                    self.cost(map, &next)
+                        .filter(|cost| *cost < current_cost)
+                        .map(|cost| (next, cost))
+                })
+                .min_by_key(|(next, cost)| {
+                    cost + occupied.get(next).unwrap_or(&0) * FLOW_FIELD_SEPARATION_COST
+                });
+
+            match next {
+                Some((next, cost)) => {
+                    path.push(next);
+                    current = next;
+                    current_cost = cost;
+                }
+                // Destination reached
+                None => break,
+            }
+        }
+
+        if current != *slot {
+            path.extend(find_path(
+                config, map, &current, slot, true, path_mode, &None,
+            )?);
+        }
+
+        for point in &path {
+            *occupied.entry(*point).or_default() += 1;
+        }
+
+        (!path.is_empty()).then_some(path)
+    }
+
+    /// Given formation position, or the nearest free one if already taken
+    pub fn free_slot(&self, map: &Map, slot: &GridPoint, taken: &[GridPoint]) -> GridPoint {
+        if !taken.contains(slot) {
+            return *slot;
+        }
+
+        let mut candidates = vec![];
+        for y in -FLOW_FIELD_FREE_SLOT_RADIUS..=FLOW_FIELD_FREE_SLOT_RADIUS {
+            for x in -FLOW_FIELD_FREE_SLOT_RADIUS..=FLOW_FIELD_FREE_SLOT_RADIUS {
+                let candidate = GridPoint::new(slot.x + x, slot.y + y);
+                if !taken.contains(&candidate) && self.cost(map, &candidate).is_some() {
+                    candidates.push(candidate);
+                }
+            }
+        }
+
+        candidates
+            .into_iter()
+            .min_by_key(|candidate| (candidate.x - slot.x).pow(2) + (candidate.y - slot.y).pow(2))
+            .unwrap_or(*slot)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::map::cluster::test::map_with_wires;
+
+    use super::*;
+
+    fn assert_walkable_path(from: &GridPoint, path: &[GridPoint]) {
+        let mut previous = *from;
+        for point in path {
+            assert!((point.x - previous.x).abs() <= 1 && (point.y - previous.y).abs() <= 1);
+            previous = *point;
+        }
+    }
+
+    #[test]
+    fn same_area_destinations_share_flow_field() {
+        let map = map_with_wires(48, &[]);
+        let cache = map.path_cache();
+
+        let field = cache
+            .flow_field(&map, &GridPoint::new(2, 2), &PathMode::Walk)
+            .unwrap();
+        let same_area = cache
+            .flow_field(&map, &GridPoint::new(10, 12), &PathMode::Walk)
+            .unwrap();
+        let other_area = cache
+            .flow_field(&map, &GridPoint::new(30, 30), &PathMode::Walk)
+            .unwrap();
+
+        assert!(Arc::ptr_eq(&field, &same_area));
+        assert!(!Arc::ptr_eq(&field, &other_area));
+        assert_eq!(field.cost(&map, &GridPoint::new(15, 15)), Some(0));
+        assert!(field.cost(&map, &GridPoint::new(16, 16)) > Some(0));
+    }
+
+    #[test]
+    fn path_follow_field_then_join_slot() {
+        let config = ServerConfig::default();
+        let map = map_with_wires(48, &[]);
+        let field = map
+            .path_cache()
+            .flow_field(&map, &GridPoint::new(2, 2), &PathMode::Walk)
+            .unwrap();
+        let from = GridPoint::new(40, 40);
+        let slot = GridPoint::new(5, 3);
+        let mut occupied = HashMap::new();
+
+        let path = field
+            .path(&config, &map, &from, &slot, &PathMode::Walk, &mut occupied)
+            .unwrap();
+
+        assert_eq!(path.last(), Some(&slot));
+        assert_walkable_path(&from, &path);
+        assert_eq!(occupied.len(), path.len());
+    }
+
+    #[test]
+    fn slot_behind_soldier_is_joined_directly() {
+        let config = ServerConfig::default();
+        let map = map_with_wires(48, &[]);
+        let field = map
+            .path_cache()
+            .flow_field(&map, &GridPoint::new(2, 2), &PathMode::Walk)
+            .unwrap();
+        let from = GridPoint::new(20, 20);
+        let slot = GridPoint::new(40, 40);
+
+        let path = field
+            .path(
+                &config,
+                &map,
+                &from,
+                &slot,
+                &PathMode::Walk,
+                &mut HashMap::new(),
+            )
+            .unwrap();
+
+        assert_eq!(path.last(), Some(&slot));
+        assert_eq!(path.len(), 20);
+        assert_walkable_path(&from, &path);
+    }
+
+    #[test]
+    fn taken_slot_is_replaced_by_nearest_free_one() {
+        let map = map_with_wires(48, &[]);
+        let field = map
+            .path_cache()
+            .flow_field(&map, &GridPoint::new(2, 2), &PathMode::Walk)
+            .unwrap();
+        let slot = GridPoint::new(10, 10);
+
+        assert_eq!(field.free_slot(&map, &slot, &[]), slot);
+        let free = field.free_slot(&map, &slot, &[slot]);
+        assert_ne!(free, slot);
+        assert!((free.x - slot.x).abs() <= 1 && (free.y - slot.y).abs() <= 1);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod coverage;
 pub mod event;
+pub mod flow;
 pub mod path;
 pub mod utils;
 pub mod visibility;
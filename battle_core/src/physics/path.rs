@@ -1,16 +1,34 @@
-use crate::{config::ServerConfig, map::Map, types::*, utils::angleg};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    config::ServerConfig,
+    map::{cluster::Clusters, region::Regions, Map},
+    physics::flow::FlowField,
+    types::*,
+    utils::angleg,
+};
 use pathfinding::prelude::astar;
 use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
+// Cache is emptied when reaching this count of paths
+const PATH_CACHE_MAX_ENTRIES: usize = 4096;
+// Flow fields cache is emptied when reaching this count of flow fields
+const FLOW_FIELD_CACHE_MAX_ENTRIES: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PathMode {
     Walk,
+    Sneak,
     Drive(VehicleSize),
 }
 impl PathMode {
     pub fn include_vehicles(&self) -> bool {
         match self {
-            PathMode::Walk => false,
+            PathMode::Walk | PathMode::Sneak => false,
             PathMode::Drive(_) => true,
         }
     }
@@ -155,7 +173,154 @@ impl Direction {
     }
 }
 
-// TODO : When "to" is unreachable (ex. for vehicle) do not search a path (it consume all path before stop)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PathKey {
+    from: GridPoint,
+    to: GridPoint,
+    exclude_first: bool,
+    path_mode: PathMode,
+    // Only for vehicles, pedestrian costs don't depend on direction
+    start_direction: Option<Direction>,
+    heuristic_coefficient: u32,
+}
+
+/// Already found paths and computed regions and clusters of a map. Must be cleared when map
+/// terrain change.
+#[derive(Debug, Default)]
+pub struct PathCache {
+    paths: Mutex<HashMap<PathKey, Option<Vec<GridPoint>>>>,
+    regions: Mutex<HashMap<PathMode, Arc<Regions>>>,
+    clusters: Mutex<HashMap<PathMode, Arc<Clusters>>>,
+    // By destination cluster node : soldiers going to the same area share the same flow field
+    flow_fields: Mutex<HashMap<(usize, PathMode), Arc<FlowField>>>,
+}
+
+impl PathCache {
+    fn path(&self, key: &PathKey) -> Option<Option<Vec<GridPoint>>> {
+        self.paths
+            .lock()
+            .expect("Path cache lock must not be poisoned")
+            .get(key)
+            .cloned()
+    }
+
+    fn insert_path(&self, key: PathKey, path: Option<Vec<GridPoint>>) {
+        let mut paths = self
+            .paths
+            .lock()
+            .expect("Path cache lock must not be poisoned");
+        if paths.len() >= PATH_CACHE_MAX_ENTRIES {
+            paths.clear();
+        }
+        paths.insert(key, path);
+    }
+
+    /// Regions of given path mode, computed at first use
+    fn regions(&self, map: &Map, path_mode: &PathMode) -> Arc<Regions> {
+        if let Some(regions) = self
+            .regions
+            .lock()
+            .expect("Path cache lock must not be poisoned")
+            .get(path_mode)
+        {
+            return regions.clone();
+        }
+
+        // Computed outside of lock to not block other path findings
+        let regions = Arc::new(Regions::new(map, path_mode));
+        self.regions
+            .lock()
+            .expect("Path cache lock must not be poisoned")
+            .insert(*path_mode, regions.clone());
+        regions
+    }
+
+    /// Clusters of given path mode, computed at first use
+    fn clusters(&self, map: &Map, path_mode: &PathMode) -> Arc<Clusters> {
+        if let Some(clusters) = self
+            .clusters
+            .lock()
+            .expect("Path cache lock must not be poisoned")
+            .get(path_mode)
+        {
+            return clusters.clone();
+        }
+
+        // Computed outside of lock to not block other path findings
+        let clusters = Arc::new(Clusters::new(map, path_mode));
+        self.clusters
+            .lock()
+            .expect("Path cache lock must not be poisoned")
+            .insert(*path_mode, clusters.clone());
+        clusters
+    }
+
+    /// Flow field to the cluster node of given destination, computed at first use. None if
+    /// destination can't be entered.
+    pub fn flow_field(
+        &self,
+        map: &Map,
+        destination: &GridPoint,
+        path_mode: &PathMode,
+    ) -> Option<Arc<FlowField>> {
+        let clusters = self.clusters(map, path_mode);
+        let key = (clusters.node(map, destination)?, *path_mode);
+        if let Some(flow_field) = self
+            .flow_fields
+            .lock()
+            .expect("Path cache lock must not be poisoned")
+            .get(&key)
+        {
+            return Some(flow_field.clone());
+        }
+
+        // Computed outside of lock to not block other path findings
+        let flow_field = Arc::new(FlowField::new(
+            map,
+            &clusters.node_points(map, key.0),
+            path_mode,
+        ));
+        let mut flow_fields = self
+            .flow_fields
+            .lock()
+            .expect("Path cache lock must not be poisoned");
+        if flow_fields.len() >= FLOW_FIELD_CACHE_MAX_ENTRIES {
+            flow_fields.clear();
+        }
+        flow_fields.insert(key, flow_field.clone());
+        Some(flow_field)
+    }
+
+    pub fn clear(&self) {
+        self.paths
+            .lock()
+            .expect("Path cache lock must not be poisoned")
+            .clear();
+        self.regions
+            .lock()
+            .expect("Path cache lock must not be poisoned")
+            .clear();
+        self.clusters
+            .lock()
+            .expect("Path cache lock must not be poisoned")
+            .clear();
+        self.flow_fields
+            .lock()
+            .expect("Path cache lock must not be poisoned")
+            .clear();
+    }
+}
+
+// A cloned map (which can have its own terrain changes) starts with an empty cache
+impl Clone for PathCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Find a path on map tiles. Destinations outside of start region are rejected without
+/// search, tiles search is restricted to the clusters nodes found on clusters graph and found
+/// paths are cached until next terrain change.
 pub fn find_path(
     config: &ServerConfig,
     map: &Map,
@@ -170,27 +335,266 @@ pub fn find_path(
     }
     let start_direction = start_direction.unwrap_or(Direction::from_angle(&angleg(to, from)));
 
-    match astar(
-        &(*from, start_direction),
-        |p| map.successors(p, path_mode),
-        |p| {
-            (p.0.to_vec2().distance(to.to_vec2()) * config.path_finding_heuristic_coefficient)
-                as i32
-        },
-        |p| p.0 == *to,
-    ) {
-        None => None,
-        Some(path) => {
-            if exclude_first {
-                let new_path = path.0[1..].to_vec();
-                if !new_path.is_empty() {
-                    Some(new_path.iter().map(|x| x.0).collect())
+    let key = PathKey {
+        from: *from,
+        to: *to,
+        exclude_first,
+        path_mode: *path_mode,
+        start_direction: path_mode.include_vehicles().then_some(start_direction),
+        heuristic_coefficient: config.path_finding_heuristic_coefficient.to_bits(),
+    };
+    if let Some(path) = map.path_cache().path(&key) {
+        return path;
+    }
+
+    let path = if from == to
+        || map
+            .path_cache()
+            .regions(map, path_mode)
+            .connected(map, from, to, path_mode)
+    {
+        let clusters = map.path_cache().clusters(map, path_mode);
+        let corridor = (from != to)
+            .then(|| clusters.corridor(map, from, to, config.path_finding_heuristic_coefficient))
+            .flatten();
+
+        // Corridor can miss a way (ex. start point not enterable), then search on all tiles
+        corridor
+            .and_then(|corridor| {
+                find_tiles_path(
+                    config,
+                    map,
+                    from,
+                    to,
+                    exclude_first,
+                    path_mode,
+                    start_direction,
+                    |point| corridor.contains(map, point),
+                )
+            })
+            .or_else(|| {
+                find_tiles_path(
+                    config,
+                    map,
+                    from,
+                    to,
+                    exclude_first,
+                    path_mode,
+                    start_direction,
+                    |_| true,
+                )
+            })
+    } else {
+        None
+    };
+
+    map.path_cache().insert_path(key, path.clone());
+    path
+}
+
+/// Find a path on map tiles, without cache, regions or clusters
+pub fn find_raw_path(
+    config: &ServerConfig,
+    map: &Map,
+    from: &GridPoint,
+    to: &GridPoint,
+    exclude_first: bool,
+    path_mode: &PathMode,
+    start_direction: &Option<Direction>,
+) -> Option<Vec<GridPoint>> {
+    if !map.contains(from) || !map.contains(to) {
+        return None;
+    }
+    let start_direction = start_direction.unwrap_or(Direction::from_angle(&angleg(to, from)));
+
+    find_tiles_path(
+        config,
+        map,
+        from,
+        to,
+        exclude_first,
+        path_mode,
+        start_direction,
+        |_| true,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn find_tiles_path(
+    config: &ServerConfig,
+    map: &Map,
+    from: &GridPoint,
+    to: &GridPoint,
+    exclude_first: bool,
+    path_mode: &PathMode,
+    start_direction: Direction,
+    allowed: impl Fn(&GridPoint) -> bool,
+) -> Option<Vec<GridPoint>> {
+    let heuristic = |p: &GridPoint| {
+        (p.to_vec2().distance(to.to_vec2()) * config.path_finding_heuristic_coefficient) as i32
+    };
+
+    let path: Vec<GridPoint> = match path_mode {
+        // Pedestrian costs don't depend on direction, so search only on points (instead of
+        // each point for each direction)
+        PathMode::Walk | PathMode::Sneak => {
+            astar(
+                from,
+                |p| {
+                    map.successors(&(*p, start_direction), path_mode)
+                        .into_iter()
+                        .filter(|((point, _), _)| allowed(point))
+                        .map(|((point, _), cost)| (point, cost))
+                        .collect::<Vec<_>>()
+                },
+                heuristic,
+                |p| p == to,
+            )?
+            .0
+        }
+        PathMode::Drive(_) => astar(
+            &(*from, start_direction),
+            |p| {
+                map.successors(p, path_mode)
+                    .into_iter()
+                    .filter(|((point, _), _)| allowed(point))
+                    .collect::<Vec<_>>()
+            },
+            |p| heuristic(&p.0),
+            |p| p.0 == *to,
+        )?
+        .0
+        .into_iter()
+        .map(|(point, _)| point)
+        .collect(),
+    };
+
+    if exclude_first {
+        let new_path = path[1..].to_vec();
+        if !new_path.is_empty() {
+            Some(new_path)
+        } else {
+            None
+        }
+    } else {
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::map::{
+        cluster::test::{map_with_tiles, map_with_wires},
+        terrain::TileType,
+    };
+
+    use super::*;
+
+    // Wire wall with a gap at its bottom, crossing several clusters
+    fn walled_map() -> Map {
+        let wires = (0..56).map(|y| GridPoint::new(20, y)).collect::<Vec<_>>();
+        map_with_wires(64, &wires)
+    }
+
+    fn assert_valid_path(map: &Map, from: &GridPoint, to: &GridPoint, path: &[GridPoint]) {
+        assert_eq!(path.last(), Some(to));
+        let mut previous = *from;
+        for point in path {
+            assert!((point.x - previous.x).abs() <= 1 && (point.y - previous.y).abs() <= 1);
+            assert!(map.contains(point));
+            assert!(point.x != 20 || point.y >= 56, "{:?} is in wires", point);
+            previous = *point;
+        }
+    }
+
+    #[test]
+    fn clusters_path_is_valid_and_near_raw_path() {
+        let config = ServerConfig::default();
+        let map = walled_map();
+
+        for (from, to) in [
+            (GridPoint::new(2, 2), GridPoint::new(40, 2)),
+            (GridPoint::new(10, 30), GridPoint::new(60, 60)),
+            (GridPoint::new(62, 1), GridPoint::new(1, 62)),
+            (GridPoint::new(3, 3), GridPoint::new(5, 4)),
+        ] {
+            let raw =
+                find_raw_path(&config, &map, &from, &to, true, &PathMode::Sneak, &None).unwrap();
+            let path = find_path(&config, &map, &from, &to, true, &PathMode::Sneak, &None).unwrap();
+
+            assert_valid_path(&map, &from, &to, &path);
+            assert!(
+                path.len() as f32 <= raw.len() as f32 * 1.2,
+                "{:?} -> {:?} : {} tiles instead of {}",
+                from,
+                to,
+                path.len(),
+                raw.len()
+            );
+        }
+    }
+
+    #[test]
+    fn sneak_path_cross_wheat_fields_around_wires() {
+        let config = ServerConfig::default();
+        // Wire wall closed at its bottom by a wheat field
+        let tiles = (0..16)
+            .map(|y| {
+                let type_ = if y < 12 {
+                    TileType::BarbedWire
                 } else {
-                    None
-                }
-            } else {
-                Some(path.0.iter().map(|x| x.0).collect())
-            }
+                    TileType::WheatField
+                };
+                (GridPoint::new(8, y), type_)
+            })
+            .collect::<Vec<_>>();
+        let map = map_with_tiles(16, &tiles);
+
+        let from = GridPoint::new(2, 2);
+        let to = GridPoint::new(14, 2);
+        let path = find_path(&config, &map, &from, &to, true, &PathMode::Sneak, &None).unwrap();
+        let crossing = path.iter().filter(|point| point.x == 8).collect::<Vec<_>>();
+        assert!(!crossing.is_empty());
+        assert!(crossing.iter().all(|point| point.y >= 12), "{:?}", path);
+    }
+
+    #[test]
+    fn unreachable_destination_has_no_path() {
+        let config = ServerConfig::default();
+        let wires = (0..64).map(|y| GridPoint::new(20, y)).collect::<Vec<_>>();
+        let map = map_with_wires(64, &wires);
+
+        let from = GridPoint::new(2, 2);
+        let to = GridPoint::new(40, 2);
+        assert!(find_path(&config, &map, &from, &to, true, &PathMode::Sneak, &None).is_none());
+        assert!(find_path(&config, &map, &from, &to, true, &PathMode::Walk, &None).is_some());
+    }
+
+    #[test]
+    fn pedestrian_paths_are_cached_whatever_start_direction() {
+        let config = ServerConfig::default();
+        let map = walled_map();
+        let from = GridPoint::new(2, 2);
+        let to = GridPoint::new(40, 2);
+
+        for direction in [Direction::North, Direction::South, Direction::West] {
+            find_path(
+                &config,
+                &map,
+                &from,
+                &to,
+                true,
+                &PathMode::Walk,
+                &Some(direction),
+            );
+        }
+        assert_eq!(map.path_cache().paths.lock().unwrap().len(), 1);
+
+        map.path_cache().clear();
+        let drive = PathMode::Drive(VehicleSize(1));
+        for direction in [Direction::North, Direction::South] {
+            find_path(&config, &map, &from, &to, true, &drive, &Some(direction));
         }
+        assert_eq!(map.path_cache().paths.lock().unwrap().len(), 2);
     }
 }
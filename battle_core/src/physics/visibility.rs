@@ -6,11 +6,14 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    config::{ServerConfig, VISIBILITY_FIRSTS, VISIBILITY_PIXEL_STEPS},
+    config::{
+        ServerConfig, FLOOR_HEIGHT, UPPER_FLOOR_OPACITY_FACTOR, VISIBILITY_FIRSTS,
+        VISIBILITY_PIXEL_STEPS,
+    },
     entity::soldier::Soldier,
     game::Side,
-    map::Map,
-    types::{Distance, GridPath, SoldierIndex, WorldPoint},
+    map::{terrain::Opening, Map},
+    types::{Distance, GridPath, GridPoint, SoldierIndex, WorldPoint},
 };
 
 use super::utils::distance_between_points;
@@ -145,6 +148,8 @@ impl Visibility {
             map,
             config.visibility_firsts,
             exclude_lasts,
+            (from_soldier.floor(), to_soldier.floor()),
+            from_soldier.traits().aim_alteration_factor(),
         );
 
         to_soldier_item_opacity -= by_behavior_modifier;
@@ -192,6 +197,8 @@ impl Visibility {
             map,
             VISIBILITY_FIRSTS,
             exclude_lasts,
+            (from_soldier.floor(), 0),
+            from_soldier.traits().aim_alteration_factor(),
         );
 
         let visible = to_soldier_item_opacity < config.visible_starts_at;
@@ -226,7 +233,16 @@ impl Visibility {
             break_point,
             blocked,
             altered_to,
-        ) = Self::between_points_raw(config, from_point, to_point, map, VISIBILITY_FIRSTS, 0);
+        ) = Self::between_points_raw(
+            config,
+            from_point,
+            to_point,
+            map,
+            VISIBILITY_FIRSTS,
+            0,
+            (0, 0),
+            1.0,
+        );
 
         let visible = to_soldier_item_opacity < config.visible_starts_at;
         let distance = distance_between_points(from_point, to_point);
@@ -248,6 +264,8 @@ impl Visibility {
     }
 
     // TODO : Optimize performances here
+    #[allow(clippy::type_complexity)]
+    #[allow(clippy::too_many_arguments)]
     pub fn between_points_raw(
         config: &ServerConfig,
         from_point: &WorldPoint,
@@ -255,6 +273,8 @@ impl Visibility {
         map: &Map,
         exclude_firsts: usize,
         exclude_lasts: usize,
+        floors: (u8, u8),
+        aim_alteration_factor: f32,
     ) -> (
         f32,
         Vec<(WorldPoint, f32)>,
@@ -270,6 +290,8 @@ impl Visibility {
         let mut break_point = None;
         let mut blocked = false;
         let _visible_by_bullet_fire = false;
+        // Looking from (or to) upper floor see over ground level obstacles
+        let floor_opacity_factor = UPPER_FLOOR_OPACITY_FACTOR.powi(floors.0.max(floors.1) as i32);
 
         // Compute line pixels
         let pixels = Bresenham::new(
@@ -292,12 +314,15 @@ impl Visibility {
                         continue;
                     }
                 };
-                let grid_point_opacity = if grid_path.len() <= exclude_firsts {
+                let is_window = terrain_tile.opening() == Some(&Opening::Window);
+                let grid_point_opacity = if grid_path.len() <= exclude_firsts || is_window {
                     0.0
+                } else if terrain_tile.type_().block_bullet() {
+                    terrain_tile.opacity(config)
                 } else {
-                    config.terrain_tile_opacity(&terrain_tile.type_)
+                    terrain_tile.opacity(config) * floor_opacity_factor
                 };
-                if i >= exclude_firsts && terrain_tile.type_().block_bullet() {
+                if i >= exclude_firsts && !is_window && terrain_tile.type_().block_bullet() {
                     // FIXME BS NOW: defend and move etc. must change their order only if visible and not !blocked !
                     blocked = true
                 }
@@ -330,9 +355,24 @@ impl Visibility {
             }
         }
 
-        // Compute a target point altered by opacity
+        // Ground elevation (ridge, hill crest) between points hide the target
+        let heights = (
+            floors.0 as f32 * FLOOR_HEIGHT,
+            floors.1 as f32 * FLOOR_HEIGHT,
+        );
+        if let Some(ridge_point) = Self::ridge_point(config, map, &grid_path, heights) {
+            to_opacity += config.elevation_hidden_opacity;
+            blocked = true;
+            if break_point.is_none() {
+                break_point = Some(ridge_point);
+            }
+        }
+
+        // Compute a target point altered by opacity (and by shooter skills)
         let altered_to = {
-            let range = path_final_opacity * config.target_alteration_by_opacity_factor;
+            let range = path_final_opacity
+                * config.target_alteration_by_opacity_factor
+                * aim_alteration_factor;
             if range > 0. {
                 let x_change = rng.gen_range(-range..range);
                 let y_change = rng.gen_range(-range..range);
@@ -351,4 +391,113 @@ impl Visibility {
             altered_to,
         )
     }
+
+    /// Return first point of the path where ground is above the line of sight
+    /// going from first to last path point (eyes and given heights above ground included)
+    fn ridge_point(
+        config: &ServerConfig,
+        map: &Map,
+        grid_path: &GridPath,
+        heights: (f32, f32),
+    ) -> Option<WorldPoint> {
+        let (first, last) = match (grid_path.points.first(), grid_path.points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return None,
+        };
+        let from_height = map.elevation_at(first) + heights.0 + config.sight_height;
+        let to_height = map.elevation_at(last) + heights.1 + config.sight_height;
+        let length = grid_distance(first, last);
+        if length <= 1. {
+            return None;
+        }
+
+        grid_path.points[1..grid_path.points.len() - 1]
+            .iter()
+            .find(|grid_point| {
+                let progress = grid_distance(first, grid_point) / length;
+                let sight_height = from_height + (to_height - from_height) * progress;
+                map.elevation_at(grid_point) > sight_height
+            })
+            .map(|grid_point| map.world_point_from_grid_point(*grid_point))
+    }
+}
+
+fn grid_distance(from: &GridPoint, to: &GridPoint) -> f32 {
+    (((to.x - from.x).pow(2) + (to.y - from.y).pow(2)) as f32).sqrt()
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::{
+        map::{
+            decor::Decor,
+            terrain::{TerrainTile, TileType},
+        },
+        types::Offset,
+    };
+
+    use super::*;
+
+    /// Short grass map of 20x3 tiles crossed by a brick wall at x=10, with a window at (10, 1)
+    fn wall_map() -> Map {
+        let (width, height) = (20, 3);
+        let tiles = (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                let type_ = if x == 10 {
+                    TileType::BrickWall
+                } else {
+                    TileType::ShortGrass
+                };
+                let mut tile = TerrainTile::new(type_, 5, 5, 1., 1., x, y, 0, 0);
+                if (x, y) == (10, 1) {
+                    tile.opening = Some(Opening::Window);
+                }
+                tile
+            })
+            .collect();
+
+        Map::new(
+            "TestMap".to_string(),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            vec![],
+            vec![],
+            width,
+            height,
+            tiles,
+            5,
+            5,
+            Decor::new(vec![], vec![], Offset::new(0., 0.)),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn wall_block_sight_but_window_dont() {
+        let config = ServerConfig::default();
+        let map = wall_map();
+
+        let through_wall = Visibility::between_points(
+            &config,
+            &WorldPoint::new(12., 2.),
+            &WorldPoint::new(90., 2.),
+            &map,
+        );
+        let through_window = Visibility::between_points(
+            &config,
+            &WorldPoint::new(12., 7.),
+            &WorldPoint::new(90., 7.),
+            &map,
+        );
+
+        assert!(through_wall.blocked);
+        assert!(!through_wall.visible);
+        assert!(!through_window.blocked);
+        assert!(through_window.visible);
+        assert!(through_window.to_scene_item_opacity < through_wall.to_scene_item_opacity);
+    }
 }
@@ -1,9 +1,17 @@
 use std::{collections::HashMap, fmt::Display};
 
 use crate::{
+    config::rules::RuleSet,
     deployment::SquadTypes,
-    game::flag::FlagsOwnership,
+    game::{
+        flag::FlagsOwnership,
+        squad::{SquadFireDisciplines, SquadFormations, SquadLeadersReplaced},
+    },
     map::{reader::MapReaderError, Map},
+    order::{
+        trigger::{SquadDestinationsReached, SquadTriggeredOrders},
+        SquadOrderQueues,
+    },
 };
 
 use super::{phase::Phase, BattleState};
@@ -46,8 +54,17 @@ impl BattleStateBuilder {
             vec![],
             HashMap::new(),
             SquadTypes::new(),
+            SquadLeadersReplaced::new(),
+            SquadFormations::new(),
+            SquadFireDisciplines::new(),
+            SquadOrderQueues::new(),
+            SquadTriggeredOrders::new(),
+            SquadDestinationsReached::new(),
+            vec![],
+            vec![],
             Phase::Placement,
             FlagsOwnership::empty(),
+            RuleSet::default(),
         );
         state.resolve();
         Ok(state)
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{flag::FlagName, Side},
+    types::{Distance, SoldierIndex, SquadUuid, VehicleIndex},
+};
+
+/// Battle events (with frame when they happened), in order
+pub type BattleEvents = Vec<(u64, BattleEvent)>;
+
+/// Notable things which happened during battle, kept for after-action report
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub enum BattleEvent {
+    Kill {
+        // None when not produced by a soldier (debug explosion, etc)
+        shooter: Option<SoldierIndex>,
+        victim: SoldierIndex,
+        // Weapon or explosive name
+        weapon: String,
+        distance: Distance,
+    },
+    FlagCaptured(FlagName, Side),
+    SquadRouted(SquadUuid),
+    // Vehicles have no damage model yet : vehicle is considered destroyed when all its crew is out
+    VehicleDestroyed(VehicleIndex),
+}
+
+impl BattleEvent {
+    pub fn name(&self) -> &str {
+        match self {
+            BattleEvent::Kill { .. } => "kill",
+            BattleEvent::FlagCaptured(_, _) => "flag_captured",
+            BattleEvent::SquadRouted(_) => "squad_routed",
+            BattleEvent::VehicleDestroyed(_) => "vehicle_destroyed",
+        }
+    }
+}
@@ -6,8 +6,13 @@ use serde::{Deserialize, Serialize};
 use crate::{
     behavior::{gesture::Gesture, Behavior},
     entity::soldier::WeaponClass,
-    game::{flag::FlagsOwnership, weapon::Shot},
-    order::Order,
+    game::{
+        flag::FlagsOwnership,
+        squad::{FireDiscipline, Formation},
+        weapon::{Magazine, Shot, Weapon},
+    },
+    map::terrain::TerrainChange,
+    order::{trigger::OrderTrigger, Order, OrderLegId},
     physics::{
         event::{bullet::BulletFire, cannon_blast::CannonBlast, explosion::Explosion},
         visibility::Visibility,
@@ -15,7 +20,7 @@ use crate::{
     types::{Angle, SoldierIndex, SquadUuid, VehicleIndex, WorldPoint},
 };
 
-use super::phase::Phase;
+use super::{event::BattleEvent, phase::Phase};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum BattleStateMessage {
@@ -35,6 +40,20 @@ pub enum BattleStateMessage {
     SetBMorale(Morale),
     SetFlagsOwnership(FlagsOwnership),
     SetSquadLeader(SquadUuid, SoldierIndex),
+    SetSquadAssistant(SquadUuid, Option<SoldierIndex>),
+    SetSquadFormation(SquadUuid, Formation),
+    SetSquadFireDiscipline(SquadUuid, FireDiscipline),
+    PushSquadOrder(SquadUuid, Order),
+    ReplaceSquadOrder(SquadUuid, OrderLegId, Order),
+    RemoveSquadOrder(SquadUuid, OrderLegId),
+    // Cancel queued and triggered orders, when a direct order is given
+    ClearSquadOrders(SquadUuid),
+    SetSquadTriggeredOrder(SquadUuid, Option<(OrderTrigger, Order)>),
+    SetSquadDestinationReached(SquadUuid),
+    ChangeTerrain(Vec<TerrainChange>),
+    PushEvent(BattleEvent),
+    // Seconds of cooling, applied to all weapons of all soldiers
+    CoolWeapons(f32),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -43,6 +62,7 @@ pub enum SoldierMessage {
     SetBehavior(Behavior),
     SetGesture(Gesture),
     SetOrder(Order),
+    SetDelayedOrder(Order, u64),
     SetOrientation(Angle),
     SetAlive(bool),
     SetUnconscious(bool),
@@ -51,7 +71,14 @@ pub enum SoldierMessage {
     DecreaseUnderFire,
     ReloadWeapon(WeaponClass),
     WeaponShot(WeaponClass, Shot),
+    SetWeapon(WeaponClass, Option<Weapon>),
+    SetMagazines(Vec<Magazine>),
+    SetWeaponDeployed(WeaponClass, bool),
+    WeaponJammed(WeaponClass),
+    ClearWeaponJam(WeaponClass),
+    ChangeWeaponBarrel(WeaponClass),
     SetLastShootFrameI(u64),
+    SetFloor(u8),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -68,4 +95,6 @@ pub enum SideEffect {
     // FIXME Systematic gui side
     RefreshEntityAnimation(SoldierIndex),
     SoldierFinishHisBehavior(SoldierIndex, Option<Order>),
+    // FIXME Systematic gui side
+    RefreshMapTerrain,
 }
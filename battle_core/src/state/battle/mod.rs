@@ -3,12 +3,21 @@ use std::collections::HashMap;
 use oc_core::morale::Morale;
 
 use crate::{
+    config::rules::RuleSet,
     deployment::{Deployment, SquadTypes},
     entity::{soldier::Soldier, vehicle::Vehicle},
-    game::{control::MapControl, flag::FlagsOwnership, Side},
+    game::{
+        control::MapControl,
+        flag::FlagsOwnership,
+        squad::{SquadFireDisciplines, SquadFormations, SquadLeadersReplaced},
+        Side,
+    },
     graphics::vehicle::VehicleGraphicInfos,
-    map::Map,
-    order::Order,
+    map::{terrain::TerrainChange, Map},
+    order::{
+        trigger::{SquadDestinationsReached, SquadTriggeredOrders},
+        Order, SquadOrderQueues,
+    },
     physics::{
         event::{bullet::BulletFire, cannon_blast::CannonBlast, explosion::Explosion},
         path::{Direction, PathMode},
@@ -20,18 +29,21 @@ use crate::{
         Distance, SoldierBoard, SoldierIndex, SoldiersOnBoard, SquadComposition, SquadUuid,
         VehicleBoard, VehicleIndex, WorldPoint,
     },
-    utils::{vehicle_board_from_soldiers_on_board, WorldShape},
+    utils::{vehicle_board_from_soldiers_on_board, WorldPolygon},
 };
 
 use self::{
+    event::{BattleEvent, BattleEvents},
     message::{BattleStateMessage, SideEffect},
     phase::Phase,
 };
 
 pub mod builder;
+pub mod event;
 pub mod message;
 pub mod order;
 pub mod phase;
+pub mod report;
 pub mod soldier;
 pub mod squad;
 pub mod vehicle;
@@ -47,6 +59,20 @@ pub struct BattleState {
     vehicle_board: VehicleBoard,
     squads: HashMap<SquadUuid, SquadComposition>,
     squad_types: SquadTypes,
+    // Frame when squad leader has been replaced (previous one dead, unconscious, etc)
+    squad_leaders_replaced: SquadLeadersReplaced,
+    squad_formations: SquadFormations,
+    squad_fire_disciplines: SquadFireDisciplines,
+    // Orders to give to squad leader when its current order is finished
+    squad_order_queues: SquadOrderQueues,
+    // Orders to give to squad leader when their trigger is satisfied
+    squad_triggered_orders: SquadTriggeredOrders,
+    // Frame when squad reached its move destination
+    squad_destinations_reached: SquadDestinationsReached,
+    // Map modifications since battle start (explosion craters, etc), already applied on map
+    terrain_changes: Vec<TerrainChange>,
+    // Battle events since battle start (kills, flag captures, etc)
+    events: BattleEvents,
     bullet_fires: Vec<BulletFire>,
     explosions: Vec<Explosion>,
     cannon_blasts: Vec<CannonBlast>,
@@ -58,9 +84,12 @@ pub struct BattleState {
     a_morale: Morale,
     b_morale: Morale,
     flags: FlagsOwnership,
+    // Rule set the battle is played with (server config values are already overridden)
+    rule_set: RuleSet,
 }
 
 impl BattleState {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         frame_i: u64,
         map: Map,
@@ -68,10 +97,23 @@ impl BattleState {
         vehicles: Vec<Vehicle>,
         soldier_on_board: SoldiersOnBoard,
         squad_types: SquadTypes,
+        squad_leaders_replaced: SquadLeadersReplaced,
+        squad_formations: SquadFormations,
+        squad_fire_disciplines: SquadFireDisciplines,
+        squad_order_queues: SquadOrderQueues,
+        squad_triggered_orders: SquadTriggeredOrders,
+        squad_destinations_reached: SquadDestinationsReached,
+        terrain_changes: Vec<TerrainChange>,
+        events: BattleEvents,
         phase: Phase,
         flags: FlagsOwnership,
+        rule_set: RuleSet,
     ) -> Self {
         let vehicle_board = vehicle_board_from_soldiers_on_board(&soldier_on_board);
+        let mut map = map;
+        for terrain_change in &terrain_changes {
+            map.apply_terrain_change(terrain_change);
+        }
         Self {
             frame_i,
             map,
@@ -82,6 +124,14 @@ impl BattleState {
             vehicle_board,
             squads: HashMap::new(),
             squad_types,
+            squad_leaders_replaced,
+            squad_formations,
+            squad_fire_disciplines,
+            squad_order_queues,
+            squad_triggered_orders,
+            squad_destinations_reached,
+            terrain_changes,
+            events,
             bullet_fires: vec![],
             explosions: vec![],
             cannon_blasts: vec![],
@@ -93,6 +143,7 @@ impl BattleState {
             a_morale: Morale(1.0), // FIXME BS NOW : from context ?
             b_morale: Morale(1.0), // FIXME BS NOW : from context ?
             flags,
+            rule_set,
         }
     }
 
@@ -107,6 +158,14 @@ impl BattleState {
             vehicle_board: HashMap::new(),
             squads: HashMap::new(),
             squad_types: SquadTypes::new(),
+            squad_leaders_replaced: SquadLeadersReplaced::new(),
+            squad_formations: SquadFormations::new(),
+            squad_fire_disciplines: SquadFireDisciplines::new(),
+            squad_order_queues: SquadOrderQueues::new(),
+            squad_triggered_orders: SquadTriggeredOrders::new(),
+            squad_destinations_reached: SquadDestinationsReached::new(),
+            terrain_changes: vec![],
+            events: vec![],
             bullet_fires: vec![],
             explosions: vec![],
             cannon_blasts: vec![],
@@ -118,6 +177,7 @@ impl BattleState {
             a_morale: Morale(1.0),
             b_morale: Morale(1.0),
             flags: FlagsOwnership::empty(),
+            rule_set: RuleSet::default(),
         }
     }
 
@@ -129,8 +189,17 @@ impl BattleState {
             copy.vehicles().clone(),
             copy.soldier_on_board().clone(),
             copy.squad_types().clone(),
+            copy.squad_leaders_replaced().clone(),
+            copy.squad_formations().clone(),
+            copy.squad_fire_disciplines().clone(),
+            copy.squad_order_queues().clone(),
+            copy.squad_triggered_orders().clone(),
+            copy.squad_destinations_reached().clone(),
+            copy.terrain_changes().clone(),
+            copy.events().clone(),
             copy.phase().clone(),
             copy.flags().clone(),
+            copy.rule_set(),
         )
     }
 
@@ -157,6 +226,41 @@ impl BattleState {
         &self.map
     }
 
+    pub fn terrain_changes(&self) -> &Vec<TerrainChange> {
+        &self.terrain_changes
+    }
+
+    /// Keep only the last change of each tile, so changes count can't exceed map tiles count
+    fn push_terrain_change(&mut self, change: &TerrainChange) {
+        match self
+            .terrain_changes
+            .iter_mut()
+            .find(|previous| previous.grid_point == change.grid_point)
+        {
+            Some(previous) => {
+                previous.type_ = change.type_.clone();
+                previous.remove_decor |= change.remove_decor;
+            }
+            None => self.terrain_changes.push(change.clone()),
+        }
+    }
+
+    pub fn events(&self) -> &BattleEvents {
+        &self.events
+    }
+
+    pub fn squad_routed(&self, squad_uuid: &SquadUuid) -> bool {
+        self.events
+            .iter()
+            .any(|(_, event)| event == &BattleEvent::SquadRouted(*squad_uuid))
+    }
+
+    pub fn vehicle_destroyed(&self, vehicle_index: &VehicleIndex) -> bool {
+        self.events
+            .iter()
+            .any(|(_, event)| event == &BattleEvent::VehicleDestroyed(*vehicle_index))
+    }
+
     pub fn visibilities(&self) -> &Visibilities {
         &self.visibilities
     }
@@ -308,7 +412,75 @@ impl BattleState {
                     .squads
                     .get_mut(squad_uuid)
                     .expect("Squad indexes must be consistent")
-                    .leader_mut() = *soldier_index
+                    .leader_mut() = *soldier_index;
+                self.squad_leaders_replaced
+                    .insert(*squad_uuid, self.frame_i);
+            }
+            BattleStateMessage::SetSquadAssistant(squad_uuid, soldier_index) => {
+                *self
+                    .squads
+                    .get_mut(squad_uuid)
+                    .expect("Squad indexes must be consistent")
+                    .assistant_mut() = *soldier_index;
+            }
+            BattleStateMessage::SetSquadFormation(squad_uuid, formation) => {
+                self.squad_formations.insert(*squad_uuid, *formation);
+            }
+            BattleStateMessage::SetSquadFireDiscipline(squad_uuid, fire_discipline) => {
+                self.squad_fire_disciplines
+                    .insert(*squad_uuid, *fire_discipline);
+            }
+            BattleStateMessage::PushSquadOrder(squad_uuid, order) => {
+                self.squad_order_queues
+                    .entry(*squad_uuid)
+                    .or_default()
+                    .push(order.clone());
+            }
+            BattleStateMessage::ReplaceSquadOrder(squad_uuid, id, order) => {
+                if let Some(queue) = self.squad_order_queues.get_mut(squad_uuid) {
+                    queue.replace(id, order.clone());
+                }
+            }
+            BattleStateMessage::RemoveSquadOrder(squad_uuid, id) => {
+                if let Some(queue) = self.squad_order_queues.get_mut(squad_uuid) {
+                    queue.remove(id);
+                }
+            }
+            BattleStateMessage::ClearSquadOrders(squad_uuid) => {
+                if let Some(queue) = self.squad_order_queues.get_mut(squad_uuid) {
+                    queue.clear();
+                }
+                self.squad_triggered_orders.remove(squad_uuid);
+            }
+            BattleStateMessage::SetSquadTriggeredOrder(squad_uuid, triggered_order) => {
+                match triggered_order {
+                    Some(triggered_order) => {
+                        self.squad_triggered_orders
+                            .insert(*squad_uuid, triggered_order.clone());
+                    }
+                    None => {
+                        self.squad_triggered_orders.remove(squad_uuid);
+                    }
+                }
+            }
+            BattleStateMessage::SetSquadDestinationReached(squad_uuid) => {
+                self.squad_destinations_reached
+                    .insert(*squad_uuid, self.frame_i);
+            }
+            BattleStateMessage::ChangeTerrain(terrain_changes) => {
+                for terrain_change in terrain_changes {
+                    self.map.apply_terrain_change(terrain_change);
+                    self.push_terrain_change(terrain_change);
+                }
+                return vec![SideEffect::RefreshMapTerrain];
+            }
+            BattleStateMessage::PushEvent(event) => {
+                self.events.push((self.frame_i, event.clone()));
+            }
+            BattleStateMessage::CoolWeapons(seconds) => {
+                for soldier in &mut self.soldiers {
+                    soldier.cool_weapons(*seconds);
+                }
             }
         };
 
@@ -323,6 +495,7 @@ impl BattleState {
             self.vehicles.push(Vehicle::from(vehicle_deployment))
         }
         self.soldier_on_board = deployment.boards().clone();
+        self.vehicle_board = vehicle_board_from_soldiers_on_board(&self.soldier_on_board);
         self.squad_types = deployment.squad_types().clone();
         self.resolve();
     }
@@ -348,8 +521,17 @@ impl BattleState {
             self.vehicles.clone(),
             self.soldier_on_board.clone(),
             self.squad_types.clone(),
+            self.squad_leaders_replaced.clone(),
+            self.squad_formations.clone(),
+            self.squad_fire_disciplines.clone(),
+            self.squad_order_queues.clone(),
+            self.squad_triggered_orders.clone(),
+            self.squad_destinations_reached.clone(),
+            self.terrain_changes.clone(),
+            self.events.clone(),
             self.phase.clone(),
             self.flags.clone(),
+            self.rule_set,
         )
     }
 
@@ -397,12 +579,20 @@ impl BattleState {
         &self.flags
     }
 
-    pub fn there_is_side_soldier_in(&self, side: &Side, shape: WorldShape) -> bool {
+    pub fn there_is_side_soldier_in(&self, side: &Side, polygon: &WorldPolygon) -> bool {
         self.soldiers
             .iter()
             .filter(|s| s.side() == side)
             .filter(|s| s.can_take_flag())
-            .any(|s| shape.contains(&s.world_point()))
+            .any(|s| polygon.contains(&s.world_point()))
+    }
+
+    pub fn rule_set(&self) -> RuleSet {
+        self.rule_set
+    }
+
+    pub fn set_rule_set(&mut self, rule_set: RuleSet) {
+        self.rule_set = rule_set
     }
 
     pub fn a_morale(&self) -> &Morale {
@@ -424,7 +614,7 @@ impl BattleState {
             .filter(|s| s.can_seek())
             .filter(|s| s.side() == side)
             .filter(|s| {
-                distance_between_points(&s.world_point(), &point).millimeters()
+                distance_between_points(&s.world_point(), point).millimeters()
                     <= distance.millimeters()
             })
             .collect()
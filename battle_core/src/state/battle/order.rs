@@ -1,6 +1,12 @@
 use crate::{
+    behavior::Behavior,
+    config::{ServerConfig, TARGET_FPS},
     game::Side,
-    order::{marker::OrderMarker, Order},
+    order::{
+        marker::OrderMarker,
+        trigger::{OrderTrigger, SquadTriggeredOrders},
+        Order, OrderLegId,
+    },
     types::*,
 };
 
@@ -46,7 +52,7 @@ impl BattleState {
                             .collect(),
                     );
                 }
-                Order::Defend(_) | Order::Hide(_) => {
+                Order::Defend(_, _) | Order::Hide(_) => {
                     let squad_leader = self.soldier(squad.leader());
                     marker_data.push((
                         order.clone(),
@@ -68,7 +74,7 @@ impl BattleState {
                         OrderMarkerIndex(0),
                     ));
                 }
-                Order::SuppressFire(point) => {
+                Order::SuppressFire(point) | Order::AreaFire(point, _) => {
                     marker_data.push((
                         order.clone(),
                         marker.clone().unwrap(), // FIXME unwrap to remove
@@ -82,4 +88,146 @@ impl BattleState {
 
         marker_data
     }
+
+    pub fn squad_order_queue(&self, squad_uuid: &SquadUuid) -> &[(OrderLegId, Order)] {
+        self.squad_order_queues
+            .get(squad_uuid)
+            .map(|queue| queue.legs())
+            .unwrap_or(&[])
+    }
+
+    /// Current position of given leg in squad order queue, None if no longer queued
+    pub fn squad_order_leg_index(&self, squad_uuid: &SquadUuid, id: &OrderLegId) -> Option<usize> {
+        self.squad_order_queue(squad_uuid)
+            .iter()
+            .position(|(id_, _)| id_ == id)
+    }
+
+    /// Point where squad leader will be when queued order at given index begins
+    pub fn squad_order_leg_start_point(&self, squad_uuid: &SquadUuid, leg_i: usize) -> WorldPoint {
+        let leader = self.soldier(self.squad(*squad_uuid).leader());
+        self.squad_order_queue(squad_uuid)
+            .iter()
+            .take(leg_i)
+            .rev()
+            .find_map(|(_, order)| order.end_point())
+            .or_else(|| leader.order().end_point())
+            .unwrap_or(leader.world_point())
+    }
+
+    /// Current squad leader order is considered as finished : Idle, positioned
+    /// in Defend/Hide or fired during the queued fire duration. Behavior angle must be
+    /// the order one, so that a behavior left by a previous leg don't finish a
+    /// Defend/Hide leg as soon as it is given.
+    pub fn squad_ready_for_next_order(
+        &self,
+        config: &ServerConfig,
+        squad_uuid: &SquadUuid,
+    ) -> bool {
+        let leader = self.soldier(self.squad(*squad_uuid).leader());
+        match leader.order() {
+            Order::Idle => true,
+            Order::Defend(angle, _) => leader.behavior() == &Behavior::Defend(*angle),
+            Order::Hide(angle) => leader.behavior() == &Behavior::Hide(*angle),
+            // Nothing else end a suppress fire order
+            Order::SuppressFire(_) | Order::AreaFire(_, _) => {
+                self.frame_i >= leader.order_frame_i() + config.queued_fire_duration
+            }
+            Order::MoveTo(_, _)
+            | Order::MoveFastTo(_, _)
+            | Order::SneakTo(_, _)
+            | Order::EngageSquad(_) => false,
+        }
+    }
+
+    pub fn queued_order_markers(
+        &self,
+        side: &Side,
+    ) -> Vec<(Order, OrderMarker, SquadUuid, WorldPoint, OrderLegId)> {
+        let mut marker_data = vec![];
+
+        for (squad_uuid, queue) in &self.squad_order_queues {
+            if side != &Side::All && self.squad_side(squad_uuid) != side {
+                continue;
+            }
+
+            for (i, (id, order)) in queue.legs().iter().enumerate() {
+                let point = match order {
+                    Order::MoveTo(_, _) | Order::MoveFastTo(_, _) | Order::SneakTo(_, _) => {
+                        order.end_point()
+                    }
+                    Order::Defend(_, _) | Order::Hide(_) => {
+                        Some(self.squad_order_leg_start_point(squad_uuid, i))
+                    }
+                    Order::EngageSquad(squad_index) => Some(
+                        self.soldier(self.squad(*squad_index).leader())
+                            .world_point(),
+                    ),
+                    Order::SuppressFire(point) | Order::AreaFire(point, _) => Some(*point),
+                    Order::Idle => None,
+                };
+
+                if let (Some(marker), Some(point)) = (order.marker(), point) {
+                    marker_data.push((order.clone(), marker, *squad_uuid, point, *id));
+                }
+            }
+        }
+
+        marker_data
+    }
+
+    pub fn squad_triggered_order(&self, squad_uuid: &SquadUuid) -> Option<&(OrderTrigger, Order)> {
+        self.squad_triggered_orders.get(squad_uuid)
+    }
+
+    pub fn squad_triggered_orders(&self) -> &SquadTriggeredOrders {
+        &self.squad_triggered_orders
+    }
+
+    pub fn order_trigger_satisfied(&self, trigger: &OrderTrigger) -> bool {
+        match trigger {
+            OrderTrigger::Frame(frame_i) => self.frame_i >= *frame_i,
+            OrderTrigger::SquadReachedDestination(squad_uuid, since) => self
+                .squad_destinations_reached
+                .get(squad_uuid)
+                .map(|reached| reached >= since)
+                .unwrap_or(false),
+            OrderTrigger::SquadUnderFire(squad_uuid, since) => self
+                .squad(*squad_uuid)
+                .members()
+                .iter()
+                .map(|soldier_index| self.soldier(*soldier_index))
+                .any(|soldier| soldier.can_be_animated() && soldier.last_shot_frame_i() > since),
+        }
+    }
+
+    /// Trigger can't be satisfied anymore because its reference squad has no more
+    /// animated members
+    pub fn order_trigger_obsolete(&self, trigger: &OrderTrigger) -> bool {
+        trigger.reference_squad().is_some_and(|squad_uuid| {
+            !self
+                .squad(*squad_uuid)
+                .members()
+                .iter()
+                .any(|soldier_index| self.soldier(*soldier_index).can_be_animated())
+        })
+    }
+
+    pub fn order_trigger_display(&self, trigger: &OrderTrigger) -> String {
+        let squad_name = |squad_uuid: &SquadUuid| {
+            format!("{} #{}", self.squad_type(squad_uuid).name(), squad_uuid.0)
+        };
+        match trigger {
+            OrderTrigger::Frame(frame_i) => {
+                let seconds = frame_i / TARGET_FPS;
+                format!("at {:02}:{:02}", seconds / 60, seconds % 60)
+            }
+            OrderTrigger::SquadReachedDestination(squad_uuid, _) => {
+                format!("when {} arrived", squad_name(squad_uuid))
+            }
+            OrderTrigger::SquadUnderFire(squad_uuid, _) => {
+                format!("when {} under fire", squad_name(squad_uuid))
+            }
+        }
+    }
 }
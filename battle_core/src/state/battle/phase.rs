@@ -26,20 +26,15 @@ impl Phase {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Display)]
+// Default to be compliant with Phase EnumIter
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Display, Default)]
 pub enum EndReason {
+    #[default]
     Flags,
     Morale,
     Aborted,
 }
 
-// To be compliant with Phase EnumIter
-impl Default for EndReason {
-    fn default() -> Self {
-        Self::Flags
-    }
-}
-
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Victorious(pub Side);
 
@@ -0,0 +1,365 @@
+use oc_core::game::squad::SquadType;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::Side,
+    types::{SoldierIndex, SquadUuid},
+};
+
+use super::{
+    event::{BattleEvent, BattleEvents},
+    BattleState,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SquadReport {
+    pub squad: SquadUuid,
+    pub side: Side,
+    pub type_: SquadType,
+    pub members: usize,
+    pub dead: usize,
+    pub unconscious: usize,
+    pub kills: usize,
+    pub ammunition_spent: usize,
+    pub routed: bool,
+}
+
+/// After-action report : per squad statistics and all battle events
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BattleReport {
+    pub map: String,
+    pub frame_i: u64,
+    pub squads: Vec<SquadReport>,
+    pub events: BattleEvents,
+}
+
+impl BattleReport {
+    pub fn from_battle_state(battle_state: &BattleState) -> Self {
+        let events = battle_state.events();
+        let mut squad_uuids = battle_state.squads().keys().collect::<Vec<&SquadUuid>>();
+        squad_uuids.sort_by_key(|squad_uuid| squad_uuid.0);
+
+        let squads = squad_uuids
+            .into_iter()
+            .map(|squad_uuid| {
+                let composition = battle_state.squad(*squad_uuid);
+                let members = composition.members();
+                let is_member = |soldier_index: &SoldierIndex| members.contains(soldier_index);
+                let soldiers = members
+                    .iter()
+                    .map(|i| battle_state.soldier(*i))
+                    .collect::<Vec<_>>();
+
+                SquadReport {
+                    squad: *squad_uuid,
+                    side: *battle_state.squad_side(squad_uuid),
+                    type_: *composition.type_(),
+                    members: members.len(),
+                    dead: soldiers.iter().filter(|s| !s.alive()).count(),
+                    unconscious: soldiers
+                        .iter()
+                        .filter(|s| s.alive() && s.unconscious())
+                        .count(),
+                    kills: events
+                        .iter()
+                        .filter(|(_, event)| match event {
+                            BattleEvent::Kill {
+                                shooter: Some(shooter),
+                                ..
+                            } => is_member(shooter),
+                            _ => false,
+                        })
+                        .count(),
+                    ammunition_spent: soldiers.iter().map(|s| s.ammunition_spent()).sum(),
+                    routed: battle_state.squad_routed(squad_uuid),
+                }
+            })
+            .collect();
+
+        Self {
+            map: battle_state.map().name().to_string(),
+            frame_i: *battle_state.frame_i(),
+            squads,
+            events: events.clone(),
+        }
+    }
+
+    pub fn flags_captured(&self, side: &Side) -> usize {
+        self.events
+            .iter()
+            .filter(
+                |(_, event)| matches!(event, BattleEvent::FlagCaptured(_, side_) if side_ == side),
+            )
+            .count()
+    }
+
+    pub fn vehicles_destroyed(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|(_, event)| matches!(event, BattleEvent::VehicleDestroyed(_)))
+            .count()
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn squads_csv(&self) -> String {
+        let mut lines = vec![
+            "squad,side,type,members,dead,unconscious,kills,ammunition_spent,routed".to_string(),
+        ];
+        for squad in &self.squads {
+            lines.push(
+                [
+                    squad.squad.0.to_string(),
+                    squad.side.to_string(),
+                    csv_field(squad.type_.name()),
+                    squad.members.to_string(),
+                    squad.dead.to_string(),
+                    squad.unconscious.to_string(),
+                    squad.kills.to_string(),
+                    squad.ammunition_spent.to_string(),
+                    squad.routed.to_string(),
+                ]
+                .join(","),
+            );
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    /// One line per event. Columns meaning depend on event : kill (shooter, victim, weapon,
+    /// distance in meters), flag captured (side, flag), squad routed (squad), vehicle destroyed
+    /// (vehicle).
+    pub fn events_csv(&self) -> String {
+        let mut lines = vec!["frame,event,actor,target,detail,value".to_string()];
+        for (frame_i, event) in &self.events {
+            let (actor, target, detail, value) = match event {
+                BattleEvent::Kill {
+                    shooter,
+                    victim,
+                    weapon,
+                    distance,
+                } => (
+                    shooter.map(|i| i.0.to_string()).unwrap_or_default(),
+                    victim.0.to_string(),
+                    weapon.clone(),
+                    distance.meters().to_string(),
+                ),
+                BattleEvent::FlagCaptured(flag_name, side) => (
+                    side.to_string(),
+                    flag_name.0.clone(),
+                    String::new(),
+                    String::new(),
+                ),
+                BattleEvent::SquadRouted(squad_uuid) => (
+                    squad_uuid.0.to_string(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                ),
+                BattleEvent::VehicleDestroyed(vehicle_index) => (
+                    String::new(),
+                    vehicle_index.0.to_string(),
+                    String::new(),
+                    String::new(),
+                ),
+            };
+            lines.push(
+                [
+                    frame_i.to_string(),
+                    event.name().to_string(),
+                    csv_field(&actor),
+                    csv_field(&target),
+                    csv_field(&detail),
+                    value,
+                ]
+                .join(","),
+            );
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, path::PathBuf};
+
+    use oc_core::game::soldier::SoldierType;
+
+    use crate::{
+        config::rules::RuleSet,
+        entity::soldier::{Soldier, WeaponClass},
+        game::{
+            flag::{FlagName, FlagsOwnership},
+            weapon::{Magazine, MagazineType, Shot, Weapon, WeaponType},
+        },
+        map::{
+            decor::Decor,
+            terrain::{TerrainTile, TileType},
+            Map,
+        },
+        state::battle::phase::Phase,
+        types::{Distance, Offset, SoldiersOnBoard, WorldPoint},
+    };
+
+    use super::*;
+
+    fn map() -> Map {
+        let tiles = (0..10)
+            .flat_map(|y| {
+                (0..10).map(move |x| {
+                    TerrainTile::new(TileType::ShortGrass, 10, 10, 1., 1., x, y, 0, 0)
+                })
+            })
+            .collect();
+
+        Map::new(
+            "TestMap".to_string(),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            PathBuf::from("."),
+            vec![],
+            vec![],
+            10,
+            10,
+            tiles,
+            10,
+            10,
+            Decor::new(vec![], vec![], Offset::new(0., 0.)),
+            vec![],
+        )
+    }
+
+    fn soldier(index: usize, squad: usize, side: Side) -> Soldier {
+        Soldier::new(
+            SoldierIndex(index),
+            SoldierType::Type1,
+            WorldPoint::new(10. + index as f32 * 20., 50.),
+            SquadUuid(squad),
+            side,
+            Some(Weapon::new(
+                WeaponType::new("MosinNagantM1924"),
+                false,
+                Some(Magazine::full(MagazineType::new("MosinNagant"))),
+            )),
+            vec![],
+        )
+    }
+
+    /// Side A squad of two soldiers (one of them shot twice and killed a soldier) against side B
+    /// squad of one soldier (dead, and routed)
+    fn battle_state() -> BattleState {
+        let soldiers = vec![
+            soldier(0, 0, Side::A),
+            soldier(1, 0, Side::A),
+            soldier(2, 1, Side::B),
+        ];
+        let squad_types = HashMap::from([
+            (SquadUuid(0), SquadType::Type1),
+            (SquadUuid(1), SquadType::Type1),
+        ]);
+        let events = vec![
+            (
+                10,
+                BattleEvent::Kill {
+                    shooter: Some(SoldierIndex(0)),
+                    victim: SoldierIndex(2),
+                    weapon: "Mosin, Nagant".to_string(),
+                    distance: Distance::from_meters(12),
+                },
+            ),
+            (20, BattleEvent::SquadRouted(SquadUuid(1))),
+            (
+                30,
+                BattleEvent::FlagCaptured(FlagName("Church".to_string()), Side::A),
+            ),
+        ];
+        let mut battle_state = BattleState::new(
+            40,
+            map(),
+            soldiers,
+            vec![],
+            SoldiersOnBoard::new(),
+            squad_types,
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+            vec![],
+            events,
+            Phase::Battle,
+            FlagsOwnership::empty(),
+            RuleSet::default(),
+        );
+        battle_state.resolve();
+        for _ in 0..2 {
+            battle_state
+                .soldier_mut(SoldierIndex(0))
+                .weapon_shot(&WeaponClass::Main, &Shot::x1);
+        }
+        battle_state.soldier_mut(SoldierIndex(2)).set_alive(false);
+        battle_state
+    }
+
+    #[test]
+    fn squads_statistics() {
+        let report = BattleReport::from_battle_state(&battle_state());
+
+        assert_eq!(report.frame_i, 40);
+        assert_eq!(report.squads.len(), 2);
+        let (a, b) = (&report.squads[0], &report.squads[1]);
+        assert_eq!(
+            (a.members, a.dead, a.kills, a.ammunition_spent, a.routed),
+            (2, 0, 1, 2, false)
+        );
+        assert_eq!(
+            (b.members, b.dead, b.kills, b.ammunition_spent, b.routed),
+            (1, 1, 0, 0, true)
+        );
+        assert_eq!(report.flags_captured(&Side::A), 1);
+        assert_eq!(report.flags_captured(&Side::B), 0);
+        assert_eq!(report.vehicles_destroyed(), 0);
+    }
+
+    #[test]
+    fn csv_export() {
+        let report = BattleReport::from_battle_state(&battle_state());
+
+        assert_eq!(
+            report.squads_csv(),
+            "squad,side,type,members,dead,unconscious,kills,ammunition_spent,routed\n\
+             0,A,Type 1,2,0,0,1,2,false\n\
+             1,B,Type 1,1,1,0,0,0,true\n"
+        );
+        // Fields containing a comma are quoted
+        assert_eq!(
+            report.events_csv(),
+            "frame,event,actor,target,detail,value\n\
+             10,kill,0,2,\"Mosin, Nagant\",12\n\
+             20,squad_routed,1,,,\n\
+             30,flag_captured,A,Church,,\n"
+        );
+    }
+
+    #[test]
+    fn json_export() {
+        let report = BattleReport::from_battle_state(&battle_state());
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["map"], "TestMap");
+        assert_eq!(json["squads"][1]["routed"], true);
+        assert_eq!(json["events"].as_array().unwrap().len(), 3);
+    }
+}
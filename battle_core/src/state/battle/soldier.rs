@@ -40,10 +40,26 @@ impl BattleState {
                 soldier.increase_under_fire(*value);
             }
             SoldierMessage::DecreaseUnderFire => soldier.decrease_under_fire(),
-            SoldierMessage::SetOrder(order) => soldier.set_order(order.clone()),
+            SoldierMessage::SetOrder(order) => {
+                soldier.set_order(order.clone());
+                soldier.set_order_frame_i(frame_i);
+                soldier.clear_delayed_order();
+            }
+            SoldierMessage::SetDelayedOrder(order, frame_i) => {
+                soldier.set_delayed_order(order.clone(), *frame_i)
+            }
             SoldierMessage::ReloadWeapon(class) => soldier.reload_weapon(class),
             SoldierMessage::WeaponShot(class, shot) => soldier.weapon_shot(class, shot),
+            SoldierMessage::SetWeapon(class, weapon) => soldier.set_weapon(class, weapon.clone()),
+            SoldierMessage::SetMagazines(magazines) => soldier.set_magazines(magazines.clone()),
+            SoldierMessage::SetWeaponDeployed(class, value) => {
+                soldier.set_weapon_deployed(class, *value)
+            }
+            SoldierMessage::WeaponJammed(class) => soldier.jam_weapon(class),
+            SoldierMessage::ClearWeaponJam(class) => soldier.clear_weapon_jam(class),
+            SoldierMessage::ChangeWeaponBarrel(class) => soldier.change_weapon_barrel(class),
             SoldierMessage::SetLastShootFrameI(frame_i) => soldier.set_last_shoot_frame_i(*frame_i),
+            SoldierMessage::SetFloor(floor) => soldier.set_floor(*floor),
         }
 
         vec![]
@@ -4,7 +4,18 @@ use oc_core::game::squad::SquadType;
 
 use crate::{
     behavior::BehaviorMode,
-    types::{SoldierIndex, SquadComposition, SquadUuid},
+    config::ServerConfig,
+    entity::soldier::Soldier,
+    game::{
+        cover::CoverFinder,
+        squad::{formation_spacing, squad_positions, FireDiscipline, Formation},
+    },
+    order::Order,
+    physics::{
+        path::{find_path, PathMode},
+        utils::distance_between_points,
+    },
+    types::{SoldierIndex, SquadComposition, SquadUuid, WorldPath, WorldPaths, WorldPoint},
 };
 
 use super::BattleState;
@@ -19,9 +30,10 @@ impl BattleState {
                 .expect("At this point, there must be at least one soldier in the squad");
             let squad_entities = self.squad_entities(squad_uuid);
             let type_ = self.squad_type(&squad_uuid);
+            let assistant = self.elect_squad_assistant(new_squad_leader, &squad_entities);
             new_squads.insert(
                 squad_uuid,
-                SquadComposition::new(new_squad_leader, type_.clone(), squad_entities),
+                SquadComposition::new(new_squad_leader, *type_, squad_entities, assistant),
             );
         }
 
@@ -31,7 +43,7 @@ impl BattleState {
     pub fn squad_type(&self, squad_id: &SquadUuid) -> &SquadType {
         self.squad_types
             .get(squad_id)
-            .expect(&format!("Squad consistency required ({:?})", squad_id))
+            .unwrap_or_else(|| panic!("Squad consistency required ({:?})", squad_id))
     }
 
     fn unique_squad_ids(&self) -> Vec<SquadUuid> {
@@ -56,6 +68,34 @@ impl BattleState {
         )
     }
 
+    /// First member after the crew-served weapon gunner (leader last) assist him
+    fn elect_squad_assistant(
+        &self,
+        leader: SoldierIndex,
+        members: &[SoldierIndex],
+    ) -> Option<SoldierIndex> {
+        let gunner = members.iter().position(|i| {
+            self.soldier(*i)
+                .main_weapon()
+                .as_ref()
+                .is_some_and(|weapon| weapon.crew_served())
+        })?;
+
+        members[gunner + 1..]
+            .iter()
+            .chain(members[..gunner].iter())
+            .map(|i| self.soldier(*i))
+            .filter(|soldier| soldier.can_be_count_for_morale())
+            .filter(|soldier| {
+                !soldier
+                    .main_weapon()
+                    .as_ref()
+                    .is_some_and(|weapon| weapon.crew_served())
+            })
+            .map(|soldier| soldier.uuid())
+            .min_by_key(|i| *i == leader)
+    }
+
     fn squad_entities(&self, squad_uuid: SquadUuid) -> Vec<SoldierIndex> {
         self.soldiers()
             .iter()
@@ -69,4 +109,137 @@ impl BattleState {
         let squad = self.squad(*squad_index);
         self.soldier_behavior_mode(self.soldier(squad.leader()))
     }
+
+    pub fn squad_formation(&self, squad_uuid: &SquadUuid) -> Formation {
+        self.squad_formations
+            .get(squad_uuid)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn squad_fire_discipline(&self, squad_uuid: &SquadUuid) -> FireDiscipline {
+        self.squad_fire_disciplines
+            .get(squad_uuid)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Squad members can open fire by themselves according to squad fire discipline
+    pub fn squad_may_open_fire(&self, config: &ServerConfig, squad_uuid: &SquadUuid) -> bool {
+        match self.squad_fire_discipline(squad_uuid) {
+            FireDiscipline::FireAtWill => true,
+            FireDiscipline::HoldFire | FireDiscipline::Ambush => false,
+            FireDiscipline::ReturnFire => self
+                .squad(*squad_uuid)
+                .members()
+                .iter()
+                .map(|soldier_index| self.soldier(*soldier_index))
+                .any(|soldier| {
+                    *soldier.last_shot_frame_i() != 0
+                        && soldier.last_shot_frame_i() + config.return_fire_duration >= self.frame_i
+                }),
+        }
+    }
+
+    /// Squad members (except leader) positions according to squad formation
+    /// around given point (or leader position if None).
+    pub fn squad_formation_positions(
+        &self,
+        config: &ServerConfig,
+        squad_uuid: &SquadUuid,
+        point: Option<WorldPoint>,
+    ) -> HashMap<SoldierIndex, WorldPoint> {
+        let squad = self.squad(*squad_uuid);
+        let leader = self.soldier(squad.leader());
+        let ref_point = point.unwrap_or(leader.world_point());
+        squad_positions(
+            squad,
+            &self.squad_formation(squad_uuid),
+            leader,
+            Some(ref_point),
+            formation_spacing(config, self.map(), &ref_point),
+        )
+    }
+
+    pub fn squad_leader_replaced_at(&self, squad_uuid: &SquadUuid) -> Option<u64> {
+        self.squad_leaders_replaced.get(squad_uuid).copied()
+    }
+
+    /// Return the frame until which squad orders are delayed because its leader
+    /// has just been replaced. None if squad command is not disrupted.
+    pub fn squad_command_disrupted_until(
+        &self,
+        config: &ServerConfig,
+        squad_uuid: &SquadUuid,
+    ) -> Option<u64> {
+        if let Some(replaced_at) = self.squad_leader_replaced_at(squad_uuid) {
+            let until = replaced_at + config.leader_replacement_order_delay;
+            if until > self.frame_i {
+                return Some(until);
+            }
+        }
+
+        None
+    }
+
+    pub fn soldier_is_in_command_radius(&self, config: &ServerConfig, soldier: &Soldier) -> bool {
+        let leader = self.soldier(self.squad(soldier.squad_uuid()).leader());
+        if leader.uuid() == soldier.uuid() {
+            return true;
+        }
+
+        distance_between_points(&leader.world_point(), &soldier.world_point()).millimeters()
+            <= config.command_radius.millimeters()
+    }
+
+    /// Orders to give to squad members to regroup around their leader.
+    /// Leader stay in place while subordinates run to cover points around him.
+    pub fn squad_rally_orders(
+        &self,
+        config: &ServerConfig,
+        squad_uuid: &SquadUuid,
+    ) -> Vec<(SoldierIndex, Order)> {
+        let squad = self.squad(*squad_uuid);
+        let leader = self.soldier(squad.leader());
+        let map = self.map();
+
+        if self.squad_behavior_mode(squad_uuid) != BehaviorMode::Ground {
+            return vec![];
+        }
+
+        let mut orders = vec![(leader.uuid(), Order::Idle)];
+        let (moves, _) = CoverFinder::new(self, config)
+            .point(Some(leader.world_point()))
+            .exclude_grid_points(vec![map.grid_point_from_world_point(&leader.world_point())])
+            .find_arbitrary_cover_points(leader);
+        for (member_id, from_world_point, cover_world_point) in moves {
+            if !self.soldier(member_id).can_be_animated() {
+                continue;
+            }
+
+            if let Some(grid_path) = find_path(
+                config,
+                map,
+                &map.grid_point_from_world_point(&from_world_point),
+                &map.grid_point_from_world_point(&cover_world_point),
+                true,
+                &PathMode::Walk,
+                &None,
+            ) {
+                if grid_path.is_empty() {
+                    continue;
+                }
+                let world_path = grid_path
+                    .iter()
+                    .map(|p| map.world_point_from_grid_point(*p))
+                    .collect();
+                orders.push((
+                    member_id,
+                    Order::MoveFastTo(WorldPaths::new(vec![WorldPath::new(world_path)]), None),
+                ));
+            }
+        }
+
+        orders
+    }
 }
@@ -1,10 +1,20 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    config::rules::RuleSet,
     deployment::SquadTypes,
     entity::{soldier::Soldier, vehicle::Vehicle},
-    game::flag::FlagsOwnership,
-    state::battle::phase::Phase,
+    game::weapon::definition::{WeaponDefinitions, WeaponDefinitionsError},
+    game::{
+        flag::FlagsOwnership,
+        squad::{SquadFireDisciplines, SquadFormations, SquadLeadersReplaced},
+    },
+    map::terrain::TerrainChange,
+    order::{
+        trigger::{SquadDestinationsReached, SquadTriggeredOrders},
+        SquadOrderQueues,
+    },
+    state::battle::{event::BattleEvents, phase::Phase},
     types::SoldiersOnBoard,
 };
 
@@ -15,19 +25,47 @@ pub struct BattleStateCopy {
     vehicles: Vec<Vehicle>,
     soldier_on_board: SoldiersOnBoard,
     squad_types: SquadTypes,
+    #[serde(default)]
+    squad_leaders_replaced: SquadLeadersReplaced,
+    #[serde(default)]
+    squad_formations: SquadFormations,
+    #[serde(default)]
+    squad_fire_disciplines: SquadFireDisciplines,
+    #[serde(default)]
+    squad_order_queues: SquadOrderQueues,
+    #[serde(default)]
+    squad_triggered_orders: SquadTriggeredOrders,
+    #[serde(default)]
+    squad_destinations_reached: SquadDestinationsReached,
+    #[serde(default)]
+    terrain_changes: Vec<TerrainChange>,
+    #[serde(default)]
+    events: BattleEvents,
     phase: Phase,
     flags: FlagsOwnership,
+    #[serde(default)]
+    rule_set: RuleSet,
 }
 
 impl BattleStateCopy {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         frame_i: u64,
         soldiers: Vec<Soldier>,
         vehicles: Vec<Vehicle>,
         soldier_on_board: SoldiersOnBoard,
         squad_types: SquadTypes,
+        squad_leaders_replaced: SquadLeadersReplaced,
+        squad_formations: SquadFormations,
+        squad_fire_disciplines: SquadFireDisciplines,
+        squad_order_queues: SquadOrderQueues,
+        squad_triggered_orders: SquadTriggeredOrders,
+        squad_destinations_reached: SquadDestinationsReached,
+        terrain_changes: Vec<TerrainChange>,
+        events: BattleEvents,
         phase: Phase,
         flags: FlagsOwnership,
+        rule_set: RuleSet,
     ) -> BattleStateCopy {
         Self {
             frame_i,
@@ -35,11 +73,33 @@ impl BattleStateCopy {
             vehicles,
             soldier_on_board,
             squad_types,
+            squad_leaders_replaced,
+            squad_formations,
+            squad_fire_disciplines,
+            squad_order_queues,
+            squad_triggered_orders,
+            squad_destinations_reached,
+            terrain_changes,
+            events,
             phase,
             flags,
+            rule_set,
         }
     }
 
+    /// Copies come from network or save files : check they only use known weapons
+    pub fn validate_weapons(
+        &self,
+        definitions: &WeaponDefinitions,
+    ) -> Result<(), WeaponDefinitionsError> {
+        for soldier in &self.soldiers {
+            definitions.validate_equipment(soldier.main_weapon().as_ref(), soldier.magazines())?;
+            definitions.validate_equipment(soldier.secondary_weapon().as_ref(), &[])?;
+        }
+
+        Ok(())
+    }
+
     pub fn frame_i(&self) -> u64 {
         self.frame_i
     }
@@ -67,4 +127,40 @@ impl BattleStateCopy {
     pub fn squad_types(&self) -> &SquadTypes {
         &self.squad_types
     }
+
+    pub fn squad_leaders_replaced(&self) -> &SquadLeadersReplaced {
+        &self.squad_leaders_replaced
+    }
+
+    pub fn squad_formations(&self) -> &SquadFormations {
+        &self.squad_formations
+    }
+
+    pub fn squad_fire_disciplines(&self) -> &SquadFireDisciplines {
+        &self.squad_fire_disciplines
+    }
+
+    pub fn squad_order_queues(&self) -> &SquadOrderQueues {
+        &self.squad_order_queues
+    }
+
+    pub fn squad_triggered_orders(&self) -> &SquadTriggeredOrders {
+        &self.squad_triggered_orders
+    }
+
+    pub fn squad_destinations_reached(&self) -> &SquadDestinationsReached {
+        &self.squad_destinations_reached
+    }
+
+    pub fn terrain_changes(&self) -> &Vec<TerrainChange> {
+        &self.terrain_changes
+    }
+
+    pub fn events(&self) -> &BattleEvents {
+        &self.events
+    }
+
+    pub fn rule_set(&self) -> RuleSet {
+        self.rule_set
+    }
 }
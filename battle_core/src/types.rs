@@ -331,6 +331,10 @@ impl WorldPaths {
         }
     }
 
+    pub fn last_point(&self) -> Option<WorldPoint> {
+        self.paths.last().and_then(|path| path.last_point())
+    }
+
     pub fn _next_path_last_point(&self) -> Option<WorldPoint> {
         if self.paths.is_empty() {
             None
@@ -398,11 +402,21 @@ pub struct ExplosionIndex(pub usize);
 pub struct SquadUuid(pub usize);
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct SquadComposition(SoldierIndex, SquadType, Vec<SoldierIndex>);
+pub struct SquadComposition(
+    SoldierIndex,
+    SquadType,
+    Vec<SoldierIndex>,
+    Option<SoldierIndex>,
+);
 
 impl SquadComposition {
-    pub fn new(leader: SoldierIndex, type_: SquadType, members: Vec<SoldierIndex>) -> Self {
-        Self(leader, type_, members)
+    pub fn new(
+        leader: SoldierIndex,
+        type_: SquadType,
+        members: Vec<SoldierIndex>,
+        assistant: Option<SoldierIndex>,
+    ) -> Self {
+        Self(leader, type_, members, assistant)
     }
 
     pub fn leader(&self) -> SoldierIndex {
@@ -424,6 +438,15 @@ impl SquadComposition {
     pub fn subordinates(&self) -> Vec<&SoldierIndex> {
         self.2.iter().filter(|i| i != &&self.0).collect()
     }
+
+    /// Member designated to assist the squad crew-served weapon gunner
+    pub fn assistant(&self) -> Option<SoldierIndex> {
+        self.3
+    }
+
+    pub fn assistant_mut(&mut self) -> &mut Option<SoldierIndex> {
+        &mut self.3
+    }
 }
 
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
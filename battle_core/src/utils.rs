@@ -1,7 +1,12 @@
-use geo::{coord, Contains, Triangle};
+use geo::{
+    coord, Area, BooleanOps, BoundingRect, Contains, Intersects, LineString, Polygon, Triangle,
+};
 use glam::Vec2;
 use serde::{Deserialize, Serialize};
-use std::{f32::consts::FRAC_PI_2, sync::atomic::AtomicUsize};
+use std::{
+    f32::consts::{FRAC_PI_2, PI},
+    sync::atomic::AtomicUsize,
+};
 
 use crate::{physics::utils::DISTANCE_TO_METERS_COEFFICIENT, types::*};
 
@@ -224,6 +229,7 @@ impl WorldShape {
         }
     }
 
+    /// Point is inside shape or on its border (like its center, on both triangles border)
     pub fn contains(&self, point: &WorldPoint) -> bool {
         let triangle1 = Triangle::new(
             coord! { x: self.top_left.x, y: self.top_left.y },
@@ -236,8 +242,155 @@ impl WorldShape {
             coord! { x: self.top_right.x, y: self.top_right.y },
         );
 
-        triangle1.contains(&coord! { x: point.x, y: point.y })
-            || triangle2.contains(&coord! { x: point.x, y: point.y })
+        triangle1.intersects(&coord! { x: point.x, y: point.y })
+            || triangle2.intersects(&coord! { x: point.x, y: point.y })
+    }
+}
+
+// Number of points used to approximate an ellipse as a polygon
+const ELLIPSE_POLYGON_POINTS: usize = 32;
+
+/// Any closed shape (rectangle, polygon or ellipse approximation) like map zones
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldPolygon {
+    points: Vec<WorldPoint>,
+    // Built once, used by containment tests
+    polygon: Polygon<f32>,
+}
+
+impl WorldPolygon {
+    pub fn new(points: Vec<WorldPoint>) -> Self {
+        let polygon = Polygon::new(
+            LineString::from(
+                points
+                    .iter()
+                    .map(|p| coord! { x: p.x, y: p.y })
+                    .collect::<Vec<_>>(),
+            ),
+            vec![],
+        );
+        Self { points, polygon }
+    }
+
+    pub fn from_rect(rect: &Rect) -> Self {
+        Self::from(&WorldShape::from_rect(rect))
+    }
+
+    /// Ellipse inscribed in given rect
+    pub fn from_ellipse(rect: &Rect) -> Self {
+        let center = WorldPoint::new(rect.x + rect.w / 2., rect.y + rect.h / 2.);
+        let points = (0..ELLIPSE_POLYGON_POINTS)
+            .map(|i| {
+                let angle = PI * 2. * i as f32 / ELLIPSE_POLYGON_POINTS as f32;
+                center.apply(Vec2::new(
+                    angle.cos() * rect.w / 2.,
+                    angle.sin() * rect.h / 2.,
+                ))
+            })
+            .collect();
+
+        Self::new(points)
+    }
+
+    /// Polygon rotated around given point (like Tiled objects, around their position)
+    pub fn rotated(&self, reference_point: &WorldPoint, angle: &Angle) -> Self {
+        Self::new(
+            self.points
+                .iter()
+                .map(|point| apply_angle_on_point(point, reference_point, angle))
+                .collect(),
+        )
+    }
+
+    pub fn points(&self) -> &Vec<WorldPoint> {
+        &self.points
+    }
+
+    pub fn bounding_rect(&self) -> Rect {
+        let min_x = self
+            .points
+            .iter()
+            .map(|p| p.x)
+            .fold(f32::INFINITY, f32::min);
+        let min_y = self
+            .points
+            .iter()
+            .map(|p| p.y)
+            .fold(f32::INFINITY, f32::min);
+        let max_x = self
+            .points
+            .iter()
+            .map(|p| p.x)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let max_y = self
+            .points
+            .iter()
+            .map(|p| p.y)
+            .fold(f32::NEG_INFINITY, f32::max);
+
+        Rect::new(min_x, min_y, max_x - min_x, max_y - min_y)
+    }
+
+    pub fn center(&self) -> WorldPoint {
+        let rect = self.bounding_rect();
+        WorldPoint::new(rect.x + rect.w / 2., rect.y + rect.h / 2.)
+    }
+
+    /// Point is inside polygon or on its border
+    pub fn contains(&self, point: &WorldPoint) -> bool {
+        self.polygon.intersects(&coord! { x: point.x, y: point.y })
+    }
+
+    /// Polygon parts crossed by the horizontal line at given y, as (start x, end x) spans
+    /// from left to right. Concave polygons can give several spans.
+    pub fn horizontal_spans(&self, y: f32) -> Vec<(f32, f32)> {
+        let mut crossings = vec![];
+        for (i, from) in self.points.iter().enumerate() {
+            let to = &self.points[(i + 1) % self.points.len()];
+            // Half open test to count a vertex on the line only once
+            if (from.y <= y) != (to.y <= y) {
+                crossings.push(from.x + (y - from.y) / (to.y - from.y) * (to.x - from.x));
+            }
+        }
+        crossings.sort_by(|a, b| a.total_cmp(b));
+
+        crossings
+            .chunks_exact(2)
+            .map(|span| (span[0], span[1]))
+            .collect()
+    }
+
+    /// Polygons share some surface (touching borders are not overlapping)
+    pub fn overlaps(&self, other: &WorldPolygon) -> bool {
+        self.polygon.intersection(&other.polygon).unsigned_area() > 0.
+    }
+
+    /// Thickness of the surface shared with other polygon (its area divided by its longest
+    /// side), zero if polygons don't overlap. Tells a drawing inaccuracy from a real overlap.
+    pub fn overlap_thickness(&self, other: &WorldPolygon) -> f32 {
+        let intersection = self.polygon.intersection(&other.polygon);
+        let area = intersection.unsigned_area();
+        match intersection.bounding_rect() {
+            Some(rect) if area > 0. => area / rect.width().max(rect.height()),
+            _ => 0.,
+        }
+    }
+
+    /// Given polygon is inside this polygon, borders can touch. Concave polygons are considered :
+    /// all points inside is not enough.
+    pub fn contains_polygon(&self, other: &WorldPolygon) -> bool {
+        self.polygon.contains(&other.polygon)
+    }
+}
+
+impl From<&WorldShape> for WorldPolygon {
+    fn from(shape: &WorldShape) -> Self {
+        Self::new(vec![
+            shape.top_left,
+            shape.top_right,
+            shape.bottom_right,
+            shape.bottom_left,
+        ])
     }
 }
 
@@ -334,3 +487,91 @@ pub fn start_puffin_server(address: String) {
         }
     };
 }
+
+#[cfg(test)]
+mod test {
+    use rstest::*;
+
+    use super::*;
+
+    fn polygon(points: &[(f32, f32)]) -> WorldPolygon {
+        WorldPolygon::new(
+            points
+                .iter()
+                .map(|(x, y)| WorldPoint::new(*x, *y))
+                .collect(),
+        )
+    }
+
+    // L-shaped building : 20x20 square without its 10x10 top right quarter
+    fn l_shape() -> WorldPolygon {
+        polygon(&[
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (20., 10.),
+            (20., 20.),
+            (0., 20.),
+        ])
+    }
+
+    #[rstest]
+    #[case(polygon(&[(1., 1.), (9., 1.), (9., 19.), (1., 19.)]), true)]
+    #[case(polygon(&[(0., 10.), (20., 10.), (20., 20.), (0., 20.)]), true)]
+    // All points inside, but crossing the missing quarter
+    #[case(polygon(&[(8., 2.), (18., 12.), (8., 18.)]), false)]
+    #[case(polygon(&[(15., 5.), (18., 5.), (18., 8.)]), false)]
+    fn contains_polygon(#[case] other: WorldPolygon, #[case] expected: bool) {
+        assert_eq!(l_shape().contains_polygon(&other), expected);
+    }
+
+    #[rstest]
+    #[case(polygon(&[(30., 0.), (40., 0.), (40., 20.), (30., 20.)]), 0.)]
+    // Drawing inaccuracy : shares a 0.4 wide band of the right side
+    #[case(polygon(&[(19.6, 10.), (30., 10.), (30., 20.), (19.6, 20.)]), 0.4)]
+    #[case(polygon(&[(15., 10.), (30., 10.), (30., 20.), (15., 20.)]), 5.)]
+    fn overlap_thickness(#[case] other: WorldPolygon, #[case] expected: f32) {
+        assert!((l_shape().overlap_thickness(&other) - expected).abs() < 0.01);
+    }
+
+    #[rstest]
+    #[case(l_shape(), 5., vec![(0., 10.)])]
+    #[case(l_shape(), 15., vec![(0., 20.)])]
+    #[case(l_shape(), 25., vec![])]
+    // U-shaped building : 30x20 rect without its 10x10 top middle part
+    #[case(
+        polygon(&[
+            (0., 0.),
+            (10., 0.),
+            (10., 10.),
+            (20., 10.),
+            (20., 0.),
+            (30., 0.),
+            (30., 20.),
+            (0., 20.),
+        ]),
+        5.,
+        vec![(0., 10.), (20., 30.)]
+    )]
+    #[case(polygon(&[(0., 0.), (20., 20.), (0., 20.)]), 10., vec![(0., 10.)])]
+    fn horizontal_spans(
+        #[case] polygon: WorldPolygon,
+        #[case] y: f32,
+        #[case] expected: Vec<(f32, f32)>,
+    ) {
+        assert_eq!(polygon.horizontal_spans(y), expected);
+    }
+
+    #[test]
+    fn rotated_around_position() {
+        // Tiled like rect object at (10, 10), rotated by 90 degrees clockwise
+        let rect = WorldPolygon::from_rect(&Rect::new(10., 10., 20., 5.));
+        let rotated = rect.rotated(&WorldPoint::new(10., 10.), &Angle(90_f32.to_radians()));
+
+        assert!(rotated.contains(&WorldPoint::new(8., 25.)));
+        assert!(!rotated.contains(&WorldPoint::new(25., 12.)));
+        let bounds = rotated.bounding_rect();
+        assert!((bounds.x - 5.).abs() < 0.001 && (bounds.y - 10.).abs() < 0.001);
+        assert!((bounds.w - 5.).abs() < 0.001 && (bounds.h - 20.).abs() < 0.001);
+    }
+}
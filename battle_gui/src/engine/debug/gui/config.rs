@@ -1,27 +1,15 @@
 use ggegui::egui::{Context as EguiContext, Grid, Slider, Ui};
 use ggez::Context;
 
-use battle_core::config::{
-    ChangeConfigMessage, FEELING_DECREASING_FREQ, INTERIORS_UPDATE_FREQ, SOLDIER_ANIMATE_FREQ,
-    SOLDIER_UPDATE_FREQ, TARGET_CYCLE_DURATION_US, TARGET_FPS, TILE_TYPE_OPACITY_BRICK_WALL,
-    TILE_TYPE_OPACITY_CONCRETE, TILE_TYPE_OPACITY_DEEP_WATER, TILE_TYPE_OPACITY_DIRT,
-    TILE_TYPE_OPACITY_HEDGE, TILE_TYPE_OPACITY_HIGH_GRASS, TILE_TYPE_OPACITY_LIGHT_UNDERBRUSH,
-    TILE_TYPE_OPACITY_MIDDLE_GRASS, TILE_TYPE_OPACITY_MIDDLE_ROCK,
-    TILE_TYPE_OPACITY_MIDDLE_WOOD_LOGS, TILE_TYPE_OPACITY_MUD, TILE_TYPE_OPACITY_SHORT_GRASS,
-    TILE_TYPE_OPACITY_TRUNK, TILE_TYPE_OPACITY_UNDERBRUSH, TILE_TYPE_OPACITY_WATER,
-    VISIBILITY_BY_LAST_FRAME_SHOOT, VISIBILITY_BY_LAST_FRAME_SHOOT_DISTANCE,
-    VISIBILITY_DEAD_MODIFIER, VISIBILITY_DEFEND_MODIFIER, VISIBILITY_ENGAGE_MODIFIER,
-    VISIBILITY_FIRSTS, VISIBILITY_HIDE_MODIFIER, VISIBILITY_IDLE_CROUCH_MODIFIER,
-    VISIBILITY_IDLE_LYING_MODIFIER, VISIBILITY_IDLE_STANDUP_MODIFIER,
-    VISIBILITY_IN_VEHICLE_MODIFIER, VISIBILITY_MOVE_FAST_TO_MODIFIER, VISIBILITY_MOVE_TO_MODIFIER,
-    VISIBILITY_SNEAK_TO_MODIFIER, VISIBILITY_SUPPRESS_FIRE_MODIFIER,
-    VISIBILITY_UNCONSCIOUS_MODIFIER, VISIBILITY_UPDATE_FREQ, VISIBLE_STARTS_AT,
+use battle_core::{
+    config::{
+        value::{ConfigGroup, ConfigValue, ConfigValueMeta},
+        ChangeConfigMessage, ServerConfig, INTERIORS_UPDATE_FREQ, SERVER_CONFIG_VALUES, TARGET_FPS,
+    },
+    types::Distance,
 };
 
-use crate::{
-    engine::{message::EngineMessage, Engine},
-    graphics::message::GraphicsMessage,
-};
+use crate::engine::{message::EngineMessage, Engine};
 
 impl Engine {
     pub fn debug_gui_server_config(
@@ -30,78 +18,17 @@ impl Engine {
         _egui_ctx: &EguiContext,
         ui: &mut Ui,
     ) -> Vec<EngineMessage> {
-        let mut messages = vec![];
-
-        Grid::new("meta")
-            .num_columns(3)
-            .spacing([40.0, 4.0])
-            .striped(true)
-            .show(ui, |ui| {
-                for (name, value, min, max, default, message) in [
-                    (
-                        "TARGET_CYCLE_DURATION_US",
-                        &mut self.server_config.target_cycle_duration_us,
-                        0,
-                        TARGET_CYCLE_DURATION_US * 5,
-                        TARGET_CYCLE_DURATION_US,
-                        ChangeConfigMessage::TargetCycleDuration,
-                    ),
-                    (
-                        "SOLDIER_UPDATE_FREQ",
-                        &mut self.server_config.soldier_update_freq,
-                        1,
-                        120,
-                        SOLDIER_UPDATE_FREQ,
-                        ChangeConfigMessage::SoldierUpdateFreq,
-                    ),
-                    (
-                        "SOLDIER_ANIMATE_FREQ",
-                        &mut self.server_config.soldier_animate_freq,
-                        1,
-                        120,
-                        SOLDIER_ANIMATE_FREQ,
-                        ChangeConfigMessage::SoldierAnimateFreq,
-                    ),
-                    (
-                        "INTERIORS_UPDATE_FREQ",
-                        &mut self.server_config.interiors_update_freq,
-                        1,
-                        120,
-                        INTERIORS_UPDATE_FREQ,
-                        ChangeConfigMessage::InteriorsUpdateFreq,
-                    ),
-                    (
-                        "VISIBILITY_UPDATE_FREQ",
-                        &mut self.server_config.visibility_update_freq,
-                        1,
-                        120,
-                        VISIBILITY_UPDATE_FREQ,
-                        ChangeConfigMessage::VisibilityUpdateFreq,
-                    ),
-                    (
-                        "FEELING_DECREASING_FREQ",
-                        &mut self.server_config.feeling_decreasing_freq,
-                        1,
-                        120,
-                        FEELING_DECREASING_FREQ,
-                        ChangeConfigMessage::FeelingDecreasingFreq,
-                    ),
-                ]
-                    as [(_, _, _, _, _, fn(_) -> _); 6]
-                {
-                    ui.label(name);
-                    if ui.button("reset").clicked() {
-                        *value = default;
-                    };
-                    if ui.add(Slider::new(value, min..=max)).changed() {
-                        messages.push(EngineMessage::ChangeServerConfig(message(*value)));
-                    };
-                    ui.end_row();
-                }
-            });
-
-        messages
+        if ui.button("Export to file").clicked() {
+            match self.save_server_config() {
+                Ok(path) => println!("Server config exported to {}", path.display()),
+                Err(error) => eprintln!("Error happen during server config export : {}", error),
+            }
+        }
+        ui.separator();
+
+        self.debug_gui_config_values(ui, &[ConfigGroup::Server])
     }
+
     pub fn debug_gui_gui_config(
         &mut self,
         _ctx: &mut Context,
@@ -137,248 +64,7 @@ impl Engine {
         _egui_ctx: &EguiContext,
         ui: &mut Ui,
     ) -> Vec<EngineMessage> {
-        let mut messages = vec![];
-
-        Grid::new("meta")
-            .num_columns(2)
-            .spacing([40.0, 4.0])
-            .striped(true)
-            .show(ui, |ui| {
-                ui.label("VISIBILITY_FIRSTS");
-                if ui.button("reset").clicked() {
-                    self.server_config.visibility_firsts = VISIBILITY_FIRSTS;
-                };
-                if ui
-                    .add(Slider::new(
-                        &mut self.server_config.visibility_firsts,
-                        0..=10,
-                    ))
-                    .changed()
-                {
-                    messages.push(EngineMessage::ChangeServerConfig(
-                        ChangeConfigMessage::VisibilityFirsts(self.server_config.visibility_firsts),
-                    ));
-                };
-                ui.end_row();
-
-                ui.label("VISIBLE_STARTS_AT");
-                if ui.button("reset").clicked() {
-                    self.server_config.visible_starts_at = VISIBLE_STARTS_AT;
-                };
-                if ui
-                    .add(Slider::new(
-                        &mut self.server_config.visible_starts_at,
-                        (0.)..=1.,
-                    ))
-                    .changed()
-                {
-                    messages.push(EngineMessage::ChangeServerConfig(
-                        ChangeConfigMessage::VisibleStartsAt(self.server_config.visible_starts_at),
-                    ));
-                };
-                ui.end_row();
-
-                for (name, value, default, message) in [
-                    (
-                        "IDLE STANDUP",
-                        &mut self.server_config.visibility_idle_standup_modifier,
-                        VISIBILITY_IDLE_STANDUP_MODIFIER,
-                        ChangeConfigMessage::VisibilityIdleStandupModifier,
-                    ),
-                    (
-                        "IDLE CROUCHED",
-                        &mut self.server_config.visibility_idle_crouch_modifier,
-                        VISIBILITY_IDLE_CROUCH_MODIFIER,
-                        ChangeConfigMessage::VisibilityIdleCrouchModifier,
-                    ),
-                    (
-                        "IDLE LYING",
-                        &mut self.server_config.visibility_idle_lying_modifier,
-                        VISIBILITY_IDLE_LYING_MODIFIER,
-                        ChangeConfigMessage::VisibilityIdleLyingModifier,
-                    ),
-                    (
-                        "MOVE_TO",
-                        &mut self.server_config.visibility_move_to_modifier,
-                        VISIBILITY_MOVE_TO_MODIFIER,
-                        ChangeConfigMessage::VisibilityMoveModifier,
-                    ),
-                    (
-                        "MOVE_FAST_TO",
-                        &mut self.server_config.visibility_move_fast_to_modifier,
-                        VISIBILITY_MOVE_FAST_TO_MODIFIER,
-                        ChangeConfigMessage::VisibilityMoveFastModifier,
-                    ),
-                    (
-                        "SNEAK_TO",
-                        &mut self.server_config.visibility_sneak_to_modifier,
-                        VISIBILITY_SNEAK_TO_MODIFIER,
-                        ChangeConfigMessage::VisibilitySneakToModifier,
-                    ),
-                    (
-                        "DEFEND",
-                        &mut self.server_config.visibility_defend_modifier,
-                        VISIBILITY_DEFEND_MODIFIER,
-                        ChangeConfigMessage::VisibilityDefendModifier,
-                    ),
-                    (
-                        "HIDE",
-                        &mut self.server_config.visibility_hide_modifier,
-                        VISIBILITY_HIDE_MODIFIER,
-                        ChangeConfigMessage::VisibilityHideModifier,
-                    ),
-                    (
-                        "IN_VEHICLE",
-                        &mut self.server_config.visibility_in_vehicle_modifier,
-                        VISIBILITY_IN_VEHICLE_MODIFIER,
-                        ChangeConfigMessage::VisibilityInVehicleModifier,
-                    ),
-                    (
-                        "SUPPRESS_FIRE",
-                        &mut self.server_config.visibility_suppress_fire_modifier,
-                        VISIBILITY_SUPPRESS_FIRE_MODIFIER,
-                        ChangeConfigMessage::VisibilitySuppressFireModifier,
-                    ),
-                    (
-                        "ENGAGE",
-                        &mut self.server_config.visibility_engage_modifier,
-                        VISIBILITY_ENGAGE_MODIFIER,
-                        ChangeConfigMessage::VisibilityEngageModifier,
-                    ),
-                    (
-                        "DEAD",
-                        &mut self.server_config.visibility_dead_modifier,
-                        VISIBILITY_DEAD_MODIFIER,
-                        ChangeConfigMessage::VisibilityDeadModifier,
-                    ),
-                    (
-                        "UNCONSCIOUS",
-                        &mut self.server_config.visibility_unconscious_modifier,
-                        VISIBILITY_UNCONSCIOUS_MODIFIER,
-                        ChangeConfigMessage::VisibilityUnconsciousModifier,
-                    ),
-                ]
-                    as [(_, _, _, fn(_) -> _); 13]
-                {
-                    ui.label(format!("VISIBILITY_BEHAVIOR_MODIFIER__{}", name));
-                    if ui.button("reset").clicked() {
-                        *value = default;
-                    };
-                    if ui.add(Slider::new(value, (-10.)..=10.)).changed() {
-                        messages.push(EngineMessage::ChangeServerConfig(message(*value)));
-                    };
-                    ui.end_row();
-                }
-
-                for (name, value, default, message) in [
-                    (
-                        "SHORT_GRASS",
-                        &mut self.server_config.tile_type_opacity_short_grass,
-                        TILE_TYPE_OPACITY_SHORT_GRASS,
-                        ChangeConfigMessage::TileTypeOpacityShortGrass,
-                    ),
-                    (
-                        "MIDDLE_GRASS",
-                        &mut self.server_config.tile_type_opacity_middle_grass,
-                        TILE_TYPE_OPACITY_MIDDLE_GRASS,
-                        ChangeConfigMessage::TileTypeOpacityMiddleGrass,
-                    ),
-                    (
-                        "HIGH_GRASS",
-                        &mut self.server_config.tile_type_opacity_high_grass,
-                        TILE_TYPE_OPACITY_HIGH_GRASS,
-                        ChangeConfigMessage::TileTypeOpacityHighGrass,
-                    ),
-                    (
-                        "DIRT",
-                        &mut self.server_config.tile_type_opacity_dirt,
-                        TILE_TYPE_OPACITY_DIRT,
-                        ChangeConfigMessage::TileTypeOpacityDirt,
-                    ),
-                    (
-                        "CONCRETE",
-                        &mut self.server_config.tile_type_opacity_concrete,
-                        TILE_TYPE_OPACITY_CONCRETE,
-                        ChangeConfigMessage::TileTypeOpacityConcrete,
-                    ),
-                    (
-                        "MUD",
-                        &mut self.server_config.tile_type_opacity_mud,
-                        TILE_TYPE_OPACITY_MUD,
-                        ChangeConfigMessage::TileTypeOpacityMud,
-                    ),
-                    (
-                        "WALL",
-                        &mut self.server_config.tile_type_opacity_brick_wall,
-                        TILE_TYPE_OPACITY_BRICK_WALL,
-                        ChangeConfigMessage::TileTypeOpacityBrickWall,
-                    ),
-                    (
-                        "TRUNK",
-                        &mut self.server_config.tile_type_opacity_trunk,
-                        TILE_TYPE_OPACITY_TRUNK,
-                        ChangeConfigMessage::TileTypeOpacityTrunk,
-                    ),
-                    (
-                        "WATER",
-                        &mut self.server_config.tile_type_opacity_water,
-                        TILE_TYPE_OPACITY_WATER,
-                        ChangeConfigMessage::TileTypeOpacityWater,
-                    ),
-                    (
-                        "DEEP_WATER",
-                        &mut self.server_config.tile_type_opacity_deep_water,
-                        TILE_TYPE_OPACITY_DEEP_WATER,
-                        ChangeConfigMessage::TileTypeOpacityDeepWater,
-                    ),
-                    (
-                        "UNDERBRUSH",
-                        &mut self.server_config.tile_type_opacity_underbrush,
-                        TILE_TYPE_OPACITY_UNDERBRUSH,
-                        ChangeConfigMessage::TileTypeOpacityUnderbrush,
-                    ),
-                    (
-                        "LIGHT_UNDERBRUSH",
-                        &mut self.server_config.tile_type_opacity_light_underbrush,
-                        TILE_TYPE_OPACITY_LIGHT_UNDERBRUSH,
-                        ChangeConfigMessage::TileTypeOpacityLightUnderbrush,
-                    ),
-                    (
-                        "MIDDLE_WOOD_LOGS",
-                        &mut self.server_config.tile_type_opacity_middle_wood_logs,
-                        TILE_TYPE_OPACITY_MIDDLE_WOOD_LOGS,
-                        ChangeConfigMessage::TileTypeOpacityMiddleWoodLogs,
-                    ),
-                    (
-                        "HEDGE",
-                        &mut self.server_config.tile_type_opacity_hedge,
-                        TILE_TYPE_OPACITY_HEDGE,
-                        ChangeConfigMessage::TileTypeOpacityHedge,
-                    ),
-                    (
-                        "MIDDLE_ROCK",
-                        &mut self.server_config.tile_type_opacity_middle_rock,
-                        TILE_TYPE_OPACITY_MIDDLE_ROCK,
-                        ChangeConfigMessage::TileTypeOpacityMiddleRock,
-                    ),
-                ]
-                    as [(_, _, _, fn(_) -> _); 15]
-                {
-                    ui.label(format!("TILE_TYPE_OPACITY_{}", name));
-                    if ui.button("reset").clicked() {
-                        *value = default;
-                    };
-                    if ui.add(Slider::new(value, (0.)..=1.)).changed() {
-                        messages.push(EngineMessage::Graphics(
-                            GraphicsMessage::RecomputeDebugTerrainOpacity,
-                        ));
-                        messages.push(EngineMessage::ChangeServerConfig(message(*value)))
-                    };
-                    ui.end_row();
-                }
-            });
-
-        messages
+        self.debug_gui_config_values(ui, &[ConfigGroup::Visibility, ConfigGroup::Opacity])
     }
 
     pub fn debug_gui_fight_config(
@@ -386,54 +72,87 @@ impl Engine {
         _ctx: &mut Context,
         _egui_ctx: &EguiContext,
         ui: &mut Ui,
+    ) -> Vec<EngineMessage> {
+        self.debug_gui_config_values(ui, &[ConfigGroup::Fight, ConfigGroup::Rules])
+    }
+
+    /// Widgets of server config values of given groups, built from their metadata
+    fn debug_gui_config_values(
+        &mut self,
+        ui: &mut Ui,
+        groups: &[ConfigGroup],
     ) -> Vec<EngineMessage> {
         let mut messages = vec![];
+        let default = ServerConfig::default();
 
         Grid::new("meta")
-            .num_columns(2)
+            .num_columns(3)
             .spacing([40.0, 4.0])
             .striped(true)
             .show(ui, |ui| {
-                ui.label("VISIBILITY_BY_LAST_FRAME_SHOOT");
-                if ui.button("reset").clicked() {
-                    self.server_config.visibility_by_last_frame_shoot =
-                        VISIBILITY_BY_LAST_FRAME_SHOOT;
-                }
-                if ui
-                    .add(Slider::new(
-                        &mut self.server_config.visibility_by_last_frame_shoot,
-                        0..=600,
-                    ))
-                    .changed()
-                {
-                    messages.push(EngineMessage::ChangeServerConfig(
-                        ChangeConfigMessage::VisibilityByLastFrameShot(
-                            self.server_config.visibility_by_last_frame_shoot,
-                        ),
-                    ));
-                };
-                ui.end_row();
-
-                ui.label("VISIBILITY_BY_LAST_FRAME_SHOOT_DISTANCE");
-                if ui.button("reset").clicked() {
-                    self.server_config.visibility_by_last_frame_shoot_distance =
-                        VISIBILITY_BY_LAST_FRAME_SHOOT_DISTANCE;
-                }
-                if ui
-                    .add(Slider::new(
-                        &mut self.server_config.visibility_by_last_frame_shoot_distance,
-                        0..=30,
-                    ))
-                    .changed()
+                for meta in SERVER_CONFIG_VALUES
+                    .iter()
+                    .filter(|meta| groups.contains(&meta.group))
                 {
-                    messages.push(EngineMessage::ChangeServerConfig(
-                        ChangeConfigMessage::VisibilityByLastFrameShotDistance(
-                            self.server_config.visibility_by_last_frame_shoot_distance,
-                        ),
-                    ));
+                    if let Some(value) = self.server_config.value(meta.key) {
+                        ui.label(meta.name());
+                        let mut new_value = None;
+                        if ui.button("reset").clicked() {
+                            new_value = default.value(meta.key);
+                        };
+                        if let Some(value) = config_value_widget(ui, meta, value) {
+                            new_value = Some(value);
+                        }
+
+                        if let Some(value) = new_value {
+                            match self.server_config.set_value(meta.key, value) {
+                                Ok(_) => messages.push(EngineMessage::ChangeServerConfig(
+                                    ChangeConfigMessage::Set(meta.key.to_string(), value),
+                                )),
+                                Err(error) => eprintln!("{}", error),
+                            }
+                        }
+                        ui.end_row();
+                    }
                 }
             });
 
         messages
     }
 }
+
+/// Widget matching value type, return the new value if changed
+pub fn config_value_widget(
+    ui: &mut Ui,
+    meta: &ConfigValueMeta,
+    value: ConfigValue,
+) -> Option<ConfigValue> {
+    let suffix = match meta.unit.suffix() {
+        "" => String::new(),
+        suffix => format!(" {}", suffix),
+    };
+    match value {
+        ConfigValue::Bool(mut value) => ui
+            .checkbox(&mut value, "")
+            .changed()
+            .then_some(ConfigValue::Bool(value)),
+        ConfigValue::U64(mut value) => ui
+            .add(Slider::new(&mut value, meta.min as u64..=meta.max as u64).suffix(suffix))
+            .changed()
+            .then_some(ConfigValue::U64(value)),
+        ConfigValue::Usize(mut value) => ui
+            .add(Slider::new(&mut value, meta.min as usize..=meta.max as usize).suffix(suffix))
+            .changed()
+            .then_some(ConfigValue::Usize(value)),
+        ConfigValue::F32(mut value) => ui
+            .add(Slider::new(&mut value, meta.min as f32..=meta.max as f32).suffix(suffix))
+            .changed()
+            .then_some(ConfigValue::F32(value)),
+        ConfigValue::Distance(distance) => {
+            let mut meters = distance.millimeters() as f32 / 1000.;
+            ui.add(Slider::new(&mut meters, meta.min as f32..=meta.max as f32).suffix(suffix))
+                .changed()
+                .then(|| ConfigValue::Distance(Distance::from_millimeters((meters * 1000.) as i64)))
+        }
+    }
+}
@@ -1,9 +1,14 @@
 use crate::engine::{message::EngineMessage, Engine};
-use battle_core::{config::ChangeConfigMessage, game::explosive::ExplosiveType, types::Distance};
-use ggegui::egui::{ComboBox, Context as EguiContext, Grid, Slider, Ui};
+use battle_core::{
+    config::{explosive_distance_key, ChangeConfigMessage, EXPLOSIVE_DISTANCES_VALUES},
+    game::explosive::ExplosiveType,
+};
+use ggegui::egui::{ComboBox, Context as EguiContext, Grid, Ui};
 use ggez::Context;
 use strum::IntoEnumIterator;
 
+use super::config::config_value_widget;
+
 impl Engine {
     pub fn debug_gui_explosives(
         &mut self,
@@ -32,80 +37,20 @@ impl Engine {
             .spacing([40.0, 4.0])
             .striped(true)
             .show(ui, |ui| {
-                if let (
-                    Some(direct_death_rayons),
-                    Some(regressive_death_rayon),
-                    Some(regressive_injured_rayon),
-                ) = (
-                    self.server_config
-                        .explosive_direct_death_rayon
-                        .get_mut(&self.debug_gui.explosive),
-                    self.server_config
-                        .explosive_regressive_death_rayon
-                        .get_mut(&self.debug_gui.explosive),
-                    self.server_config
-                        .explosive_regressive_injured_rayon
-                        .get_mut(&self.debug_gui.explosive),
-                ) {
-                    ui.label("Direct death rayon");
-                    // if ui.button("reset").clicked() {
-                    //     *value = default;
-                    // };
-                    if ui
-                        .add(Slider::new(
-                            &mut direct_death_rayons.millimeters,
-                            0..=100000,
-                        ))
-                        .changed()
-                    {
-                        messages.push(EngineMessage::ChangeServerConfig(
-                            ChangeConfigMessage::ExplosiveDirectDeathRayon(
-                                self.debug_gui.explosive.clone(),
-                                Distance::from_millimeters(direct_death_rayons.millimeters),
-                            ),
-                        ));
-                    };
-                    ui.end_row();
-
-                    ui.label("Regressive death rayon");
-                    // if ui.button("reset").clicked() {
-                    //     *value = default;
-                    // };
-                    if ui
-                        .add(Slider::new(
-                            &mut regressive_death_rayon.millimeters,
-                            0..=100000,
-                        ))
-                        .changed()
-                    {
-                        messages.push(EngineMessage::ChangeServerConfig(
-                            ChangeConfigMessage::ExplosiveRegressiveDeathRayon(
-                                self.debug_gui.explosive.clone(),
-                                Distance::from_millimeters(regressive_death_rayon.millimeters),
-                            ),
-                        ));
-                    };
-                    ui.end_row();
-
-                    ui.label("Regressive injured rayon");
-                    // if ui.button("reset").clicked() {
-                    //     *value = default;
-                    // };
-                    if ui
-                        .add(Slider::new(
-                            &mut regressive_injured_rayon.millimeters,
-                            0..=100000,
-                        ))
-                        .changed()
-                    {
-                        messages.push(EngineMessage::ChangeServerConfig(
-                            ChangeConfigMessage::ExplosiveRegressiveInjuredRayon(
-                                self.debug_gui.explosive.clone(),
-                                Distance::from_millimeters(regressive_injured_rayon.millimeters),
-                            ),
-                        ));
-                    };
-                    ui.end_row();
+                for meta in EXPLOSIVE_DISTANCES_VALUES {
+                    let key = explosive_distance_key(meta.key, &self.debug_gui.explosive);
+                    if let Some(value) = self.server_config.value(&key) {
+                        ui.label(meta.name());
+                        if let Some(value) = config_value_widget(ui, meta, value) {
+                            match self.server_config.set_value(&key, value) {
+                                Ok(_) => messages.push(EngineMessage::ChangeServerConfig(
+                                    ChangeConfigMessage::Set(key, value),
+                                )),
+                                Err(error) => eprintln!("{}", error),
+                            }
+                        }
+                        ui.end_row();
+                    }
                 }
             });
 
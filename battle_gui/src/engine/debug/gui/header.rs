@@ -72,7 +72,8 @@ impl Engine {
         ui.horizontal(|ui| {
             ui.label("Cursor physics");
             ui.horizontal(|ui| {
-                let changes = [ui.radio_value(self.gui_state.debug_physics_mut(), DebugPhysics::None, "No")
+                let changes = [
+                    ui.radio_value(self.gui_state.debug_physics_mut(), DebugPhysics::None, "No")
                         .changed(),
                     ui.radio_value(
                         self.gui_state.debug_physics_mut(),
@@ -85,7 +86,8 @@ impl Engine {
                         DebugPhysics::BrandtMle2731Shelling,
                         "BrandtMle2731",
                     )
-                    .changed()];
+                    .changed(),
+                ];
 
                 if changes.iter().any(|v| *v) {
                     messages.extend(vec![EngineMessage::GuiState(GuiStateMessage::SetControl(
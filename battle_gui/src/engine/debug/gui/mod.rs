@@ -18,8 +18,9 @@ pub mod state;
 pub mod terrain;
 pub mod textures;
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq, Eq, Default)]
 pub enum Panel {
+    #[default]
     Terrain,
     Soldiers,
     BattleState,
@@ -33,12 +34,6 @@ pub enum Panel {
     Saves,
 }
 
-impl Default for Panel {
-    fn default() -> Self {
-        Self::Terrain
-    }
-}
-
 impl Engine {
     pub fn update_debug_gui(&mut self, ctx: &mut Context) -> GameResult<()> {
         let messages = self.debug_gui(ctx);
@@ -7,8 +7,7 @@ use battle_core::{
     behavior::Behavior,
     game::{
         explosive::ExplosiveType,
-        squad::{squad_positions, Formation},
-        weapon::{Shot, Weapon},
+        weapon::{Shot, Weapon, WeaponType},
         Side,
     },
     physics::event::{bullet::BulletFire, explosion::Explosion},
@@ -91,9 +90,10 @@ impl Engine {
     ) -> GameResult {
         // Display selected squad formation positions
         for squad_id in &self.gui_state.selected_squads().1 {
-            let squad = self.battle_state.squad(*squad_id);
-            let leader = self.battle_state.soldier(squad.leader());
-            for (_, point) in squad_positions(squad, Formation::Line, leader, None) {
+            for (_, point) in
+                self.battle_state
+                    .squad_formation_positions(&self.server_config, squad_id, None)
+            {
                 let window_point = self.gui_state.window_point_from_world_point(point);
                 mesh_builder.circle(DrawMode::fill(), window_point.to_vec2(), 2.0, 2.0, YELLOW)?;
             }
@@ -196,7 +196,6 @@ impl Engine {
         Ok(())
     }
 
-    ///
     pub fn generate_visibilities_meshes(&mut self, mesh_builder: &mut MeshBuilder) -> GameResult {
         for squad_uuid in &self.gui_state.selected_squads().1 {
             let squad_composition = self.battle_state.squad(*squad_uuid);
@@ -277,7 +276,7 @@ impl Engine {
         match self.gui_state.debug_physics() {
             DebugPhysics::None => {}
             DebugPhysics::MosinNagantM1924GunFire => {
-                let weapon = Weapon::MosinNagantM1924(true, None);
+                let weapon = Weapon::new(WeaponType::new("MosinNagantM1924"), true, None);
                 messages.extend(
                     [vec![EngineMessage::BattleState(
                         BattleStateMessage::PushBulletFire(BulletFire::new(
@@ -286,8 +285,10 @@ impl Engine {
                             to,
                             None,
                             weapon.ammunition(),
-                            Some(weapon.gun_fire_sound_type()),
+                            Some(weapon.type_().clone()),
                             Shot::x1,
+                            weapon.target(),
+                            None,
                         )),
                     )]]
                     .concat(),
@@ -298,6 +299,7 @@ impl Engine {
                     BattleStateMessage::PushExplosion(Explosion::new(
                         from,
                         ExplosiveType::FA19241927,
+                        None,
                     )),
                 ));
             }
@@ -311,6 +313,7 @@ impl Engine {
             let explosion = Explosion::new(
                 self.gui_state.current_cursor_world_point(),
                 explosive.clone(),
+                None,
             );
             self.generate_explosive_areas_meshes(mesh_builder, &explosion)?;
         };
@@ -401,15 +404,12 @@ impl Engine {
 
     pub fn inspect_for_bullet_fire_into_debug_points(&mut self, message: &BattleStateMessage) {
         let frame_i = self.gui_state.frame_i();
-        match message {
-            BattleStateMessage::PushBulletFire(bullet_fire) => {
-                self.gui_state.debug_points_mut().push(DebugPoint {
-                    frame_i: frame_i + 30,
-                    point: bullet_fire.point().clone(),
-                    color: RED.into(),
-                })
-            }
-            _ => {}
+        if let BattleStateMessage::PushBulletFire(bullet_fire) = message {
+            self.gui_state.debug_points_mut().push(DebugPoint {
+                frame_i: frame_i + 30,
+                point: *bullet_fire.point(),
+                color: RED.into(),
+            })
         }
     }
 }
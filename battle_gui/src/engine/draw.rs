@@ -8,8 +8,7 @@ use ggez::{
 
 use battle_core::{
     entity::soldier::{Soldier, WeaponClass},
-    game::squad::{squad_positions, Formation},
-    order::{marker::OrderMarker, Order, PendingOrder},
+    order::{arc::FireArc, marker::OrderMarker, Order, PendingOrder},
     physics::{utils::DISTANCE_TO_METERS_COEFFICIENT, visibility::Visibility},
     types::*,
 };
@@ -60,9 +59,11 @@ impl Engine {
             let cursor_immobile_since =
                 self.gui_state.frame_i() - self.gui_state.last_cursor_move_frame();
             if cursor_immobile_since >= 15 {
-                for (member_id, formation_position) in
-                    squad_positions(squad, Formation::Line, leader, Some(cursor))
-                {
+                for (member_id, formation_position) in self.battle_state.squad_formation_positions(
+                    &self.server_config,
+                    squad_index,
+                    Some(cursor),
+                ) {
                     let soldier = self.battle_state.soldier(member_id);
                     let (sprites, _) = self.graphics.soldier_sprites(
                         soldier,
@@ -286,6 +287,37 @@ impl Engine {
             self.generate_select_rectangle_meshes(mesh_builder)?;
         }
 
+        if self.battle_state.phase().is_placement() {
+            self.generate_spawn_zones_meshes(mesh_builder)?;
+        }
+
+        Ok(())
+    }
+
+    /// Spawn zones can be polygons : draw their outline over (rectangular) background parts
+    pub fn generate_spawn_zones_meshes(&self, mesh_builder: &mut MeshBuilder) -> GameResult {
+        let (allowed_control, _) = self.zone_controls();
+        for spawn_zone in self
+            .battle_state
+            .map()
+            .find_spawn_zones(allowed_control.spawn_zone_names())
+        {
+            let mut points: Vec<Vec2> = spawn_zone
+                .polygon()
+                .points()
+                .iter()
+                .map(|point| {
+                    self.gui_state
+                        .window_point_from_world_point(*point)
+                        .to_vec2()
+                })
+                .collect();
+            if let Some(first) = points.first().copied() {
+                points.push(first);
+                mesh_builder.line(&points, 1.0, Color::WHITE)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -399,6 +431,19 @@ impl Engine {
                         .scale(self.gui_state.zoom.to_vec2()),
                 );
 
+                if let Some(fire_arc) =
+                    self.fire_arc_from_cursor_and_squad(*pending_order.squad_index())
+                {
+                    let mut color = self.graphics.order_leg_color(&OrderMarker::Defend);
+                    color.a = 0.6;
+                    self.generate_fire_arc_meshes(
+                        mesh_builder,
+                        &self.pending_order_start_point(*pending_order.squad_index()),
+                        &fire_arc,
+                        color,
+                    )?;
+                }
+
                 if pending_order.is_hide() {
                     let radius = ((self.server_config.hide_maximum_rayon.millimeters() as f32
                         / DISTANCE_TO_METERS_COEFFICIENT)
@@ -450,6 +495,15 @@ impl Engine {
                     )?;
                 }
 
+                if pending_order_marker == OrderMarker::AreaFire {
+                    self.generate_area_fire_meshes(
+                        mesh_builder,
+                        &self.gui_state.current_cursor_world_point(),
+                        &self.server_config.area_fire_radius,
+                        Color::RED,
+                    )?;
+                }
+
                 draw_params.push(self.graphics.order_marker_draw_params(
                     &pending_order_marker,
                     *to_point,
@@ -470,7 +524,7 @@ impl Engine {
             PendingOrder::Hide(_) => OrderMarker::Hide,
             PendingOrder::EngageOrFire(_) => {
                 let cursor_point = self.gui_state.current_cursor_world_point();
-                if self
+                if !self
                     .get_opponent_soldiers_at_point(cursor_point)
                     .iter()
                     .filter(|s| s.can_be_designed_as_target())
@@ -479,10 +533,11 @@ impl Engine {
                             .soldier_is_visible_by_side(s, self.gui_state.side())
                     })
                     .collect::<Vec<&&Soldier>>()
-                    .first()
-                    .is_some()
+                    .is_empty()
                 {
                     OrderMarker::EngageSquad
+                } else if self.gui_state.area_fire() {
+                    OrderMarker::AreaFire
                 } else {
                     OrderMarker::SuppressFire
                 }
@@ -490,6 +545,190 @@ impl Engine {
         }
     }
 
+    /// Draw fire arcs of defending squads and zones of area fire orders
+    pub fn generate_fire_zones_meshes(&self, mesh_builder: &mut MeshBuilder) -> GameResult {
+        for (squad_uuid, order) in self.battle_state.all_orders(self.gui_state.side()) {
+            let Some(order_marker) = order.marker() else {
+                continue;
+            };
+            let mut color = self.graphics.order_leg_color(&order_marker);
+            color.a = 0.4;
+
+            match order {
+                Order::Defend(_, Some(fire_arc)) => {
+                    let leader = self
+                        .battle_state
+                        .soldier(self.battle_state.squad(squad_uuid).leader());
+                    self.generate_fire_arc_meshes(
+                        mesh_builder,
+                        &leader.world_point(),
+                        fire_arc,
+                        color,
+                    )?;
+                }
+                Order::AreaFire(center, radius) => {
+                    self.generate_area_fire_meshes(mesh_builder, center, radius, color)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn generate_fire_arc_meshes(
+        &self,
+        mesh_builder: &mut MeshBuilder,
+        from: &WorldPoint,
+        fire_arc: &FireArc,
+        color: Color,
+    ) -> GameResult {
+        let range = (fire_arc.range.millimeters() as f32 / DISTANCE_TO_METERS_COEFFICIENT) / 1000.;
+        let steps = 16;
+        let mut points = vec![self
+            .gui_state
+            .window_point_from_world_point(*from)
+            .to_vec2()];
+        for i in 0..=steps {
+            let angle = fire_arc.left.0 + fire_arc.width() * i as f32 / steps as f32;
+            // Angles are north oriented
+            let point = from.apply(angle.sin() * range, -angle.cos() * range);
+            points.push(
+                self.gui_state
+                    .window_point_from_world_point(point)
+                    .to_vec2(),
+            );
+        }
+        points.push(points[0]);
+
+        mesh_builder.line(&points, 2.0, color)?;
+        Ok(())
+    }
+
+    pub fn generate_area_fire_meshes(
+        &self,
+        mesh_builder: &mut MeshBuilder,
+        center: &WorldPoint,
+        radius: &Distance,
+        color: Color,
+    ) -> GameResult {
+        let radius = ((radius.millimeters() as f32 / DISTANCE_TO_METERS_COEFFICIENT) / 1000.)
+            * self.gui_state.zoom.factor();
+        mesh_builder.circle(
+            DrawMode::Stroke(StrokeOptions::default()),
+            self.gui_state
+                .window_point_from_world_point(*center)
+                .to_vec2(),
+            radius,
+            1.0,
+            color,
+        )?;
+        Ok(())
+    }
+
+    /// Display fire options of pending orders near cursor
+    pub fn draw_pending_order_texts(&self, canvas: &mut Canvas) -> GameResult {
+        for pending_order in self.gui_state.pending_order() {
+            let text = match pending_order {
+                PendingOrder::Defend(squad_index) => {
+                    match self.fire_arc_from_cursor_and_squad(*squad_index) {
+                        Some(fire_arc) => format!(
+                            "Arc (A): {} - {}m",
+                            self.gui_state.fire_arc_mode(),
+                            fire_arc.range.meters()
+                        ),
+                        None => format!("Arc (A): {}", self.gui_state.fire_arc_mode()),
+                    }
+                }
+                PendingOrder::EngageOrFire(_) => {
+                    if self.pending_order_marker(pending_order) == OrderMarker::EngageSquad {
+                        continue;
+                    }
+                    if self.gui_state.area_fire() {
+                        "Area fire".to_string()
+                    } else {
+                        "Area fire (Shift)".to_string()
+                    }
+                }
+                _ => continue,
+            };
+
+            canvas.draw(
+                &Text::new(TextFragment::new(text).color(Color::WHITE)),
+                DrawParam::default().dest(
+                    self.gui_state
+                        .current_cursor_window_point()
+                        .apply(15., 15.)
+                        .to_vec2(),
+                ),
+            );
+            // Only one text for all pending orders
+            break;
+        }
+
+        Ok(())
+    }
+
+    /// Draw current and queued orders of squads having queued orders, each leg with its color
+    pub fn generate_order_legs_meshes(&self, mesh_builder: &mut MeshBuilder) -> GameResult {
+        for (squad_uuid, current_order) in self.battle_state.all_orders(self.gui_state.side()) {
+            let queue = self.battle_state.squad_order_queue(&squad_uuid);
+            if queue.is_empty() {
+                continue;
+            }
+
+            let leader = self
+                .battle_state
+                .soldier(self.battle_state.squad(squad_uuid).leader());
+            let mut from = leader.world_point();
+            let queued = queue.iter().map(|(_, order)| order.clone());
+            for leg in std::iter::once(current_order.clone()).chain(queued) {
+                let Some(order_marker) = leg.marker() else {
+                    continue;
+                };
+
+                let mut points = vec![from];
+                match &leg {
+                    Order::MoveTo(paths, _)
+                    | Order::MoveFastTo(paths, _)
+                    | Order::SneakTo(paths, _) => {
+                        points.extend(paths.paths.iter().flat_map(|path| path.points.clone()))
+                    }
+                    Order::EngageSquad(squad_index) => points.push(
+                        self.battle_state
+                            .soldier(self.battle_state.squad(*squad_index).leader())
+                            .world_point(),
+                    ),
+                    Order::SuppressFire(point) | Order::AreaFire(point, _) => points.push(*point),
+                    Order::Idle | Order::Defend(_, _) | Order::Hide(_) => {}
+                }
+
+                if points.len() > 1 {
+                    let mut color = self.graphics.order_leg_color(&order_marker);
+                    color.a = 0.6;
+                    mesh_builder.line(
+                        &points
+                            .iter()
+                            .map(|point| {
+                                self.gui_state
+                                    .window_point_from_world_point(*point)
+                                    .to_vec2()
+                            })
+                            .collect::<Vec<Vec2>>(),
+                        2.0,
+                        color,
+                    )?;
+                }
+
+                if let Some(end_point) = leg.end_point() {
+                    from = end_point;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn generate_order_marker_sprites(
         &self,
         order: &Order,
@@ -1,5 +1,8 @@
-use battle_core::state::battle::phase::Phase;
-use ggegui::egui::{Align, Align2, Layout, Vec2, Window};
+use battle_core::{
+    game::Side,
+    state::battle::{phase::Phase, report::BattleReport},
+};
+use ggegui::egui::{Align, Align2, Grid, Layout, Vec2, Window};
 use ggez::{Context, GameResult};
 
 use super::{gui::EGUI_SCALE, message::EngineMessage, Engine};
@@ -23,6 +26,13 @@ impl Engine {
 
             let winner = victorious.to_string();
             let reason = end_reason.to_string();
+            if self.battle_report.is_none() {
+                self.battle_report = Some(BattleReport::from_battle_state(&self.battle_state));
+            }
+            let report = self
+                .battle_report
+                .as_ref()
+                .expect("Battle report computed just before");
 
             Window::new("End of battle")
                 .collapsible(false)
@@ -33,10 +43,84 @@ impl Engine {
                         "Battle is end : {} winning by {} victory.",
                         winner, reason
                     ));
+                    ui.separator();
+
+                    Grid::new("squads_report")
+                        .num_columns(8)
+                        .spacing([20.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in [
+                                "Side",
+                                "Squad",
+                                "Members",
+                                "Dead",
+                                "Unconscious",
+                                "Kills",
+                                "Ammunition",
+                                "Routed",
+                            ] {
+                                ui.label(header);
+                            }
+                            ui.end_row();
+
+                            for squad in &report.squads {
+                                ui.label(squad.side.to_string());
+                                ui.label(format!("{} ({})", squad.type_.name(), squad.squad.0));
+                                ui.label(squad.members.to_string());
+                                ui.label(squad.dead.to_string());
+                                ui.label(squad.unconscious.to_string());
+                                ui.label(squad.kills.to_string());
+                                ui.label(squad.ammunition_spent.to_string());
+                                ui.label(if squad.routed { "yes" } else { "no" });
+                                ui.end_row();
+                            }
+                        });
+                    ui.separator();
+
+                    ui.label(format!(
+                        "Flags captured : A {}, B {}",
+                        report.flags_captured(&Side::A),
+                        report.flags_captured(&Side::B)
+                    ));
+                    ui.label(format!(
+                        "Vehicles destroyed : {}",
+                        report.vehicles_destroyed()
+                    ));
+                    ui.separator();
+
                     ui.with_layout(Layout::right_to_left(Align::TOP), |ui| {
                         if ui.button("Quit").clicked() {
                             messages.push(EngineMessage::Exit)
                         }
+                        if ui.button("Export CSV").clicked() {
+                            match self.save_battle_report_csv(report) {
+                                Ok(paths) => {
+                                    for path in paths {
+                                        println!("Battle report exported to {}", path.display())
+                                    }
+                                }
+                                Err(error) => {
+                                    eprintln!(
+                                        "Error happen during battle report export : {}",
+                                        error
+                                    )
+                                }
+                            }
+                        }
+                        if ui.button("Export JSON").clicked() {
+                            match self.save_battle_report_json(report) {
+                                Ok(path) => {
+                                    println!("Battle report exported to {}", path.display())
+                                }
+                                Err(error) => {
+                                    eprintln!(
+                                        "Error happen during battle report export : {}",
+                                        error
+                                    )
+                                }
+                            }
+                        }
                     })
                 });
 
@@ -1,4 +1,5 @@
 use battle_core::{
+    audio::Sound,
     game::Side,
     state::battle::{
         message::BattleStateMessage,
@@ -24,6 +25,32 @@ impl Engine {
             }
             HudEvent::SelectSoldier(soldier_index) => self.select_soldier(&soldier_index),
             HudEvent::CenterMapOnSquad(squad_id) => self.center_screen_on_squad(&squad_id),
+            HudEvent::RallySquad(squad_id) => self.rally_squad(&squad_id),
+            HudEvent::SetOrderTriggerMode(order_trigger_mode) => {
+                vec![
+                    EngineMessage::GuiState(GuiStateMessage::SetOrderTriggerMode(
+                        order_trigger_mode,
+                    )),
+                    EngineMessage::PlaySound(Sound::Clic1),
+                ]
+            }
+            HudEvent::SetSquadFormation(squad_id, formation) => {
+                vec![
+                    EngineMessage::BattleState(BattleStateMessage::SetSquadFormation(
+                        squad_id, formation,
+                    )),
+                    EngineMessage::PlaySound(Sound::Clic1),
+                ]
+            }
+            HudEvent::SetSquadFireDiscipline(squad_id, fire_discipline) => {
+                vec![
+                    EngineMessage::BattleState(BattleStateMessage::SetSquadFireDiscipline(
+                        squad_id,
+                        fire_discipline,
+                    )),
+                    EngineMessage::PlaySound(Sound::Clic1),
+                ]
+            }
         }
     }
 
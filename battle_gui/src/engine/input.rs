@@ -2,12 +2,10 @@ use std::fmt::Display;
 
 use battle_core::{
     audio::Sound,
+    state::battle::message::BattleStateMessage,
     types::{Offset, WindowPoint},
 };
-use ggez::{
-    event::MouseButton, input::keyboard::KeyInput,
-    winit::event::VirtualKeyCode, Context,
-};
+use ggez::{event::MouseButton, input::keyboard::KeyInput, winit::event::VirtualKeyCode, Context};
 
 use crate::{
     debug::DebugPhysics,
@@ -142,6 +140,20 @@ impl Engine {
             )))
         }
 
+        if input.keycode == Some(VirtualKeyCode::LAlt)
+            || input.keycode == Some(VirtualKeyCode::RAlt)
+        {
+            messages.push(EngineMessage::GuiState(GuiStateMessage::SetQueueOrders(
+                true,
+            )))
+        }
+
+        if input.keycode == Some(VirtualKeyCode::LShift)
+            || input.keycode == Some(VirtualKeyCode::RShift)
+        {
+            messages.push(EngineMessage::GuiState(GuiStateMessage::SetAreaFire(true)))
+        }
+
         messages
     }
 
@@ -155,6 +167,31 @@ impl Engine {
             Some(VirtualKeyCode::T) => {
                 messages.push(EngineMessage::SwitchDecorDisplay);
             }
+            Some(VirtualKeyCode::F) => {
+                for squad_id in &self.gui_state.selected_squads().1 {
+                    messages.push(EngineMessage::BattleState(
+                        BattleStateMessage::SetSquadFormation(
+                            *squad_id,
+                            self.battle_state.squad_formation(squad_id).next(),
+                        ),
+                    ));
+                }
+            }
+            Some(VirtualKeyCode::H) => {
+                for squad_id in &self.gui_state.selected_squads().1 {
+                    messages.push(EngineMessage::BattleState(
+                        BattleStateMessage::SetSquadFireDiscipline(
+                            *squad_id,
+                            self.battle_state.squad_fire_discipline(squad_id).next(),
+                        ),
+                    ));
+                }
+            }
+            Some(VirtualKeyCode::R) => {
+                for squad_id in &self.gui_state.selected_squads().1 {
+                    messages.extend(self.rally_squad(squad_id));
+                }
+            }
             Some(VirtualKeyCode::F5) => {
                 messages.push(EngineMessage::TryLoadLastSave);
             }
@@ -182,15 +219,42 @@ impl Engine {
             Some(VirtualKeyCode::LControl) | Some(VirtualKeyCode::RControl) => messages.push(
                 EngineMessage::GuiState(GuiStateMessage::SetControl(self.determine_controlling())),
             ),
-            Some(VirtualKeyCode::Escape) => {
-                if !self.gui_state.pending_order().is_empty() {
-                    messages.extend([
-                        EngineMessage::GuiState(GuiStateMessage::SetPendingOrders(vec![])),
-                        EngineMessage::GuiState(GuiStateMessage::SetDisplayPaths(vec![])),
-                        EngineMessage::PlaySound(Sound::Bip1),
-                    ])
+            Some(VirtualKeyCode::LAlt) | Some(VirtualKeyCode::RAlt) => messages.push(
+                EngineMessage::GuiState(GuiStateMessage::SetQueueOrders(false)),
+            ),
+            Some(VirtualKeyCode::LShift) | Some(VirtualKeyCode::RShift) => {
+                messages.push(EngineMessage::GuiState(GuiStateMessage::SetAreaFire(false)))
+            }
+            Some(VirtualKeyCode::A) => messages.push(EngineMessage::GuiState(
+                GuiStateMessage::SetFireArcMode(self.gui_state.fire_arc_mode().next()),
+            )),
+            // Cancel last queued order of selected squads
+            Some(VirtualKeyCode::Back) => {
+                for squad_id in &self.gui_state.selected_squads().1 {
+                    if let Some((id, _)) = self.battle_state.squad_order_queue(squad_id).last() {
+                        messages.push(EngineMessage::BattleState(
+                            BattleStateMessage::RemoveSquadOrder(*squad_id, *id),
+                        ));
+                    }
                 }
             }
+            Some(VirtualKeyCode::W) => messages.push(EngineMessage::GuiState(
+                GuiStateMessage::SetOrderTriggerMode(self.gui_state.order_trigger_mode().next()),
+            )),
+            Some(VirtualKeyCode::Escape)
+                if !self.gui_state.orders_awaiting_trigger_squad().is_empty() =>
+            {
+                messages.extend([
+                    EngineMessage::GuiState(GuiStateMessage::ClearOrdersAwaitingTriggerSquad),
+                    EngineMessage::PlaySound(Sound::Bip1),
+                ])
+            }
+            Some(VirtualKeyCode::Escape) if !self.gui_state.pending_order().is_empty() => messages
+                .extend([
+                    EngineMessage::GuiState(GuiStateMessage::SetPendingOrders(vec![])),
+                    EngineMessage::GuiState(GuiStateMessage::SetDisplayPaths(vec![])),
+                    EngineMessage::PlaySound(Sound::Bip1),
+                ]),
             _ => {}
         };
 
@@ -302,6 +366,7 @@ impl Engine {
                     && !opened_squad_menu
                     && !have_pending_order
                 {
+                    let mut order_marker_found = false;
                     for (order, order_marker, squad_id, world_point, order_marker_i) in
                         self.battle_state.order_markers(self.gui_state.side())
                     {
@@ -321,6 +386,29 @@ impl Engine {
                             messages.push(EngineMessage::GuiState(
                                 GuiStateMessage::SetPendingOrders(vec![pending_order]),
                             ));
+                            order_marker_found = true;
+                        }
+                    }
+
+                    // Queued order marker under cursor means edit it
+                    if !order_marker_found {
+                        if let Some((order_marker, squad_id, leg_id)) =
+                            self.queued_order_marker_under_cursor()
+                        {
+                            let pending_order = self.create_pending_order_from_order_marker(
+                                &order_marker,
+                                &squad_id,
+                                &None,
+                                &[],
+                            );
+                            messages.extend(vec![
+                                EngineMessage::GuiState(GuiStateMessage::SetPendingOrders(vec![
+                                    pending_order,
+                                ])),
+                                EngineMessage::GuiState(GuiStateMessage::SetEditingQueuedOrder(
+                                    Some((squad_id, leg_id)),
+                                )),
+                            ]);
                         }
                     }
 
@@ -9,7 +9,10 @@ impl Engine {
     pub fn tick_interiors(&mut self) -> Vec<EngineMessage> {
         puffin::profile_scope!("tick_interiors");
         let messages = vec![];
-        let tick_update = self.gui_state.frame_i() % self.config.interiors_update_freq == 0;
+        let tick_update = self
+            .gui_state
+            .frame_i()
+            .is_multiple_of(self.config.interiors_update_freq);
 
         if tick_update {
             self.update_interior_sprites();
@@ -23,12 +26,6 @@ impl Engine {
             .clear_map_interiors_batch(&self.gui_state.zoom);
 
         for interior in self.battle_state.map().interiors() {
-            // World coordinates
-            let start_x = interior.x();
-            let start_y = interior.y();
-            let end_x = start_x + interior.width();
-            let end_y = start_y + interior.height();
-
             for soldier in self.battle_state.soldiers() {
                 let can_see_interior = soldier.can_see_interior();
                 let is_current_side = soldier.side() == self.gui_state.side();
@@ -37,27 +34,20 @@ impl Engine {
                     continue;
                 }
 
-                let soldier_position = soldier.world_point();
-                if soldier_position.x >= start_x
-                    && soldier_position.x <= end_x
-                    && soldier_position.y >= start_y
-                    && soldier_position.y <= end_y
-                {
-                    self.graphics.interiors_mut().push(
-                        &self.gui_state.zoom,
-                        DrawParam::new()
-                            .src(Rect::new(
-                                interior.relative_x(),
-                                interior.relative_y(),
-                                interior.relative_width(),
-                                interior.relative_height(),
-                            ))
-                            .dest(
-                                WorldPoint::new(start_x, start_y).to_vec2()
-                                    * self.gui_state.zoom.factor(),
-                            ),
-                    );
-                    continue;
+                if interior.contains(&soldier.world_point()) {
+                    // Reveal interior polygon only, by strips
+                    for (world, relative) in interior.reveal_parts() {
+                        self.graphics.interiors_mut().push(
+                            &self.gui_state.zoom,
+                            DrawParam::new()
+                                .src(Rect::new(relative.x, relative.y, relative.w, relative.h))
+                                .dest(
+                                    WorldPoint::new(world.x, world.y).to_vec2()
+                                        * self.gui_state.zoom.factor(),
+                                ),
+                        );
+                    }
+                    break;
                 }
             }
         }
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use battle_core::{
     audio::Sound,
     config::ChangeConfigMessage,
-    order::PendingOrder,
+    order::{arc::FireArcMode, trigger::OrderTriggerMode, Order, OrderLegId, PendingOrder},
     state::battle::message::BattleStateMessage,
     types::{Offset, SoldierIndex, SquadUuid, WindowPoint, WorldPaths, WorldPoint},
     utils::DebugPoint,
@@ -44,6 +44,13 @@ pub enum GuiStateMessage {
     SetSelectedSquads(Option<SoldierIndex>, Vec<SquadUuid>),
     SetSquadMenu(Option<(WindowPoint, Vec<SquadUuid>)>),
     SetPendingOrders(Vec<PendingOrder>),
+    SetQueueOrders(bool),
+    SetEditingQueuedOrder(Option<(SquadUuid, OrderLegId)>),
+    SetOrderTriggerMode(OrderTriggerMode),
+    PushOrderAwaitingTriggerSquad(SquadUuid, Order),
+    ClearOrdersAwaitingTriggerSquad,
+    SetFireArcMode(FireArcMode),
+    SetAreaFire(bool),
     AddCachePointToPendingOrder(WorldPoint),
     SetDisplayPaths(Vec<Vec<(WorldPaths, SquadUuid)>>),
     PushDebugPoint(DebugPoint),
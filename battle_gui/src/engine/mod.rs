@@ -5,7 +5,7 @@ use battle_core::config::{GuiConfig, ServerConfig};
 use battle_core::game::control::MapControl;
 use battle_core::game::Side;
 use battle_core::message::{InputMessage, OutputMessage};
-use battle_core::state::battle::BattleState;
+use battle_core::state::battle::{report::BattleReport, BattleState};
 use battle_core::types::WindowPoint;
 use crossbeam_channel::{Receiver, Sender};
 use ggegui::Gui;
@@ -65,13 +65,14 @@ pub struct Engine {
     // Debug gui
     debug_gui: DebugGuiState,
     egui_backend: Gui,
-    ///
     hud: Hud,
     a_control: MapControl,
     b_control: MapControl,
     //
     first_copy_loaded: bool,
     when_first_copy_messages: Vec<EngineMessage>,
+    // Computed once, when battle ends
+    battle_report: Option<BattleReport>,
 }
 
 impl Engine {
@@ -99,7 +100,7 @@ impl Engine {
                 .unwrap_or_default(),
         );
 
-        let hud = HudBuilder::new(&gui_state, &battle_state).build(ctx);
+        let hud = HudBuilder::new(&gui_state, &battle_state, &server_config).build(ctx);
         let mut engine = Engine {
             config,
             server_config,
@@ -118,6 +119,7 @@ impl Engine {
             b_control,
             first_copy_loaded: false,
             when_first_copy_messages: when_first_copy_apply,
+            battle_report: None,
         };
         engine.react(apply, ctx)?;
 
@@ -150,7 +152,7 @@ impl EventHandler<ggez::GameError> for Engine {
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
         let mut canvas = Canvas::from_frame(ctx, Color::from((0.392, 0.584, 0.929)));
         let window = ctx.gfx.window().inner_size();
-        self.hud = HudBuilder::new(&self.gui_state, &self.battle_state)
+        self.hud = HudBuilder::new(&self.gui_state, &self.battle_state, &self.server_config)
             .point(WindowPoint::new(0., window.height as f32 - HUD_HEIGHT))
             .width(window.width as f32)
             .height(HUD_HEIGHT)
@@ -194,6 +196,7 @@ impl EventHandler<ggez::GameError> for Engine {
         let ui_draw_param = graphics::DrawParam::new();
         self.graphics
             .draw_ui(ctx, &mut canvas, ui_draw_param, mesh_builder)?;
+        self.draw_pending_order_texts(&mut canvas)?;
 
         self.graphics.draw_minimap(ctx, &mut canvas, &self.hud)?;
         HudPainter::new(&self.hud, &self.gui_state).draw(ctx, &mut canvas)?;
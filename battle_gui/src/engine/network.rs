@@ -1,6 +1,7 @@
 use std::sync::atomic::Ordering;
 
 use battle_core::{
+    game::weapon::definition::WeaponDefinitions,
     message::OutputMessage,
     state::{
         battle::{message::SideEffect, BattleState},
@@ -22,6 +23,10 @@ impl Engine {
             for message in &messages {
                 match message {
                     OutputMessage::LoadFromCopy(copy) => {
+                        if let Err(error) = copy.validate_weapons(WeaponDefinitions::get()) {
+                            eprintln!("Refused battle state copy : {}", error);
+                            continue;
+                        }
                         let mut battle_state =
                             BattleState::from_copy(copy, self.battle_state.map());
                         battle_state.resolve();
@@ -33,6 +38,9 @@ impl Engine {
                                 .map(|soldier| SideEffect::RefreshEntityAnimation(soldier.uuid()))
                                 .collect::<Vec<SideEffect>>(),
                         );
+                        if !copy.terrain_changes().is_empty() {
+                            side_effects.push(SideEffect::RefreshMapTerrain);
+                        }
 
                         self.sync_required.swap(false, Ordering::Relaxed);
                         self.battle_state = battle_state;
@@ -80,7 +88,7 @@ impl Engine {
             }
         }
 
-        self.side_effects(side_effects);
+        self.side_effects(side_effects, ctx)?;
 
         Ok(())
     }
@@ -1,8 +1,10 @@
 use battle_core::{
+    audio::Sound,
     behavior::Behavior,
     entity::soldier::Soldier,
     game::cover::CoverFinder,
-    order::{marker::OrderMarker, Order, PendingOrder},
+    order::{arc::FireArc, marker::OrderMarker, Order, PendingOrder},
+    physics::utils::distance_between_points,
     state::battle::message::{BattleStateMessage, SoldierMessage, VehicleMessage},
     types::*,
     utils::DebugPoint,
@@ -63,7 +65,26 @@ impl Engine {
 
     pub fn create_defend_order(&self, squad_id: SquadUuid) -> Option<Order> {
         let angle = self.angle_from_cursor_and_squad(squad_id);
-        Some(Order::Defend(angle))
+        Some(Order::Defend(
+            angle,
+            self.fire_arc_from_cursor_and_squad(squad_id),
+        ))
+    }
+
+    /// Fire arc centered on cursor direction, with cursor distance (at least configured minimum)
+    /// as range, if a fire arc mode is selected
+    pub fn fire_arc_from_cursor_and_squad(&self, squad_id: SquadUuid) -> Option<FireArc> {
+        let width = self.gui_state.fire_arc_mode().width()?;
+        let angle = self.angle_from_cursor_and_squad(squad_id);
+        let range = Distance::from_millimeters(
+            distance_between_points(
+                &self.pending_order_start_point(squad_id),
+                &self.gui_state.current_cursor_world_point(),
+            )
+            .millimeters()
+            .max(self.server_config.fire_arc_minimum_range.millimeters()),
+        );
+        Some(FireArc::centered(angle, width, range))
     }
 
     pub fn create_hide_order(&self, squad_id: SquadUuid) -> Option<Order> {
@@ -92,6 +113,12 @@ impl Engine {
             // Shoot a hidden point is possible (like fire through a wall)
             self.server_config.visibility_by_last_frame_shoot_distance,
         ) {
+            if self.gui_state.area_fire() {
+                return Some(Order::AreaFire(
+                    world_point,
+                    self.server_config.area_fire_radius,
+                ));
+            }
             return Some(Order::SuppressFire(world_point));
         }
 
@@ -118,14 +145,108 @@ impl Engine {
             OrderMarker::Defend => PendingOrder::Defend(*squad_index),
             OrderMarker::Hide => PendingOrder::Hide(*squad_index),
             OrderMarker::EngageSquad => PendingOrder::EngageOrFire(*squad_index),
-            OrderMarker::SuppressFire => PendingOrder::EngageOrFire(*squad_index),
+            OrderMarker::SuppressFire | OrderMarker::AreaFire => {
+                PendingOrder::EngageOrFire(*squad_index)
+            }
+        }
+    }
+
+    /// Replace edited queued order, set a triggered order, append order to squad order queue
+    /// or, by default, replace squad current order (queued and triggered orders are then
+    /// cancelled)
+    pub fn give_order(&self, squad_uuid: &SquadUuid, order: &Order) -> Vec<EngineMessage> {
+        if self.battle_state.phase().is_battle() {
+            if let Some((editing_squad_uuid, leg_id)) = self.gui_state.editing_queued_order() {
+                if editing_squad_uuid == squad_uuid {
+                    return vec![EngineMessage::BattleState(
+                        BattleStateMessage::ReplaceSquadOrder(*squad_uuid, *leg_id, order.clone()),
+                    )];
+                }
+            }
+
+            let trigger_mode = self.gui_state.order_trigger_mode();
+            if trigger_mode.expect_squad() {
+                // Reference squad will be given by next click
+                return vec![EngineMessage::GuiState(
+                    GuiStateMessage::PushOrderAwaitingTriggerSquad(*squad_uuid, order.clone()),
+                )];
+            }
+            if let Some(trigger) = trigger_mode.trigger(*self.battle_state.frame_i(), None) {
+                return vec![EngineMessage::BattleState(
+                    BattleStateMessage::SetSquadTriggeredOrder(
+                        *squad_uuid,
+                        Some((trigger, order.clone())),
+                    ),
+                )];
+            }
+
+            if self.gui_state.queue_orders() {
+                return vec![EngineMessage::BattleState(
+                    BattleStateMessage::PushSquadOrder(*squad_uuid, order.clone()),
+                )];
+            }
         }
+
+        let squad_leader = self.battle_state.squad(*squad_uuid).leader();
+        [
+            vec![EngineMessage::BattleState(
+                BattleStateMessage::ClearSquadOrders(*squad_uuid),
+            )],
+            self.define_order(&squad_leader, order),
+        ]
+        .concat()
+    }
+
+    /// Set awaiting orders as triggered orders with squad under cursor as trigger reference
+    pub fn define_trigger_squad(&self) -> Vec<EngineMessage> {
+        let mut messages = vec![EngineMessage::GuiState(
+            GuiStateMessage::ClearOrdersAwaitingTriggerSquad,
+        )];
+        let world_point = self.gui_state.current_cursor_world_point();
+        let Some(reference) = self
+            .soldiers_at_point(world_point, Some(self.gui_state.side()))
+            .first()
+            .map(|soldier| soldier.squad_uuid())
+        else {
+            messages.push(EngineMessage::PlaySound(Sound::Bip1));
+            return messages;
+        };
+
+        let frame_i = *self.battle_state.frame_i();
+        for (squad_uuid, order) in self.gui_state.orders_awaiting_trigger_squad() {
+            if let Some(trigger) = self
+                .gui_state
+                .order_trigger_mode()
+                .trigger(frame_i, Some(reference))
+            {
+                messages.push(EngineMessage::BattleState(
+                    BattleStateMessage::SetSquadTriggeredOrder(
+                        *squad_uuid,
+                        Some((trigger, order.clone())),
+                    ),
+                ));
+            }
+        }
+        messages.push(EngineMessage::PlaySound(Sound::Clac1));
+
+        messages
     }
 
     pub fn define_order(&self, squad_leader: &SoldierIndex, order: &Order) -> Vec<EngineMessage> {
+        let squad_uuid = self.battle_state.soldier(*squad_leader).squad_uuid();
+        // A new squad leader need time before his orders are executed
+        let soldier_message = match self
+            .battle_state
+            .squad_command_disrupted_until(&self.server_config, &squad_uuid)
+        {
+            Some(until) if self.battle_state.phase().is_battle() => {
+                SoldierMessage::SetDelayedOrder(order.clone(), until)
+            }
+            _ => SoldierMessage::SetOrder(order.clone()),
+        };
         let mut messages = vec![EngineMessage::BattleState(BattleStateMessage::Soldier(
             *squad_leader,
-            SoldierMessage::SetOrder(order.clone()),
+            soldier_message,
         ))];
 
         if self.battle_state.phase().is_placement() {
@@ -154,7 +275,7 @@ impl Engine {
         order: &Order,
     ) -> Vec<EngineMessage> {
         match order {
-            Order::Defend(angle) | Order::Hide(angle) => {
+            Order::Defend(angle, _) | Order::Hide(angle) => {
                 vec![EngineMessage::BattleState(BattleStateMessage::Vehicle(
                     *vehicle_index,
                     VehicleMessage::SetChassisOrientation(*angle),
@@ -162,6 +283,7 @@ impl Engine {
             }
             Order::EngageSquad(_)
             | Order::SuppressFire(_)
+            | Order::AreaFire(_, _)
             | Order::Idle
             | Order::MoveTo(_, _)
             | Order::MoveFastTo(_, _)
@@ -178,13 +300,12 @@ impl Engine {
         order: &Order,
     ) -> Vec<EngineMessage> {
         match order {
-            Order::Defend(angle) | Order::Hide(angle) => {
+            Order::Defend(angle, _) | Order::Hide(angle) => {
                 let mut messages = vec![];
                 let leader = self.battle_state.soldier(*squad_leader);
-                let squad = self.battle_state.squad(leader.squad_uuid());
                 let (moves, debug_points) =
                     CoverFinder::new(&self.battle_state, &self.server_config)
-                        .find_arbitrary_cover_points(squad, leader);
+                        .find_arbitrary_cover_points(leader);
 
                 // Debug points
                 messages.extend(debug_points.iter().map(|d| {
@@ -224,7 +345,7 @@ impl Engine {
 
                 // Set soldiers behavior
                 let behavior = match order {
-                    Order::Defend(angle) => Behavior::Defend(*angle),
+                    Order::Defend(angle, _) => Behavior::Defend(*angle),
                     Order::Hide(angle) => Behavior::Hide(*angle),
                     _ => unreachable!(),
                 };
@@ -263,6 +384,7 @@ impl Engine {
             }
             Order::EngageSquad(_)
             | Order::SuppressFire(_)
+            | Order::AreaFire(_, _)
             | Order::Idle
             | Order::MoveTo(_, _)
             | Order::MoveFastTo(_, _)
@@ -272,4 +394,37 @@ impl Engine {
             }
         }
     }
+
+    pub fn rally_squad(&self, squad_uuid: &SquadUuid) -> Vec<EngineMessage> {
+        if !self.battle_state.phase().is_battle() {
+            return vec![EngineMessage::PlaySound(Sound::Bip1)];
+        }
+
+        let orders = self
+            .battle_state
+            .squad_rally_orders(&self.server_config, squad_uuid);
+        if orders.is_empty() {
+            return vec![EngineMessage::PlaySound(Sound::Bip1)];
+        }
+
+        let disrupted_until = self
+            .battle_state
+            .squad_command_disrupted_until(&self.server_config, squad_uuid);
+        let mut messages = vec![
+            EngineMessage::PlaySound(Sound::Clac1),
+            EngineMessage::BattleState(BattleStateMessage::ClearSquadOrders(*squad_uuid)),
+        ];
+        for (soldier_index, order) in orders {
+            let soldier_message = match disrupted_until {
+                Some(until) => SoldierMessage::SetDelayedOrder(order, until),
+                None => SoldierMessage::SetOrder(order),
+            };
+            messages.push(EngineMessage::BattleState(BattleStateMessage::Soldier(
+                soldier_index,
+                soldier_message,
+            )));
+        }
+
+        messages
+    }
 }
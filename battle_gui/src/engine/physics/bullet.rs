@@ -22,8 +22,8 @@ impl Engine {
         let mut messages = vec![];
 
         if bullet_fire.start() == self.gui_state.frame_i() {
-            if let Some(sound_type) = bullet_fire.gun_fire_sound_type() {
-                for sound in sound_type.fire_sounds(bullet_fire.shots()) {
+            if let Some(weapon_type) = bullet_fire.gun_fire_sound() {
+                for sound in weapon_type.definition().fire_sounds(bullet_fire.shots()) {
                     messages.push(EngineMessage::PlaySound(sound));
                 }
             }
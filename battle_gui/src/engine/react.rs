@@ -1,6 +1,8 @@
 use battle_core::{message::InputMessage, state::battle::message::SideEffect};
 use ggez::{Context, GameError, GameResult};
 
+use crate::graphics::message::GraphicsMessage;
+
 use super::{message::EngineMessage, Engine};
 
 impl Engine {
@@ -62,8 +64,9 @@ impl Engine {
                 // TODO : manage failures in user display
                 EngineMessage::LoadFromSave(save_path) => {
                     if let Some(copy) = self.load_from_save(&save_path) {
-                        if let Err(error) =
-                            self.output.send(vec![InputMessage::SetBattleState(copy)])
+                        if let Err(error) = self
+                            .output
+                            .send(vec![InputMessage::SetBattleState(Box::new(copy))])
                         {
                             eprintln!(
                                 "Error when try to send battle state copy to server : {}",
@@ -78,8 +81,9 @@ impl Engine {
                     saves.sort();
                     if let Some(save_path) = saves.first() {
                         if let Some(copy) = self.load_from_save(save_path) {
-                            if let Err(error) =
-                                self.output.send(vec![InputMessage::SetBattleState(copy)])
+                            if let Err(error) = self
+                                .output
+                                .send(vec![InputMessage::SetBattleState(Box::new(copy))])
                             {
                                 eprintln!(
                                     "Error when try to send battle state copy to server : {}",
@@ -99,21 +103,31 @@ impl Engine {
             }
         }
 
-        self.side_effects(side_effects);
+        self.side_effects(side_effects, ctx)?;
 
         Ok(())
     }
 
-    pub fn side_effects(&mut self, side_effects: Vec<SideEffect>) {
+    pub fn side_effects(&mut self, side_effects: Vec<SideEffect>, ctx: &mut Context) -> GameResult {
         for side_effect in side_effects {
             match side_effect {
                 SideEffect::RefreshEntityAnimation(soldier_index) => {
                     let soldier = self.battle_state.soldier(soldier_index);
                     self.graphics.refresh_soldier_animation(soldier);
                 }
+                SideEffect::RefreshMapTerrain => {
+                    self.graphics.react(
+                        GraphicsMessage::ReloadMapTerrain,
+                        self.battle_state.map(),
+                        &self.server_config,
+                        ctx,
+                    )?;
+                }
                 // Server side effect
                 SideEffect::SoldierFinishHisBehavior(_, _) => {}
             }
         }
+
+        Ok(())
     }
 }
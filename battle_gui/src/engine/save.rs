@@ -1,7 +1,10 @@
 use std::{fs, path::PathBuf, time::SystemTime};
 
 use anyhow::{Context, Result};
-use battle_core::{deployment::Deployment, sync::BattleStateCopy};
+use battle_core::{
+    deployment::Deployment, game::weapon::definition::WeaponDefinitions,
+    state::battle::report::BattleReport, sync::BattleStateCopy,
+};
 use oc_core::resources::{EnsureDir, Resources};
 
 use crate::saves::writer::BattleStateWriter;
@@ -31,6 +34,10 @@ impl Engine {
     pub fn load_from_save(&self, save: &PathBuf) -> Option<BattleStateCopy> {
         if let Ok(bytes) = fs::read(save) {
             if let Ok(copy) = bincode::deserialize::<BattleStateCopy>(&bytes) {
+                if let Err(error) = copy.validate_weapons(WeaponDefinitions::get()) {
+                    eprintln!("Save {} can't be loaded : {}", save.display(), error);
+                    return None;
+                }
                 return Some(copy);
             }
         }
@@ -57,4 +64,47 @@ impl Engine {
 
         Ok(save_to)
     }
+
+    pub fn save_battle_report_json(&self, report: &BattleReport) -> Result<PathBuf> {
+        let now_ns = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_secs();
+        let save_to = PathBuf::from(format!(
+            "{}_{}_report.json",
+            self.battle_state.map().name(),
+            now_ns
+        ));
+
+        fs::write(&save_to, report.to_json().context("Serialize report")?)
+            .context("Write report file")?;
+
+        Ok(save_to)
+    }
+
+    pub fn save_battle_report_csv(&self, report: &BattleReport) -> Result<Vec<PathBuf>> {
+        let now_ns = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_secs();
+        let prefix = format!("{}_{}", self.battle_state.map().name(), now_ns);
+        let squads_to = PathBuf::from(format!("{}_report_squads.csv", prefix));
+        let events_to = PathBuf::from(format!("{}_report_events.csv", prefix));
+
+        fs::write(&squads_to, report.squads_csv()).context("Write squads report file")?;
+        fs::write(&events_to, report.events_csv()).context("Write events report file")?;
+
+        Ok(vec![squads_to, events_to])
+    }
+
+    pub fn save_server_config(&self) -> Result<PathBuf> {
+        let now_ns = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_secs();
+        let save_to = PathBuf::from(format!("{}_server_config.toml", now_ns));
+
+        self.server_config
+            .write(&save_to)
+            .context("Write server config file")?;
+
+        Ok(save_to)
+    }
 }
@@ -2,7 +2,9 @@ use std::path::PathBuf;
 
 use battle_core::game::Side;
 use battle_core::map::Map;
-use battle_core::order::PendingOrder;
+use battle_core::order::arc::FireArcMode;
+use battle_core::order::trigger::OrderTriggerMode;
+use battle_core::order::{Order, OrderLegId, PendingOrder};
 use battle_core::physics::utils::DISTANCE_TO_METERS_COEFFICIENT;
 use battle_core::types::{
     Distance, Offset, SoldierIndex, SquadUuid, WindowPoint, WorldPaths, WorldPoint,
@@ -65,6 +67,18 @@ pub struct GuiState {
     squad_menu: Option<(WindowPoint, Vec<SquadUuid>)>,
     /// Possible current player squad order
     pending_order: Vec<PendingOrder>,
+    /// Pending orders must be appended to squad order queue
+    queue_orders: bool,
+    /// Queued order (squad and queue index) currently edited by pending order
+    editing_queued_order: Option<(SquadUuid, OrderLegId)>,
+    /// Trigger to apply on next given orders
+    order_trigger_mode: OrderTriggerMode,
+    /// Given orders waiting for player to click on trigger reference squad
+    orders_awaiting_trigger_squad: Vec<(SquadUuid, Order)>,
+    /// Fire arc width to apply on next given defend orders
+    fire_arc_mode: FireArcMode,
+    /// Fire orders on a point must suppress a zone around it
+    area_fire: bool,
     /// Paths to display
     display_paths: Vec<Vec<(WorldPaths, SquadUuid)>>,
     /// Used to know a path already search here last frame
@@ -74,7 +88,6 @@ pub struct GuiState {
     /// Contains current control mode
     control: Control,
     cursor_in_hud: bool,
-    ///
     begin_click_on_soldier: Option<SoldierIndex>,
     dragged_squad: Option<SquadUuid>,
     //
@@ -117,6 +130,12 @@ impl GuiState {
             selected_squads: (None, vec![]),
             squad_menu: None,
             pending_order: vec![],
+            queue_orders: false,
+            editing_queued_order: None,
+            order_trigger_mode: OrderTriggerMode::default(),
+            orders_awaiting_trigger_squad: vec![],
+            fire_arc_mode: FireArcMode::default(),
+            area_fire: false,
             display_paths: vec![],
             last_computed_path_point: None,
             debug_points: vec![],
@@ -257,6 +276,30 @@ impl GuiState {
         &self.pending_order
     }
 
+    pub fn queue_orders(&self) -> bool {
+        self.queue_orders
+    }
+
+    pub fn editing_queued_order(&self) -> &Option<(SquadUuid, OrderLegId)> {
+        &self.editing_queued_order
+    }
+
+    pub fn order_trigger_mode(&self) -> &OrderTriggerMode {
+        &self.order_trigger_mode
+    }
+
+    pub fn orders_awaiting_trigger_squad(&self) -> &Vec<(SquadUuid, Order)> {
+        &self.orders_awaiting_trigger_squad
+    }
+
+    pub fn fire_arc_mode(&self) -> &FireArcMode {
+        &self.fire_arc_mode
+    }
+
+    pub fn area_fire(&self) -> bool {
+        self.area_fire
+    }
+
     pub fn display_paths(&self) -> &Vec<Vec<(WorldPaths, SquadUuid)>> {
         &self.display_paths
     }
@@ -349,9 +392,40 @@ impl GuiState {
                 self.squad_menu = squad_menu.clone()
             }
             GuiStateMessage::SetPendingOrders(pending_orders) => {
-                //
+                if pending_orders.is_empty() {
+                    self.editing_queued_order = None;
+                }
                 self.pending_order = pending_orders.clone()
             }
+            GuiStateMessage::SetQueueOrders(value) => {
+                //
+                self.queue_orders = *value
+            }
+            GuiStateMessage::SetEditingQueuedOrder(editing_queued_order) => {
+                //
+                self.editing_queued_order = *editing_queued_order
+            }
+            GuiStateMessage::SetOrderTriggerMode(order_trigger_mode) => {
+                //
+                self.order_trigger_mode = *order_trigger_mode
+            }
+            GuiStateMessage::PushOrderAwaitingTriggerSquad(squad_uuid, order) => {
+                //
+                self.orders_awaiting_trigger_squad
+                    .push((*squad_uuid, order.clone()))
+            }
+            GuiStateMessage::ClearOrdersAwaitingTriggerSquad => {
+                //
+                self.orders_awaiting_trigger_squad.clear()
+            }
+            GuiStateMessage::SetFireArcMode(fire_arc_mode) => {
+                //
+                self.fire_arc_mode = *fire_arc_mode
+            }
+            GuiStateMessage::SetAreaFire(value) => {
+                //
+                self.area_fire = *value
+            }
             GuiStateMessage::SetDisplayPaths(display_paths) => {
                 //
                 self.display_paths = display_paths.clone();
@@ -229,6 +229,17 @@ impl Engine {
             self.graphics.extend_ui_batch(sprites);
         }
 
+        for (order, order_marker, _squad_id, point, _leg_id) in self
+            .battle_state
+            .queued_order_markers(self.gui_state.side())
+        {
+            let window_point = self.gui_state.window_point_from_world_point(point);
+            let sprites = self.generate_order_marker_sprites(&order, &order_marker, window_point);
+            self.graphics.extend_ui_batch(sprites);
+        }
+        self.generate_order_legs_meshes(mesh_builder)?;
+        self.generate_fire_zones_meshes(mesh_builder)?;
+
         Ok(())
     }
 
@@ -288,6 +299,31 @@ impl Engine {
                     };
                 }
                 UIEvent::FinishedCursorRightClick(point) => {
+                    // Right click cancel orders awaiting trigger squad
+                    if !self.gui_state.orders_awaiting_trigger_squad().is_empty() {
+                        messages.extend([
+                            EngineMessage::GuiState(
+                                GuiStateMessage::ClearOrdersAwaitingTriggerSquad,
+                            ),
+                            EngineMessage::PlaySound(Sound::Bip1),
+                        ]);
+                        continue;
+                    }
+
+                    // Right click on a queued order marker cancel it
+                    if self.gui_state.pending_order().is_empty() {
+                        if let Some((_, squad_id, leg_id)) = self.queued_order_marker_under_cursor()
+                        {
+                            messages.extend([
+                                EngineMessage::BattleState(BattleStateMessage::RemoveSquadOrder(
+                                    squad_id, leg_id,
+                                )),
+                                EngineMessage::PlaySound(Sound::Clic1),
+                            ]);
+                            continue;
+                        }
+                    }
+
                     let world_point = self.gui_state.world_point_from_window_point(point);
                     let soldiers = self.soldiers_at_point(world_point, Some(self.gui_state.side()));
                     let mut squad_ids: Vec<SquadUuid> = vec![];
@@ -352,7 +388,7 @@ impl Engine {
 
                     for (squad_id, order_marker_index, cached_points) in draw_path_findings {
                         let (path_mode, start_direction) =
-                            self.battle_state.squad_path_mode_and_direction(squad_id);
+                            self.pending_order_path_mode_and_direction(squad_id);
 
                         if let Some(world_paths) = self.create_path_finding(
                             squad_id,
@@ -390,6 +426,11 @@ impl Engine {
     ) -> Vec<EngineMessage> {
         let mut messages = vec![];
 
+        // Click is the choice of trigger reference squad for awaiting orders
+        if !self.gui_state.orders_awaiting_trigger_squad().is_empty() {
+            return self.define_trigger_squad();
+        }
+
         if let Some((squad_menu_point, squads)) = self.gui_state.squad_menu() {
             messages.extend(self.digest_squad_menu_select_by_click(
                 &point,
@@ -415,14 +456,10 @@ impl Engine {
             } else {
                 // If order produced, push it on shared state
                 if let Some(order) = self.order_from_pending_order(pending_order) {
-                    let squad_leader = self
-                        .battle_state
-                        .squad(*pending_order.squad_index())
-                        .leader();
                     messages.extend(
                         [
                             vec![EngineMessage::PlaySound(Sound::Clac1)],
-                            self.define_order(&squad_leader, &order),
+                            self.give_order(pending_order.squad_index(), &order),
                         ]
                         .concat(),
                     )
@@ -479,14 +516,10 @@ impl Engine {
         if !self.gui_state.pending_order().is_empty() {
             for pending_order in self.gui_state.pending_order() {
                 if let Some(order_) = self.order_from_pending_order(pending_order) {
-                    let squad_leader = self
-                        .battle_state
-                        .squad(*pending_order.squad_index())
-                        .leader();
                     messages.extend(
                         [
                             vec![EngineMessage::PlaySound(Sound::Clac1)],
-                            self.define_order(&squad_leader, &order_),
+                            self.give_order(pending_order.squad_index(), &order_),
                         ]
                         .concat(),
                     )
@@ -572,7 +605,7 @@ impl Engine {
         let (moves, debug_points) = CoverFinder::new(&self.battle_state, &self.server_config)
             .point(Some(*point))
             .exclude_grid_points(vec![cursor_grid_point])
-            .find_arbitrary_cover_points(squad, leader);
+            .find_arbitrary_cover_points(leader);
 
         messages.push(EngineMessage::BattleState(BattleStateMessage::Soldier(
             squad.leader(),
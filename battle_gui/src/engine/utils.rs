@@ -6,7 +6,7 @@ use battle_core::{
     behavior::BehaviorMode,
     entity::{soldier::Soldier, vehicle::OnBoardPlace},
     game::Side,
-    order::{marker::OrderMarker, Order},
+    order::{marker::OrderMarker, Order, OrderLegId, PendingOrder},
     physics::path::{find_path, Direction, PathMode},
     types::*,
     utils::{Rect as CoreRect, WorldShape},
@@ -100,13 +100,11 @@ impl Engine {
         path_mode: &PathMode,
         start_direction: &Option<Direction>,
     ) -> Option<WorldPaths> {
-        let squad = self.battle_state.squad(squad_id);
-        let soldier = self.battle_state.soldier(squad.leader());
-        let soldier_world_point = soldier.world_point();
-        let soldier_grid_point = self
+        let start_world_point = self.pending_order_start_point(squad_id);
+        let start_grid_point = self
             .battle_state
             .map()
-            .grid_point_from_world_point(&soldier_world_point);
+            .grid_point_from_world_point(&start_world_point);
         let cursor_world_point = self.gui_state.current_cursor_world_point();
         let cursor_grid_point = self
             .battle_state
@@ -154,11 +152,11 @@ impl Engine {
                 }
                 bounds_
             } else {
-                vec![(soldier_grid_point, cursor_grid_point)]
+                vec![(start_grid_point, cursor_grid_point)]
             }
         // Some points already cached (append)
         } else if cached_points.len() > 1 {
-            let mut last = soldier_grid_point;
+            let mut last = start_grid_point;
             let mut bounds_ = vec![];
             for cached_point in cached_points {
                 let grid_cached_point = self
@@ -172,13 +170,14 @@ impl Engine {
             bounds_
         // First point
         } else {
-            vec![(soldier_grid_point, cursor_grid_point)]
+            vec![(start_grid_point, cursor_grid_point)]
         };
 
         // Build path finding on each parts
         let mut world_paths = vec![];
         for (bound_start, bound_end) in bounds {
             if let Some(grid_points_path) = find_path(
+                &self.server_config,
                 self.battle_state.map(),
                 &bound_start,
                 &bound_end,
@@ -251,8 +250,7 @@ impl Engine {
         }
 
         // Else, create a path
-        let (path_mode, start_direction) =
-            self.battle_state.squad_path_mode_and_direction(*squad_id);
+        let (path_mode, start_direction) = self.pending_order_path_mode_and_direction(*squad_id);
         self.create_path_finding(
             *squad_id,
             order_marker_index,
@@ -262,14 +260,78 @@ impl Engine {
         )
     }
 
+    /// Squad path mode, considering sneaking soldiers can't cross some terrain
+    pub fn pending_order_path_mode_and_direction(
+        &self,
+        squad_id: SquadUuid,
+    ) -> (PathMode, Option<Direction>) {
+        let (path_mode, start_direction) =
+            self.battle_state.squad_path_mode_and_direction(squad_id);
+        let sneaking = self.gui_state.pending_order().iter().any(|pending_order| {
+            matches!(pending_order, PendingOrder::SneakTo(pending_squad_id, _, _)
+                if *pending_squad_id == squad_id)
+        });
+
+        match path_mode {
+            PathMode::Walk if sneaking => (PathMode::Sneak, start_direction),
+            _ => (path_mode, start_direction),
+        }
+    }
+
     pub fn angle_from_cursor_and_squad(&self, squad_id: SquadUuid) -> Angle {
-        let squad = self.battle_state.squad(squad_id);
-        let squad_leader = self.battle_state.soldier(squad.leader());
         let to_point = self.gui_state.current_cursor_world_point().to_vec2();
-        let from_point = squad_leader.world_point().to_vec2();
+        let from_point = self.pending_order_start_point(squad_id).to_vec2();
         Angle::from_points(&to_point, &from_point)
     }
 
+    /// Point from where pending order of given squad begins : squad leader position, or end of
+    /// previous order leg when editing or appending to squad order queue
+    pub fn pending_order_start_point(&self, squad_id: SquadUuid) -> WorldPoint {
+        if self.battle_state.phase().is_battle() {
+            if let Some((editing_squad_id, leg_id)) = self.gui_state.editing_queued_order() {
+                if *editing_squad_id == squad_id {
+                    // Edited leg can have been given meanwhile
+                    if let Some(leg_i) = self.battle_state.squad_order_leg_index(&squad_id, leg_id)
+                    {
+                        return self
+                            .battle_state
+                            .squad_order_leg_start_point(&squad_id, leg_i);
+                    }
+                }
+            }
+
+            if self.gui_state.queue_orders() {
+                let queue_len = self.battle_state.squad_order_queue(&squad_id).len();
+                return self
+                    .battle_state
+                    .squad_order_leg_start_point(&squad_id, queue_len);
+            }
+        }
+
+        let squad = self.battle_state.squad(squad_id);
+        self.battle_state.soldier(squad.leader()).world_point()
+    }
+
+    pub fn queued_order_marker_under_cursor(&self) -> Option<(OrderMarker, SquadUuid, OrderLegId)> {
+        let cursor = self.gui_state.current_cursor_window_point();
+        for (order, order_marker, squad_id, world_point, leg_id) in self
+            .battle_state
+            .queued_order_markers(self.gui_state.side())
+        {
+            let world_shape =
+                self.order_marker_selection_shape(&order, &order_marker, &world_point);
+            if self
+                .gui_state
+                .window_shape_from_world_shape(&world_shape)
+                .contains(cursor)
+            {
+                return Some((order_marker, squad_id, leg_id));
+            }
+        }
+
+        None
+    }
+
     pub fn order_marker_selection_shape(
         &self,
         order: &Order,
@@ -1,7 +1,4 @@
-use std::{
-    any::Any,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 
 use battle_core::{config::ServerConfig, map::Map, types::WorldPoint};
 use ggez::{
@@ -157,7 +154,7 @@ pub fn create_debug_terrain_opacity_mesh_builder(
     for tile in map.terrain_tiles() {
         let dest_x = tile.x as f32 * tile.tile_width as f32;
         let dest_y = tile.y as f32 * tile.tile_height as f32;
-        let color_modifier = config.terrain_tile_opacity(&tile.type_);
+        let color_modifier = tile.opacity(config);
         debug_terrain_opacity_mesh.rectangle(
             DrawMode::fill(),
             Rect::new(
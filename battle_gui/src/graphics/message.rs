@@ -12,6 +12,7 @@ pub enum GraphicsMessage {
     RemoveExplosionAnimation(WorldPoint),
     RemoveCanonBlastAnimation(WorldPoint),
     RecomputeDebugTerrainOpacity,
+    ReloadMapTerrain,
     ReloadSoldiersAsset,
     ReloadVehiclesAsset,
     ReloadExplosionsAsset,
@@ -590,6 +590,12 @@ impl Graphics {
                 self.debug_terrain_opacity_mesh_builder =
                     map::create_debug_terrain_opacity_mesh_builder(map, config)?;
             }
+            GraphicsMessage::ReloadMapTerrain => {
+                // Terrain only change during battle, when all decors are displayed
+                self.decor = DecorsBuilder::new(ctx, map).rule(DrawAll).build()?;
+                self.debug_terrain_opacity_mesh_builder =
+                    map::create_debug_terrain_opacity_mesh_builder(map, config)?;
+            }
             GraphicsMessage::ReloadSoldiersAsset => {
                 self.soldiers = SoldiersBuilder::new(ctx).build()?;
                 // FIXME same for weapons, cannon_blasts
@@ -2,7 +2,9 @@ use battle_core::{
     order::marker::OrderMarker,
     types::{Angle, WindowPoint, WorldPoint},
 };
-use ggez::graphics::{DrawParam, Rect};
+use ggez::graphics::{Color, DrawParam, Rect};
+
+use crate::utils::{BLUE, DARK_MAGENTA, GREEN, RED, YELLOW};
 
 use super::Graphics;
 
@@ -26,6 +28,17 @@ impl Graphics {
             .offset(sprite_info.offset().to_vec2())
     }
 
+    /// Color used to draw an order leg (path or target line) according to its kind
+    pub fn order_leg_color(&self, order_marker: &OrderMarker) -> Color {
+        match order_marker {
+            OrderMarker::MoveTo => BLUE,
+            OrderMarker::MoveFastTo => YELLOW,
+            OrderMarker::SneakTo => GREEN,
+            OrderMarker::Defend | OrderMarker::Hide => DARK_MAGENTA,
+            OrderMarker::EngageSquad | OrderMarker::SuppressFire | OrderMarker::AreaFire => RED,
+        }
+    }
+
     pub fn order_marker_selection_rect(
         &self,
         order_marker: &OrderMarker,
@@ -10,7 +10,9 @@ pub mod server;
 pub mod ui;
 pub mod utils;
 
+use battle_core::config::ServerConfigError;
 use battle_core::deployment::DeploymentReaderError;
+use battle_core::game::weapon::definition::WeaponDefinitionsError;
 use battle_core::map::reader::MapReaderError;
 use battle_core::message::InputMessage;
 use battle_core::network::error::NetworkError;
@@ -40,6 +42,10 @@ pub enum GuiError {
     EmbeddedServer(EmbeddedServerError),
     #[error("Battle state builder error : {0}")]
     BattleStateBuilderError(BattleStateBuilderError),
+    #[error("Server config error : {0}")]
+    ServerConfig(ServerConfigError),
+    #[error("Weapon definitions error : {0}")]
+    WeaponDefinitions(WeaponDefinitionsError),
 }
 
 impl From<MapReaderError> for GuiError {
@@ -84,6 +90,18 @@ impl From<DeploymentReaderError> for GuiError {
     }
 }
 
+impl From<ServerConfigError> for GuiError {
+    fn from(error: ServerConfigError) -> Self {
+        Self::ServerConfig(error)
+    }
+}
+
+impl From<WeaponDefinitionsError> for GuiError {
+    fn from(error: WeaponDefinitionsError) -> Self {
+        Self::WeaponDefinitions(error)
+    }
+}
+
 impl From<BattleStateBuilderError> for GuiError {
     fn from(error: BattleStateBuilderError) -> Self {
         Self::BattleStateBuilderError(error)
@@ -2,6 +2,7 @@ use battle_core::config::GuiConfig;
 use battle_core::config::ServerConfig;
 use battle_core::deployment::DeploymentReader;
 use battle_core::game::control::MapControl;
+use battle_core::game::weapon::definition::{WeaponDefinitions, WEAPONS_FILE_NAME};
 use battle_core::map::reader::MapReader;
 use battle_core::state::battle::builder::BattleStateBuilder;
 use battle_core::utils::start_puffin_server;
@@ -21,14 +22,31 @@ fn main() -> Result<(), GuiError> {
         start_puffin_server(opt.profile_address.clone())
     };
 
-    let deployment = DeploymentReader::from_file(&opt.deployment)?;
+    // Weapon definitions must be known before deployment read
+    let weapons = opt
+        .weapons
+        .clone()
+        .unwrap_or_else(|| resources.lib().join(WEAPONS_FILE_NAME));
+    WeaponDefinitions::from_file(&weapons)?.install()?;
+
+    let mut deployment = DeploymentReader::from_file(&opt.deployment)?;
+    // Chosen rule set replace the deployment one, server will apply it when receive deployment
+    if opt.rules.is_some() {
+        deployment.set_rule_set(opt.rules);
+    }
     let a_control = MapControl::new(opt.a_control.clone());
     let b_control = MapControl::new(opt.b_control.clone());
 
     // TODO : If remote server, download map before read it
     let map = MapReader::new(map_name, &resources.lib())?.build()?;
     let config = GuiConfig::default();
-    let server_config = ServerConfig::default();
+    let mut server_config = match &opt.server_config {
+        Some(path) => ServerConfig::from_file(path)?,
+        None => ServerConfig::default(),
+    };
+    if let Some(rule_set) = deployment.rule_set() {
+        server_config.apply_rule_set(rule_set);
+    }
     let battle_state = BattleStateBuilder::new(map.clone()).build()?;
 
     run(
@@ -2,6 +2,7 @@ use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
+use battle_core::config::rules::RuleSet;
 use battle_core::config::GuiConfig;
 use battle_core::config::ServerConfig;
 use battle_core::config::DEFAULT_SERVER_PUB_ADDRESS;
@@ -63,6 +64,18 @@ pub struct Opt {
 
     #[structopt(long = "--init-sync")]
     pub init_sync: bool,
+
+    /// Embedded server config file (.toml or .json), default values are used if not given
+    #[structopt(long = "--server-config", parse(from_os_str))]
+    pub server_config: Option<PathBuf>,
+
+    /// Gameplay rule set (standard, arcade, realistic or training), default is the deployment one
+    #[structopt(long = "rules")]
+    pub rules: Option<RuleSet>,
+
+    /// Weapon definitions file, default is the resources one
+    #[structopt(long = "weapons", parse(from_os_str))]
+    pub weapons: Option<PathBuf>,
 }
 
 pub struct RunSettings {
@@ -120,7 +120,7 @@ impl EmbeddedServer {
         let map = if let Some(map) = &self.force_map {
             map.clone()
         } else {
-            MapReader::new(map_name, &PathBuf::from("./resources"))?.build()?
+            MapReader::new(map_name, &self.resources)?.build()?
         };
         let state = BattleStateBuilder::new(map).build()?;
 
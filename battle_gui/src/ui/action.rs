@@ -98,7 +98,11 @@ impl IntoSprite for CurrentAction {
                 ACTION_REL_WIDTH,
                 ACTION_REL_HEIGHT,
             ],
-            CurrentAction::Reloading => [
+            CurrentAction::Reloading
+            | CurrentAction::ClearingJam
+            | CurrentAction::ChangingBarrel
+            | CurrentAction::SettingUp
+            | CurrentAction::TearingDown => [
                 ACTION_RELOADING_START_REL_X,
                 ACTION_RELOADING_START_REL_Y,
                 ACTION_REL_WIDTH,
@@ -1,4 +1,5 @@
 use battle_core::{
+    config::ServerConfig,
     game::squad::{SquadStatusResume, SquadStatusesResume},
     state::battle::{phase::Phase, BattleState},
     types::WindowPoint,
@@ -25,16 +26,22 @@ pub const BOTTOM_LINE_HEIGHT: f32 = 25.;
 pub struct HudBuilder<'a> {
     gui_state: &'a GuiState,
     battle_state: &'a BattleState,
+    server_config: &'a ServerConfig,
     point: WindowPoint,
     width: f32,
     height: f32,
 }
 
 impl<'a> HudBuilder<'a> {
-    pub fn new(gui_state: &'a GuiState, battle_state: &'a BattleState) -> Self {
+    pub fn new(
+        gui_state: &'a GuiState,
+        battle_state: &'a BattleState,
+        server_config: &'a ServerConfig,
+    ) -> Self {
         Self {
             gui_state,
             battle_state,
+            server_config,
             point: WindowPoint::new(0., 0.),
             width: 0.,
             height: 0.,
@@ -100,12 +107,18 @@ impl<'a> HudBuilder<'a> {
             *point,
             self.battle_state.a_morale().clone(),
             self.battle_state.b_morale().clone(),
+            self.server_config.rule_set,
+            self.server_config.end_morale,
         )
     }
 
     fn squad_statuses(&self, point: &WindowPoint) -> SquadStatuses {
         SquadStatuses::new(
-            SquadStatusesResume::from_battle_state(self.gui_state.side(), self.battle_state),
+            SquadStatusesResume::from_battle_state(
+                self.gui_state.side(),
+                self.battle_state,
+                self.server_config,
+            ),
             *point,
             self.gui_state.selected_squads().1.clone(),
         )
@@ -115,8 +128,14 @@ impl<'a> HudBuilder<'a> {
         if let Some(squad_uuid) = self.gui_state.selected_squads().1.first() {
             SquadDetail::new(
                 *point,
-                Some(SquadStatusResume::from_squad(self.battle_state, squad_uuid)),
+                Some(SquadStatusResume::from_squad(
+                    self.battle_state,
+                    self.server_config,
+                    squad_uuid,
+                )),
                 self.gui_state.selected_squads().0,
+                *self.gui_state.order_trigger_mode(),
+                !self.gui_state.orders_awaiting_trigger_squad().is_empty(),
             )
         } else {
             SquadDetail::empty(*point)
@@ -129,8 +148,8 @@ impl<'a> HudBuilder<'a> {
         let blue_positions = self
             .battle_state
             .squads()
-            .iter()
-            .map(|(s, _)| self.battle_state.squad(*s))
+            .keys()
+            .map(|s| self.battle_state.squad(*s))
             .map(|s| self.battle_state.soldier(s.leader()))
             .filter(|s| s.side() == self.gui_state.side())
             .map(|s| s.world_point())
@@ -138,8 +157,8 @@ impl<'a> HudBuilder<'a> {
         let red_positions = self
             .battle_state
             .squads()
-            .iter()
-            .map(|(s, _)| self.battle_state.squad(*s))
+            .keys()
+            .map(|s| self.battle_state.squad(*s))
             .map(|s| self.battle_state.soldier(s.leader()))
             .filter(|s| s.side() != self.gui_state.side())
             .filter(|s| {
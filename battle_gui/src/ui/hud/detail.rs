@@ -1,18 +1,18 @@
 use battle_core::{
     game::squad::SquadStatusResume,
+    order::trigger::OrderTriggerMode,
     types::{SoldierIndex, WindowPoint},
 };
 use ggez::{
     graphics::{
         Canvas, Color, DrawMode, DrawParam, FillOptions, Mesh, MeshBuilder, Rect, StrokeOptions,
-        Text, TextFragment, TextLayout,
+        Text, TextAlign, TextFragment, TextLayout,
     },
     Context, GameResult,
 };
 use glam::Vec2;
 use oc_core::graphics::squad::{
-    SOLDIER_HEIGHT, SOLDIER_REL_1_START_X, SOLDIER_REL_1_START_Y, SOLDIER_REL_HEIGHT,
-    SOLDIER_REL_WIDTH, SOLDIER_WIDTH, SQUAD_TYPE_HEIGHT, SQUAD_TYPE_WIDTH,
+    SOLDIER_HEIGHT, SOLDIER_WIDTH, SQUAD_TYPE_HEIGHT, SQUAD_TYPE_WIDTH,
 };
 
 use crate::{
@@ -24,15 +24,23 @@ use crate::{
     utils::IntoSprite,
 };
 
-use super::{event::HudEvent, HUD_HEIGHT};
+use super::{builder::MARGIN as HUD_MARGIN, event::HudEvent, HUD_HEIGHT};
 
 pub const SQUAD_DETAIL_WIDTH: f32 = 250.;
 pub const MARGIN: f32 = 1.;
+pub const TRIGGER_LINE_HEIGHT: f32 = 18.;
+pub const TRAITS_TEXT_SCALE: f32 = 11.;
+pub const TRAITS_WIDTH: f32 = 90.;
+pub const ACTION_TEXT_SCALE: f32 = 16.;
+pub const HEALTH_BAR_HEIGHT: f32 = 8.;
+pub const HEADER_BUTTON_TEXT_SCALE: f32 = 12.;
 
 pub struct SquadDetail {
     point: WindowPoint,
     squad: Option<SquadStatusResume>,
     selected_soldier: Option<SoldierIndex>,
+    order_trigger_mode: OrderTriggerMode,
+    awaiting_trigger_squad: bool,
 }
 
 impl SquadDetail {
@@ -40,11 +48,15 @@ impl SquadDetail {
         point: WindowPoint,
         status: Option<SquadStatusResume>,
         selected_soldier: Option<SoldierIndex>,
+        order_trigger_mode: OrderTriggerMode,
+        awaiting_trigger_squad: bool,
     ) -> Self {
         Self {
             point,
             squad: status,
             selected_soldier,
+            order_trigger_mode,
+            awaiting_trigger_squad,
         }
     }
 
@@ -53,8 +65,29 @@ impl SquadDetail {
             point,
             squad: None,
             selected_soldier: None,
+            order_trigger_mode: OrderTriggerMode::default(),
+            awaiting_trigger_squad: false,
         }
     }
+
+    /// Squad header button (rally, formation, then fire discipline rows) rect, under the
+    /// squad health bar
+    fn header_button_rect(&self, row: usize) -> Rect {
+        let height = (SQUAD_TYPE_HEIGHT - HEALTH_BAR_HEIGHT - MARGIN) / 3.;
+        Rect::new(
+            self.point.x + SQUAD_TYPE_WIDTH + MARGIN,
+            self.point.y + HEALTH_BAR_HEIGHT + MARGIN + height * row as f32,
+            SQUAD_DETAIL_WIDTH - (SQUAD_TYPE_WIDTH + MARGIN * 2.),
+            height,
+        )
+    }
+
+    fn trigger_line_point(&self) -> WindowPoint {
+        self.point.apply(Vec2::new(
+            0.,
+            HUD_HEIGHT - HUD_MARGIN * 2. - TRIGGER_LINE_HEIGHT * 2.,
+        ))
+    }
 }
 
 impl Component<HudEvent> for SquadDetail {
@@ -136,10 +169,34 @@ impl Component<HudEvent> for SquadDetail {
                     health_point.x,
                     health_point.y,
                     self.width(ctx) - (SQUAD_TYPE_WIDTH + MARGIN * 2.),
-                    SQUAD_TYPE_HEIGHT,
+                    HEALTH_BAR_HEIGHT,
                 ),
                 Color::new(0.5, squad.health().0, 0., 1.),
             )?;
+            let rally_text = if squad.command_disrupted() {
+                "Rally (R) - leader replaced".to_string()
+            } else {
+                "Rally (R)".to_string()
+            };
+            let formation_text = format!("Formation (F): {}", squad.formation());
+            let fire_discipline_text = format!("Fire (H): {}", squad.fire_discipline());
+            let header_buttons = [rally_text, formation_text, fire_discipline_text]
+                .into_iter()
+                .enumerate()
+                .map(|(row, text)| (self.header_button_rect(row), text))
+                .collect::<Vec<_>>();
+            for (rect, _) in &header_buttons {
+                mesh_builder.rectangle(
+                    DrawMode::Fill(FillOptions::default()),
+                    *rect,
+                    Color::new(0.2, 0.2, 0.2, 1.),
+                )?;
+                mesh_builder.rectangle(
+                    DrawMode::Stroke(StrokeOptions::default()),
+                    *rect,
+                    Color::BLACK,
+                )?;
+            }
 
             let soldiers_status_start_point = self
                 .point
@@ -147,16 +204,54 @@ impl Component<HudEvent> for SquadDetail {
             for (i, soldier_status) in squad.members().iter().enumerate() {
                 let text_dest = soldiers_status_start_point
                     .apply(Vec2::new(0., (SOLDIER_HEIGHT + MARGIN) * i as f32));
-                let text_center_dest = text_dest.apply(Vec2::new(
-                    (self.width(ctx) - MARGIN - SOLDIER_WIDTH) / 2.,
+                // Action is centered in the row part left of the traits column
+                let action_width = self.width(ctx) - MARGIN - SOLDIER_WIDTH - TRAITS_WIDTH;
+                let text_center_dest =
+                    text_dest.apply(Vec2::new(action_width / 2., SOLDIER_HEIGHT / 2.));
+                let action = match soldier_status.weapon_status() {
+                    Some(weapon_status) => {
+                        format!("{} [{}]", soldier_status.current().display(), weapon_status)
+                    }
+                    None => soldier_status.current().display().to_string(),
+                };
+                let (text, color) = if soldier_status.in_command_radius() {
+                    (action, Color::WHITE)
+                } else {
+                    (
+                        format!("{} (out of command)", action),
+                        Color::new(0.6, 0.6, 0.6, 1.),
+                    )
+                };
+                let mut action_text = Text::new(TextFragment::new(text.trim()).color(color));
+                action_text
+                    .set_scale(ACTION_TEXT_SCALE)
+                    .set_layout(TextLayout::center());
+                // Shrink long actions (weapon status, out of command) to not overlap traits
+                let text_width = action_text.measure(ctx)?.x;
+                if text_width > action_width {
+                    action_text.set_scale(ACTION_TEXT_SCALE * action_width / text_width);
+                }
+                canvas.draw(
+                    &action_text,
+                    DrawParam::default().dest(text_center_dest.to_vec2()),
+                );
+
+                // Soldier experience, marksmanship and courage at the row end
+                let traits_dest = text_dest.apply(Vec2::new(
+                    self.width(ctx) - MARGIN * 2. - SOLDIER_WIDTH,
                     SOLDIER_HEIGHT / 2.,
                 ));
                 canvas.draw(
                     Text::new(
-                        TextFragment::new(soldier_status.current().display()).color(Color::WHITE),
+                        TextFragment::new(soldier_status.traits().to_string())
+                            .color(Color::new(0.8, 0.8, 0.8, 1.)),
                     )
-                    .set_layout(TextLayout::center()),
-                    DrawParam::default().dest(text_center_dest.to_vec2()),
+                    .set_scale(TRAITS_TEXT_SCALE)
+                    .set_layout(TextLayout {
+                        h_align: TextAlign::End,
+                        v_align: TextAlign::Middle,
+                    }),
+                    DrawParam::default().dest(traits_dest.to_vec2()),
                 );
 
                 if soldier_status.leader() {
@@ -190,6 +285,38 @@ impl Component<HudEvent> for SquadDetail {
                 &Mesh::from_data(ctx, mesh_builder.build()),
                 DrawParam::new(),
             );
+            for (rect, text) in header_buttons {
+                canvas.draw(
+                    Text::new(TextFragment::new(text).color(Color::WHITE))
+                        .set_scale(HEADER_BUTTON_TEXT_SCALE)
+                        .set_layout(TextLayout::center()),
+                    DrawParam::default().dest(rect.center()),
+                );
+            }
+
+            // Trigger of next orders, then order waiting for its trigger
+            let trigger_text = if self.awaiting_trigger_squad {
+                format!("Wait (W): {} - click squad", self.order_trigger_mode)
+            } else {
+                format!("Wait (W): {}", self.order_trigger_mode)
+            };
+            canvas.draw(
+                &Text::new(TextFragment::new(trigger_text).color(Color::WHITE)),
+                DrawParam::default().dest(self.trigger_line_point().to_vec2()),
+            );
+            if let Some(triggered_order) = squad.triggered_order() {
+                canvas.draw(
+                    &Text::new(
+                        TextFragment::new(format!("Waiting: {}", triggered_order))
+                            .color(Color::YELLOW),
+                    ),
+                    DrawParam::default().dest(
+                        self.trigger_line_point()
+                            .apply(Vec2::new(0., TRIGGER_LINE_HEIGHT))
+                            .to_vec2(),
+                    ),
+                );
+            }
         }
 
         Ok(())
@@ -199,6 +326,35 @@ impl Component<HudEvent> for SquadDetail {
         let mouse_position = ctx.mouse.position();
 
         if let Some(squad) = &self.squad {
+            // Squad header rows under health bar are rally, formation and fire discipline
+            // buttons
+            if self.header_button_rect(0).contains(mouse_position) {
+                return Some(HudEvent::RallySquad(*squad.squad_id()));
+            }
+            if self.header_button_rect(1).contains(mouse_position) {
+                return Some(HudEvent::SetSquadFormation(
+                    *squad.squad_id(),
+                    squad.formation().next(),
+                ));
+            }
+            if self.header_button_rect(2).contains(mouse_position) {
+                return Some(HudEvent::SetSquadFireDiscipline(
+                    *squad.squad_id(),
+                    squad.fire_discipline().next(),
+                ));
+            }
+
+            let trigger_line_point = self.trigger_line_point();
+            if mouse_position.x >= trigger_line_point.x
+                && mouse_position.x <= trigger_line_point.x + SQUAD_DETAIL_WIDTH
+                && mouse_position.y >= trigger_line_point.y
+                && mouse_position.y <= trigger_line_point.y + TRIGGER_LINE_HEIGHT
+            {
+                return Some(HudEvent::SetOrderTriggerMode(
+                    self.order_trigger_mode.next(),
+                ));
+            }
+
             let soldiers_status_start_point = self
                 .point
                 .apply(Vec2::new(SOLDIER_WIDTH, SQUAD_TYPE_HEIGHT + MARGIN));
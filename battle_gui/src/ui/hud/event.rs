@@ -1,4 +1,8 @@
-use battle_core::types::{SoldierIndex, SquadUuid, WorldPoint};
+use battle_core::{
+    game::squad::{FireDiscipline, Formation},
+    order::trigger::OrderTriggerMode,
+    types::{SoldierIndex, SquadUuid, WorldPoint},
+};
 
 #[derive(Debug, Clone)]
 pub enum HudEvent {
@@ -8,4 +12,8 @@ pub enum HudEvent {
     CenterMapOnSquad(SquadUuid),
     SelectSoldier(SoldierIndex),
     CenterMapOn(WorldPoint),
+    RallySquad(SquadUuid),
+    SetSquadFormation(SquadUuid, Formation),
+    SetSquadFireDiscipline(SquadUuid, FireDiscipline),
+    SetOrderTriggerMode(OrderTriggerMode),
 }
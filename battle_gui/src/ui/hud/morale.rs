@@ -1,8 +1,12 @@
-use battle_core::{audio::Sound, types::WindowPoint};
+use battle_core::{audio::Sound, config::rules::RuleSet, types::WindowPoint};
 use ggez::{
-    graphics::{Canvas, Color, DrawMode, DrawParam, FillOptions, Mesh, MeshBuilder, Rect},
+    graphics::{
+        Canvas, Color, DrawMode, DrawParam, FillOptions, Mesh, MeshBuilder, Rect, Text,
+        TextFragment, TextLayout,
+    },
     Context, GameResult,
 };
+use glam::Vec2;
 use oc_core::morale::Morale;
 
 use crate::ui::component::Component;
@@ -19,14 +23,24 @@ pub struct MoraleIndicator {
     point: WindowPoint,
     a_morale: Morale,
     b_morale: Morale,
+    rule_set: RuleSet,
+    end_morale: f32,
 }
 
 impl MoraleIndicator {
-    pub fn new(point: WindowPoint, a_morale: Morale, b_morale: Morale) -> Self {
+    pub fn new(
+        point: WindowPoint,
+        a_morale: Morale,
+        b_morale: Morale,
+        rule_set: RuleSet,
+        end_morale: f32,
+    ) -> Self {
         Self {
             point,
             a_morale,
             b_morale,
+            rule_set,
+            end_morale,
         }
     }
 }
@@ -95,11 +109,30 @@ impl Component<HudEvent> for MoraleIndicator {
             ),
             Color::new(0.5, self.b_morale.0, 0., 1.),
         )?;
+        // Morale under which a side lose the battle (depend on rule set)
+        let a_end_morale = end_a - (a_total_width * self.end_morale);
+        let b_end_morale = start_b + (b_total_width * self.end_morale);
+        for end in [a_end_morale, b_end_morale] {
+            mesh_builder.line(
+                &[
+                    Vec2::new(end, self.point(ctx).y),
+                    Vec2::new(end, self.point(ctx).y + self.height(ctx)),
+                ],
+                1.,
+                Color::WHITE,
+            )?;
+        }
 
         canvas.draw(
             &Mesh::from_data(ctx, mesh_builder.build()),
             DrawParam::new(),
         );
+        canvas.draw(
+            Text::new(TextFragment::new(self.rule_set.to_string()).color(Color::WHITE))
+                .set_layout(TextLayout::center())
+                .set_bounds(self.bounds(ctx)),
+            DrawParam::default().dest(self.center(ctx).to_vec2()),
+        );
 
         Ok(())
     }
@@ -5,6 +5,7 @@ use battle_core::{
 use ggez::{
     graphics::{
         Canvas, Color, DrawMode, DrawParam, FillOptions, Mesh, MeshBuilder, Rect, StrokeOptions,
+        Text, TextFragment,
     },
     Context, GameResult,
 };
@@ -130,6 +131,7 @@ impl Component<HudEvent> for SquadStatuses {
 
     fn draw(&self, ctx: &mut Context, hovered: &WindowPoint, canvas: &mut Canvas) -> GameResult {
         let mut mesh_builder = MeshBuilder::new();
+        let mut texts = vec![];
 
         for draw_card in self.cards(ctx) {
             // Health color
@@ -172,6 +174,20 @@ impl Component<HudEvent> for SquadStatuses {
                     },
                 )?;
 
+                // Soldier too far from his leader
+                if !soldier_status.in_command_radius() {
+                    mesh_builder.rectangle(
+                        DrawMode::Fill(FillOptions::default()),
+                        Rect::new(
+                            soldiers_health_dest.x,
+                            soldiers_health_dest.y,
+                            SQUAD_CARD_SOLDIER_HEALTH_WIDTH,
+                            SQUAD_CARD_SOLDIER_HEALTH_HEIGHT,
+                        ),
+                        Color::new(0., 0., 0., 0.5),
+                    )?;
+                }
+
                 if soldier_status.leader() {
                     mesh_builder.rectangle(
                         DrawMode::Stroke(StrokeOptions::default()),
@@ -186,6 +202,19 @@ impl Component<HudEvent> for SquadStatuses {
                 }
             }
 
+            if draw_card.squad_status.command_disrupted() {
+                texts.push((
+                    Text::new(
+                        TextFragment::new("leader replaced")
+                            .color(Color::WHITE)
+                            .scale(SQUAD_CARD_HEADER_HEIGHT),
+                    ),
+                    draw_card
+                        .dest
+                        .apply(Vec2::new(SQUAD_TYPE_WIDTH + SQUAD_CARD_MARGIN, 0.)),
+                ));
+            }
+
             // Outline when hover or selected
             let outline = Rect::new(
                 draw_card.dest.x,
@@ -210,6 +239,9 @@ impl Component<HudEvent> for SquadStatuses {
             &Mesh::from_data(ctx, mesh_builder.build()),
             DrawParam::new(),
         );
+        for (text, dest) in texts {
+            canvas.draw(&text, DrawParam::default().dest(dest.to_vec2()));
+        }
 
         Ok(())
     }
@@ -5,7 +5,10 @@ use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
-use battle_core::config::ServerConfig;
+use battle_core::config::{rules::RuleSet, ServerConfig, ServerConfigError};
+use battle_core::game::weapon::definition::{
+    WeaponDefinitions, WeaponDefinitionsError, WEAPONS_FILE_NAME,
+};
 use battle_core::network::error::NetworkError;
 use battle_core::network::server::Server;
 use battle_core::state::battle::builder::{BattleStateBuilder, BattleStateBuilderError};
@@ -30,6 +33,18 @@ pub struct Opt {
 
     #[structopt(long = "--profile-address", default_value = "0.0.0.0:8585")]
     profile_address: String,
+
+    /// Server config file (.toml or .json), default values are used if not given
+    #[structopt(long = "config", parse(from_os_str))]
+    config: Option<PathBuf>,
+
+    /// Gameplay rule set (standard, arcade, realistic or training)
+    #[structopt(long = "rules")]
+    rules: Option<RuleSet>,
+
+    /// Weapon definitions file, default is the resources one
+    #[structopt(long = "weapons", parse(from_os_str))]
+    weapons: Option<PathBuf>,
 }
 
 fn main() -> Result<(), Error> {
@@ -39,6 +54,11 @@ fn main() -> Result<(), Error> {
     let opt = Opt::from_args();
     let resources = PathBuf::from("./resources");
     let map_name = &opt.map_name;
+    let weapons = opt
+        .weapons
+        .clone()
+        .unwrap_or_else(|| resources.join(WEAPONS_FILE_NAME));
+    WeaponDefinitions::from_file(&weapons)?.install()?;
 
     let _puffin_server = if opt.profile {
         let puffin_server = puffin_http::Server::new(&opt.profile_address).unwrap();
@@ -61,7 +81,14 @@ fn main() -> Result<(), Error> {
     server.serve()?;
 
     let stop_required_ = stop_required.clone();
-    let config = ServerConfig::default();
+    let mut config = match &opt.config {
+        Some(path) => ServerConfig::from_file(path)?,
+        None => ServerConfig::default(),
+    };
+    if let Some(rule_set) = opt.rules {
+        config.apply_rule_set(rule_set);
+        config.rule_set_locked = true;
+    }
     let map = MapReader::new(map_name, &resources)?.build()?;
     let battle_state = BattleStateBuilder::new(map).build()?;
     let mut runner = Runner::new(
@@ -77,13 +104,28 @@ fn main() -> Result<(), Error> {
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 enum Error {
+    Config(ServerConfigError),
+    Weapons(WeaponDefinitionsError),
     MapReaderError(MapReaderError),
     LoadBattle(BattleStateBuilderError),
     Network(NetworkError),
     Run(RunnerError),
 }
 
+impl From<ServerConfigError> for Error {
+    fn from(error: ServerConfigError) -> Self {
+        Self::Config(error)
+    }
+}
+
+impl From<WeaponDefinitionsError> for Error {
+    fn from(error: WeaponDefinitionsError) -> Self {
+        Self::Weapons(error)
+    }
+}
+
 impl From<RunnerError> for Error {
     fn from(error: RunnerError) -> Self {
         Self::Run(error)
@@ -26,12 +26,12 @@ impl Runner {
                 &self.config.hide_maximum_rayon,
                 &leader.side().opposite(),
             ) {
-                return (vec![(leader, Order::Defend(*angle))], vec![]);
+                return (vec![(leader, Order::Defend(*angle, None))], vec![]);
             }
         }
 
-        let (moves, debug_points) = CoverFinder::new(&self.battle_state, &self.config)
-            .find_arbitrary_cover_points(squad, leader);
+        let (moves, debug_points) =
+            CoverFinder::new(&self.battle_state, &self.config).find_arbitrary_cover_points(leader);
 
         for (member_id, from_world_point, cover_world_point) in &moves {
             let path = WorldPaths::new(vec![WorldPath::new(vec![
@@ -41,7 +41,9 @@ impl Runner {
 
             let then_order = match behavior {
                 Behavior::Hide(angle) => Order::Hide(*angle),
-                Behavior::Defend(angle) => Order::Defend(*angle),
+                Behavior::Defend(angle) => {
+                    Order::Defend(*angle, leader.order().fire_arc().copied())
+                }
                 _ => unreachable!(),
             };
 
@@ -62,13 +64,16 @@ impl Runner {
         behavior: &Behavior,
     ) -> (Vec<(&Soldier, Order)>, Vec<NewDebugPoint>) {
         let squad = self.battle_state.squad(squad_uuid);
+        let leader = self.battle_state.soldier(squad.leader());
 
         for member_index in squad.members() {
             if let Some((_, place)) = self.battle_state.soldier_board(*member_index) {
                 if place == &OnBoardPlace::Driver {
                     let soldier = self.battle_state.soldier(*member_index);
                     let order = match &behavior {
-                        Behavior::Defend(angle) => Order::Defend(*angle),
+                        Behavior::Defend(angle) => {
+                            Order::Defend(*angle, leader.order().fire_arc().copied())
+                        }
                         Behavior::Hide(angle) => Order::Hide(*angle),
                         _ => {
                             unreachable!()
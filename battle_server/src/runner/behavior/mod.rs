@@ -1,12 +1,15 @@
+use std::mem::discriminant;
+
 use battle_core::{
     behavior::{Behavior, BehaviorMode, BehaviorPropagation, Body},
     entity::soldier::Soldier,
     order::Order,
+    physics::path::PathMode,
     state::{
         battle::message::{BattleStateMessage, SoldierMessage},
         client::ClientStateMessage,
     },
-    types::{Angle, SquadUuid, WorldPaths, WorldPoint},
+    types::{Angle, Distance, SquadUuid, WorldPaths, WorldPoint},
     utils::NewDebugPoint,
 };
 
@@ -31,10 +34,11 @@ impl Runner {
             Order::MoveTo(paths, _) => self.move_behavior(soldier, paths),
             Order::MoveFastTo(paths, _) => self.move_fast_behavior(soldier, paths),
             Order::SneakTo(paths, _) => self.sneak_to_behavior(soldier, paths),
-            Order::Defend(angle) => self.defend_behavior(soldier, angle),
+            Order::Defend(angle, _) => self.defend_behavior(soldier, angle),
             Order::Hide(angle) => self.hide_behavior(soldier, angle),
             Order::EngageSquad(squad_index) => self.engage_behavior(soldier, squad_index),
             Order::SuppressFire(point) => self.suppress_fire_behavior(soldier, point),
+            Order::AreaFire(center, radius) => self.area_fire_behavior(soldier, center, radius),
         };
 
         // In case of squad leader and regularly propagation
@@ -102,9 +106,12 @@ impl Runner {
             Behavior::Idle(_) | Behavior::Dead | Behavior::Unconscious => {
                 vec![]
             }
-            Behavior::SuppressFire(point) => {
-                self.propagate_suppress_fire(leader.squad_uuid(), point)
-            }
+            Behavior::SuppressFire(point) => match leader.order() {
+                Order::AreaFire(center, radius) => {
+                    self.propagate_area_fire(leader.squad_uuid(), center, radius)
+                }
+                _ => self.propagate_suppress_fire(leader.squad_uuid(), point),
+            },
             Behavior::EngageSoldier(soldier_index) => {
                 self.propagate_engage_soldier(&leader.squad_uuid(), soldier_index)
             }
@@ -113,11 +120,22 @@ impl Runner {
         for (subordinate, order) in orders {
             // Give order only if different from subordinate current order
             if subordinate.order() != &order {
+                // Soldier outside leader command radius need time to react to a new kind of order
+                let new_kind = discriminant(subordinate.order()) != discriminant(&order);
+                let soldier_message = if new_kind
+                    && !self
+                        .battle_state
+                        .soldier_is_in_command_radius(&self.config, subordinate)
+                {
+                    SoldierMessage::SetDelayedOrder(
+                        order,
+                        self.battle_state.frame_i() + self.config.out_of_command_order_delay,
+                    )
+                } else {
+                    SoldierMessage::SetOrder(order)
+                };
                 messages.extend(vec![RunnerMessage::BattleState(
-                    BattleStateMessage::Soldier(
-                        subordinate.uuid(),
-                        SoldierMessage::SetOrder(order),
-                    ),
+                    BattleStateMessage::Soldier(subordinate.uuid(), soldier_message),
                 )]);
             }
         }
@@ -132,9 +150,7 @@ impl Runner {
     }
 
     pub fn idle_behavior(&self, soldier: &Soldier) -> Behavior {
-        if let Some(opponent) =
-            self.soldier_find_opponent_to_target(soldier, None, &ChooseMethod::RandomFromNearest)
-        {
+        if let Some(opponent) = self.soldier_find_opponent_to_engage(soldier) {
             return Behavior::EngageSoldier(opponent.uuid());
         }
 
@@ -147,17 +163,17 @@ impl Runner {
     }
 
     pub fn move_behavior(&self, soldier: &Soldier, paths: &WorldPaths) -> Behavior {
-        if let Some(opponent) =
-            self.soldier_find_opponent_to_target(soldier, None, &ChooseMethod::RandomFromNearest)
-        {
+        if let Some(opponent) = self.soldier_find_opponent_to_engage(soldier) {
             return Behavior::EngageSoldier(opponent.uuid());
         }
 
         match self.battle_state.soldier_behavior_mode(soldier) {
             BehaviorMode::Ground => {
-                if soldier.under_fire().is_warning()
-                    || soldier.under_fire().is_danger()
-                    || soldier.under_fire().is_max()
+                let thresholds = soldier.traits().under_fire_thresholds();
+                if (soldier.under_fire().is_warning(&thresholds)
+                    || soldier.under_fire().is_danger(&thresholds)
+                    || soldier.under_fire().is_max())
+                    && self.can_sneak_along(paths)
                 {
                     Behavior::SneakTo(paths.clone())
                 } else {
@@ -169,13 +185,32 @@ impl Runner {
     }
 
     pub fn move_fast_behavior(&self, soldier: &Soldier, paths: &WorldPaths) -> Behavior {
-        if soldier.under_fire().is_danger() || soldier.under_fire().is_max() {
+        let thresholds = soldier.traits().under_fire_thresholds();
+        if (soldier.under_fire().is_danger(&thresholds) || soldier.under_fire().is_max())
+            && self.can_sneak_along(paths)
+        {
             Behavior::SneakTo(paths.clone())
         } else {
             Behavior::MoveFastTo(paths.clone())
         }
     }
 
+    /// Paths given for a walk can cross tiles which can't be sneaked (like barbed wire), soldier
+    /// must then keep walking
+    fn can_sneak_along(&self, paths: &WorldPaths) -> bool {
+        let map = self.battle_state.map();
+        paths
+            .paths
+            .iter()
+            .flat_map(|path| &path.points)
+            .all(|point| {
+                let grid_point = map.grid_point_from_world_point(point);
+                map.terrain_tile_at(&grid_point)
+                    .map(|tile| map.can_enter(&grid_point, tile, &PathMode::Sneak))
+                    .unwrap_or(true)
+            })
+    }
+
     pub fn sneak_to_behavior(&self, _soldier: &Soldier, paths: &WorldPaths) -> Behavior {
         Behavior::SneakTo(paths.clone())
     }
@@ -183,11 +218,7 @@ impl Runner {
     pub fn defend_behavior(&self, soldier: &Soldier, angle: &Angle) -> Behavior {
         match self.battle_state.soldier_behavior_mode(soldier) {
             BehaviorMode::Ground => {
-                if let Some(opponent) = self.soldier_find_opponent_to_target(
-                    soldier,
-                    None,
-                    &ChooseMethod::RandomFromNearest,
-                ) {
+                if let Some(opponent) = self.soldier_find_opponent_to_engage(soldier) {
                     Behavior::EngageSoldier(opponent.uuid())
                 } else {
                     Behavior::Defend(*angle)
@@ -216,11 +247,7 @@ impl Runner {
     pub fn hide_behavior(&self, soldier: &Soldier, angle: &Angle) -> Behavior {
         match self.battle_state.soldier_behavior_mode(soldier) {
             BehaviorMode::Ground => {
-                if let Some(opponent) = self.soldier_find_opponent_to_target(
-                    soldier,
-                    None,
-                    &ChooseMethod::RandomFromNearest,
-                ) {
+                if let Some(opponent) = self.soldier_find_opponent_to_engage(soldier) {
                     Behavior::EngageSoldier(opponent.uuid())
                 } else {
                     Behavior::Hide(*angle)
@@ -269,4 +296,129 @@ impl Runner {
     pub fn suppress_fire_behavior(&self, _soldier: &Soldier, point: &WorldPoint) -> Behavior {
         Behavior::SuppressFire(*point)
     }
+
+    pub fn area_fire_behavior(
+        &self,
+        soldier: &Soldier,
+        center: &WorldPoint,
+        radius: &Distance,
+    ) -> Behavior {
+        Behavior::SuppressFire(self.area_fire_point(soldier, center, radius))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use battle_core::{
+        behavior::feeling::UNDER_FIRE_MAX,
+        deployment::SoldierDeployment,
+        game::{
+            traits::{Experience, SoldierTraits},
+            Side,
+        },
+        map::terrain::{TerrainChange, TileType},
+        types::{GridPoint, SoldierIndex, SquadUuid, WorldPath, WorldPoint},
+    };
+    use oc_core::game::soldier::SoldierType;
+
+    use crate::runner::test::{runner, soldier};
+
+    use super::*;
+
+    #[test]
+    fn idle_soldier_hide_as_soon_as_under_fire() {
+        let mut runner = runner(vec![soldier(0, 0, WorldPoint::new(100., 100.), None)]);
+        let soldier = runner.battle_state.soldier(SoldierIndex(0));
+        assert_eq!(
+            runner.idle_behavior(soldier),
+            Behavior::Idle(Body::Crouched)
+        );
+
+        runner
+            .battle_state
+            .soldier_mut(SoldierIndex(0))
+            .increase_under_fire(10);
+        let soldier = runner.battle_state.soldier(SoldierIndex(0));
+        assert_eq!(runner.idle_behavior(soldier), Behavior::Hide(Angle(0.)));
+    }
+
+    #[test]
+    fn moving_soldier_under_fire_dont_sneak_through_wires() {
+        let mut runner = runner(vec![soldier(0, 0, WorldPoint::new(102., 102.), None)]);
+        runner
+            .battle_state
+            .soldier_mut(SoldierIndex(0))
+            .increase_under_fire(UNDER_FIRE_MAX);
+        let path = |to_x: f32| {
+            WorldPaths::new(vec![WorldPath::new(vec![
+                WorldPoint::new(107., 102.),
+                WorldPoint::new(to_x, 102.),
+            ])])
+        };
+        let clear = path(112.);
+        let wired = path(117.);
+        runner.battle_state.react(
+            &BattleStateMessage::ChangeTerrain(vec![TerrainChange::new(
+                GridPoint::new(23, 20),
+                TileType::BarbedWire,
+                false,
+            )]),
+            0,
+        );
+
+        let soldier = runner.battle_state.soldier(SoldierIndex(0));
+        assert_eq!(
+            runner.move_behavior(soldier, &clear),
+            Behavior::SneakTo(clear.clone())
+        );
+        assert_eq!(
+            runner.move_behavior(soldier, &wired),
+            Behavior::MoveTo(wired.clone())
+        );
+        assert_eq!(
+            runner.move_fast_behavior(soldier, &wired),
+            Behavior::MoveFastTo(wired.clone())
+        );
+    }
+
+    #[test]
+    fn moving_soldier_sneak_according_to_its_courage() {
+        let soldier = |index: usize, traits: SoldierTraits| {
+            SoldierDeployment::new(
+                SoldierIndex(index),
+                SoldierType::Type1,
+                Side::A,
+                WorldPoint::new(100. + index as f32 * 10., 100.),
+                SquadUuid(index),
+                None,
+                vec![],
+                Order::Idle,
+                Behavior::Idle(Body::StandUp),
+                traits,
+            )
+        };
+        let mut runner = runner(vec![
+            soldier(0, SoldierTraits::new(Experience::Green, 0.5, 0.)),
+            soldier(1, SoldierTraits::new(Experience::Veteran, 0.5, 1.)),
+        ]);
+        // Over warning threshold of a green coward, under the one of a brave veteran
+        for index in [0, 1] {
+            runner
+                .battle_state
+                .soldier_mut(SoldierIndex(index))
+                .increase_under_fire(80);
+        }
+        let paths = WorldPaths::new(vec![]);
+
+        let coward = runner.battle_state.soldier(SoldierIndex(0));
+        assert_eq!(
+            runner.move_behavior(coward, &paths),
+            Behavior::SneakTo(paths.clone())
+        );
+        let brave = runner.battle_state.soldier(SoldierIndex(1));
+        assert_eq!(
+            runner.move_behavior(brave, &paths),
+            Behavior::MoveTo(paths.clone())
+        );
+    }
 }
@@ -1,10 +1,11 @@
+use std::collections::HashMap;
+
 use battle_core::{
     behavior::Behavior,
     entity::{soldier::Soldier, vehicle::OnBoardPlace},
-    game::squad::{squad_positions, Formation},
     order::Order,
     physics::path::{find_path, PathMode},
-    types::{SquadUuid, WorldPath, WorldPaths},
+    types::{GridPoint, SquadUuid, WorldPath, WorldPaths},
 };
 
 use crate::runner::Runner;
@@ -16,39 +17,117 @@ impl Runner {
         behavior: &Behavior,
     ) -> Vec<(&Soldier, Order)> {
         let mut behaviors = vec![];
-        let squad = self.battle_state.squad(squad_uuid);
-        let leader = self.battle_state.soldier(squad.leader());
+        let map = self.battle_state.map();
+        let path_mode = match behavior {
+            Behavior::SneakTo(_) => PathMode::Sneak,
+            _ => PathMode::Walk,
+        };
+
+        if self.config.flow_field_movement {
+            if let Some(behaviors) = self.propagate_move_with_flow_field(squad_uuid, behavior) {
+                return behaviors;
+            }
+        }
 
-        for (soldier_index, point) in squad_positions(squad, Formation::Line, leader, None) {
+        for (soldier_index, point) in
+            self.battle_state
+                .squad_formation_positions(&self.config, &squad_uuid, None)
+        {
             let soldier = self.battle_state.soldier(soldier_index);
-            let map = self.battle_state.map();
             if let Some(grid_path) = find_path(
+                &self.config,
                 map,
                 &map.grid_point_from_world_point(&soldier.world_point()),
                 &map.grid_point_from_world_point(&point),
                 true,
-                &PathMode::Walk,
+                &path_mode,
                 &None,
             ) {
-                let world_path = grid_path
-                    .iter()
-                    .map(|p| map.world_point_from_grid_point(*p))
-                    .collect();
-                let world_paths = WorldPaths::new(vec![WorldPath::new(world_path)]);
-
-                let order = match behavior {
-                    Behavior::MoveTo(_) => Order::MoveTo(world_paths, None),
-                    Behavior::MoveFastTo(_) => Order::MoveFastTo(world_paths, None),
-                    Behavior::SneakTo(_) => Order::SneakTo(world_paths, None),
-                    _ => unreachable!(),
-                };
-                behaviors.push((soldier, order))
+                behaviors.push((soldier, self.move_order(behavior, &grid_path)))
             }
         }
 
         behaviors
     }
 
+    /// Squad members follow the flow field of the leader destination area up to their formation
+    /// position around the leader. Tiles and positions already used by other moving soldiers
+    /// are avoided to not pile up all soldiers on the same path. None if not applicable.
+    fn propagate_move_with_flow_field(
+        &self,
+        squad_uuid: SquadUuid,
+        behavior: &Behavior,
+    ) -> Option<Vec<(&Soldier, Order)>> {
+        let map = self.battle_state.map();
+        let paths = match behavior {
+            Behavior::MoveTo(paths) | Behavior::MoveFastTo(paths) => paths,
+            _ => return None,
+        };
+        let destination = paths.last_point()?;
+        let path_mode = PathMode::Walk;
+        let field = map.path_cache().flow_field(
+            map,
+            &map.grid_point_from_world_point(&destination),
+            &path_mode,
+        )?;
+
+        let mut occupied: HashMap<GridPoint, i32> = HashMap::new();
+        let mut taken = vec![];
+        for soldier in self.battle_state.soldiers() {
+            if soldier.squad_uuid() == squad_uuid || !soldier.alive() {
+                continue;
+            }
+            if let Behavior::MoveTo(paths) | Behavior::MoveFastTo(paths) = soldier.behavior() {
+                for path in &paths.paths {
+                    for point in &path.points {
+                        *occupied
+                            .entry(map.grid_point_from_world_point(point))
+                            .or_default() += 1;
+                    }
+                }
+                if let Some(last_point) = paths.last_point() {
+                    taken.push(map.grid_point_from_world_point(&last_point));
+                }
+            }
+        }
+
+        let mut behaviors = vec![];
+        for (soldier_index, point) in
+            self.battle_state
+                .squad_formation_positions(&self.config, &squad_uuid, None)
+        {
+            let soldier = self.battle_state.soldier(soldier_index);
+            let from = map.grid_point_from_world_point(&soldier.world_point());
+            let slot = field.free_slot(map, &map.grid_point_from_world_point(&point), &taken);
+            let grid_path = field
+                .path(&self.config, map, &from, &slot, &path_mode, &mut occupied)
+                .or_else(|| find_path(&self.config, map, &from, &slot, true, &path_mode, &None));
+
+            if let Some(grid_path) = grid_path {
+                taken.push(slot);
+                behaviors.push((soldier, self.move_order(behavior, &grid_path)))
+            }
+        }
+
+        Some(behaviors)
+    }
+
+    fn move_order(&self, behavior: &Behavior, grid_path: &[GridPoint]) -> Order {
+        let map = self.battle_state.map();
+        let world_path = grid_path
+            .iter()
+            .map(|p| map.world_point_from_grid_point(*p))
+            .collect();
+        let world_paths = WorldPaths::new(vec![WorldPath::new(world_path)]);
+
+        match behavior {
+            Behavior::MoveTo(_) => Order::MoveTo(world_paths, None),
+            Behavior::MoveFastTo(_) => Order::MoveFastTo(world_paths, None),
+            Behavior::SneakTo(_) => Order::SneakTo(world_paths, None),
+            _ => unreachable!(),
+        }
+    }
+
     pub fn propagate_drive(
         &self,
         squad_uuid: SquadUuid,
@@ -76,3 +155,43 @@ impl Runner {
         vec![]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use battle_core::types::WorldPoint;
+
+    use crate::runner::test::{runner, soldier};
+
+    use super::*;
+
+    #[test]
+    fn flow_field_members_keep_formation_around_leader() {
+        let mut runner = runner(vec![
+            soldier(0, 0, WorldPoint::new(100., 100.), None),
+            soldier(1, 0, WorldPoint::new(60., 100.), None),
+            soldier(2, 0, WorldPoint::new(100., 60.), None),
+        ]);
+        runner.config.flow_field_movement = true;
+        let destination = WorldPoint::new(400., 400.);
+        let behavior = Behavior::MoveTo(WorldPaths::new(vec![WorldPath::new(vec![destination])]));
+        let map = runner.battle_state.map();
+        let formation =
+            runner
+                .battle_state
+                .squad_formation_positions(&runner.config, &SquadUuid(0), None);
+
+        let orders = runner.propagate_move(SquadUuid(0), &behavior);
+
+        assert_eq!(orders.len(), 2);
+        for (soldier, order) in orders {
+            let last_point = match order {
+                Order::MoveTo(paths, _) => paths.last_point().unwrap(),
+                order => panic!("Expected move order, got {:?}", order),
+            };
+            assert_eq!(
+                map.grid_point_from_world_point(&last_point),
+                map.grid_point_from_world_point(&formation[&soldier.uuid()])
+            );
+        }
+    }
+}
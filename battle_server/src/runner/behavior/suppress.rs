@@ -1,8 +1,13 @@
+use std::f32::consts::PI;
+
 use battle_core::{
     entity::soldier::Soldier,
     order::Order,
-    types::{SquadUuid, WorldPoint},
+    physics::utils::DISTANCE_TO_METERS_COEFFICIENT,
+    types::{Distance, SquadUuid, WorldPoint},
 };
+use glam::Vec2;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 use crate::runner::Runner;
 
@@ -26,4 +31,41 @@ impl Runner {
 
         orders
     }
+
+    pub fn propagate_area_fire(
+        &self,
+        squad_uuid: SquadUuid,
+        center: &WorldPoint,
+        radius: &Distance,
+    ) -> Vec<(&Soldier, Order)> {
+        self.battle_state
+            .squad(squad_uuid)
+            .subordinates()
+            .iter()
+            .map(|i| {
+                (
+                    self.battle_state.soldier(**i),
+                    Order::AreaFire(*center, *radius),
+                )
+            })
+            .collect()
+    }
+
+    /// Point of area fire zone targeted by given soldier. Each soldier target its own point
+    /// and change it regularly to cover the whole zone.
+    pub fn area_fire_point(
+        &self,
+        soldier: &Soldier,
+        center: &WorldPoint,
+        radius: &Distance,
+    ) -> WorldPoint {
+        let shift = self.battle_state.frame_i() / self.config.area_fire_shift_duration.max(1);
+        let mut rng = StdRng::seed_from_u64((soldier.uuid().0 as u64) << 32 | shift);
+        let radius = (radius.millimeters() as f32 / DISTANCE_TO_METERS_COEFFICIENT) / 1000.;
+        // Square root to distribute points uniformly over the zone
+        let distance = radius * rng.gen::<f32>().sqrt();
+        let angle = rng.gen_range(0. ..PI * 2.);
+
+        center.apply(Vec2::new(angle.cos() * distance, angle.sin() * distance))
+    }
 }
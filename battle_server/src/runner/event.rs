@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use battle_core::{
+    entity::soldier::Soldier,
+    physics::utils::distance_between_points,
+    state::battle::{event::BattleEvent, message::BattleStateMessage},
+    types::{SoldierIndex, WorldPoint},
+};
+
+use super::{message::RunnerMessage, Runner};
+
+impl Runner {
+    pub fn tick_events(&self) -> Vec<RunnerMessage> {
+        puffin::profile_scope!("tick_events");
+
+        if self
+            .battle_state
+            .frame_i()
+            .is_multiple_of(self.config.morale_update_freq())
+        {
+            return [self.squad_rout_events(), self.vehicle_destroyed_events()].concat();
+        }
+
+        vec![]
+    }
+
+    fn squad_rout_events(&self) -> Vec<RunnerMessage> {
+        let mut messages = vec![];
+
+        for (squad_uuid, squad) in self.battle_state.squads() {
+            if self.battle_state.squad_routed(squad_uuid) || squad.members().is_empty() {
+                continue;
+            }
+
+            let able = squad
+                .members()
+                .iter()
+                .filter(|i| self.battle_state.soldier(**i).can_be_count_for_morale())
+                .count();
+            if (able as f32 / squad.members().len() as f32) < self.config.squad_rout_morale {
+                messages.push(RunnerMessage::BattleState(BattleStateMessage::PushEvent(
+                    BattleEvent::SquadRouted(*squad_uuid),
+                )));
+            }
+        }
+
+        messages
+    }
+
+    fn vehicle_destroyed_events(&self) -> Vec<RunnerMessage> {
+        let mut messages = vec![];
+
+        for (vehicle_index, board) in self.battle_state.vehicle_board() {
+            if self.battle_state.vehicle_destroyed(vehicle_index) || board.is_empty() {
+                continue;
+            }
+
+            if !board
+                .iter()
+                .any(|(_, i)| self.battle_state.soldier(*i).can_be_count_for_morale())
+            {
+                messages.push(RunnerMessage::BattleState(BattleStateMessage::PushEvent(
+                    BattleEvent::VehicleDestroyed(*vehicle_index),
+                )));
+            }
+        }
+
+        messages
+    }
+
+    pub fn kill_event(
+        &self,
+        shooter: Option<SoldierIndex>,
+        victim: &Soldier,
+        weapon: String,
+        from: &WorldPoint,
+    ) -> RunnerMessage {
+        RunnerMessage::BattleState(BattleStateMessage::PushEvent(BattleEvent::Kill {
+            shooter,
+            victim: victim.uuid(),
+            weapon,
+            distance: distance_between_points(from, &victim.world_point()),
+        }))
+    }
+}
+
+/// A soldier can be killed by several bullets or explosions during the same frame : keep only
+/// the first kill event
+pub fn dedup_kill_events(messages: Vec<RunnerMessage>) -> Vec<RunnerMessage> {
+    let mut victims = HashSet::new();
+
+    messages
+        .into_iter()
+        .filter(|message| match message {
+            RunnerMessage::BattleState(BattleStateMessage::PushEvent(BattleEvent::Kill {
+                victim,
+                ..
+            })) => victims.insert(*victim),
+            _ => true,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use battle_core::{
+        game::explosive::ExplosiveType, physics::event::explosion::Explosion, types::Distance,
+    };
+
+    use crate::runner::test::{runner, soldier};
+
+    use super::*;
+
+    #[test]
+    fn explosion_kill_is_attributed_to_thrower() {
+        let victim = SoldierIndex(0);
+        let thrower = SoldierIndex(1);
+        let mut runner = runner(vec![
+            soldier(0, 0, WorldPoint::new(50., 50.), None),
+            soldier(1, 1, WorldPoint::new(300., 50.), None),
+        ]);
+        runner.battle_state.react(
+            &BattleStateMessage::PushExplosion(Explosion::new(
+                WorldPoint::new(50., 50.),
+                ExplosiveType::FA19241927,
+                Some(thrower),
+            )),
+            0,
+        );
+        runner
+            .battle_state
+            .react(&BattleStateMessage::IncrementFrameI, 0);
+
+        let kills = runner
+            .tick_explosions()
+            .into_iter()
+            .filter_map(|message| match message {
+                RunnerMessage::BattleState(BattleStateMessage::PushEvent(event)) => Some(event),
+                _ => None,
+            })
+            .collect::<Vec<BattleEvent>>();
+        assert_eq!(
+            kills,
+            vec![BattleEvent::Kill {
+                shooter: Some(thrower),
+                victim,
+                weapon: ExplosiveType::FA19241927.to_string(),
+                distance: Distance::from_meters(75),
+            }]
+        );
+    }
+}
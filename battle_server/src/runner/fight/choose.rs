@@ -2,7 +2,7 @@ use rand::seq::SliceRandom;
 
 use battle_core::{
     entity::soldier::Soldier,
-    physics::{utils::distance_between_points, visibility::Visibility},
+    physics::utils::distance_between_points,
     state::battle::BattleState,
     types::{Distance, SoldierIndex, SquadUuid},
 };
@@ -46,7 +46,19 @@ impl ChooseMethod {
 }
 
 impl Runner {
-    // TODO : choose soldier according to distance, weapon type, etc
+    /// Find an opponent to engage by soldier own initiative, according to its squad fire discipline
+    pub fn soldier_find_opponent_to_engage(&self, soldier: &Soldier) -> Option<&Soldier> {
+        if !self
+            .battle_state
+            .squad_may_open_fire(&self.config, &soldier.squad_uuid())
+        {
+            return None;
+        }
+
+        self.soldier_find_opponent_to_target(soldier, None, &ChooseMethod::RandomFromNearest)
+    }
+
+    // TODO : choose soldier according to distance, etc
     // TODO : choose soldier according to other squad targets (distribution)
     // TODO : don't make it if soldier is driver, working assistant, etc
     pub fn soldier_find_opponent_to_target(
@@ -76,6 +88,14 @@ impl Runner {
 
         visibles.retain(|s| s.can_be_designed_as_target());
 
+        // Only opponents which soldier weapon can engage (range, infantry or vehicle)
+        visibles.retain(|s| self.soldier_weapon_for_target(soldier, s).is_some());
+
+        // Defending soldier only engage inside its fire arc
+        if let Some(fire_arc) = soldier.order().fire_arc() {
+            visibles.retain(|s| fire_arc.contains(&soldier.world_point(), &s.world_point()))
+        }
+
         if let Some(squad_index) = squad_index {
             visibles.retain(|s| s.squad_uuid() == *squad_index)
         }
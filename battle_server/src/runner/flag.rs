@@ -3,7 +3,7 @@ use battle_core::{
         flag::{FlagOwnership, FlagsOwnership},
         Side,
     },
-    state::battle::message::BattleStateMessage,
+    state::battle::{event::BattleEvent, message::BattleStateMessage},
 };
 
 use super::{message::RunnerMessage, Runner};
@@ -11,16 +11,21 @@ use super::{message::RunnerMessage, Runner};
 impl Runner {
     pub fn tick_flags(&self) -> Vec<RunnerMessage> {
         puffin::profile_scope!("tick_flags");
-        if self.battle_state.frame_i() % self.config.flags_update_freq() == 0 {
+        if self
+            .battle_state
+            .frame_i()
+            .is_multiple_of(self.config.flags_update_freq())
+        {
+            let mut messages = vec![];
             let mut new_ownerships = vec![];
             for (flag_name, ownership) in self.battle_state.flags().ownerships() {
                 let flag = self.battle_state.map().flag(flag_name);
                 let a_inside = self
                     .battle_state
-                    .there_is_side_soldier_in(&Side::A, flag.shape());
+                    .there_is_side_soldier_in(&Side::A, flag.polygon());
                 let b_inside = self
                     .battle_state
-                    .there_is_side_soldier_in(&Side::B, flag.shape());
+                    .there_is_side_soldier_in(&Side::B, flag.polygon());
 
                 let new_ownership = match (ownership, a_inside, b_inside) {
                     (FlagOwnership::Nobody, true, true) => FlagOwnership::Both,
@@ -40,11 +45,22 @@ impl Runner {
                     (FlagOwnership::Both, false, true) => FlagOwnership::B,
                     (FlagOwnership::Both, false, false) => FlagOwnership::Both,
                 };
+                let captured_by = match new_ownership {
+                    FlagOwnership::A => Some(Side::A),
+                    FlagOwnership::B => Some(Side::B),
+                    FlagOwnership::Nobody | FlagOwnership::Both => None,
+                };
+                if let Some(side) = captured_by.filter(|_| &new_ownership != ownership) {
+                    messages.push(RunnerMessage::BattleState(BattleStateMessage::PushEvent(
+                        BattleEvent::FlagCaptured(flag_name.clone(), side),
+                    )));
+                }
                 new_ownerships.push((flag_name.clone(), new_ownership));
             }
-            return vec![RunnerMessage::BattleState(
+            messages.push(RunnerMessage::BattleState(
                 BattleStateMessage::SetFlagsOwnership(FlagsOwnership::new(new_ownerships)),
-            )];
+            ));
+            return messages;
         }
 
         vec![]
@@ -1,4 +1,9 @@
-use battle_core::{entity::soldier::Soldier, types::SoldierIndex};
+use battle_core::{
+    behavior::gesture::{Gesture, GestureContext},
+    entity::soldier::Soldier,
+    order::Order,
+    types::SoldierIndex,
+};
 
 use crate::runner::Runner;
 
@@ -10,6 +15,16 @@ impl Runner {
         soldier: &Soldier,
         engaged_soldier_index: &SoldierIndex,
     ) -> GestureResult {
+        // Engagement by own initiative stop as soon as squad fire discipline forbid it (hold
+        // fire, ambush not yet triggered, etc). Ordered engagement always fire.
+        if !matches!(soldier.order(), Order::EngageSquad(_))
+            && !self
+                .battle_state
+                .squad_may_open_fire(&self.config, &soldier.squad_uuid())
+        {
+            return GestureResult::Handled(GestureContext::Idle, Gesture::Idle);
+        }
+
         let target_soldier = self.battle_state.soldier(*engaged_soldier_index);
 
         if target_soldier.can_be_designed_as_target() {
@@ -26,3 +41,64 @@ impl Runner {
         GestureResult::Cant(None)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use battle_core::{
+        game::squad::FireDiscipline,
+        state::battle::message::BattleStateMessage,
+        types::{SquadUuid, WorldPoint},
+    };
+
+    use crate::runner::test::{runner, soldier};
+
+    use super::*;
+
+    const SHOOTER: SoldierIndex = SoldierIndex(0);
+    const ENEMY: SoldierIndex = SoldierIndex(1);
+
+    /// Rifleman engaging an enemy, with given squad fire discipline
+    fn engage_gesture(order: Order, fire_discipline: FireDiscipline) -> GestureResult {
+        let mut runner = runner(vec![
+            soldier(
+                0,
+                0,
+                WorldPoint::new(50., 50.),
+                Some(("MosinNagantM1924", "MosinNagant")),
+            ),
+            soldier(1, 1, WorldPoint::new(150., 50.), None),
+        ]);
+        runner.battle_state.soldier_mut(SHOOTER).set_order(order);
+        runner.battle_state.react(
+            &BattleStateMessage::SetSquadFireDiscipline(SquadUuid(0), fire_discipline),
+            0,
+        );
+
+        runner.engage_soldier_gesture(runner.battle_state.soldier(SHOOTER), &ENEMY)
+    }
+
+    fn is_idle(result: &GestureResult) -> bool {
+        matches!(
+            result,
+            GestureResult::Handled(GestureContext::Idle, Gesture::Idle)
+        )
+    }
+
+    #[test]
+    fn engage_by_initiative_follow_fire_discipline() {
+        assert!(!is_idle(&engage_gesture(
+            Order::Idle,
+            FireDiscipline::FireAtWill
+        )));
+        for fire_discipline in [FireDiscipline::HoldFire, FireDiscipline::Ambush] {
+            assert!(is_idle(&engage_gesture(Order::Idle, fire_discipline)));
+        }
+    }
+
+    #[test]
+    fn ordered_engage_ignore_fire_discipline() {
+        let result = engage_gesture(Order::EngageSquad(SquadUuid(1)), FireDiscipline::HoldFire);
+        assert!(matches!(result, GestureResult::Handled(_, _)));
+        assert!(!is_idle(&result));
+    }
+}
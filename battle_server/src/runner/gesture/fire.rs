@@ -2,6 +2,7 @@ use battle_core::{
     behavior::gesture::{Gesture, GestureContext},
     entity::soldier::{Soldier, WeaponClass},
     game::weapon::Weapon,
+    order::Order,
     physics::{utils::distance_between_points, visibility::Visibility},
     types::WorldPoint,
 };
@@ -15,7 +16,7 @@ impl Runner {
         &'a self,
         soldier: &'a Soldier,
         point: &WorldPoint,
-    ) -> Option<(WeaponClass, &Weapon, Visibility)> {
+    ) -> Option<(WeaponClass, &'a Weapon, Visibility)> {
         let visibility = self.battle_state.point_is_visible_by_soldier(
             &self.config,
             soldier,
@@ -29,7 +30,12 @@ impl Runner {
         }
 
         if let Some((weapon_class, weapon)) = self.soldier_weapon_for_point(soldier, point) {
-            if weapon.can_fire() || weapon.can_reload() {
+            // Without spare barrel, weapon must cool down before firing again
+            if weapon.overheated() && !weapon.can_change_barrel() {
+                return None;
+            }
+
+            if weapon.can_fire() || weapon.can_reload() || weapon.jammed() || weapon.overheated() {
                 return Some((weapon_class, weapon, visibility));
             }
 
@@ -51,7 +57,22 @@ impl Runner {
         let (weapon_class, weapon, visibility) = engagement;
         let gesture = match current {
             Gesture::Idle => {
-                if weapon.can_fire() {
+                if weapon.need_setup() {
+                    Gesture::SettingUp(
+                        self.soldier_setup_end(soldier, weapon),
+                        weapon_class.clone(),
+                    )
+                } else if weapon.jammed() {
+                    Gesture::ClearingJam(
+                        self.soldier_jam_clearing_end(soldier, weapon),
+                        weapon_class.clone(),
+                    )
+                } else if weapon.overheated() {
+                    Gesture::ChangingBarrel(
+                        self.soldier_barrel_change_end(soldier, weapon),
+                        weapon_class.clone(),
+                    )
+                } else if weapon.can_fire() {
                     Gesture::Aiming(
                         self.soldier_aiming_end(soldier, weapon),
                         weapon_class.clone(),
@@ -78,7 +99,12 @@ impl Runner {
                 let end = self.soldier_firing_end(soldier, weapon);
                 current.next(*frame_i, Gesture::Firing(end, weapon_class.clone()))
             }
-            Gesture::Firing(_, _) => {
+            // Weapon state is checked again before aiming
+            Gesture::Firing(_, _)
+            | Gesture::ClearingJam(_, _)
+            | Gesture::ChangingBarrel(_, _)
+            | Gesture::SettingUp(_, _)
+            | Gesture::TearingDown(_, _) => {
                 //
                 current.next(*frame_i, Gesture::Idle)
             }
@@ -95,14 +121,35 @@ impl Runner {
     pub fn soldier_fire_point(
         &self,
         soldier: &Soldier,
-        _weapon_class: &WeaponClass,
+        weapon_class: &WeaponClass,
         target_point: &WorldPoint,
     ) -> WorldPoint {
         let mut rng = rand::thread_rng();
         // TODO : change precision according to weapon, stress, distance, etc
         let factor_by_meter = self.config.inaccurate_fire_factor_by_meter;
         let distance = distance_between_points(&soldier.world_point(), target_point);
-        let range = distance.meters() as f32 * factor_by_meter;
+        // Firing from higher ground is more accurate
+        let height_advantage = self
+            .battle_state
+            .map()
+            .height_advantage(&soldier.world_point(), target_point);
+        let elevation_bonus = (height_advantage * self.config.elevation_accuracy_bonus_by_meter)
+            .clamp(0., self.config.elevation_accuracy_bonus_max);
+        let defending = matches!(soldier.order(), Order::Defend(_, _));
+        let weapon_factor = soldier
+            .weapon(weapon_class)
+            .as_ref()
+            .map(|weapon| {
+                let defend_factor = if defending {
+                    weapon.defend_accuracy_factor()
+                } else {
+                    1.
+                };
+                weapon.accuracy_factor(distance.meters() as f32) * defend_factor
+            })
+            .unwrap_or(1.);
+        let range =
+            distance.meters() as f32 * factor_by_meter * weapon_factor * (1. - elevation_bonus);
 
         if range == 0. {
             eprintln!(
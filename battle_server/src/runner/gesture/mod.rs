@@ -1,13 +1,11 @@
 use battle_core::{
+    audio::Sound,
     behavior::{
         gesture::{Gesture, GestureContext},
         Behavior,
     },
     entity::soldier::{Soldier, WeaponClass},
-    game::{
-        weapon::{Shot, Weapon},
-        Side,
-    },
+    game::{weapon::Weapon, Side},
     physics::{
         event::{bullet::BulletFire, cannon_blast::CannonBlast},
         utils::distance_between_points,
@@ -56,6 +54,14 @@ impl Runner {
                 //
                 self.engage_soldier_gesture(soldier, soldier_index)
             }
+            Behavior::MoveTo(_) | Behavior::MoveFastTo(_) | Behavior::SneakTo(_) => {
+                //
+                self.movement_gesture(soldier)
+            }
+            Behavior::Defend(_) | Behavior::Hide(_) => {
+                //
+                self.deploy_gesture(soldier)
+            }
             _ => GestureResult::Handled(GestureContext::Idle, Gesture::Idle),
         };
 
@@ -99,6 +105,36 @@ impl Runner {
                 }
             }
             (_, Gesture::Aiming(_, _)) => {}
+            (_, Gesture::ClearingJam(_, class)) => {
+                return vec![
+                    RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                        soldier.uuid(),
+                        SoldierMessage::ClearWeaponJam(class.clone()),
+                    )),
+                    RunnerMessage::ClientsState(ClientStateMessage::PlayBattleSound(Sound::Clac1)),
+                ];
+            }
+            (_, Gesture::SettingUp(_, class)) => {
+                return vec![RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                    soldier.uuid(),
+                    SoldierMessage::SetWeaponDeployed(class.clone(), true),
+                ))];
+            }
+            (_, Gesture::TearingDown(_, class)) => {
+                return vec![RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                    soldier.uuid(),
+                    SoldierMessage::SetWeaponDeployed(class.clone(), false),
+                ))];
+            }
+            (_, Gesture::ChangingBarrel(_, class)) => {
+                return vec![
+                    RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                        soldier.uuid(),
+                        SoldierMessage::ChangeWeaponBarrel(class.clone()),
+                    )),
+                    RunnerMessage::ClientsState(ClientStateMessage::PlayBattleSound(Sound::Clac1)),
+                ];
+            }
             (GestureContext::Firing(point, target, visibility), Gesture::Firing(_, class)) => {
                 if let Some(weapon) = soldier.weapon(class) {
                     return self.firing_gesture_messages(
@@ -158,7 +194,8 @@ impl Runner {
         let bullet_fires = (0..shot.count())
             .map(|i| {
                 let point = if i > 0 {
-                    let weapon_factor_multiplier = weapon.range_on_burst();
+                    let weapon_factor_multiplier =
+                        weapon.range_on_burst() * self.config.weapon_burst_dispersion_factor;
                     let factor_by_meter = self.config.inaccurate_fire_factor_by_meter;
                     let distance = visibility.distance;
                     let range =
@@ -174,7 +211,7 @@ impl Runner {
                     *point
                 };
                 let sound = if i == 0 {
-                    Some(weapon.gun_fire_sound_type())
+                    Some(weapon.type_().clone())
                 } else {
                     None
                 };
@@ -186,10 +223,23 @@ impl Runner {
                     weapon.ammunition(),
                     sound,
                     shot,
+                    weapon.target(),
+                    Some((soldier.uuid(), weapon.name().to_string())),
                 )))
             })
             .collect();
 
+        let mut jam_messages = vec![];
+        if rng.gen::<f32>() < weapon.jam_chance() {
+            jam_messages.extend([
+                RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                    soldier.uuid(),
+                    SoldierMessage::WeaponJammed(class.clone()),
+                )),
+                RunnerMessage::ClientsState(ClientStateMessage::PlayBattleSound(Sound::Clic1)),
+            ]);
+        }
+
         [
             vec![
                 RunnerMessage::BattleState(BattleStateMessage::Soldier(
@@ -208,6 +258,7 @@ impl Runner {
                 )),
             ],
             bullet_fires,
+            jam_messages,
         ]
         .concat()
     }
@@ -1,25 +1,79 @@
-use battle_core::{config::TARGET_FPS, entity::soldier::Soldier, game::weapon::Weapon};
+use battle_core::{
+    config::TARGET_FPS, entity::soldier::Soldier, game::weapon::Weapon, order::Order,
+};
 use rand::Rng;
 
 use crate::runner::Runner;
 
 impl Runner {
-    pub fn soldier_reloading_end(&self, _soldier: &Soldier, weapon: &Weapon) -> u64 {
+    pub fn soldier_reloading_end(&self, soldier: &Soldier, weapon: &Weapon) -> u64 {
         // TODO : Depending multiple factor
         let mut rng = rand::thread_rng();
-        self.battle_state.frame_i() + TARGET_FPS + weapon.reloading_frames() + rng.gen_range(0..50)
+        // Assistant gunner feed crew-served weapons
+        let assistant_factor =
+            if weapon.crew_served() && self.soldier_assistant_gunner(soldier).is_some() {
+                self.config.assistant_reloading_factor
+            } else {
+                1.
+            };
+        let reloading_frames = (weapon.reloading_frames() as f32
+            * self.config.weapon_reloading_frames_factor
+            * assistant_factor
+            * soldier.traits().reloading_factor()) as u64;
+        self.battle_state.frame_i() + TARGET_FPS + reloading_frames + rng.gen_range(0..50)
     }
 
-    pub fn soldier_aiming_end(&self, _soldier: &Soldier, weapon: &Weapon) -> u64 {
+    pub fn soldier_aiming_end(&self, soldier: &Soldier, weapon: &Weapon) -> u64 {
         // TODO : Depending multiple factor
         let mut rng = rand::thread_rng();
-        self.battle_state.frame_i() + TARGET_FPS + weapon.aiming_frames() + rng.gen_range(0..50)
+        // Experienced soldiers react faster
+        let reaction_frames = (TARGET_FPS as f32 * soldier.traits().reaction_factor()) as u64;
+        let aiming_frames =
+            (weapon.aiming_frames() as f32 * self.config.weapon_aiming_frames_factor) as u64;
+        self.battle_state.frame_i() + reaction_frames + aiming_frames + rng.gen_range(0..50)
     }
 
-    pub fn soldier_firing_end(&self, _soldier: &Soldier, weapon: &Weapon) -> u64 {
+    pub fn soldier_firing_end(&self, soldier: &Soldier, weapon: &Weapon) -> u64 {
         // TODO : Depending multiple factor like weapon, riffle or single shot etc
         let mut rng = rand::thread_rng();
+        // Gunner alone must feed its crew-served weapon himself
+        let unassisted_factor =
+            if weapon.crew_served() && self.soldier_assistant_gunner(soldier).is_none() {
+                self.config.unassisted_firing_frames_factor
+            } else {
+                1.
+            };
         // FIXME: firing_frames depend on Shot type
-        self.battle_state.frame_i() + 5 + weapon.firing_frames() + rng.gen_range(0..50)
+        let firing_frames = (weapon.firing_frames() as f32
+            * self.config.weapon_firing_frames_factor
+            * unassisted_factor) as u64;
+        self.battle_state.frame_i() + 5 + firing_frames + rng.gen_range(0..50)
+    }
+
+    pub fn soldier_setup_end(&self, soldier: &Soldier, weapon: &Weapon) -> u64 {
+        let mut rng = rand::thread_rng();
+        // Team engaging while moving set up its weapon hastily, on an unprepared position
+        let setup_frames = match soldier.order() {
+            Order::MoveTo(_, _) | Order::MoveFastTo(_, _) | Order::SneakTo(_, _) => {
+                weapon.hasty_setup_frames()
+            }
+            _ => weapon.setup_frames(),
+        };
+        self.battle_state.frame_i() + setup_frames + rng.gen_range(0..50)
+    }
+
+    pub fn soldier_teardown_end(&self, _soldier: &Soldier, weapon: &Weapon) -> u64 {
+        let mut rng = rand::thread_rng();
+        self.battle_state.frame_i() + weapon.teardown_frames() + rng.gen_range(0..50)
+    }
+
+    pub fn soldier_jam_clearing_end(&self, _soldier: &Soldier, weapon: &Weapon) -> u64 {
+        let mut rng = rand::thread_rng();
+        self.battle_state.frame_i() + weapon.jam_clearing_frames() + rng.gen_range(0..50)
+    }
+
+    pub fn soldier_barrel_change_end(&self, _soldier: &Soldier, weapon: &Weapon) -> u64 {
+        let mut rng = rand::thread_rng();
+        self.battle_state.frame_i() + weapon.barrel_change_frames() + rng.gen_range(0..50)
     }
 }
@@ -1,32 +1,702 @@
 use battle_core::{
+    behavior::gesture::{Gesture, GestureContext},
+    config::TARGET_FPS,
     entity::soldier::{Soldier, WeaponClass},
-    game::weapon::{Magazine, Weapon},
-    types::WorldPoint,
+    game::weapon::{definition::WeaponTarget, Magazine, Weapon},
+    order::Order,
+    physics::{
+        path::{find_path, PathMode},
+        utils::distance_between_points,
+    },
+    state::battle::message::{BattleStateMessage, SoldierMessage},
+    types::{Distance, WorldPath, WorldPaths, WorldPoint},
 };
 
-use crate::runner::Runner;
+use crate::runner::{message::RunnerMessage, Runner};
+
+use super::GestureResult;
 
 impl Runner {
     pub fn soldier_weapon_for_point<'a>(
         &'a self,
         soldier: &'a Soldier,
-        _point: &WorldPoint,
-    ) -> Option<(WeaponClass, &Weapon)> {
-        // TODO : according to distance, choose weapon
+        point: &WorldPoint,
+    ) -> Option<(WeaponClass, &'a Weapon)> {
+        let distance = distance_between_points(&soldier.world_point(), point);
         soldier
             .main_weapon()
             .as_ref()
+            .filter(|weapon| weapon.in_range(distance.meters() as f32))
             .map(|weapon| (WeaponClass::Main, weapon))
     }
 
+    /// Weapon able to engage given opponent : anti-tank weapons are only used against vehicle
+    /// crews, others against infantry
+    pub fn soldier_weapon_for_target<'a>(
+        &'a self,
+        soldier: &'a Soldier,
+        target: &Soldier,
+    ) -> Option<(WeaponClass, &'a Weapon)> {
+        let target_type = if self.battle_state.soldier_vehicle(target.uuid()).is_some() {
+            WeaponTarget::Vehicle
+        } else {
+            WeaponTarget::Infantry
+        };
+
+        self.soldier_weapon_for_point(soldier, &target.world_point())
+            .filter(|(_, weapon)| weapon.target() == target_type)
+    }
+
+    /// Squad designated assistant, if near enough of soldier to assist him with its crew-served
+    /// weapon
+    pub fn soldier_assistant_gunner(&self, soldier: &Soldier) -> Option<&Soldier> {
+        self.battle_state
+            .squad(soldier.squad_uuid())
+            .assistant()
+            .filter(|i| *i != soldier.uuid())
+            .map(|i| self.battle_state.soldier(i))
+            .filter(|assistant| assistant.can_be_count_for_morale())
+            .filter(|assistant| {
+                distance_between_points(&soldier.world_point(), &assistant.world_point())
+                    <= self.config.assistant_gunner_distance
+            })
+    }
+
+    /// One message cool all weapons (main and secondary) of all soldiers
+    pub fn tick_weapons_cooling(&self) -> Vec<RunnerMessage> {
+        puffin::profile_scope!("tick_weapons_cooling");
+        let freq = self.config.weapon_cooling_freq();
+        if !self.battle_state.frame_i().is_multiple_of(freq)
+            || !self.battle_state.soldiers().iter().any(|s| s.hot_weapon())
+        {
+            return vec![];
+        }
+
+        let seconds = freq as f32 / TARGET_FPS as f32;
+        vec![RunnerMessage::BattleState(BattleStateMessage::CoolWeapons(
+            seconds,
+        ))]
+    }
+
+    /// Moving soldier first tear down its crew-served weapon
+    pub fn movement_gesture(&self, soldier: &Soldier) -> GestureResult {
+        let frame_i = *self.battle_state.frame_i();
+        let gesture = match soldier.gesture() {
+            Gesture::TearingDown(_, _) => soldier.gesture().next(frame_i, Gesture::Idle),
+            _ => match soldier.main_weapon() {
+                Some(weapon) if weapon.deployed() => Gesture::TearingDown(
+                    self.soldier_teardown_end(soldier, weapon),
+                    WeaponClass::Main,
+                ),
+                _ => Gesture::Idle,
+            },
+        };
+
+        GestureResult::Handled(GestureContext::Idle, gesture)
+    }
+
+    /// Defending or hiding soldier set up its crew-served weapon, to be ready to fire
+    pub fn deploy_gesture(&self, soldier: &Soldier) -> GestureResult {
+        let frame_i = *self.battle_state.frame_i();
+        let gesture = match soldier.gesture() {
+            Gesture::SettingUp(_, _) => soldier.gesture().next(frame_i, Gesture::Idle),
+            _ => match soldier.main_weapon() {
+                Some(weapon) if weapon.need_setup() => {
+                    Gesture::SettingUp(self.soldier_setup_end(soldier, weapon), WeaponClass::Main)
+                }
+                _ => Gesture::Idle,
+            },
+        };
+
+        GestureResult::Handled(GestureContext::Idle, gesture)
+    }
+
+    /// Keep a designated assistant beside crew-served weapons gunners and, when a gunner falls,
+    /// send the nearest able squad member to take over its weapon and matching magazines
+    pub fn tick_crew_served_weapons(&self) -> Vec<RunnerMessage> {
+        puffin::profile_scope!("tick_crew_served_weapons");
+        let mut messages = vec![];
+        if !self
+            .battle_state
+            .frame_i()
+            .is_multiple_of(self.config.crew_served_weapons_update_freq())
+        {
+            return messages;
+        }
+
+        for (squad_uuid, squad) in self.battle_state.squads() {
+            let members = squad
+                .subordinates()
+                .iter()
+                .map(|i| self.battle_state.soldier(**i))
+                .filter(|member| member.can_be_count_for_morale())
+                .filter(|member| {
+                    !member
+                        .main_weapon()
+                        .as_ref()
+                        .is_some_and(|weapon| weapon.crew_served())
+                })
+                .collect::<Vec<&Soldier>>();
+            let gunners = squad
+                .members()
+                .iter()
+                .map(|i| self.battle_state.soldier(*i))
+                .filter(|soldier| {
+                    soldier
+                        .main_weapon()
+                        .as_ref()
+                        .is_some_and(|weapon| weapon.crew_served())
+                })
+                .collect::<Vec<&Soldier>>();
+            let nearest = |gunner: &Soldier, max: Option<Distance>| {
+                members
+                    .iter()
+                    .map(|member| {
+                        (
+                            *member,
+                            distance_between_points(&gunner.world_point(), &member.world_point()),
+                        )
+                    })
+                    .filter(|(_, distance)| max.is_none_or(|max| distance <= &max))
+                    .min_by_key(|(_, distance)| distance.millimeters())
+            };
+
+            if let Some(gunner) = gunners.iter().find(|g| g.can_be_count_for_morale()) {
+                let assistant = squad
+                    .assistant()
+                    .filter(|i| members.iter().any(|member| member.uuid() == *i))
+                    .or_else(|| nearest(gunner, None).map(|(member, _)| member.uuid()));
+                if assistant != squad.assistant() {
+                    messages.push(RunnerMessage::BattleState(
+                        BattleStateMessage::SetSquadAssistant(*squad_uuid, assistant),
+                    ));
+                }
+
+                if let Some(assistant) = assistant.map(|i| self.battle_state.soldier(i)) {
+                    if let Some(order) = self.join_gunner_order(gunner, assistant) {
+                        messages.push(RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                            assistant.uuid(),
+                            SoldierMessage::SetOrder(order),
+                        )));
+                    }
+                }
+            }
+
+            for gunner in gunners.iter().filter(|g| !g.can_be_count_for_morale()) {
+                let Some((taker, _)) = nearest(gunner, Some(self.config.weapon_take_over_distance))
+                else {
+                    continue;
+                };
+
+                if self.soldier_is_beside(taker, &gunner.world_point()) {
+                    messages.extend(self.take_over_weapon_messages(gunner, taker));
+                } else if let Some(order) = self.move_to_weapon_order(taker, &gunner.world_point())
+                {
+                    messages.push(RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                        taker.uuid(),
+                        SoldierMessage::SetOrder(order),
+                    )));
+                }
+                // One weapon at once by squad, others will wait for next tick
+                break;
+            }
+        }
+
+        messages
+    }
+
+    /// Squad formations space members wider than assistant distance : once positioned,
+    /// assistant walk to the gunner tile neighbor and keep its order there
+    fn join_gunner_order(&self, gunner: &Soldier, assistant: &Soldier) -> Option<Order> {
+        let then = match assistant.order() {
+            Order::Idle => None,
+            Order::Defend(_, _) | Order::Hide(_) => Some(Box::new(assistant.order().clone())),
+            _ => return None,
+        };
+        // Gunner still moving (move orders have an end point) will be joined once arrived
+        if !assistant.can_be_animated()
+            || gunner.order().end_point().is_some()
+            || distance_between_points(&gunner.world_point(), &assistant.world_point())
+                <= self.config.assistant_gunner_distance
+        {
+            return None;
+        }
+
+        let map = self.battle_state.map();
+        let mut grid_path = find_path(
+            &self.config,
+            map,
+            &map.grid_point_from_world_point(&assistant.world_point()),
+            &map.grid_point_from_world_point(&gunner.world_point()),
+            true,
+            &PathMode::Walk,
+            &None,
+        )?;
+        // Gunner tile is left to the gunner
+        grid_path.pop();
+        if grid_path.is_empty() {
+            return None;
+        }
+
+        let world_path = grid_path
+            .iter()
+            .map(|p| map.world_point_from_grid_point(*p))
+            .collect();
+        Some(Order::MoveTo(
+            WorldPaths::new(vec![WorldPath::new(world_path)]),
+            then,
+        ))
+    }
+
+    /// Soldier is on given point tile or on one of its neighbors
+    fn soldier_is_beside(&self, soldier: &Soldier, point: &WorldPoint) -> bool {
+        let map = self.battle_state.map();
+        let from = map.grid_point_from_world_point(&soldier.world_point());
+        let to = map.grid_point_from_world_point(point);
+        (from.x - to.x).abs() <= 1 && (from.y - to.y).abs() <= 1
+    }
+
+    /// Order to run to given point, if soldier is not already running there
+    fn move_to_weapon_order(&self, soldier: &Soldier, point: &WorldPoint) -> Option<Order> {
+        let map = self.battle_state.map();
+        let to = map.grid_point_from_world_point(point);
+        if let Order::MoveFastTo(paths, _) = soldier.order() {
+            if paths
+                .last_point()
+                .is_some_and(|last| map.grid_point_from_world_point(&last) == to)
+            {
+                return None;
+            }
+        }
+
+        let grid_path = find_path(
+            &self.config,
+            map,
+            &map.grid_point_from_world_point(&soldier.world_point()),
+            &to,
+            true,
+            &PathMode::Walk,
+            &None,
+        )?;
+        let world_path = grid_path
+            .iter()
+            .map(|p| map.world_point_from_grid_point(*p))
+            .collect();
+        Some(Order::MoveFastTo(
+            WorldPaths::new(vec![WorldPath::new(world_path)]),
+            None,
+        ))
+    }
+
+    /// Taker keep its own weapon as secondary one
+    fn take_over_weapon_messages(&self, gunner: &Soldier, taker: &Soldier) -> Vec<RunnerMessage> {
+        let mut weapon = gunner
+            .main_weapon()
+            .clone()
+            .expect("Take over only weapons of gunners");
+        weapon.set_deployed(false);
+        let (taken_magazines, left_magazines): (Vec<Magazine>, Vec<Magazine>) = gunner
+            .magazines()
+            .iter()
+            .cloned()
+            .partition(|magazine| weapon.accepted_magazine(magazine));
+        let taker_magazines = [taker.magazines().clone(), taken_magazines].concat();
+
+        vec![
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                gunner.uuid(),
+                SoldierMessage::SetWeapon(WeaponClass::Main, None),
+            )),
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                gunner.uuid(),
+                SoldierMessage::SetMagazines(left_magazines),
+            )),
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                taker.uuid(),
+                SoldierMessage::SetWeapon(WeaponClass::Secondary, taker.main_weapon().clone()),
+            )),
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                taker.uuid(),
+                SoldierMessage::SetWeapon(WeaponClass::Main, Some(weapon)),
+            )),
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                taker.uuid(),
+                SoldierMessage::SetMagazines(taker_magazines),
+            )),
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                taker.uuid(),
+                SoldierMessage::SetGesture(Gesture::Idle),
+            )),
+        ]
+    }
+
     pub fn soldier_can_reload_with<'a>(
         &'a self,
         soldier: &'a Soldier,
         weapon: &Weapon,
-    ) -> Option<&Magazine> {
+    ) -> Option<&'a Magazine> {
         soldier
             .magazines()
             .iter()
             .find(|&magazine| weapon.accepted_magazine(magazine))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::runner::test::{runner, soldier};
+    use battle_core::{
+        behavior::{Behavior, Body},
+        game::weapon::{MagazineType, Shot},
+        physics::visibility::Visibility,
+        types::{Angle, SoldierIndex, SquadUuid},
+    };
+
+    use super::*;
+
+    const GUNNER: SoldierIndex = SoldierIndex(0);
+
+    fn mg34_runner(order: Order, behavior: Behavior) -> Runner {
+        let mut runner = runner(vec![soldier(
+            0,
+            0,
+            WorldPoint::new(50., 50.),
+            Some(("Mg34", "Patronengurtx792x57s50")),
+        )]);
+        let gunner = runner.battle_state.soldier_mut(GUNNER);
+        gunner.set_order(order);
+        gunner.set_behavior(behavior);
+        runner
+    }
+
+    const RIFLEMAN: SoldierIndex = SoldierIndex(1);
+    const OTHER_RIFLEMAN: SoldierIndex = SoldierIndex(2);
+
+    /// Mg34 gunner (squad leader) and two riflemen at given points
+    fn team_runner(rifleman: WorldPoint, other_rifleman: WorldPoint) -> Runner {
+        runner(vec![
+            soldier(
+                0,
+                0,
+                WorldPoint::new(50., 50.),
+                Some(("Mg34", "Patronengurtx792x57s50")),
+            ),
+            soldier(1, 0, rifleman, Some(("MosinNagantM1924", "MosinNagant"))),
+            soldier(
+                2,
+                0,
+                other_rifleman,
+                Some(("MosinNagantM1924", "MosinNagant")),
+            ),
+        ])
+    }
+
+    fn soldier_messages(messages: &[RunnerMessage], index: SoldierIndex) -> Vec<&SoldierMessage> {
+        messages
+            .iter()
+            .filter_map(|message| match message {
+                RunnerMessage::BattleState(BattleStateMessage::Soldier(i, message))
+                    if *i == index =>
+                {
+                    Some(message)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn move_order() -> Order {
+        Order::MoveTo(
+            WorldPaths::new(vec![WorldPath::new(vec![WorldPoint::new(200., 50.)])]),
+            None,
+        )
+    }
+
+    fn new_gesture(messages: &[RunnerMessage]) -> Option<&Gesture> {
+        messages.iter().find_map(|message| match message {
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                _,
+                SoldierMessage::SetGesture(gesture),
+            )) => Some(gesture),
+            _ => None,
+        })
+    }
+
+    /// Fire long bursts until weapon is overheated, then give it a full belt
+    fn overheat(weapon: &mut Weapon) {
+        let belt = || Magazine::full(MagazineType::new("Patronengurtx792x57s250"));
+        while !weapon.overheated() {
+            weapon.set_magazine(belt());
+            weapon.shot(&Shot::x16);
+        }
+        weapon.set_magazine(belt());
+    }
+
+    /// Gesture chosen by a gunner engaging with its (deployed) weapon, and its messages
+    fn engage_gesture(runner: &Runner) -> (Gesture, Vec<RunnerMessage>) {
+        let gunner = runner.battle_state.soldier(GUNNER);
+        let weapon = gunner.main_weapon().as_ref().unwrap();
+        let visibility = Visibility::between_soldiers_no(gunner, gunner);
+        let (context, gesture) =
+            runner.engage_point_gesture(gunner, (WeaponClass::Main, weapon, visibility));
+        let messages = runner.new_gesture_messages(gunner, &context, &gesture);
+        (gesture, messages)
+    }
+
+    #[test]
+    fn jammed_weapon_is_cleared() {
+        let mut runner = mg34_runner(Order::Defend(Angle(0.), None), Behavior::Defend(Angle(0.)));
+        let gunner = runner.battle_state.soldier_mut(GUNNER);
+        gunner.set_weapon_deployed(&WeaponClass::Main, true);
+        gunner.jam_weapon(&WeaponClass::Main);
+
+        let (gesture, messages) = engage_gesture(&runner);
+        assert!(matches!(
+            gesture,
+            Gesture::ClearingJam(_, WeaponClass::Main)
+        ));
+        assert!(soldier_messages(&messages, GUNNER)
+            .contains(&&SoldierMessage::ClearWeaponJam(WeaponClass::Main)));
+
+        runner
+            .battle_state
+            .soldier_mut(GUNNER)
+            .clear_weapon_jam(&WeaponClass::Main);
+        let (gesture, _) = engage_gesture(&runner);
+        assert!(matches!(gesture, Gesture::Aiming(_, WeaponClass::Main)));
+    }
+
+    #[test]
+    fn overheated_weapon_barrel_is_changed() {
+        let mut runner = mg34_runner(Order::Defend(Angle(0.), None), Behavior::Defend(Angle(0.)));
+        let gunner = runner.battle_state.soldier_mut(GUNNER);
+        gunner.set_weapon_deployed(&WeaponClass::Main, true);
+        overheat(gunner.weapon_mut(&WeaponClass::Main).as_mut().unwrap());
+
+        let (gesture, messages) = engage_gesture(&runner);
+        assert!(matches!(
+            gesture,
+            Gesture::ChangingBarrel(_, WeaponClass::Main)
+        ));
+        assert!(soldier_messages(&messages, GUNNER)
+            .contains(&&SoldierMessage::ChangeWeaponBarrel(WeaponClass::Main)));
+
+        runner
+            .battle_state
+            .soldier_mut(GUNNER)
+            .change_weapon_barrel(&WeaponClass::Main);
+        let (gesture, _) = engage_gesture(&runner);
+        assert!(matches!(gesture, Gesture::Aiming(_, WeaponClass::Main)));
+    }
+
+    #[test]
+    fn cooling_cool_every_weapon_class() {
+        let mut runner = mg34_runner(Order::Idle, Behavior::Idle(Body::Crouched));
+        assert!(runner.tick_weapons_cooling().is_empty());
+
+        // Only the secondary weapon is hot
+        let gunner = runner.battle_state.soldier_mut(GUNNER);
+        let mut secondary = gunner.main_weapon().clone().unwrap();
+        overheat(&mut secondary);
+        gunner.set_weapon(&WeaponClass::Secondary, Some(secondary));
+
+        let messages = runner.tick_weapons_cooling();
+        assert_eq!(messages.len(), 1);
+        for message in messages {
+            match message {
+                RunnerMessage::BattleState(message) => {
+                    runner.battle_state.react(&message, 0);
+                }
+                message => panic!("Expected battle state message, got {:?}", message),
+            }
+        }
+
+        let gunner = runner.battle_state.soldier(GUNNER);
+        let heat = |class| gunner.weapon(&class).as_ref().unwrap().heat();
+        assert_eq!(heat(WeaponClass::Main), 0.);
+        assert!(heat(WeaponClass::Secondary) < 1.);
+    }
+
+    #[test]
+    fn defending_team_set_up_its_weapon() {
+        for (order, behavior) in [
+            (Order::Defend(Angle(0.), None), Behavior::Defend(Angle(0.))),
+            (Order::Hide(Angle(0.)), Behavior::Hide(Angle(0.))),
+        ] {
+            let runner = mg34_runner(order, behavior);
+            let gunner = runner.battle_state.soldier(GUNNER);
+            let setup_frames = gunner.main_weapon().as_ref().unwrap().setup_frames();
+
+            let messages = runner.soldier_gesture(gunner);
+            match new_gesture(&messages) {
+                Some(Gesture::SettingUp(end, WeaponClass::Main)) => {
+                    assert!((setup_frames..setup_frames + 50).contains(end))
+                }
+                gesture => panic!("Expected setting up gesture, got {:?}", gesture),
+            }
+        }
+    }
+
+    #[test]
+    fn deployed_weapon_is_not_set_up_again() {
+        let mut runner = mg34_runner(Order::Defend(Angle(0.), None), Behavior::Defend(Angle(0.)));
+        runner
+            .battle_state
+            .soldier_mut(GUNNER)
+            .set_weapon_deployed(&WeaponClass::Main, true);
+        let gunner = runner.battle_state.soldier(GUNNER);
+
+        assert_eq!(new_gesture(&runner.soldier_gesture(gunner)), None);
+    }
+
+    #[test]
+    fn moving_team_set_up_its_weapon_hastily() {
+        let defending = mg34_runner(
+            Order::Defend(Angle(0.), None),
+            Behavior::Idle(Body::StandUp),
+        );
+        let moving = mg34_runner(move_order(), Behavior::MoveTo(WorldPaths::new(vec![])));
+        let weapon = defending
+            .battle_state
+            .soldier(GUNNER)
+            .main_weapon()
+            .clone()
+            .unwrap();
+        assert!(weapon.hasty_setup_frames() > weapon.setup_frames() + 50);
+
+        let deliberate =
+            defending.soldier_setup_end(defending.battle_state.soldier(GUNNER), &weapon);
+        let hasty = moving.soldier_setup_end(moving.battle_state.soldier(GUNNER), &weapon);
+        assert!((weapon.setup_frames()..weapon.setup_frames() + 50).contains(&deliberate));
+        assert!((weapon.hasty_setup_frames()..weapon.hasty_setup_frames() + 50).contains(&hasty));
+    }
+
+    #[test]
+    fn moving_soldier_tear_down_its_weapon() {
+        let mut runner = mg34_runner(move_order(), Behavior::MoveTo(WorldPaths::new(vec![])));
+        runner
+            .battle_state
+            .soldier_mut(GUNNER)
+            .set_weapon_deployed(&WeaponClass::Main, true);
+        let gunner = runner.battle_state.soldier(GUNNER);
+        let teardown_frames = gunner.main_weapon().as_ref().unwrap().teardown_frames();
+
+        match runner.movement_gesture(gunner) {
+            GestureResult::Handled(_, Gesture::TearingDown(end, WeaponClass::Main)) => {
+                assert!((teardown_frames..teardown_frames + 50).contains(&end))
+            }
+            _ => panic!("Expected tearing down gesture"),
+        }
+    }
+
+    #[test]
+    fn designated_assistant_assist_gunner() {
+        let runner = team_runner(WorldPoint::new(80., 50.), WorldPoint::new(55., 50.));
+        let gunner = runner.battle_state.soldier(GUNNER);
+        assert_eq!(
+            runner.battle_state.squad(SquadUuid(0)).assistant(),
+            Some(RIFLEMAN)
+        );
+        // Other rifleman is beside the gunner, but is not its assistant
+        assert!(runner.soldier_assistant_gunner(gunner).is_none());
+
+        let runner = team_runner(WorldPoint::new(55., 50.), WorldPoint::new(80., 50.));
+        let gunner = runner.battle_state.soldier(GUNNER);
+        assert_eq!(
+            runner.soldier_assistant_gunner(gunner).map(|s| s.uuid()),
+            Some(RIFLEMAN)
+        );
+    }
+
+    #[test]
+    fn assistant_join_gunner_after_a_move() {
+        // Formation left the assistant at formation spacing of its gunner
+        let mut runner = team_runner(WorldPoint::new(80., 50.), WorldPoint::new(50., 80.));
+        let defend = Order::Defend(Angle(0.), None);
+        runner
+            .battle_state
+            .soldier_mut(RIFLEMAN)
+            .set_order(defend.clone());
+        let gunner = runner.battle_state.soldier(GUNNER);
+        assert!(runner.soldier_assistant_gunner(gunner).is_none());
+
+        let messages = runner.tick_crew_served_weapons();
+        let last = match soldier_messages(&messages, RIFLEMAN).as_slice() {
+            [SoldierMessage::SetOrder(Order::MoveTo(paths, Some(then)))] => {
+                assert_eq!(then.as_ref(), &defend);
+                paths.last_point().unwrap()
+            }
+            messages => panic!("Expected move order, got {:?}", messages),
+        };
+        assert!(soldier_messages(&messages, OTHER_RIFLEMAN).is_empty());
+
+        runner
+            .battle_state
+            .soldier_mut(RIFLEMAN)
+            .set_world_point(last);
+        let gunner = runner.battle_state.soldier(GUNNER);
+        assert_eq!(
+            runner.soldier_assistant_gunner(gunner).map(|s| s.uuid()),
+            Some(RIFLEMAN)
+        );
+        assert!(runner.tick_crew_served_weapons().is_empty());
+    }
+
+    #[test]
+    fn assistant_wait_for_moving_gunner() {
+        let mut runner = team_runner(WorldPoint::new(80., 50.), WorldPoint::new(50., 80.));
+        runner
+            .battle_state
+            .soldier_mut(GUNNER)
+            .set_order(move_order());
+
+        assert!(runner.tick_crew_served_weapons().is_empty());
+    }
+
+    #[test]
+    fn fallen_assistant_is_replaced() {
+        let mut runner = team_runner(WorldPoint::new(55., 50.), WorldPoint::new(80., 50.));
+        runner.battle_state.soldier_mut(RIFLEMAN).set_alive(false);
+
+        let messages = runner.tick_crew_served_weapons();
+        assert!(messages.iter().any(|message| matches!(
+            message,
+            RunnerMessage::BattleState(BattleStateMessage::SetSquadAssistant(
+                SquadUuid(0),
+                Some(OTHER_RIFLEMAN)
+            ))
+        )));
+    }
+
+    #[test]
+    fn taker_run_to_fallen_gunner_weapon() {
+        let mut runner = team_runner(WorldPoint::new(70., 50.), WorldPoint::new(200., 50.));
+        runner.battle_state.soldier_mut(GUNNER).set_alive(false);
+
+        let messages = runner.tick_crew_served_weapons();
+        match soldier_messages(&messages, RIFLEMAN).as_slice() {
+            [SoldierMessage::SetOrder(Order::MoveFastTo(paths, None))] => {
+                let last = paths.last_point().unwrap();
+                assert!(runner.soldier_is_beside(runner.battle_state.soldier(GUNNER), &last));
+            }
+            messages => panic!("Expected move order, got {:?}", messages),
+        }
+        // Other rifleman is too far
+        assert!(soldier_messages(&messages, OTHER_RIFLEMAN).is_empty());
+    }
+
+    #[test]
+    fn taker_beside_fallen_gunner_take_over_its_weapon() {
+        let mut runner = team_runner(WorldPoint::new(53., 50.), WorldPoint::new(80., 50.));
+        runner.battle_state.soldier_mut(GUNNER).set_alive(false);
+        let rifle = runner.battle_state.soldier(RIFLEMAN).main_weapon().clone();
+
+        let messages = runner.tick_crew_served_weapons();
+        let messages = soldier_messages(&messages, RIFLEMAN);
+        assert!(messages.contains(&&SoldierMessage::SetWeapon(WeaponClass::Secondary, rifle)));
+        assert!(messages.iter().any(|message| matches!(
+            message,
+            SoldierMessage::SetWeapon(WeaponClass::Main, Some(weapon)) if weapon.crew_served()
+        )));
+    }
+}
@@ -1,4 +1,6 @@
 use battle_core::{
+    config::ChangeConfigMessage,
+    game::weapon::definition::WeaponDefinitions,
     message::{InputMessage, OutputMessage},
     state::battle::BattleState,
 };
@@ -23,7 +25,27 @@ impl Runner {
             for input in inputs {
                 match input {
                     InputMessage::LoadDeployment(deployment) => {
-                        self.battle_state.inject(&deployment)
+                        if let Err(error) = deployment.validate_weapons(WeaponDefinitions::get()) {
+                            log::error!("Refused deployment : {}", error);
+                            continue;
+                        }
+                        if let Err(error) = deployment.validate_traits() {
+                            log::error!("Refused deployment : {}", error);
+                            continue;
+                        }
+                        self.battle_state.inject(&deployment);
+                        // Command line rule set wins over deployment one
+                        if let Some(rule_set) = deployment
+                            .rule_set()
+                            .filter(|_| !self.config.rule_set_locked)
+                        {
+                            self.config.apply_rule_set(rule_set);
+                            self.output.send(vec![OutputMessage::ChangeConfig(
+                                ChangeConfigMessage::RuleSet(rule_set),
+                            )])?;
+                        }
+                        // Clients receive it with next complete sync
+                        self.battle_state.set_rule_set(self.config.rule_set);
                     }
                     InputMessage::LoadControl((a_control, b_control)) => {
                         //
@@ -31,8 +53,13 @@ impl Runner {
                             .update_flags_from_control(a_control, b_control);
                     }
                     InputMessage::RequireCompleteSync => {
-                        self.output
-                            .send(vec![OutputMessage::LoadFromCopy(self.battle_state.copy())])?;
+                        self.output.send(vec![
+                            OutputMessage::LoadFromCopy(Box::new(self.battle_state.copy())),
+                            // Announce active rule set to (maybe newly connected) clients
+                            OutputMessage::ChangeConfig(ChangeConfigMessage::RuleSet(
+                                self.config.rule_set,
+                            )),
+                        ])?;
                     }
                     InputMessage::BattleState(battle_state_message) => {
                         side_effects.extend(
@@ -46,7 +73,10 @@ impl Runner {
                         self.config.react(&change_config);
                     }
                     InputMessage::SetBattleState(copy) => {
-                        //
+                        if let Err(error) = copy.validate_weapons(WeaponDefinitions::get()) {
+                            log::error!("Refused battle state : {}", error);
+                            continue;
+                        }
                         self.battle_state = BattleState::from_copy(&copy, self.battle_state.map());
                         self.battle_state.resolve();
                         self.output.send(vec![OutputMessage::LoadFromCopy(copy)])?;
@@ -16,6 +16,7 @@ use std::{
 
 mod behavior;
 mod engage;
+mod event;
 mod fight;
 mod flag;
 mod gesture;
@@ -28,6 +29,8 @@ mod phase;
 mod physics;
 mod react;
 mod soldier;
+#[cfg(test)]
+mod test;
 mod tick;
 mod update;
 mod utils;
@@ -1,9 +1,12 @@
 use battle_core::{
-    behavior::{Behavior, Body},
+    behavior::{gesture::Gesture, Behavior, Body},
+    entity::soldier::Soldier,
     order::Order,
+    physics::{path::PathMode, utils::distance_between_points},
     state::battle::message::{BattleStateMessage, SoldierMessage},
     types::{SoldierIndex, WorldPaths},
 };
+use glam::Vec2;
 
 use super::{message::RunnerMessage, Runner};
 
@@ -15,13 +18,24 @@ impl Runner {
     ) -> Vec<RunnerMessage> {
         let mut messages = vec![];
         let soldier = self.battle_state.soldier(soldier_index);
+
+        // Crew-served weapon must be torn down before moving
+        if matches!(soldier.gesture(), Gesture::TearingDown(_, _)) {
+            return messages;
+        }
         let point = path.next_point().expect("Must have point in path");
 
         // There is a next point in path, go to it
+        let map = self.battle_state.map();
+        let terrain_velocity_factor = map
+            .terrain_tile_at(&map.grid_point_from_world_point(&soldier.world_point()))
+            .map(|tile| tile.type_().velocity_factor())
+            .unwrap_or(1.0);
         let velocity = self
             .config
             .behavior_velocity(soldier.behavior())
-            .expect("Entity behavior must have velocity when move code called");
+            .expect("Entity behavior must have velocity when move code called")
+            * terrain_velocity_factor;
         let vector = (point.to_vec2() - soldier.world_point().to_vec2()).normalize() * velocity;
 
         // Point reached
@@ -52,6 +66,12 @@ impl Runner {
                         SoldierMessage::SetOrder(order),
                     )),
                 ]);
+
+                if self.soldier_is_squad_leader(soldier_index) {
+                    messages.push(RunnerMessage::BattleState(
+                        BattleStateMessage::SetSquadDestinationReached(soldier.squad_uuid()),
+                    ));
+                }
             } else {
                 messages.push(RunnerMessage::BattleState(BattleStateMessage::Soldier(
                     soldier_index,
@@ -61,6 +81,11 @@ impl Runner {
 
             // Movement required
         } else {
+            let vector = if self.config.flow_field_movement {
+                self.steered_vector(soldier, vector)
+            } else {
+                vector
+            };
             let new_point = soldier.world_point().apply(vector);
             messages.push(RunnerMessage::BattleState(BattleStateMessage::Soldier(
                 soldier_index,
@@ -70,4 +95,137 @@ impl Runner {
 
         messages
     }
+
+    /// Move vector deviated from near moving squad mates, so soldiers following the same flow
+    /// field don't walk into each other. Only squad members are considered to keep it cheap
+    /// with many soldiers. Never steer backward or into a tile which can't be entered.
+    fn steered_vector(&self, soldier: &Soldier, vector: Vec2) -> Vec2 {
+        let radius = self.config.flow_field_steering_radius.millimeters();
+        let mut separation = Vec2::ZERO;
+
+        for other_index in self.battle_state.squad(soldier.squad_uuid()).members() {
+            let other = self.battle_state.soldier(*other_index);
+            if other.uuid() == soldier.uuid()
+                || !other.can_be_animated()
+                || self.battle_state.soldier_board(other.uuid()).is_some()
+                || !matches!(
+                    other.behavior(),
+                    Behavior::MoveTo(_) | Behavior::MoveFastTo(_) | Behavior::SneakTo(_)
+                )
+            {
+                continue;
+            }
+
+            let distance =
+                distance_between_points(&soldier.world_point(), &other.world_point()).millimeters();
+            let away = (soldier.world_point().to_vec2() - other.world_point().to_vec2())
+                .normalize_or_zero();
+            if distance < radius {
+                // Nearest soldiers push harder
+                separation += away * (1. - distance as f32 / radius as f32);
+            }
+        }
+
+        if separation == Vec2::ZERO {
+            return vector;
+        }
+
+        let steered = (vector.normalize() + separation * self.config.flow_field_steering_factor)
+            .normalize_or_zero()
+            * vector.length();
+        if steered.dot(vector) <= 0. {
+            return vector;
+        }
+
+        let map = self.battle_state.map();
+        let path_mode = match soldier.behavior() {
+            Behavior::SneakTo(_) => PathMode::Sneak,
+            _ => PathMode::Walk,
+        };
+        let point = map.grid_point_from_world_point(&soldier.world_point().apply(steered));
+        match map.terrain_tile_at(&point) {
+            Some(tile) if map.can_enter(&point, tile, &path_mode) => steered,
+            _ => vector,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use battle_core::types::{WorldPath, WorldPoint};
+
+    use crate::runner::test::{runner, soldier};
+
+    use super::*;
+
+    fn new_position(messages: &[RunnerMessage]) -> Option<WorldPoint> {
+        messages.iter().find_map(|message| match message {
+            RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                _,
+                SoldierMessage::SetWorldPosition(point),
+            )) => Some(*point),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn moving_soldiers_steer_away_from_each_other() {
+        let mut runner = runner(vec![
+            soldier(0, 0, WorldPoint::new(100., 100.), None),
+            soldier(1, 0, WorldPoint::new(100., 103.), None),
+        ]);
+        let paths = WorldPaths::new(vec![WorldPath::new(vec![WorldPoint::new(200., 100.)])]);
+        for index in [SoldierIndex(0), SoldierIndex(1)] {
+            let soldier = runner.battle_state.soldier_mut(index);
+            soldier.set_order(Order::MoveTo(paths.clone(), None));
+            soldier.set_behavior(Behavior::MoveTo(paths.clone()));
+        }
+
+        let straight = new_position(&runner.movement_updates(SoldierIndex(0), &paths)).unwrap();
+        runner.config.flow_field_movement = true;
+        let steered = new_position(&runner.movement_updates(SoldierIndex(0), &paths)).unwrap();
+
+        assert_eq!(straight.y, 100.);
+        assert!(straight.x > 100.);
+        // Pushed away from the other soldier, still progressing along path
+        assert!(steered.y < 100.);
+        assert!(steered.x > 100.);
+    }
+
+    #[test]
+    fn other_squads_soldiers_do_not_steer_moving_ones() {
+        let mut runner = runner(vec![
+            soldier(0, 0, WorldPoint::new(100., 100.), None),
+            soldier(1, 1, WorldPoint::new(100., 103.), None),
+        ]);
+        runner.config.flow_field_movement = true;
+        let paths = WorldPaths::new(vec![WorldPath::new(vec![WorldPoint::new(200., 100.)])]);
+        for index in [SoldierIndex(0), SoldierIndex(1)] {
+            let soldier = runner.battle_state.soldier_mut(index);
+            soldier.set_order(Order::MoveTo(paths.clone(), None));
+            soldier.set_behavior(Behavior::MoveTo(paths.clone()));
+        }
+
+        let position = new_position(&runner.movement_updates(SoldierIndex(0), &paths)).unwrap();
+
+        assert_eq!(position.y, 100.);
+    }
+
+    #[test]
+    fn idle_soldiers_do_not_steer_moving_ones() {
+        let mut runner = runner(vec![
+            soldier(0, 0, WorldPoint::new(100., 100.), None),
+            soldier(1, 0, WorldPoint::new(100., 103.), None),
+        ]);
+        runner.config.flow_field_movement = true;
+        let paths = WorldPaths::new(vec![WorldPath::new(vec![WorldPoint::new(200., 100.)])]);
+        runner
+            .battle_state
+            .soldier_mut(SoldierIndex(0))
+            .set_behavior(Behavior::MoveTo(paths.clone()));
+
+        let position = new_position(&runner.movement_updates(SoldierIndex(0), &paths)).unwrap();
+
+        assert_eq!(position.y, 100.);
+    }
 }
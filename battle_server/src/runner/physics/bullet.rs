@@ -2,6 +2,7 @@ use battle_core::{
     audio::Sound,
     behavior::Body,
     entity::soldier::Soldier,
+    game::weapon::definition::WeaponTarget,
     physics::{
         coverage::SoldierCovered, event::bullet::BulletFire, utils::distance_between_points,
     },
@@ -32,6 +33,10 @@ impl Runner {
             "bullet_fire_effects",
             format!("start={} end={}", bullet_fire.start(), bullet_fire.end())
         );
+        if bullet_fire.effective_against() == &WeaponTarget::Vehicle {
+            return self.anti_vehicle_bullet_fire_effects(bullet_fire);
+        }
+
         let mut messages = vec![];
         let point = bullet_fire.point();
 
@@ -49,7 +54,8 @@ impl Runner {
                 continue;
             }
 
-            let cover = SoldierCovered::new(self.battle_state.map(), bullet_fire, soldier);
+            let cover =
+                SoldierCovered::new(&self.config, self.battle_state.map(), bullet_fire, soldier);
             let from = &soldier.world_point();
             let distance = distance_between_points(from, point);
             // FIXME these values in config
@@ -66,7 +72,8 @@ impl Runner {
                 messages.extend(self.covered_bullet_effects(soldier));
                 messages.extend(self.proximity_bullet_effects(soldier, &distance))
             } else if body_impact {
-                messages.extend(self.killing_bullet_effects(soldier))
+                messages.extend(self.killing_bullet_effects(soldier));
+                messages.push(self.bullet_kill_event(bullet_fire, soldier));
             } else if proximity {
                 messages.extend(self.proximity_bullet_effects(soldier, &distance))
             }
@@ -75,6 +82,58 @@ impl Runner {
         messages
     }
 
+    /// Anti-tank projectile : vehicles have no damage model yet, so a hit vehicle loose all its
+    /// crew. Infantry around impact is only frightened.
+    fn anti_vehicle_bullet_fire_effects(&self, bullet_fire: &BulletFire) -> Vec<RunnerMessage> {
+        let mut messages = vec![];
+        let point = bullet_fire.point();
+
+        for vehicle in self.battle_state.vehicles() {
+            if !vehicle.chassis_shape().contains(point) {
+                continue;
+            }
+
+            messages.push(RunnerMessage::ClientsState(
+                ClientStateMessage::PlayBattleSound(Sound::MetalHit1),
+            ));
+            if let Some(board) = self.battle_state.vehicle_board().get(vehicle.uuid()) {
+                for (_, soldier_index) in board {
+                    let soldier = self.battle_state.soldier(*soldier_index);
+                    if soldier.can_feel_bullet_fire() {
+                        messages.extend(self.killing_bullet_effects(soldier));
+                        messages.push(self.bullet_kill_event(bullet_fire, soldier));
+                    }
+                }
+            }
+        }
+
+        for soldier in self.battle_state.soldiers() {
+            if !soldier.can_feel_bullet_fire()
+                || self
+                    .battle_state
+                    .soldier_vehicle_place(soldier.uuid())
+                    .is_some()
+            {
+                continue;
+            }
+
+            let distance = distance_between_points(&soldier.world_point(), point);
+            if distance.meters() < 30 {
+                messages.extend(self.proximity_bullet_effects(soldier, &distance))
+            }
+        }
+
+        messages
+    }
+
+    fn bullet_kill_event(&self, bullet_fire: &BulletFire, soldier: &Soldier) -> RunnerMessage {
+        let (shooter, weapon) = match bullet_fire.shooter() {
+            Some((shooter, weapon)) => (Some(*shooter), weapon.clone()),
+            None => (None, "Unknown".to_string()),
+        };
+        self.kill_event(shooter, soldier, weapon, bullet_fire.from())
+    }
+
     pub fn killing_bullet_effects(&self, soldier: &Soldier) -> Vec<RunnerMessage> {
         puffin::profile_scope!("KillingBullet", soldier.uuid().to_string());
         let mut messages = self.soldier_die(soldier.uuid());
@@ -164,3 +223,100 @@ impl Runner {
         self.soldier_proximity_bullet(soldier.uuid(), distance)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use battle_core::{
+        deployment::VehicleDeployment,
+        entity::vehicle::{OnBoardPlace, VehicleType},
+        game::weapon::{Ammunition, Shot},
+        state::battle::{event::BattleEvent, message::BattleStateMessage},
+        types::{SoldierIndex, SoldiersOnBoard, VehicleIndex, WorldPoint},
+    };
+
+    use crate::runner::test::{runner_with_vehicles, soldier};
+
+    use super::*;
+
+    #[test]
+    fn anti_tank_hit_kills_vehicle_crew_only() {
+        let vehicle_point = WorldPoint::new(200., 200.);
+        let runner = runner_with_vehicles(
+            vec![
+                soldier(0, 0, vehicle_point, None),
+                soldier(1, 0, vehicle_point, None),
+                soldier(2, 1, WorldPoint::new(200., 240.), None),
+                soldier(3, 2, WorldPoint::new(50., 50.), None),
+            ],
+            vec![VehicleDeployment::new(
+                VehicleIndex(0),
+                VehicleType::T26,
+                vehicle_point,
+            )],
+            SoldiersOnBoard::from([
+                (SoldierIndex(0), (VehicleIndex(0), OnBoardPlace::Driver)),
+                (
+                    SoldierIndex(1),
+                    (VehicleIndex(0), OnBoardPlace::MainTurretGunner),
+                ),
+            ]),
+        );
+        let bullet_fire = BulletFire::new(
+            0,
+            WorldPoint::new(50., 50.),
+            vehicle_point,
+            None,
+            Ammunition("14.5×114mm".to_string()),
+            None,
+            Shot::x1,
+            WeaponTarget::Vehicle,
+            Some((SoldierIndex(3), "PTRD-41".to_string())),
+        );
+
+        let messages = runner.bullet_fire_effects(&bullet_fire);
+
+        let mut victims = messages
+            .iter()
+            .filter_map(|message| match message {
+                RunnerMessage::BattleState(BattleStateMessage::PushEvent(BattleEvent::Kill {
+                    shooter,
+                    victim,
+                    ..
+                })) => {
+                    assert_eq!(shooter, &Some(SoldierIndex(3)));
+                    Some(*victim)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        victims.sort_by_key(|victim| victim.0);
+        assert_eq!(victims, vec![SoldierIndex(0), SoldierIndex(1)]);
+    }
+
+    #[test]
+    fn anti_tank_miss_kills_nobody() {
+        let runner = runner_with_vehicles(
+            vec![soldier(0, 0, WorldPoint::new(200., 200.), None)],
+            vec![],
+            SoldiersOnBoard::new(),
+        );
+        let bullet_fire = BulletFire::new(
+            0,
+            WorldPoint::new(50., 50.),
+            WorldPoint::new(200., 200.),
+            None,
+            Ammunition("14.5×114mm".to_string()),
+            None,
+            Shot::x1,
+            WeaponTarget::Vehicle,
+            None,
+        );
+
+        // Infantry on impact point is not harmed by an anti-tank projectile
+        let messages = runner.bullet_fire_effects(&bullet_fire);
+        assert!(!messages.iter().any(|message| matches!(
+            message,
+            RunnerMessage::BattleState(BattleStateMessage::PushEvent(BattleEvent::Kill { .. }))
+        )));
+    }
+}
@@ -2,9 +2,12 @@ use battle_core::audio::Sound;
 use battle_core::entity::soldier::Soldier;
 use battle_core::entity::vehicle::Vehicle;
 use battle_core::game::explosive::ExplosiveType;
+use battle_core::map::terrain::{TerrainChange, TileType};
 use battle_core::physics::event::explosion::Explosion;
-use battle_core::physics::utils::distance_between_points;
+use battle_core::physics::utils::{distance_between_points, DISTANCE_TO_METERS_COEFFICIENT};
+use battle_core::state::battle::message::BattleStateMessage;
 use battle_core::state::client::ClientStateMessage;
+use battle_core::utils::grid_points_for_square;
 
 use battle_core::types::Distance;
 use rand::seq::SliceRandom;
@@ -51,7 +54,16 @@ impl Runner {
                 continue;
             }
 
+            // Soldier in trench, foxhole, etc. is affected as a farther one
+            let map = self.battle_state.map();
+            let protection = map
+                .terrain_tile_at(&map.grid_point_from_world_point(&soldier.world_point()))
+                .map(|tile| tile.type_().explosion_protection())
+                .unwrap_or(0.);
             let distance = distance_between_points(&soldier.world_point(), point);
+            let distance = Distance::from_millimeters(
+                (distance.millimeters() as f32 / (1. - protection)) as i64,
+            );
 
             // TODO : Move into dedicated struct ?
             if let (
@@ -70,7 +82,7 @@ impl Runner {
                     .get(explosion.type_()),
             ) {
                 if &distance < direct_death_rayons {
-                    messages.extend(self.killing_blast_effects(soldier));
+                    messages.extend(self.killing_blast_effects(soldier, explosion));
                 } else if &distance <= regressive_death_rayon
                     || &distance <= regressive_injured_rayon
                 {
@@ -81,7 +93,7 @@ impl Runner {
                     let roll = rng.gen_range(0.0..1.0);
 
                     if roll <= percent {
-                        messages.extend(self.killing_blast_effects(soldier));
+                        messages.extend(self.killing_blast_effects(soldier, explosion));
                     } else {
                         let percent = 1.0
                             - (distance.millimeters() as f32
@@ -106,12 +118,73 @@ impl Runner {
             }
         }
 
+        let terrain_changes = self.explosion_terrain_changes(explosion);
+        if !terrain_changes.is_empty() {
+            messages.push(RunnerMessage::BattleState(
+                BattleStateMessage::ChangeTerrain(terrain_changes),
+            ));
+        }
+
         messages
     }
 
-    fn killing_blast_effects(&self, soldier: &Soldier) -> Vec<RunnerMessage> {
+    /// Explosion dig a crater at its point and destroy terrain around
+    fn explosion_terrain_changes(&self, explosion: &Explosion) -> Vec<TerrainChange> {
+        let map = self.battle_state.map();
+        let point = explosion.point();
+        let center = map.grid_point_from_world_point(point);
+        let rayon = explosion.type_().destruction_rayon();
+        let rayon_pixels = rayon.millimeters() as f32 / 1000. / DISTANCE_TO_METERS_COEFFICIENT;
+        let tiles_rayon = (rayon_pixels / map.tile_width() as f32).ceil() as i32;
+        let mut changes = vec![];
+
+        for grid_point in grid_points_for_square(&center, tiles_rayon * 2 + 1, tiles_rayon * 2 + 1)
+        {
+            let tile = match map.terrain_tile_at(&grid_point) {
+                Some(tile) => tile,
+                None => continue,
+            };
+
+            if grid_point == center && tile.type_().can_be_cratered() {
+                changes.push(TerrainChange::new(grid_point, TileType::Crater, false));
+                continue;
+            }
+
+            if distance_between_points(point, &map.world_point_from_grid_point(grid_point)) > rayon
+            {
+                continue;
+            }
+
+            if let Some(destroyed) = tile.type_().destroyed() {
+                changes.push(TerrainChange::new(
+                    grid_point,
+                    destroyed,
+                    tile.type_().destroy_decor(),
+                ));
+            }
+        }
+
+        changes
+    }
+
+    fn killing_blast_effects(
+        &self,
+        soldier: &Soldier,
+        explosion: &Explosion,
+    ) -> Vec<RunnerMessage> {
         puffin::profile_scope!("killing_blast_effects", soldier.uuid().to_string());
         let mut messages = self.soldier_die(soldier.uuid());
+        // Distance of thrower when it is known, of explosion otherwise
+        let from = explosion
+            .thrower()
+            .map(|thrower| self.battle_state.soldier(thrower).world_point())
+            .unwrap_or(*explosion.point());
+        messages.push(self.kill_event(
+            explosion.thrower(),
+            soldier,
+            explosion.type_().to_string(),
+            &from,
+        ));
 
         if soldier.can_produce_sound() {
             let pick_from = [Sound::MaleScreaming1,
@@ -166,3 +239,43 @@ impl Runner {
         )]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use battle_core::types::{GridPoint, WorldPoint};
+
+    use crate::runner::test::runner;
+
+    use super::*;
+
+    #[test]
+    fn explosion_craters_and_destroys_near_terrain() {
+        let mut runner = runner(vec![]);
+        let near_wall = GridPoint::new(11, 10);
+        let near_hedge = GridPoint::new(10, 11);
+        let far_wall = GridPoint::new(13, 10);
+        runner.battle_state.react(
+            &BattleStateMessage::ChangeTerrain(vec![
+                TerrainChange::new(near_wall, TileType::BrickWall, false),
+                TerrainChange::new(near_hedge, TileType::Hedge, false),
+                TerrainChange::new(far_wall, TileType::BrickWall, false),
+            ]),
+            0,
+        );
+        let explosion = Explosion::new(
+            WorldPoint::new(52., 52.),
+            ExplosiveType::FA19241927,
+            None,
+        );
+
+        let changes = runner.explosion_terrain_changes(&explosion);
+        assert_eq!(changes.len(), 3);
+        for change in [
+            TerrainChange::new(GridPoint::new(10, 10), TileType::Crater, false),
+            TerrainChange::new(near_wall, TileType::MiddleRock, false),
+            TerrainChange::new(near_hedge, TileType::Dirt, true),
+        ] {
+            assert!(changes.contains(&change), "{:?} missing", change);
+        }
+    }
+}
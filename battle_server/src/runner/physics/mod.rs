@@ -1,4 +1,4 @@
-use super::{message::RunnerMessage, Runner};
+use super::{event::dedup_kill_events, message::RunnerMessage, Runner};
 
 mod bullet;
 mod explosion;
@@ -8,11 +8,15 @@ impl Runner {
         puffin::profile_scope!("tick_physics");
         let mut messages = vec![];
 
-        if self.battle_state.frame_i() % self.config.physics_update_freq() == 0 {
+        if self
+            .battle_state
+            .frame_i()
+            .is_multiple_of(self.config.physics_update_freq())
+        {
             messages.extend(self.tick_bullet_fires());
             messages.extend(self.tick_explosions());
         }
 
-        messages
+        dedup_kill_events(messages)
     }
 }
@@ -57,8 +57,8 @@ impl Runner {
                 soldier.set_behavior(behavior);
                 soldier.set_order(order);
             }
-            // Server ignore this side effect because concern Gui only
-            SideEffect::RefreshEntityAnimation(_) => {}
+            // Server ignore these side effects because concern Gui only
+            SideEffect::RefreshEntityAnimation(_) | SideEffect::RefreshMapTerrain => {}
         }
     }
 }
@@ -1,4 +1,7 @@
 use battle_core::{
+    entity::soldier::Soldier,
+    game::squad::FireDiscipline,
+    physics::utils::distance_between_points,
     state::battle::message::{BattleStateMessage, SoldierMessage},
     types::SoldierIndex,
 };
@@ -10,9 +13,15 @@ impl Runner {
     pub fn tick_soldiers(&self) -> Vec<RunnerMessage> {
         puffin::profile_scope!("tick_soldiers");
         let mut messages = vec![];
-        let tick_animate = self.battle_state.frame_i() % self.config.soldier_animate_freq() == 0
+        let tick_animate = self
+            .battle_state
+            .frame_i()
+            .is_multiple_of(self.config.soldier_animate_freq())
             && self.battle_state.phase().is_battle();
-        let tick_update = self.battle_state.frame_i() % self.config.soldier_update_freq() == 0;
+        let tick_update = self
+            .battle_state
+            .frame_i()
+            .is_multiple_of(self.config.soldier_update_freq());
 
         // Entities animation
         if tick_animate {
@@ -41,9 +50,11 @@ impl Runner {
     pub fn tick_feeling_decreasing_soldiers(&self) -> Vec<RunnerMessage> {
         puffin::profile_scope!("tick_feeling_decreasing_soldiers");
         let mut messages = vec![];
-        let tick_feeling_decreasing =
-            self.battle_state.frame_i() % self.config.feeling_decreasing_freq() == 0
-                && self.battle_state.phase().is_battle();
+        let tick_feeling_decreasing = self
+            .battle_state
+            .frame_i()
+            .is_multiple_of(self.config.feeling_decreasing_freq())
+            && self.battle_state.phase().is_battle();
 
         if tick_feeling_decreasing {
             messages.extend((0..self.battle_state.soldiers().len()).map(|i| {
@@ -76,15 +87,58 @@ impl Runner {
 
         messages.extend(self.soldier_behavior(soldier));
         messages.extend(self.soldier_gesture(soldier));
+        messages.extend(self.soldier_floor(soldier));
 
         messages
     }
 
+    fn soldier_floor(&self, soldier: &Soldier) -> Vec<RunnerMessage> {
+        let floor = self.soldier_wanted_floor(soldier);
+        if floor == soldier.floor() {
+            return vec![];
+        }
+
+        vec![RunnerMessage::BattleState(BattleStateMessage::Soldier(
+            soldier.uuid(),
+            SoldierMessage::SetFloor(floor),
+        ))]
+    }
+
+    /// Moving soldiers stay on ground floor. Stopped soldiers spread over building floors,
+    /// starting from the top one.
+    fn soldier_wanted_floor(&self, soldier: &Soldier) -> u8 {
+        if soldier.behavior().world_paths().is_some() {
+            return 0;
+        }
+
+        let map = self.battle_state.map();
+        let interior =
+            match map.interior_at(&map.grid_point_from_world_point(&soldier.world_point())) {
+                Some(interior) => interior,
+                None => return 0,
+            };
+        let floors = interior.floors();
+        if floors <= 1 {
+            return 0;
+        }
+
+        let position = self
+            .battle_state
+            .squad(soldier.squad_uuid())
+            .members()
+            .iter()
+            .position(|member| *member == soldier.uuid())
+            .unwrap_or(0);
+        floors - 1 - (position % floors as usize) as u8
+    }
+
     pub fn tick_update_squad_leaders(&self) -> Vec<RunnerMessage> {
         puffin::profile_scope!("tick_update_squad_leaders");
         let mut messages = vec![];
-        let tick_update =
-            self.battle_state.frame_i() % self.config.squad_leaders_update_freq() == 0;
+        let tick_update = self
+            .battle_state
+            .frame_i()
+            .is_multiple_of(self.config.squad_leaders_update_freq());
 
         if tick_update {
             for squad_uuid in self.battle_state.squads().keys() {
@@ -107,4 +161,505 @@ impl Runner {
         }
         messages
     }
+
+    pub fn tick_delayed_orders(&self) -> Vec<RunnerMessage> {
+        puffin::profile_scope!("tick_delayed_orders");
+        let frame_i = *self.battle_state.frame_i();
+
+        self.battle_state
+            .soldiers()
+            .iter()
+            .filter(|s| s.can_be_animated())
+            .filter_map(|s| match s.delayed_order() {
+                Some((order, reaction_frame_i)) if *reaction_frame_i <= frame_i => {
+                    Some(RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                        s.uuid(),
+                        SoldierMessage::SetOrder(order.clone()),
+                    )))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn tick_squad_order_queues(&self) -> Vec<RunnerMessage> {
+        puffin::profile_scope!("tick_squad_order_queues");
+        let mut messages = vec![];
+
+        if !self.battle_state.phase().is_battle() {
+            return messages;
+        }
+
+        for squad_uuid in self.battle_state.squads().keys() {
+            let leader_index = self.battle_state.squad(*squad_uuid).leader();
+            let leader = self.battle_state.soldier(leader_index);
+            if !leader.can_be_animated() || leader.delayed_order().is_some() {
+                continue;
+            }
+
+            if let Some((id, next_order)) = self.battle_state.squad_order_queue(squad_uuid).first()
+            {
+                if self
+                    .battle_state
+                    .squad_ready_for_next_order(&self.config, squad_uuid)
+                {
+                    messages.extend(vec![
+                        RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                            leader_index,
+                            SoldierMessage::SetOrder(next_order.clone()),
+                        )),
+                        RunnerMessage::BattleState(BattleStateMessage::RemoveSquadOrder(
+                            *squad_uuid,
+                            *id,
+                        )),
+                    ]);
+                }
+            }
+        }
+
+        messages
+    }
+
+    pub fn tick_squad_triggered_orders(&self) -> Vec<RunnerMessage> {
+        puffin::profile_scope!("tick_squad_triggered_orders");
+        let mut messages = vec![];
+
+        if !self.battle_state.phase().is_battle() {
+            return messages;
+        }
+
+        for (squad_uuid, (trigger, order)) in self.battle_state.squad_triggered_orders() {
+            // Reference squad is destroyed, its condition will never be satisfied
+            if self.battle_state.order_trigger_obsolete(trigger) {
+                messages.push(RunnerMessage::BattleState(
+                    BattleStateMessage::SetSquadTriggeredOrder(*squad_uuid, None),
+                ));
+                continue;
+            }
+
+            if self.battle_state.order_trigger_satisfied(trigger) {
+                let leader_index = self.battle_state.squad(*squad_uuid).leader();
+                // Order wait for a leader able to give it
+                if !self.battle_state.soldier(leader_index).can_be_animated() {
+                    continue;
+                }
+
+                // A new squad leader need time before his orders are executed
+                let soldier_message = match self
+                    .battle_state
+                    .squad_command_disrupted_until(&self.config, squad_uuid)
+                {
+                    Some(until) => SoldierMessage::SetDelayedOrder(order.clone(), until),
+                    None => SoldierMessage::SetOrder(order.clone()),
+                };
+                messages.extend(vec![
+                    RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                        leader_index,
+                        soldier_message,
+                    )),
+                    RunnerMessage::BattleState(BattleStateMessage::SetSquadTriggeredOrder(
+                        *squad_uuid,
+                        None,
+                    )),
+                ]);
+            }
+        }
+
+        messages
+    }
+
+    /// Release ambushing squads when an enemy is visible near one of their members
+    pub fn tick_squad_ambushes(&self) -> Vec<RunnerMessage> {
+        puffin::profile_scope!("tick_squad_ambushes");
+        let mut messages = vec![];
+
+        if !self.battle_state.phase().is_battle()
+            || !self
+                .battle_state
+                .frame_i()
+                .is_multiple_of(self.config.soldier_update_freq())
+        {
+            return messages;
+        }
+
+        for squad_uuid in self.battle_state.squads().keys() {
+            if self.battle_state.squad_fire_discipline(squad_uuid) != FireDiscipline::Ambush {
+                continue;
+            }
+
+            let squad = self.battle_state.squad(*squad_uuid);
+            let side = self.battle_state.soldier(squad.leader()).side();
+            // Dead or unconscious members don't see anything
+            let members: Vec<_> = squad
+                .members()
+                .iter()
+                .filter(|i| self.battle_state.soldier(**i).can_be_animated())
+                .copied()
+                .collect();
+            let members_points: Vec<_> = members
+                .iter()
+                .map(|i| self.battle_state.soldier(*i).world_point())
+                .collect();
+            let enemy_near = self
+                .battle_state
+                .visibilities()
+                .visibles_soldiers_by_soldiers(members)
+                .iter()
+                .map(|i| self.battle_state.soldier(*i))
+                .filter(|s| s.side() != side && s.can_be_designed_as_target())
+                .any(|enemy| {
+                    members_points.iter().any(|point| {
+                        distance_between_points(point, &enemy.world_point())
+                            <= self.config.ambush_distance
+                    })
+                });
+
+            if enemy_near {
+                messages.push(RunnerMessage::BattleState(
+                    BattleStateMessage::SetSquadFireDiscipline(
+                        *squad_uuid,
+                        FireDiscipline::FireAtWill,
+                    ),
+                ));
+            }
+        }
+
+        messages
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use battle_core::{
+        behavior::Behavior,
+        map::interior::Interior,
+        order::{trigger::OrderTrigger, Order},
+        physics::visibility::Visibility,
+        state::battle::{phase::Phase, BattleState},
+        types::{Angle, SoldiersOnBoard, SquadUuid, WorldPath, WorldPaths, WorldPoint},
+    };
+
+    use crate::runner::test::{enemy, map_with_interiors, runner, runner_on_map, soldier};
+
+    use super::*;
+
+    const LEADER: SoldierIndex = SoldierIndex(0);
+
+    /// Battling squad of two soldiers, with an idle order triggered at first frame
+    fn triggered_runner() -> Runner {
+        let mut runner = runner(vec![
+            soldier(0, 0, WorldPoint::new(50., 50.), None),
+            soldier(1, 0, WorldPoint::new(60., 50.), None),
+        ]);
+        *runner.battle_state.phase_mut() = Phase::Battle;
+        runner.battle_state.react(
+            &BattleStateMessage::SetSquadTriggeredOrder(
+                SquadUuid(0),
+                Some((OrderTrigger::Frame(0), Order::Idle)),
+            ),
+            0,
+        );
+        runner
+    }
+
+    fn leader_messages(messages: &[RunnerMessage]) -> Vec<&SoldierMessage> {
+        messages
+            .iter()
+            .filter_map(|message| match message {
+                RunnerMessage::BattleState(BattleStateMessage::Soldier(LEADER, message)) => {
+                    Some(message)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn triggered_order_is_given_to_leader() {
+        let runner = triggered_runner();
+
+        let messages = runner.tick_squad_triggered_orders();
+        assert_eq!(
+            leader_messages(&messages),
+            vec![&SoldierMessage::SetOrder(Order::Idle)]
+        );
+    }
+
+    #[test]
+    fn triggered_order_wait_for_an_able_leader() {
+        let mut runner = triggered_runner();
+        runner.battle_state.soldier_mut(LEADER).set_alive(false);
+
+        assert!(runner.tick_squad_triggered_orders().is_empty());
+    }
+
+    #[test]
+    fn triggered_order_is_delayed_by_leader_replacement() {
+        let mut runner = triggered_runner();
+        runner.config.leader_replacement_order_delay = 100;
+        runner
+            .battle_state
+            .react(&BattleStateMessage::SetSquadLeader(SquadUuid(0), LEADER), 0);
+
+        let messages = runner.tick_squad_triggered_orders();
+        assert_eq!(
+            leader_messages(&messages),
+            vec![&SoldierMessage::SetDelayedOrder(Order::Idle, 100)]
+        );
+    }
+
+    #[test]
+    fn triggered_order_of_destroyed_reference_squad_is_dropped() {
+        let mut runner = runner(vec![
+            soldier(0, 0, WorldPoint::new(50., 50.), None),
+            soldier(1, 1, WorldPoint::new(60., 50.), None),
+        ]);
+        *runner.battle_state.phase_mut() = Phase::Battle;
+        runner.battle_state.react(
+            &BattleStateMessage::SetSquadTriggeredOrder(
+                SquadUuid(0),
+                Some((OrderTrigger::SquadUnderFire(SquadUuid(1), 0), Order::Idle)),
+            ),
+            0,
+        );
+        assert!(runner.tick_squad_triggered_orders().is_empty());
+
+        runner
+            .battle_state
+            .soldier_mut(SoldierIndex(1))
+            .set_alive(false);
+        let messages = runner.tick_squad_triggered_orders();
+        assert!(leader_messages(&messages).is_empty());
+        assert!(matches!(
+            messages.as_slice(),
+            [RunnerMessage::BattleState(
+                BattleStateMessage::SetSquadTriggeredOrder(SquadUuid(0), None)
+            )]
+        ));
+    }
+
+    #[test]
+    fn triggered_order_is_cleared_by_a_direct_order() {
+        let mut runner = triggered_runner();
+
+        runner
+            .battle_state
+            .react(&BattleStateMessage::ClearSquadOrders(SquadUuid(0)), 0);
+        assert!(runner
+            .battle_state
+            .squad_triggered_order(&SquadUuid(0))
+            .is_none());
+        assert!(runner.tick_squad_triggered_orders().is_empty());
+    }
+
+    #[test]
+    fn queued_defend_is_finished_by_its_own_position() {
+        let mut runner = triggered_runner();
+        let leader = runner.battle_state.soldier_mut(LEADER);
+        leader.set_order(Order::Defend(Angle(0.), None));
+        leader.set_behavior(Behavior::Defend(Angle(0.)));
+        for order in [Order::Defend(Angle(1.), None), Order::Idle] {
+            runner
+                .battle_state
+                .react(&BattleStateMessage::PushSquadOrder(SquadUuid(0), order), 0);
+        }
+
+        let react = |runner: &mut Runner, messages: Vec<RunnerMessage>| {
+            for message in messages {
+                if let RunnerMessage::BattleState(message) = message {
+                    runner.battle_state.react(&message, 0);
+                }
+            }
+        };
+        let messages = runner.tick_squad_order_queues();
+        assert_eq!(
+            leader_messages(&messages),
+            vec![&SoldierMessage::SetOrder(Order::Defend(Angle(1.), None))]
+        );
+        react(&mut runner, messages);
+
+        // Leader still has the behavior of the previous leg
+        assert!(runner.tick_squad_order_queues().is_empty());
+
+        runner
+            .battle_state
+            .soldier_mut(LEADER)
+            .set_behavior(Behavior::Defend(Angle(1.)));
+        assert_eq!(
+            leader_messages(&runner.tick_squad_order_queues()),
+            vec![&SoldierMessage::SetOrder(Order::Idle)]
+        );
+    }
+
+    #[test]
+    fn queued_move_follow_suppress_fire_duration() {
+        let mut runner = triggered_runner();
+        runner.config.queued_fire_duration = 2;
+        let move_ = Order::MoveTo(
+            WorldPaths::new(vec![WorldPath::new(vec![WorldPoint::new(80., 50.)])]),
+            None,
+        );
+        for message in [
+            BattleStateMessage::Soldier(
+                LEADER,
+                SoldierMessage::SetOrder(Order::SuppressFire(WorldPoint::new(90., 90.))),
+            ),
+            BattleStateMessage::PushSquadOrder(SquadUuid(0), move_.clone()),
+        ] {
+            runner.battle_state.react(&message, 0);
+        }
+
+        assert!(runner.tick_squad_order_queues().is_empty());
+
+        for _ in 0..2 {
+            runner
+                .battle_state
+                .react(&BattleStateMessage::IncrementFrameI, 0);
+        }
+        assert_eq!(
+            leader_messages(&runner.tick_squad_order_queues()),
+            vec![&SoldierMessage::SetOrder(move_)]
+        );
+    }
+
+    #[test]
+    fn queued_leg_is_removed_by_id_after_previous_one_is_given() {
+        let mut runner = triggered_runner();
+        let legs = [
+            Order::Defend(Angle(0.), None),
+            Order::Hide(Angle(1.)),
+            Order::Idle,
+        ];
+        for order in legs.clone() {
+            runner
+                .battle_state
+                .react(&BattleStateMessage::PushSquadOrder(SquadUuid(0), order), 0);
+        }
+        // Gui choose the second leg, but server give the first one meanwhile
+        let (hide_id, _) = runner.battle_state.squad_order_queue(&SquadUuid(0))[1].clone();
+        for message in runner.tick_squad_order_queues() {
+            if let RunnerMessage::BattleState(message) = message {
+                runner.battle_state.react(&message, 0);
+            }
+        }
+
+        runner.battle_state.react(
+            &BattleStateMessage::RemoveSquadOrder(SquadUuid(0), hide_id),
+            0,
+        );
+        let queue = runner.battle_state.squad_order_queue(&SquadUuid(0));
+        assert_eq!(
+            queue.iter().map(|(_, order)| order).collect::<Vec<_>>(),
+            vec![&Order::Idle]
+        );
+    }
+
+    #[test]
+    fn dead_member_dont_release_ambush() {
+        let mut runner = runner(vec![
+            soldier(0, 0, WorldPoint::new(20., 20.), None),
+            soldier(1, 0, WorldPoint::new(400., 400.), None),
+            enemy(2, 1, WorldPoint::new(410., 400.)),
+        ]);
+        *runner.battle_state.phase_mut() = Phase::Battle;
+        runner.battle_state.react(
+            &BattleStateMessage::SetSquadFireDiscipline(SquadUuid(0), FireDiscipline::Ambush),
+            0,
+        );
+        // Only the member far from the leader see the enemy
+        let mut visibility = Visibility::between_soldiers_no(
+            runner.battle_state.soldier(SoldierIndex(1)),
+            runner.battle_state.soldier(SoldierIndex(2)),
+        );
+        visibility.visible = true;
+        runner.battle_state.react(
+            &BattleStateMessage::SetVisibilities(HashMap::from([(
+                (SoldierIndex(1), SoldierIndex(2)),
+                visibility,
+            )])),
+            0,
+        );
+        assert_eq!(runner.tick_squad_ambushes().len(), 1);
+
+        runner
+            .battle_state
+            .soldier_mut(SoldierIndex(1))
+            .set_alive(false);
+        assert!(runner.tick_squad_ambushes().is_empty());
+    }
+
+    #[test]
+    fn reached_destination_trigger_survive_state_copy() {
+        let mut runner = triggered_runner();
+        runner.battle_state.react(
+            &BattleStateMessage::SetSquadDestinationReached(SquadUuid(0)),
+            0,
+        );
+        let trigger = OrderTrigger::SquadReachedDestination(SquadUuid(0), 0);
+
+        let copy = runner.battle_state.copy();
+        let battle_state = BattleState::from_copy(&copy, runner.battle_state.map());
+        assert!(battle_state.order_trigger_satisfied(&trigger));
+    }
+
+    #[test]
+    fn leader_replacement_delay_survive_state_copy() {
+        let mut runner = triggered_runner();
+        runner.config.leader_replacement_order_delay = 100;
+        runner
+            .battle_state
+            .react(&BattleStateMessage::SetSquadLeader(SquadUuid(0), LEADER), 0);
+
+        let copy = runner.battle_state.copy();
+        let battle_state = BattleState::from_copy(&copy, runner.battle_state.map());
+        assert_eq!(
+            battle_state.squad_command_disrupted_until(&runner.config, &SquadUuid(0)),
+            Some(100)
+        );
+    }
+
+    #[test]
+    fn idle_squad_spread_over_building_floors() {
+        let map = map_with_interiors(vec![Interior::new(100., 100., 50., 50., 2, 1., 1.)]);
+        let mut runner = runner_on_map(
+            &map,
+            vec![
+                soldier(0, 0, WorldPoint::new(110., 110.), None),
+                soldier(1, 0, WorldPoint::new(120., 110.), None),
+                soldier(2, 0, WorldPoint::new(130., 110.), None),
+                soldier(3, 1, WorldPoint::new(300., 300.), None),
+            ],
+            vec![],
+            SoldiersOnBoard::new(),
+        );
+
+        // Members alternate from the upper floor to the ground floor
+        let members = runner.battle_state.squad(SquadUuid(0)).members().clone();
+        for (position, member) in members.iter().enumerate() {
+            let soldier = runner.battle_state.soldier(*member);
+            assert_eq!(
+                runner.soldier_wanted_floor(soldier),
+                1 - (position % 2) as u8
+            );
+        }
+
+        // Outside building, soldier stay on the ground
+        let outside = runner.battle_state.soldier(SoldierIndex(3));
+        assert_eq!(runner.soldier_wanted_floor(outside), 0);
+
+        // Moving soldier come down to the ground floor
+        let upper = members[0];
+        let paths = WorldPaths::new(vec![WorldPath::new(vec![
+            WorldPoint::new(110., 110.),
+            WorldPoint::new(200., 110.),
+        ])]);
+        runner
+            .battle_state
+            .soldier_mut(upper)
+            .set_behavior(Behavior::MoveTo(paths));
+        let moving = runner.battle_state.soldier(upper);
+        assert_eq!(runner.soldier_wanted_floor(moving), 0);
+    }
 }
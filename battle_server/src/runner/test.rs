@@ -0,0 +1,161 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+use battle_core::{
+    behavior::{Behavior, Body},
+    config::ServerConfig,
+    deployment::{Deployment, SoldierDeployment, VehicleDeployment},
+    game::{
+        traits::SoldierTraits,
+        weapon::{Magazine, MagazineType, Weapon, WeaponType},
+        Side,
+    },
+    map::{
+        decor::Decor,
+        interior::Interior,
+        terrain::{TerrainTile, TileType},
+        Map,
+    },
+    order::Order,
+    state::battle::BattleState,
+    types::{Offset, SoldierIndex, SoldiersOnBoard, SquadUuid, WorldPoint},
+};
+use crossbeam_channel::unbounded;
+use oc_core::game::{soldier::SoldierType, squad::SquadType};
+
+use super::Runner;
+
+const MAP_TILES: u32 = 100;
+const TILE_SIZE: u32 = 5;
+
+/// Flat short grass map
+pub fn map() -> Map {
+    map_with_interiors(vec![])
+}
+
+/// Flat short grass map with given building interiors
+pub fn map_with_interiors(interiors: Vec<Interior>) -> Map {
+    let tiles = (0..MAP_TILES)
+        .flat_map(|y| {
+            (0..MAP_TILES).map(move |x| {
+                TerrainTile::new(
+                    TileType::ShortGrass,
+                    TILE_SIZE,
+                    TILE_SIZE,
+                    1.,
+                    1.,
+                    x,
+                    y,
+                    0,
+                    0,
+                )
+            })
+        })
+        .collect();
+
+    Map::new(
+        "TestMap".to_string(),
+        PathBuf::from("."),
+        PathBuf::from("."),
+        PathBuf::from("."),
+        interiors,
+        vec![],
+        MAP_TILES,
+        MAP_TILES,
+        tiles,
+        TILE_SIZE,
+        TILE_SIZE,
+        Decor::new(vec![], vec![], Offset::new(0., 0.)),
+        vec![],
+    )
+}
+
+/// Side B soldier of given squad, standing up at given point without weapon
+pub fn enemy(index: usize, squad: usize, point: WorldPoint) -> SoldierDeployment {
+    SoldierDeployment::new(
+        SoldierIndex(index),
+        SoldierType::Type1,
+        Side::B,
+        point,
+        SquadUuid(squad),
+        None,
+        vec![],
+        Order::Idle,
+        Behavior::Idle(Body::StandUp),
+        SoldierTraits::default(),
+    )
+}
+
+/// Side A soldier of given squad, standing up at given point with given weapon
+pub fn soldier(
+    index: usize,
+    squad: usize,
+    point: WorldPoint,
+    weapon: Option<(&str, &str)>,
+) -> SoldierDeployment {
+    let (weapon, magazines) = match weapon {
+        Some((weapon, magazine)) => (
+            Some(Weapon::new(
+                WeaponType::new(weapon),
+                false,
+                Some(Magazine::full(MagazineType::new(magazine))),
+            )),
+            vec![Magazine::full(MagazineType::new(magazine))],
+        ),
+        None => (None, vec![]),
+    };
+
+    SoldierDeployment::new(
+        SoldierIndex(index),
+        SoldierType::Type1,
+        Side::A,
+        point,
+        SquadUuid(squad),
+        weapon,
+        magazines,
+        Order::Idle,
+        Behavior::Idle(Body::StandUp),
+        SoldierTraits::default(),
+    )
+}
+
+pub fn runner(soldiers: Vec<SoldierDeployment>) -> Runner {
+    runner_with_vehicles(soldiers, vec![], SoldiersOnBoard::new())
+}
+
+pub fn runner_with_vehicles(
+    soldiers: Vec<SoldierDeployment>,
+    vehicles: Vec<VehicleDeployment>,
+    boards: SoldiersOnBoard,
+) -> Runner {
+    runner_on_map(&map(), soldiers, vehicles, boards)
+}
+
+/// Runner of given soldiers and vehicles on given map
+pub fn runner_on_map(
+    map: &Map,
+    soldiers: Vec<SoldierDeployment>,
+    vehicles: Vec<VehicleDeployment>,
+    boards: SoldiersOnBoard,
+) -> Runner {
+    let squad_types = soldiers
+        .iter()
+        .map(|soldier| (soldier.squad_uuid(), SquadType::Type1))
+        .collect::<HashMap<_, _>>();
+    let mut battle_state = BattleState::empty(map);
+    battle_state.inject(&Deployment::new(soldiers, vehicles, boards, squad_types));
+    battle_state.resolve();
+
+    let (_, input) = unbounded();
+    let (output, _) = unbounded();
+    Runner::new(
+        ServerConfig::default(),
+        input,
+        output,
+        Arc::new(AtomicBool::new(false)),
+        battle_state,
+    )
+}
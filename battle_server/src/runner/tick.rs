@@ -15,11 +15,18 @@ impl Runner {
         )];
         messages.extend(self.tick_phase());
         messages.extend(self.tick_morale());
+        messages.extend(self.tick_events());
         messages.extend(self.tick_victory());
         messages.extend(self.tick_flags());
         messages.extend(self.tick_soldiers());
         messages.extend(self.tick_update_squad_leaders());
+        messages.extend(self.tick_delayed_orders());
+        messages.extend(self.tick_squad_order_queues());
+        messages.extend(self.tick_squad_triggered_orders());
+        messages.extend(self.tick_squad_ambushes());
         messages.extend(self.tick_feeling_decreasing_soldiers());
+        messages.extend(self.tick_weapons_cooling());
+        messages.extend(self.tick_crew_served_weapons());
         messages.extend(self.tick_visibilities());
         messages.extend(self.tick_physics());
         self.react(&messages);
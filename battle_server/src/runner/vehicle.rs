@@ -78,10 +78,15 @@ impl Runner {
         {
             // If it is the last point, move is finished
             if paths.is_last_point().expect("Must contain points") {
-                messages.push(RunnerMessage::BattleState(BattleStateMessage::Soldier(
-                    soldier_index,
-                    SoldierMessage::SetBehavior(Behavior::Idle(Body::Crouched)),
-                )));
+                messages.extend(vec![
+                    RunnerMessage::BattleState(BattleStateMessage::Soldier(
+                        soldier_index,
+                        SoldierMessage::SetBehavior(Behavior::Idle(Body::Crouched)),
+                    )),
+                    RunnerMessage::BattleState(BattleStateMessage::SetSquadDestinationReached(
+                        self.battle_state.soldier(soldier_index).squad_uuid(),
+                    )),
+                ]);
             } else {
                 messages.push(RunnerMessage::BattleState(BattleStateMessage::Soldier(
                     soldier_index,
@@ -1,5 +1,4 @@
 use battle_core::{
-    config::END_MORALE,
     game::{flag::FlagOwnership, Side},
     state::battle::{
         message::BattleStateMessage,
@@ -13,14 +12,18 @@ impl Runner {
     pub fn tick_victory(&self) -> Vec<RunnerMessage> {
         puffin::profile_scope!("tick_victory");
 
-        if self.battle_state.frame_i() % self.config.victory_update_freq() == 0 {
+        if self
+            .battle_state
+            .frame_i()
+            .is_multiple_of(self.config.victory_update_freq())
+        {
             // Victory by morale
-            if self.battle_state.a_morale().0 <= END_MORALE {
+            if self.battle_state.a_morale().0 < self.config.end_morale {
                 return vec![RunnerMessage::BattleState(BattleStateMessage::SetPhase(
                     Phase::End(Victorious(Side::B), EndReason::Morale),
                 ))];
             }
-            if self.battle_state.b_morale().0 <= END_MORALE {
+            if self.battle_state.b_morale().0 < self.config.end_morale {
                 return vec![RunnerMessage::BattleState(BattleStateMessage::SetPhase(
                     Phase::End(Victorious(Side::A), EndReason::Morale),
                 ))];
@@ -56,3 +59,45 @@ impl Runner {
         vec![]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use battle_core::{config::rules::RuleSet, state::battle::phase::Phase, types::WorldPoint};
+    use oc_core::morale::Morale;
+
+    use crate::runner::test::{enemy, runner, soldier};
+
+    use super::*;
+
+    fn routed_runner(rule_set: RuleSet) -> Runner {
+        let mut runner = runner(vec![
+            soldier(0, 0, WorldPoint::new(50., 50.), None),
+            enemy(1, 1, WorldPoint::new(250., 250.)),
+        ]);
+        *runner.battle_state.phase_mut() = Phase::Battle;
+        runner.config.apply_rule_set(rule_set);
+        runner
+            .battle_state
+            .react(&BattleStateMessage::SetAMorale(Morale(0.)), 0);
+        runner
+    }
+
+    #[test]
+    fn routed_side_lose_the_battle() {
+        let runner = routed_runner(RuleSet::Standard);
+
+        assert!(matches!(
+            runner.tick_victory().as_slice(),
+            [RunnerMessage::BattleState(BattleStateMessage::SetPhase(
+                Phase::End(Victorious(Side::B), EndReason::Morale)
+            ))]
+        ));
+    }
+
+    #[test]
+    fn routed_side_dont_lose_training_battle() {
+        let runner = routed_runner(RuleSet::Training);
+
+        assert!(runner.tick_victory().is_empty());
+    }
+}
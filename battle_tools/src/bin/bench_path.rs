@@ -0,0 +1,184 @@
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use battle_core::{
+    config::ServerConfig,
+    map::{reader::MapReader, Map},
+    physics::path::{find_path, find_raw_path, Direction, PathMode},
+    types::{GridPoint, VehicleSize},
+    utils::angleg,
+};
+use pathfinding::prelude::astar;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "basic")]
+pub struct Opt {
+    #[structopt(default_value = "Demo1,Demo2,map1,map2", use_delimiter = true)]
+    map_names: Vec<String>,
+
+    /// Count of searched paths by map and path mode
+    #[structopt(long = "paths", default_value = "50")]
+    paths: usize,
+
+    #[structopt(long = "seed", default_value = "0")]
+    seed: u64,
+
+    #[structopt(long = "resources", default_value = "./resources", parse(from_os_str))]
+    resources: PathBuf,
+
+    /// Csv file where results are recorded
+    #[structopt(long = "output", default_value = "bench_path.csv", parse(from_os_str))]
+    output: PathBuf,
+}
+
+type FindPath = fn(
+    &ServerConfig,
+    &Map,
+    &GridPoint,
+    &GridPoint,
+    bool,
+    &PathMode,
+    &Option<Direction>,
+) -> Option<Vec<GridPoint>>;
+
+/// Path finding as it was before regions and cache (search on each point for each direction)
+fn legacy_find_path(
+    config: &ServerConfig,
+    map: &Map,
+    from: &GridPoint,
+    to: &GridPoint,
+    exclude_first: bool,
+    path_mode: &PathMode,
+    start_direction: &Option<Direction>,
+) -> Option<Vec<GridPoint>> {
+    if !map.contains(from) || !map.contains(to) {
+        return None;
+    }
+    let start_direction = start_direction.unwrap_or(Direction::from_angle(&angleg(to, from)));
+
+    let (path, _) = astar(
+        &(*from, start_direction),
+        |p| map.successors(p, path_mode),
+        |p| {
+            (p.0.to_vec2().distance(to.to_vec2()) * config.path_finding_heuristic_coefficient)
+                as i32
+        },
+        |p| p.0 == *to,
+    )?;
+    let path: Vec<GridPoint> = path
+        .iter()
+        .skip(usize::from(exclude_first))
+        .map(|p| p.0)
+        .collect();
+    (!path.is_empty()).then_some(path)
+}
+
+fn bench(
+    config: &ServerConfig,
+    map: &Map,
+    points: &[(GridPoint, GridPoint)],
+    path_mode: &PathMode,
+    find: FindPath,
+) -> (Duration, Vec<Option<Vec<GridPoint>>>) {
+    let start = Instant::now();
+    let paths = points
+        .iter()
+        .map(|(from, to)| find(config, map, from, to, true, path_mode, &None))
+        .collect();
+    (start.elapsed(), paths)
+}
+
+/// Count of found paths and their total length
+fn summary(paths: &[Option<Vec<GridPoint>>]) -> (usize, usize) {
+    let found = paths.iter().flatten().collect::<Vec<_>>();
+    (found.len(), found.iter().map(|path| path.len()).sum())
+}
+
+fn main() {
+    let opt = Opt::from_args();
+    let config = ServerConfig::default();
+    let path_modes = [
+        ("walk", PathMode::Walk),
+        ("sneak", PathMode::Sneak),
+        ("drive", PathMode::Drive(VehicleSize(10))),
+    ];
+
+    println!(
+        "{:<10} {:<6} {:>12} {:>12} {:>12} {:>12} {:>10} {:>8}",
+        "map", "mode", "legacy", "raw", "clusters", "cached", "found", "length"
+    );
+    let mut records = vec![
+        "map,mode,paths,legacy_ms,raw_ms,clusters_ms,cached_ms,found,raw_found,length,raw_length"
+            .to_string(),
+    ];
+    for map_name in &opt.map_names {
+        let map = match MapReader::new(map_name, &opt.resources).and_then(|r| r.build()) {
+            Ok(map) => map,
+            Err(error) => {
+                eprintln!("Unable to load map {} : {}", map_name, error);
+                continue;
+            }
+        };
+
+        let mut rng = StdRng::seed_from_u64(opt.seed);
+        let points = (0..opt.paths)
+            .map(|_| {
+                let mut point = || {
+                    GridPoint::new(
+                        rng.gen_range(0..map.width()) as i32,
+                        rng.gen_range(0..map.height()) as i32,
+                    )
+                };
+                (point(), point())
+            })
+            .collect::<Vec<_>>();
+
+        for (name, path_mode) in &path_modes {
+            let (legacy, _) = bench(&config, &map, &points, path_mode, legacy_find_path);
+            let (raw, raw_paths) = bench(&config, &map, &points, path_mode, find_raw_path);
+            map.path_cache().clear();
+            let (clusters, paths) = bench(&config, &map, &points, path_mode, find_path);
+            let (cached, _) = bench(&config, &map, &points, path_mode, find_path);
+            let (raw_found, raw_length) = summary(&raw_paths);
+            let (found, length) = summary(&paths);
+
+            println!(
+                "{:<10} {:<6} {:>10}ms {:>10}ms {:>10}ms {:>10}ms {:>4}/{:<4} {:>7.2}x",
+                map_name,
+                name,
+                legacy.as_millis(),
+                raw.as_millis(),
+                clusters.as_millis(),
+                cached.as_millis(),
+                found,
+                raw_found,
+                length as f32 / raw_length.max(1) as f32,
+            );
+            records.push(format!(
+                "{},{},{},{:.3},{:.3},{:.3},{:.3},{},{},{},{}",
+                map_name,
+                name,
+                opt.paths,
+                legacy.as_secs_f64() * 1000.,
+                raw.as_secs_f64() * 1000.,
+                clusters.as_secs_f64() * 1000.,
+                cached.as_secs_f64() * 1000.,
+                found,
+                raw_found,
+                length,
+                raw_length,
+            ));
+        }
+    }
+
+    if let Err(error) = fs::write(&opt.output, records.join("\n") + "\n") {
+        eprintln!("Unable to write {} : {}", opt.output.display(), error);
+    } else {
+        println!("Results recorded in {}", opt.output.display());
+    }
+}
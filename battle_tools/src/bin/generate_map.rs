@@ -0,0 +1,45 @@
+use std::{path::PathBuf, process::exit};
+
+use battle_tools::{generator::MapGenerator, validate::validate_map};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "basic")]
+pub struct Opt {
+    #[structopt(default_value = "Generated")]
+    map_name: String,
+
+    #[structopt(long = "seed", default_value = "0")]
+    seed: u64,
+
+    /// Map width, in tiles
+    #[structopt(long = "width", default_value = "200")]
+    width: u32,
+
+    /// Map height, in tiles
+    #[structopt(long = "height", default_value = "120")]
+    height: u32,
+
+    #[structopt(long = "resources", default_value = "./resources", parse(from_os_str))]
+    resources: PathBuf,
+}
+
+fn main() {
+    let opt = Opt::from_args();
+    let folder = opt.resources.join("maps").join(&opt.map_name);
+
+    let map = MapGenerator::new(&opt.map_name, opt.seed, opt.width, opt.height).generate();
+    if let Err(error) = map.write(&folder) {
+        eprintln!("{}", error);
+        exit(1);
+    }
+    println!("Map {} written in {}", opt.map_name, folder.display());
+
+    let errors = validate_map(&opt.map_name, &opt.resources);
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("{}", error);
+        }
+        exit(1);
+    }
+}
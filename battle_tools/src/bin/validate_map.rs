@@ -0,0 +1,43 @@
+use std::{path::PathBuf, process::exit};
+
+use battle_tools::validate::{allowed_errors, validate_map};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "basic")]
+pub struct Opt {
+    #[structopt()]
+    map_name: String,
+
+    #[structopt(long = "resources", default_value = "./resources", parse(from_os_str))]
+    resources: PathBuf,
+}
+
+fn main() {
+    let opt = Opt::from_args();
+    let allowed = match allowed_errors(&opt.map_name, &opt.resources) {
+        Ok(allowed) => allowed,
+        Err(error) => {
+            eprintln!("{}", error);
+            exit(1);
+        }
+    };
+    let (allowed, errors): (Vec<_>, Vec<_>) = validate_map(&opt.map_name, &opt.resources)
+        .into_iter()
+        .partition(|error| allowed.contains(&error.key()));
+
+    for error in &allowed {
+        println!("{} (allowed)", error);
+    }
+
+    if errors.is_empty() {
+        println!("Map {} is valid", opt.map_name);
+        return;
+    }
+
+    for error in &errors {
+        eprintln!("{}", error);
+    }
+    eprintln!("Map {} contains {} error(s)", opt.map_name, errors.len());
+    exit(1);
+}
@@ -0,0 +1,763 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use battle_core::map::terrain::{Opening, TileType};
+use image::{imageops, ImageBuffer, ImageError, Rgba, RgbaImage};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::HD_FACTOR;
+
+/// Minimal width and height (in tiles) to place spawn zones and villages
+pub const MIN_SIZE: u32 = 100;
+
+const TILE_SIZE: u32 = 5;
+const FIELD_MIN_SIZE: u32 = 20;
+const FIELD_MAX_SIZE: u32 = 45;
+const HEDGEROW_PROBABILITY: f64 = 0.6;
+const HEDGEROW_GAP_PROBABILITY: f64 = 0.08;
+// One wood by this count of tiles
+const WOOD_TILES_RATIO: u32 = 5000;
+const WOOD_MIN_RADIUS: i32 = 6;
+const WOOD_MAX_RADIUS: i32 = 16;
+const WOOD_TRUNK_PROBABILITY: f64 = 0.06;
+const ROAD_WIDTH: u32 = 2;
+const VILLAGE_MIN_BUILDINGS: u32 = 3;
+const VILLAGE_MAX_BUILDINGS: u32 = 6;
+const BUILDING_MIN_SIZE: u32 = 6;
+const BUILDING_MAX_SIZE: u32 = 12;
+const BUILDING_MARGIN: u32 = 2;
+const BUILDING_MAX_FLOORS: u8 = 2;
+// Probability of a window for each building wall tile (except corners and door)
+const WINDOW_PROBABILITY: f64 = 0.2;
+const FLAG_SIZE: u32 = 10;
+// Spawn zones depth, as divisor of map size
+const SPAWN_ZONE_DEPTH_DIVISOR: u32 = 7;
+
+/// Tile types used by generated maps, with their tileset and background color
+const PALETTE: [(TileType, [u8; 3]); 12] = [
+    (TileType::ShortGrass, [112, 148, 76]),
+    (TileType::MiddleGrass, [98, 136, 64]),
+    (TileType::HighGrass, [86, 122, 54]),
+    (TileType::WheatField, [196, 178, 98]),
+    (TileType::Dirt, [138, 112, 80]),
+    (TileType::Road, [150, 140, 120]),
+    (TileType::Concrete, [160, 160, 156]),
+    (TileType::BrickWall, [120, 62, 48]),
+    (TileType::Hedge, [52, 92, 40]),
+    (TileType::Underbrush, [62, 100, 48]),
+    (TileType::LightUnderbrush, [78, 116, 56]),
+    (TileType::Trunk, [74, 56, 38]),
+];
+/// Openings of generated buildings, with their tileset color
+const OPENINGS: [(Opening, [u8; 3]); 2] = [
+    (Opening::Door, [96, 64, 40]),
+    (Opening::Window, [150, 190, 210]),
+];
+const FIELD_TILE_TYPES: [TileType; 4] = [
+    TileType::ShortGrass,
+    TileType::MiddleGrass,
+    TileType::HighGrass,
+    TileType::WheatField,
+];
+const INTERIOR_FLOOR_COLOR: [u8; 4] = [176, 160, 136, 255];
+const INTERIOR_WALL_COLOR: [u8; 4] = [70, 52, 44, 255];
+const SPAWN_ZONE_NAMES: [&str; 4] = ["N", "S", "W", "E"];
+
+#[derive(Debug)]
+pub enum GeneratorError {
+    Io(PathBuf, std::io::Error),
+    Image(PathBuf, ImageError),
+}
+
+impl Display for GeneratorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeneratorError::Io(path, error) => {
+                f.write_str(&format!("Unable to write {} : {}", path.display(), error))
+            }
+            GeneratorError::Image(path, error) => f.write_str(&format!(
+                "Unable to write image {} : {}",
+                path.display(),
+                error
+            )),
+        }
+    }
+}
+
+/// Rectangle, in tiles
+#[derive(Debug, Clone, Copy)]
+pub struct TileRect {
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+}
+
+impl TileRect {
+    pub fn new(x: u32, y: u32, w: u32, h: u32) -> Self {
+        Self { x, y, w, h }
+    }
+
+    fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && x < self.x + self.w && y >= self.y && y < self.y + self.h
+    }
+
+    fn intersects(&self, other: &TileRect, margin: u32) -> bool {
+        self.x < other.x + other.w + margin
+            && other.x < self.x + self.w + margin
+            && self.y < other.y + other.h + margin
+            && other.y < self.y + self.h + margin
+    }
+
+    fn on_border(&self, x: u32, y: u32) -> bool {
+        self.contains(x, y)
+            && (x == self.x || y == self.y || x == self.x + self.w - 1 || y == self.y + self.h - 1)
+    }
+}
+
+/// Procedural map (fields, hedgerows, woods, road and villages) produced from a seed
+pub struct MapGenerator {
+    name: String,
+    seed: u64,
+    width: u32,
+    height: u32,
+}
+
+pub struct GeneratedMap {
+    name: String,
+    width: u32,
+    height: u32,
+    tiles: Vec<TileType>,
+    openings: Vec<Option<Opening>>,
+    // Buildings with their floors count
+    buildings: Vec<(TileRect, u8)>,
+    flags: Vec<(String, TileRect)>,
+    spawn_zones: Vec<(String, TileRect)>,
+    seed: u64,
+}
+
+impl MapGenerator {
+    /// Width and height are given in tiles (and can't be less than `MIN_SIZE`)
+    pub fn new(name: &str, seed: u64, width: u32, height: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            seed,
+            width: width.max(MIN_SIZE),
+            height: height.max(MIN_SIZE),
+        }
+    }
+
+    pub fn generate(&self) -> GeneratedMap {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut map = GeneratedMap {
+            name: self.name.clone(),
+            width: self.width,
+            height: self.height,
+            tiles: vec![TileType::ShortGrass; (self.width * self.height) as usize],
+            openings: vec![None; (self.width * self.height) as usize],
+            buildings: vec![],
+            flags: vec![],
+            spawn_zones: self.spawn_zones(),
+            seed: self.seed,
+        };
+
+        self.fields(&mut rng, &mut map);
+        self.woods(&mut rng, &mut map);
+        let road_ys = self.road(&mut rng, &mut map);
+        self.villages(&mut rng, &mut map, &road_ys);
+
+        map
+    }
+
+    fn spawn_zones(&self) -> Vec<(String, TileRect)> {
+        let depth_y = self.height / SPAWN_ZONE_DEPTH_DIVISOR;
+        let depth_x = self.width / SPAWN_ZONE_DEPTH_DIVISOR;
+        let rects = [
+            TileRect::new(0, 0, self.width, depth_y),
+            TileRect::new(0, self.height - depth_y, self.width, depth_y),
+            TileRect::new(0, depth_y, depth_x, self.height - depth_y * 2),
+            TileRect::new(
+                self.width - depth_x,
+                depth_y,
+                depth_x,
+                self.height - depth_y * 2,
+            ),
+        ];
+
+        SPAWN_ZONE_NAMES
+            .iter()
+            .zip(rects)
+            .map(|(name, rect)| (name.to_string(), rect))
+            .collect()
+    }
+
+    /// Area where villages can be placed (outside spawn zones)
+    fn battlefield(&self) -> TileRect {
+        let depth_y = self.height / SPAWN_ZONE_DEPTH_DIVISOR;
+        let depth_x = self.width / SPAWN_ZONE_DEPTH_DIVISOR;
+        TileRect::new(
+            depth_x + BUILDING_MARGIN,
+            depth_y + BUILDING_MARGIN,
+            self.width - (depth_x + BUILDING_MARGIN) * 2,
+            self.height - (depth_y + BUILDING_MARGIN) * 2,
+        )
+    }
+
+    /// Split map in fields of random ground, separated by hedgerows
+    fn fields(&self, rng: &mut StdRng, map: &mut GeneratedMap) {
+        let xs = self.cuts(rng, self.width);
+        let ys = self.cuts(rng, self.height);
+
+        for (y_start, y_end) in ys.iter().zip(ys.iter().skip(1)) {
+            for (x_start, x_end) in xs.iter().zip(xs.iter().skip(1)) {
+                let type_ = FIELD_TILE_TYPES[rng.gen_range(0..FIELD_TILE_TYPES.len())].clone();
+                for y in *y_start..*y_end {
+                    for x in *x_start..*x_end {
+                        map.set(x, y, type_.clone());
+                    }
+                }
+            }
+        }
+
+        for x in xs.iter().filter(|x| **x != 0 && **x != self.width) {
+            for (y_start, y_end) in ys.iter().zip(ys.iter().skip(1)) {
+                if rng.gen_bool(HEDGEROW_PROBABILITY) {
+                    for y in *y_start..*y_end {
+                        if !rng.gen_bool(HEDGEROW_GAP_PROBABILITY) {
+                            map.set(*x, y, TileType::Hedge);
+                        }
+                    }
+                }
+            }
+        }
+        for y in ys.iter().filter(|y| **y != 0 && **y != self.height) {
+            for (x_start, x_end) in xs.iter().zip(xs.iter().skip(1)) {
+                if rng.gen_bool(HEDGEROW_PROBABILITY) {
+                    for x in *x_start..*x_end {
+                        if !rng.gen_bool(HEDGEROW_GAP_PROBABILITY) {
+                            map.set(x, *y, TileType::Hedge);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Random field limits along given length, including 0 and length
+    fn cuts(&self, rng: &mut StdRng, length: u32) -> Vec<u32> {
+        let mut cuts = vec![0];
+        let mut position = 0;
+        loop {
+            position += rng.gen_range(FIELD_MIN_SIZE..=FIELD_MAX_SIZE);
+            if position + FIELD_MIN_SIZE >= length {
+                break;
+            }
+            cuts.push(position);
+        }
+        cuts.push(length);
+        cuts
+    }
+
+    fn woods(&self, rng: &mut StdRng, map: &mut GeneratedMap) {
+        let count = (self.width * self.height / WOOD_TILES_RATIO).max(1);
+
+        for _ in 0..count {
+            let center_x = rng.gen_range(0..self.width) as i32;
+            let center_y = rng.gen_range(0..self.height) as i32;
+            let radius = rng.gen_range(WOOD_MIN_RADIUS..=WOOD_MAX_RADIUS);
+
+            for y in (center_y - radius * 2)..=(center_y + radius * 2) {
+                for x in (center_x - radius * 2)..=(center_x + radius * 2) {
+                    if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+                        continue;
+                    }
+
+                    // Noisy edge around wood
+                    let distance = (((x - center_x).pow(2) + (y - center_y).pow(2)) as f32).sqrt();
+                    let edge = radius as f32 * rng.gen_range(0.8..1.2);
+                    if distance > edge {
+                        continue;
+                    }
+
+                    let type_ = if rng.gen_bool(WOOD_TRUNK_PROBABILITY) {
+                        TileType::Trunk
+                    } else if distance > edge * 0.7 {
+                        TileType::LightUnderbrush
+                    } else {
+                        TileType::Underbrush
+                    };
+                    map.set(x as u32, y as u32, type_);
+                }
+            }
+        }
+    }
+
+    /// Meandering west-east road. Return road y position for each x.
+    fn road(&self, rng: &mut StdRng, map: &mut GeneratedMap) -> Vec<u32> {
+        let battlefield = self.battlefield();
+        let min_y = battlefield.y;
+        let max_y = battlefield.y + battlefield.h - ROAD_WIDTH;
+        let mut y = rng.gen_range(min_y..=max_y) as i32;
+        let mut ys = vec![];
+
+        for x in 0..self.width {
+            if rng.gen_bool(0.15) {
+                y = (y + rng.gen_range(-1..=1)).clamp(min_y as i32, max_y as i32);
+            }
+            for road_y in y as u32..y as u32 + ROAD_WIDTH {
+                map.set(x, road_y, TileType::Road);
+            }
+            ys.push(y as u32);
+        }
+
+        ys
+    }
+
+    /// Villages along the road, each one with a flag at its center
+    fn villages(&self, rng: &mut StdRng, map: &mut GeneratedMap, road_ys: &[u32]) {
+        let battlefield = self.battlefield();
+        let count = (battlefield.w / (BUILDING_MAX_SIZE * 6)).clamp(1, 3);
+        let section = battlefield.w / count;
+
+        for i in 0..count {
+            let center_x = battlefield.x + section * i + section / 2;
+            let center_y = road_ys[center_x as usize] + ROAD_WIDTH / 2;
+            let buildings_count = rng.gen_range(VILLAGE_MIN_BUILDINGS..=VILLAGE_MAX_BUILDINGS);
+            let mut village_buildings: Vec<TileRect> = vec![];
+
+            let mut attempts = 0;
+            while (village_buildings.len() as u32) < buildings_count && attempts < 100 {
+                attempts += 1;
+                let w = rng.gen_range(BUILDING_MIN_SIZE..=BUILDING_MAX_SIZE);
+                let h = rng.gen_range(BUILDING_MIN_SIZE..=BUILDING_MAX_SIZE);
+                // Buildings are on one side of the road or the other
+                let spread = BUILDING_MAX_SIZE * 2;
+                let x = center_x as i32 + rng.gen_range(-(spread as i32)..spread as i32);
+                let y = if rng.gen_bool(0.5) {
+                    center_y as i32 - ROAD_WIDTH as i32 - h as i32 - rng.gen_range(1..4)
+                } else {
+                    center_y as i32 + ROAD_WIDTH as i32 + rng.gen_range(1..4)
+                };
+                if x < battlefield.x as i32
+                    || y < battlefield.y as i32
+                    || x as u32 + w > battlefield.x + battlefield.w
+                    || y as u32 + h > battlefield.y + battlefield.h
+                {
+                    continue;
+                }
+
+                let building = TileRect::new(x as u32, y as u32, w, h);
+                let on_road = (building.x..building.x + building.w).any(|x| {
+                    let road_y = road_ys[x as usize];
+                    road_y + ROAD_WIDTH > building.y && road_y < building.y + building.h
+                });
+                if on_road
+                    || map
+                        .buildings
+                        .iter()
+                        .map(|(other, _)| other)
+                        .chain(village_buildings.iter())
+                        .any(|other| building.intersects(other, BUILDING_MARGIN))
+                {
+                    continue;
+                }
+
+                village_buildings.push(building);
+            }
+
+            for building in village_buildings {
+                self.building(rng, map, &building, center_y);
+                let floors = rng.gen_range(1..=BUILDING_MAX_FLOORS);
+                map.buildings.push((building, floors));
+            }
+
+            let flag_name = ((b'A' + i as u8) as char).to_string();
+            map.flags.push((
+                flag_name,
+                TileRect::new(
+                    center_x - FLAG_SIZE / 2,
+                    center_y.saturating_sub(FLAG_SIZE / 2),
+                    FLAG_SIZE,
+                    FLAG_SIZE,
+                ),
+            ));
+        }
+    }
+
+    /// Brick walls, some with windows, around a concrete floor, with a door facing the road
+    fn building(&self, rng: &mut StdRng, map: &mut GeneratedMap, building: &TileRect, road_y: u32) {
+        let (right, bottom) = (building.x + building.w - 1, building.y + building.h - 1);
+        for y in building.y..building.y + building.h {
+            for x in building.x..building.x + building.w {
+                if !building.on_border(x, y) {
+                    map.set(x, y, TileType::Concrete);
+                    continue;
+                }
+
+                map.set(x, y, TileType::BrickWall);
+                let corner = (x == building.x || x == right) && (y == building.y || y == bottom);
+                if !corner && rng.gen_bool(WINDOW_PROBABILITY) {
+                    map.set_opening(x, y, Opening::Window);
+                }
+            }
+        }
+
+        let door_x = building.x + building.w / 2;
+        let door_y = if building.y > road_y {
+            building.y
+        } else {
+            bottom
+        };
+        map.set(door_x, door_y, TileType::Concrete);
+        map.set_opening(door_x, door_y, Opening::Door);
+    }
+}
+
+impl GeneratedMap {
+    fn set(&mut self, x: u32, y: u32, type_: TileType) {
+        if x < self.width && y < self.height {
+            let i = (y * self.width + x) as usize;
+            self.tiles[i] = type_;
+        }
+    }
+
+    fn set_opening(&mut self, x: u32, y: u32, opening: Opening) {
+        if x < self.width && y < self.height {
+            let i = (y * self.width + x) as usize;
+            self.openings[i] = Some(opening);
+        }
+    }
+
+    fn palette_index(type_: &TileType) -> usize {
+        PALETTE
+            .iter()
+            .position(|(palette_type, _)| palette_type == type_)
+            .expect("Generated tile types must be in palette")
+    }
+
+    fn opening_index(opening: &Opening) -> usize {
+        OPENINGS
+            .iter()
+            .position(|(palette_opening, _)| palette_opening == opening)
+            .expect("Generated openings must be in openings palette")
+    }
+
+    /// Write map files (tmx, tilesets and images) into given folder
+    pub fn write(&self, folder: &Path) -> Result<(), GeneratorError> {
+        fs::create_dir_all(folder).map_err(|error| GeneratorError::Io(folder.into(), error))?;
+
+        let terrain_colors = PALETTE.iter().map(|(_, color)| *color).collect::<Vec<_>>();
+        let opening_colors = OPENINGS.iter().map(|(_, color)| *color).collect::<Vec<_>>();
+        self.write_image(&self.tileset_image(&terrain_colors), folder, "terrain")?;
+        self.write_image(&self.tileset_image(&opening_colors), folder, "openings")?;
+        self.write_image(&self.background_image(), folder, &self.name)?;
+        self.write_image(&self.interiors_image(), folder, "interiors")?;
+        self.write_text(
+            &self.tileset(
+                "terrain",
+                &PALETTE
+                    .iter()
+                    .map(|(type_, _)| ("ID", format!("{:?}", type_)))
+                    .collect::<Vec<_>>(),
+            ),
+            &folder.join("terrain.tsx"),
+        )?;
+        self.write_text(
+            &self.tileset(
+                "openings",
+                &OPENINGS
+                    .iter()
+                    .map(|(opening, _)| ("OPENING", format!("{:?}", opening)))
+                    .collect::<Vec<_>>(),
+            ),
+            &folder.join("openings.tsx"),
+        )?;
+        self.write_text(&self.tmx(), &folder.join(format!("{}.tmx", self.name)))?;
+
+        Ok(())
+    }
+
+    fn write_text(&self, content: &str, path: &Path) -> Result<(), GeneratorError> {
+        fs::write(path, content).map_err(|error| GeneratorError::Io(path.into(), error))
+    }
+
+    /// Write image and its HD version
+    fn write_image(
+        &self,
+        image: &RgbaImage,
+        folder: &Path,
+        name: &str,
+    ) -> Result<(), GeneratorError> {
+        let path = folder.join(format!("{}.png", name));
+        image
+            .save(&path)
+            .map_err(|error| GeneratorError::Image(path, error))?;
+
+        let hd_path = folder.join(format!("{}__HD.png", name));
+        imageops::resize(
+            image,
+            image.width() * HD_FACTOR,
+            image.height() * HD_FACTOR,
+            imageops::FilterType::Nearest,
+        )
+        .save(&hd_path)
+        .map_err(|error| GeneratorError::Image(hd_path, error))
+    }
+
+    /// One row of plain color tiles
+    fn tileset_image(&self, colors: &[[u8; 3]]) -> RgbaImage {
+        ImageBuffer::from_fn(colors.len() as u32 * TILE_SIZE, TILE_SIZE, |x, _| {
+            let [r, g, b] = colors[(x / TILE_SIZE) as usize];
+            Rgba([r, g, b, 255])
+        })
+    }
+
+    fn background_image(&self) -> RgbaImage {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        ImageBuffer::from_fn(self.width * TILE_SIZE, self.height * TILE_SIZE, |x, y| {
+            let i = ((y / TILE_SIZE) * self.width + x / TILE_SIZE) as usize;
+            let [r, g, b] = PALETTE[Self::palette_index(&self.tiles[i])].1;
+            // Light noise to avoid flat colors
+            let noise: i16 = rng.gen_range(-8..=8);
+            let shade = |channel: u8| (channel as i16 + noise).clamp(0, 255) as u8;
+            Rgba([shade(r), shade(g), shade(b), 255])
+        })
+    }
+
+    fn interiors_image(&self) -> RgbaImage {
+        ImageBuffer::from_fn(self.width * TILE_SIZE, self.height * TILE_SIZE, |x, y| {
+            let (tile_x, tile_y) = (x / TILE_SIZE, y / TILE_SIZE);
+            match self
+                .buildings
+                .iter()
+                .map(|(building, _)| building)
+                .find(|building| building.contains(tile_x, tile_y))
+            {
+                Some(building) if building.on_border(tile_x, tile_y) => Rgba(INTERIOR_WALL_COLOR),
+                Some(_) => Rgba(INTERIOR_FLOOR_COLOR),
+                None => Rgba([0, 0, 0, 0]),
+            }
+        })
+    }
+
+    /// Tileset of given name (and image), each tile having given property
+    fn tileset(&self, name: &str, properties: &[(&str, String)]) -> String {
+        let tiles = properties
+            .iter()
+            .enumerate()
+            .map(|(i, (key, value))| {
+                format!(
+                    " <tile id=\"{}\">\n  <properties>\n   <property name=\"{}\" value=\"{}\"/>\n  </properties>\n </tile>\n",
+                    i, key, value
+                )
+            })
+            .collect::<String>();
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<tileset version=\"1.10\" tiledversion=\"1.10.0\" name=\"{name}\" tilewidth=\"{tile}\" tileheight=\"{tile}\" tilecount=\"{count}\" columns=\"{count}\">\n <image source=\"{name}.png\" width=\"{image_width}\" height=\"{tile}\"/>\n{tiles}</tileset>\n",
+            name = name,
+            tile = TILE_SIZE,
+            count = properties.len(),
+            image_width = properties.len() as u32 * TILE_SIZE,
+            tiles = tiles,
+        )
+    }
+
+    fn csv(&self, gid: impl Fn(usize) -> usize) -> String {
+        (0..self.height as usize)
+            .map(|y| {
+                (0..self.width as usize)
+                    .map(|x| gid(y * self.width as usize + x).to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            })
+            .collect::<Vec<String>>()
+            .join(",\n")
+    }
+
+    /// Objects with their optional name and floors count
+    fn objects(
+        &self,
+        objects: &[(Option<&str>, &TileRect, Option<u8>)],
+        next_id: &mut u32,
+    ) -> String {
+        objects
+            .iter()
+            .map(|(name, rect, floors)| {
+                let id = *next_id;
+                *next_id += 1;
+                let name = name
+                    .map(|name| format!(" name=\"{}\"", name))
+                    .unwrap_or_default();
+                let position = format!(
+                    "  <object id=\"{}\"{} x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"",
+                    id,
+                    name,
+                    rect.x * TILE_SIZE,
+                    rect.y * TILE_SIZE,
+                    rect.w * TILE_SIZE,
+                    rect.h * TILE_SIZE
+                );
+                match floors {
+                    Some(floors) => format!(
+                        "{}>\n   <properties>\n    <property name=\"FLOORS\" type=\"int\" value=\"{}\"/>\n   </properties>\n  </object>\n",
+                        position, floors
+                    ),
+                    None => format!("{}/>\n", position),
+                }
+            })
+            .collect()
+    }
+
+    fn tmx(&self) -> String {
+        let terrain = self.csv(|i| Self::palette_index(&self.tiles[i]) + 1);
+        // Openings tileset comes after terrain one
+        let openings_first_gid = PALETTE.len() + 1;
+        let openings = self.csv(|i| {
+            self.openings[i]
+                .map(|opening| Self::opening_index(&opening) + openings_first_gid)
+                .unwrap_or(0)
+        });
+        let decor = self.csv(|_| 0);
+        let mut next_id = 1;
+        let interiors = self.objects(
+            &self
+                .buildings
+                .iter()
+                .map(|(building, floors)| (None, building, Some(*floors)))
+                .collect::<Vec<_>>(),
+            &mut next_id,
+        );
+        let flags = self.objects(
+            &self
+                .flags
+                .iter()
+                .map(|(name, rect)| (Some(name.as_str()), rect, None))
+                .collect::<Vec<_>>(),
+            &mut next_id,
+        );
+        let spawn_zones = self.objects(
+            &self
+                .spawn_zones
+                .iter()
+                .map(|(name, rect)| (Some(name.as_str()), rect, None))
+                .collect::<Vec<_>>(),
+            &mut next_id,
+        );
+        let (pixel_width, pixel_height) = (self.width * TILE_SIZE, self.height * TILE_SIZE);
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" tiledversion="1.10.0" orientation="orthogonal" renderorder="right-down" width="{width}" height="{height}" tilewidth="{tile}" tileheight="{tile}" infinite="0" nextlayerid="9" nextobjectid="{next_id}">
+ <tileset firstgid="1" source="terrain.tsx"/>
+ <tileset firstgid="{openings_first_gid}" source="openings.tsx"/>
+ <imagelayer id="1" name="background_image">
+  <image source="{name}.png" width="{pixel_width}" height="{pixel_height}"/>
+ </imagelayer>
+ <imagelayer id="2" name="interiors_image">
+  <image source="interiors.png" width="{pixel_width}" height="{pixel_height}"/>
+ </imagelayer>
+ <layer id="3" name="terrain" width="{width}" height="{height}" visible="0">
+  <data encoding="csv">
+{terrain}
+</data>
+ </layer>
+ <layer id="8" name="openings" width="{width}" height="{height}" visible="0">
+  <data encoding="csv">
+{openings}
+</data>
+ </layer>
+ <layer id="4" name="decor" width="{width}" height="{height}">
+  <data encoding="csv">
+{decor}
+</data>
+ </layer>
+ <objectgroup id="5" name="interiors_zones" visible="0">
+{interiors} </objectgroup>
+ <objectgroup id="6" name="flags">
+{flags} </objectgroup>
+ <objectgroup id="7" name="spawn_zones">
+{spawn_zones} </objectgroup>
+</map>
+"#,
+            width = self.width,
+            height = self.height,
+            tile = TILE_SIZE,
+            next_id = next_id,
+            name = self.name,
+            pixel_width = pixel_width,
+            pixel_height = pixel_height,
+            openings_first_gid = openings_first_gid,
+            terrain = terrain,
+            openings = openings,
+            decor = decor,
+            interiors = interiors,
+            flags = flags,
+            spawn_zones = spawn_zones,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use battle_core::map::reader::MapReader;
+
+    use super::*;
+    use crate::validate::validate_map;
+
+    #[test]
+    fn same_seed_generate_same_map() {
+        let map = MapGenerator::new("Generated", 42, MIN_SIZE, MIN_SIZE).generate();
+        let same = MapGenerator::new("Generated", 42, MIN_SIZE, MIN_SIZE).generate();
+
+        assert_eq!(map.tmx(), same.tmx());
+    }
+
+    #[test]
+    fn generated_maps_are_valid() {
+        let name = "Generated";
+
+        for seed in [0, 1, 42] {
+            let resources =
+                std::env::temp_dir().join(format!("oc_generator_{}_{}", std::process::id(), seed));
+            let map = MapGenerator::new(name, seed, MIN_SIZE, MIN_SIZE + 20).generate();
+            map.write(&resources.join("maps").join(name)).unwrap();
+
+            let errors = validate_map(name, &resources)
+                .into_iter()
+                .map(|error| error.to_string())
+                .collect::<Vec<_>>();
+            std::fs::remove_dir_all(&resources).unwrap();
+
+            assert!(errors.is_empty(), "seed {} : {:?}", seed, errors);
+        }
+    }
+
+    #[test]
+    fn generated_buildings_have_openings_and_floors() {
+        let name = "Generated";
+        let resources =
+            std::env::temp_dir().join(format!("oc_generator_openings_{}", std::process::id()));
+        let generated = MapGenerator::new(name, 42, MIN_SIZE, MIN_SIZE).generate();
+        generated.write(&resources.join("maps").join(name)).unwrap();
+        let map = MapReader::new(name, &resources).unwrap().build().unwrap();
+        std::fs::remove_dir_all(&resources).unwrap();
+
+        let openings = |opening: Opening| {
+            map.terrain_tiles()
+                .iter()
+                .filter(|tile| tile.opening() == Some(&opening))
+                .count()
+        };
+        assert!(!generated.buildings.is_empty());
+        assert_eq!(openings(Opening::Door), generated.buildings.len());
+        assert!(openings(Opening::Window) > 0);
+        assert_eq!(map.interiors().len(), generated.buildings.len());
+        for (interior, (_, floors)) in map.interiors().iter().zip(&generated.buildings) {
+            assert_eq!(interior.floors(), *floors);
+        }
+    }
+}
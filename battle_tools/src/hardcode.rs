@@ -5,7 +5,8 @@ use battle_core::{
     deployment::{Deployment, SoldierDeployment, VehicleDeployment},
     entity::vehicle::{OnBoardPlace, VehicleType},
     game::{
-        weapon::{Magazine, Weapon},
+        traits::SoldierTraits,
+        weapon::{Magazine, MagazineType, Weapon, WeaponType},
         Side,
     },
     order::Order,
@@ -34,16 +35,18 @@ pub fn demo1_deployment() -> Deployment {
                 Side::A,
                 WorldPoint::from(Vec2::new(x as f32 * 10. + 20.0, y as f32 * 10. + 100.)),
                 squad,
-                Some(Weapon::MosinNagantM1924(
+                Some(Weapon::new(
+                    WeaponType::new("MosinNagantM1924"),
                     false,
-                    Some(Magazine::full(Magazine::MosinNagant(0))),
+                    Some(Magazine::full(MagazineType::new("MosinNagant"))),
                 )),
                 vec![
-                    Magazine::full(Magazine::MosinNagant(0)),
-                    Magazine::full(Magazine::MosinNagant(0)),
+                    Magazine::full(MagazineType::new("MosinNagant")),
+                    Magazine::full(MagazineType::new("MosinNagant")),
                 ],
                 Order::Idle,
                 Behavior::Idle(Body::StandUp),
+                SoldierTraits::default(),
             );
             soldiers.push(soldier);
             soldiers_index += 1;
@@ -62,16 +65,18 @@ pub fn demo1_deployment() -> Deployment {
                 Side::B,
                 WorldPoint::from(Vec2::new(x as f32 * 10. + 550., y as f32 * 10. + 250.)),
                 squad,
-                Some(Weapon::MosinNagantM1924(
+                Some(Weapon::new(
+                    WeaponType::new("MosinNagantM1924"),
                     false,
-                    Some(Magazine::full(Magazine::MosinNagant(0))),
+                    Some(Magazine::full(MagazineType::new("MosinNagant"))),
                 )),
                 vec![
-                    Magazine::full(Magazine::MosinNagant(0)),
-                    Magazine::full(Magazine::MosinNagant(0)),
+                    Magazine::full(MagazineType::new("MosinNagant")),
+                    Magazine::full(MagazineType::new("MosinNagant")),
                 ],
                 Order::Idle,
                 Behavior::Idle(Body::StandUp),
+                SoldierTraits::default(),
             );
             soldiers.push(soldier);
             soldiers_index += 1;
@@ -97,6 +102,7 @@ pub fn demo1_deployment() -> Deployment {
         vec![],
         Order::Idle,
         Behavior::Idle(Body::StandUp),
+        SoldierTraits::default(),
     );
     soldiers.push(tank_driver);
     soldiers_index += 1;
@@ -114,6 +120,7 @@ pub fn demo1_deployment() -> Deployment {
         vec![],
         Order::Idle,
         Behavior::Idle(Body::StandUp),
+        SoldierTraits::default(),
     );
     soldiers.push(tank_gunner);
     boards.insert(
@@ -1 +1,6 @@
+pub mod generator;
 pub mod hardcode;
+pub mod validate;
+
+// Size factor of "__HD" images compared to their base image (see gui zoom)
+pub const HD_FACTOR: u32 = 3;
@@ -0,0 +1,358 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use battle_core::{
+    config::ServerConfig,
+    map::{
+        reader::{MapReader, MapReaderError},
+        Map,
+    },
+    physics::path::{find_path, PathMode},
+    types::{GridPoint, WorldPoint},
+    utils::WorldPolygon,
+};
+
+use crate::HD_FACTOR;
+
+const HD_SUFFIX: &str = "__HD";
+// Drawing inaccuracy (pixels) accepted on zones and flags bounds and overlaps
+const TOLERANCE: f32 = 1.;
+// Known errors of bundled maps, by map name (see `MapValidationError::key`)
+const ALLOWLIST_FILE: &str = "maps/validation_allowlist.toml";
+
+#[derive(Debug)]
+pub enum MapValidationError {
+    Reader(MapReaderError),
+    SpawnZoneOutsideMap(String),
+    SpawnZonesOverlap(String, String),
+    SpawnZoneNotWalkable(String),
+    FlagOutsideMap(String),
+    DuplicatedFlagName(String),
+    UnreachableFlag(String, String),
+    MissingImage(PathBuf),
+    InvalidImage(PathBuf, String),
+    HdImageSize(PathBuf, (u32, u32), (u32, u32)),
+}
+
+impl Display for MapValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MapValidationError::Reader(error) => f.write_str(&format!("{}", error)),
+            MapValidationError::SpawnZoneOutsideMap(name) => {
+                f.write_str(&format!("Spawn zone {} is not inside the map", name))
+            }
+            MapValidationError::SpawnZonesOverlap(a, b) => {
+                f.write_str(&format!("Spawn zones {} and {} overlap", a, b))
+            }
+            MapValidationError::SpawnZoneNotWalkable(name) => {
+                f.write_str(&format!("Spawn zone {} contains no walkable tile", name))
+            }
+            MapValidationError::FlagOutsideMap(name) => {
+                f.write_str(&format!("Flag '{}' is not inside the map", name))
+            }
+            MapValidationError::DuplicatedFlagName(name) => {
+                f.write_str(&format!("Flag name '{}' is used by several flags", name))
+            }
+            MapValidationError::UnreachableFlag(flag, spawn_zone) => f.write_str(&format!(
+                "Flag '{}' is not reachable from spawn zone {}",
+                flag, spawn_zone
+            )),
+            MapValidationError::MissingImage(path) => {
+                f.write_str(&format!("Image {} is missing", path.display()))
+            }
+            MapValidationError::InvalidImage(path, error) => f.write_str(&format!(
+                "Image {} can't be read : {}",
+                path.display(),
+                error
+            )),
+            MapValidationError::HdImageSize(path, expected, found) => f.write_str(&format!(
+                "HD image {} should be {}x{} but is {}x{}",
+                path.display(),
+                expected.0,
+                expected.1,
+                found.0,
+                found.1
+            )),
+        }
+    }
+}
+
+impl MapValidationError {
+    /// Error kind and subject, as written in allowlist file
+    pub fn key(&self) -> String {
+        let file_name = |path: &PathBuf| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+
+        match self {
+            MapValidationError::Reader(_) => "Reader".to_string(),
+            MapValidationError::SpawnZoneOutsideMap(name) => {
+                format!("SpawnZoneOutsideMap {}", name)
+            }
+            MapValidationError::SpawnZonesOverlap(a, b) => format!("SpawnZonesOverlap {} {}", a, b),
+            MapValidationError::SpawnZoneNotWalkable(name) => {
+                format!("SpawnZoneNotWalkable {}", name)
+            }
+            MapValidationError::FlagOutsideMap(name) => format!("FlagOutsideMap {}", name),
+            MapValidationError::DuplicatedFlagName(name) => format!("DuplicatedFlagName {}", name),
+            MapValidationError::UnreachableFlag(flag, spawn_zone) => {
+                format!("UnreachableFlag {} {}", flag, spawn_zone)
+            }
+            MapValidationError::MissingImage(path) => format!("MissingImage {}", file_name(path)),
+            MapValidationError::InvalidImage(path, _) => {
+                format!("InvalidImage {}", file_name(path))
+            }
+            MapValidationError::HdImageSize(path, _, _) => {
+                format!("HdImageSize {}", file_name(path))
+            }
+        }
+    }
+}
+
+impl From<MapReaderError> for MapValidationError {
+    fn from(error: MapReaderError) -> Self {
+        Self::Reader(error)
+    }
+}
+
+/// Check given map and return all found errors
+pub fn validate_map(name: &str, resources: &Path) -> Vec<MapValidationError> {
+    let reader = match MapReader::new(name, resources) {
+        Ok(reader) => reader,
+        Err(error) => return vec![error.into()],
+    };
+
+    let layer_errors = reader.check_layers();
+    if !layer_errors.is_empty() {
+        return layer_errors.into_iter().map(|error| error.into()).collect();
+    }
+
+    let map = match reader.build() {
+        Ok(map) => map,
+        Err(error) => return vec![error.into()],
+    };
+
+    let mut errors = vec![];
+    errors.extend(spawn_zones_errors(&map));
+    errors.extend(flags_errors(&map));
+    errors.extend(reachability_errors(&map));
+    errors.extend(hd_images_errors(&map, resources));
+    errors
+}
+
+/// Allowed errors keys of given map, from the allowlist file of resources (if any)
+pub fn allowed_errors(name: &str, resources: &Path) -> Result<Vec<String>, String> {
+    let path = resources.join(ALLOWLIST_FILE);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|error| format!("Unable to read {} : {}", path.display(), error))?;
+    let mut allowlist: HashMap<String, Vec<String>> = toml::from_str(&content)
+        .map_err(|error| format!("Unable to parse {} : {}", path.display(), error))?;
+    Ok(allowlist.remove(name).unwrap_or_default())
+}
+
+fn in_map(map: &Map, point: &WorldPoint) -> bool {
+    point.x >= -TOLERANCE
+        && point.y >= -TOLERANCE
+        && point.x <= map.visual_width() as f32 + TOLERANCE
+        && point.y <= map.visual_height() as f32 + TOLERANCE
+}
+
+/// Walkable tile inside given polygon, the nearest of its center
+fn walkable_point(map: &Map, polygon: &WorldPolygon) -> Option<GridPoint> {
+    let rect = polygon.bounding_rect();
+    let center = polygon.center();
+    let start = map.grid_point_from_world_point(&WorldPoint::new(rect.x, rect.y));
+    let end = map.grid_point_from_world_point(&WorldPoint::new(rect.x + rect.w, rect.y + rect.h));
+
+    (start.y..=end.y)
+        .flat_map(|y| (start.x..=end.x).map(move |x| GridPoint::new(x, y)))
+        .filter(|point| {
+            polygon.contains(&map.world_point_from_grid_point(*point))
+                && map
+                    .terrain_tile_at(point)
+                    .is_some_and(|tile| map.can_enter(point, tile, &PathMode::Walk))
+        })
+        .min_by(|a, b| {
+            let distance = |point: &GridPoint| {
+                map.world_point_from_grid_point(*point)
+                    .to_vec2()
+                    .distance(center.to_vec2())
+            };
+            distance(a).total_cmp(&distance(b))
+        })
+}
+
+fn spawn_zones_errors(map: &Map) -> Vec<MapValidationError> {
+    let mut errors = vec![];
+    let spawn_zones = map.spawn_zones();
+
+    for (i, spawn_zone) in spawn_zones.iter().enumerate() {
+        if !spawn_zone
+            .polygon()
+            .points()
+            .iter()
+            .all(|point| in_map(map, point))
+        {
+            errors.push(MapValidationError::SpawnZoneOutsideMap(format!(
+                "{:?}",
+                spawn_zone.name()
+            )))
+        }
+
+        for other in &spawn_zones[i + 1..] {
+            if spawn_zone.polygon().overlap_thickness(other.polygon()) > TOLERANCE {
+                errors.push(MapValidationError::SpawnZonesOverlap(
+                    format!("{:?}", spawn_zone.name()),
+                    format!("{:?}", other.name()),
+                ))
+            }
+        }
+    }
+
+    errors
+}
+
+fn flags_errors(map: &Map) -> Vec<MapValidationError> {
+    let mut errors = vec![];
+    let flags = map.flags();
+
+    for (i, flag) in flags.iter().enumerate() {
+        if !flag
+            .polygon()
+            .points()
+            .iter()
+            .all(|point| in_map(map, point))
+        {
+            errors.push(MapValidationError::FlagOutsideMap(flag.name().0.clone()))
+        }
+
+        // Report duplicated name once, at its first occurrence
+        if !flags[..i].iter().any(|other| other.name() == flag.name())
+            && flags[i + 1..]
+                .iter()
+                .any(|other| other.name() == flag.name())
+        {
+            errors.push(MapValidationError::DuplicatedFlagName(
+                flag.name().0.clone(),
+            ))
+        }
+    }
+
+    errors
+}
+
+fn reachability_errors(map: &Map) -> Vec<MapValidationError> {
+    let config = ServerConfig::default();
+    let mut errors = vec![];
+
+    for spawn_zone in map.spawn_zones() {
+        // Zone center can be outside of a concave zone or on a blocked tile
+        let from = match walkable_point(map, spawn_zone.polygon()) {
+            Some(point) => point,
+            None => {
+                errors.push(MapValidationError::SpawnZoneNotWalkable(format!(
+                    "{:?}",
+                    spawn_zone.name()
+                )));
+                continue;
+            }
+        };
+        for flag in map.flags() {
+            let to = map.grid_point_from_world_point(&flag.position());
+            if from == to {
+                continue;
+            }
+
+            if find_path(&config, map, &from, &to, true, &PathMode::Walk, &None).is_none() {
+                errors.push(MapValidationError::UnreachableFlag(
+                    flag.name().0.clone(),
+                    format!("{:?}", spawn_zone.name()),
+                ))
+            }
+        }
+    }
+
+    errors
+}
+
+fn hd_images_errors(map: &Map, resources: &Path) -> Vec<MapValidationError> {
+    let mut image_paths = vec![
+        map.background_image_path().clone(),
+        map.interiors_image_path().clone(),
+    ];
+    image_paths.extend(map.decor().image_paths().clone());
+
+    image_paths
+        .iter()
+        .filter_map(|image_path| {
+            hd_image_error(&resources.join(image_path.strip_prefix("/").unwrap_or(image_path)))
+        })
+        .collect()
+}
+
+fn hd_image_error(image_path: &Path) -> Option<MapValidationError> {
+    let hd_image_path = image_path.with_file_name(format!(
+        "{}{}.png",
+        image_path.file_stem()?.to_string_lossy(),
+        HD_SUFFIX
+    ));
+    let (width, height) = match image_dimensions(image_path) {
+        Ok(dimensions) => dimensions,
+        Err(error) => return Some(error),
+    };
+    let (hd_width, hd_height) = match image_dimensions(&hd_image_path) {
+        Ok(dimensions) => dimensions,
+        Err(error) => return Some(error),
+    };
+
+    let expected = (width * HD_FACTOR, height * HD_FACTOR);
+    if (hd_width, hd_height) != expected {
+        return Some(MapValidationError::HdImageSize(
+            hd_image_path,
+            expected,
+            (hd_width, hd_height),
+        ));
+    }
+
+    None
+}
+
+fn image_dimensions(path: &Path) -> Result<(u32, u32), MapValidationError> {
+    if !path.exists() {
+        return Err(MapValidationError::MissingImage(path.to_path_buf()));
+    }
+
+    image::image_dimensions(path)
+        .map_err(|error| MapValidationError::InvalidImage(path.to_path_buf(), error.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bundled_maps_are_valid() {
+        let resources = PathBuf::from("../resources");
+
+        for name in ["Demo1", "Demo2", "map1", "map2"] {
+            let allowed = allowed_errors(name, &resources).unwrap();
+            let errors = validate_map(name, &resources)
+                .into_iter()
+                .filter(|error| !allowed.contains(&error.key()))
+                .map(|error| error.to_string())
+                .collect::<Vec<_>>();
+
+            assert!(errors.is_empty(), "{} : {:?}", name, errors);
+        }
+    }
+}
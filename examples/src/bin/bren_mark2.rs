@@ -4,7 +4,7 @@ use battle_core::{
     config::TARGET_CYCLE_DURATION_US,
     deployment::{Deployment, SquadTypes},
     game::{
-        weapon::{Magazine, Weapon},
+        weapon::{Magazine, MagazineType, Weapon, WeaponType},
         Side,
     },
     types::{SquadUuid, WorldPoint},
@@ -22,15 +22,17 @@ fn main() -> Result<(), RunnerError> {
     let soldiers = ManualSoldiersGenerator::default()
         .side(Side::A)
         .squad(SquadUuid(0))
-        .main_weapon(Some(Weapon::BrenMark2(Some(Magazine::full(
-            Magazine::BrenCurved30(0),
-        )))))
+        .main_weapon(Some(Weapon::new(
+            WeaponType::new("BrenMark2"),
+            true,
+            Some(Magazine::full(MagazineType::new("BrenCurved30"))),
+        )))
         .magazines(vec![
-            Magazine::full(Magazine::BrenCurved30(0)),
-            Magazine::full(Magazine::BrenCurved30(0)),
-            Magazine::full(Magazine::BrenCurved30(0)),
-            Magazine::full(Magazine::BrenCurved30(0)),
-            Magazine::full(Magazine::BrenCurved30(0)),
+            Magazine::full(MagazineType::new("BrenCurved30")),
+            Magazine::full(MagazineType::new("BrenCurved30")),
+            Magazine::full(MagazineType::new("BrenCurved30")),
+            Magazine::full(MagazineType::new("BrenCurved30")),
+            Magazine::full(MagazineType::new("BrenCurved30")),
         ])
         .world_point(WorldPoint::new(25., 25.))
         .place(1, |p: WorldPoint| p)
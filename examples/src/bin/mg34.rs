@@ -4,7 +4,7 @@ use battle_core::{
     config::TARGET_CYCLE_DURATION_US,
     deployment::{Deployment, SquadTypes},
     game::{
-        weapon::{Magazine, Weapon},
+        weapon::{Magazine, MagazineType, Weapon, WeaponType},
         Side,
     },
     types::{SquadUuid, WorldPoint},
@@ -23,12 +23,14 @@ fn main() -> Result<(), RunnerError> {
         .side(Side::A)
         .squad(SquadUuid(0))
         .type_(SoldierType::Mg34)
-        .main_weapon(Some(Weapon::Mg34(Some(Magazine::full(
-            Magazine::Patronengurtx792x57s250(0),
-        )))))
+        .main_weapon(Some(Weapon::new(
+            WeaponType::new("Mg34"),
+            true,
+            Some(Magazine::full(MagazineType::new("Patronengurtx792x57s250"))),
+        )))
         .magazines(vec![
-            Magazine::full(Magazine::Patronengurtx792x57s250(0)),
-            Magazine::full(Magazine::Patronengurtx792x57s250(0)),
+            Magazine::full(MagazineType::new("Patronengurtx792x57s250")),
+            Magazine::full(MagazineType::new("Patronengurtx792x57s250")),
         ])
         .world_point(WorldPoint::new(25., 25.))
         .place(1, |p: WorldPoint| p)
@@ -2,6 +2,7 @@ use battle_core::{
     behavior::{Behavior, Body},
     deployment::SoldierDeployment,
     game::{
+        traits::SoldierTraits,
         weapon::{Magazine, Weapon},
         Side,
     },
@@ -18,6 +19,7 @@ pub struct ManualSoldiersGenerator {
     main_weapon: Option<Weapon>,
     magazines: Vec<Magazine>,
     world_point: WorldPoint,
+    traits: SoldierTraits,
 }
 
 impl ManualSoldiersGenerator {
@@ -51,6 +53,11 @@ impl ManualSoldiersGenerator {
         self
     }
 
+    pub fn traits(mut self, value: SoldierTraits) -> Self {
+        self.traits = value;
+        self
+    }
+
     pub fn place<F>(mut self, count: usize, placer: F) -> Self
     where
         F: FnOnce(WorldPoint) -> WorldPoint + Copy,
@@ -67,6 +74,7 @@ impl ManualSoldiersGenerator {
                 self.magazines.clone(),
                 Order::Idle,
                 Behavior::Idle(Body::StandUp),
+                self.traits.clone(),
             );
             self.soldiers.push(soldier);
         }
@@ -89,6 +97,7 @@ impl Default for ManualSoldiersGenerator {
             main_weapon: None,
             magazines: vec![],
             world_point: WorldPoint::new(0., 0.),
+            traits: SoldierTraits::default(),
         }
     }
 }
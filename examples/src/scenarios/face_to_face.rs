@@ -7,7 +7,7 @@ use crate::{
 use battle_core::{
     deployment::{Deployment, SquadTypes},
     game::{
-        weapon::{Magazine, Weapon},
+        weapon::{Magazine, MagazineType, Weapon, WeaponType},
         Side,
     },
     map::{terrain::TileType, Map},
@@ -16,30 +16,38 @@ use battle_core::{
 use oc_core::game::squad::SquadType;
 
 fn mosin_nagant() -> Weapon {
-    Weapon::MosinNagantM1924(true, Some(Magazine::full(Magazine::MosinNagant(0))))
+    Weapon::new(
+        WeaponType::new("MosinNagantM1924"),
+        true,
+        Some(Magazine::full(MagazineType::new("MosinNagant"))),
+    )
 }
 
 fn mosin_nagant_magazines() -> Vec<Magazine> {
     vec![
-        Magazine::full(Magazine::MosinNagant(0)),
-        Magazine::full(Magazine::MosinNagant(0)),
-        Magazine::full(Magazine::MosinNagant(0)),
-        Magazine::full(Magazine::MosinNagant(0)),
+        Magazine::full(MagazineType::new("MosinNagant")),
+        Magazine::full(MagazineType::new("MosinNagant")),
+        Magazine::full(MagazineType::new("MosinNagant")),
+        Magazine::full(MagazineType::new("MosinNagant")),
     ]
 }
 
 fn mauser() -> Weapon {
-    Weapon::MauserG41(true, Some(Magazine::full(Magazine::Mauser(0))))
+    Weapon::new(
+        WeaponType::new("MauserG41"),
+        true,
+        Some(Magazine::full(MagazineType::new("Mauser"))),
+    )
 }
 
 fn mauser_magazines() -> Vec<Magazine> {
     vec![
-        Magazine::full(Magazine::Mauser(0)),
-        Magazine::full(Magazine::Mauser(0)),
-        Magazine::full(Magazine::Mauser(0)),
-        Magazine::full(Magazine::Mauser(0)),
-        Magazine::full(Magazine::Mauser(0)),
-        Magazine::full(Magazine::Mauser(0)),
+        Magazine::full(MagazineType::new("Mauser")),
+        Magazine::full(MagazineType::new("Mauser")),
+        Magazine::full(MagazineType::new("Mauser")),
+        Magazine::full(MagazineType::new("Mauser")),
+        Magazine::full(MagazineType::new("Mauser")),
+        Magazine::full(MagazineType::new("Mauser")),
     ]
 }
 
@@ -3,12 +3,14 @@
 use std::path::Path;
 
 use anyhow::{Context, Result};
+use battle_core::config::rules::RuleSet;
 use eframe::{
     egui::{self, RichText, TextStyle},
     epaint::{Color32, Vec2},
 };
 
 use run::BattleLauncher;
+use strum::IntoEnumIterator;
 
 mod run;
 
@@ -31,6 +33,8 @@ fn main() -> Result<(), eframe::Error> {
 struct Launcher {
     error: Option<String>,
     map1_preview: Option<egui::TextureHandle>,
+    // None to use deployment one
+    rule_set: Option<RuleSet>,
 }
 
 impl eframe::App for Launcher {
@@ -68,6 +72,15 @@ impl eframe::App for Launcher {
 
             ui.separator();
 
+            ui.horizontal(|ui| {
+                ui.label("Rules : ");
+                ui.selectable_value(&mut self.rule_set, None, "From deployment");
+                for rule_set in RuleSet::iter() {
+                    ui.selectable_value(&mut self.rule_set, Some(rule_set), rule_set.to_string());
+                }
+            });
+            ui.separator();
+
             if let Some(error) = &self.error {
                 ui.label(RichText::new(error).color(Color32::RED));
             }
@@ -128,10 +141,13 @@ impl Launcher {
         side_a_controls: Vec<&str>,
         side_b_controls: Vec<&str>,
     ) -> Result<()> {
-        BattleLauncher::new(map_name, &Path::new(deployment).to_path_buf(), "a")?
+        let mut launcher = BattleLauncher::new(map_name, Path::new(deployment), "a")?
             .side_a_controls(side_a_controls.into_iter().map(String::from).collect())
-            .side_b_controls(side_b_controls.into_iter().map(String::from).collect())
-            .launch()?;
+            .side_b_controls(side_b_controls.into_iter().map(String::from).collect());
+        if let Some(rule_set) = self.rule_set {
+            launcher = launcher.rule_set(rule_set.to_string());
+        }
+        launcher.launch()?;
         Ok(())
     }
 }
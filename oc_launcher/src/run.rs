@@ -1,4 +1,8 @@
-use std::{env::current_exe, path::PathBuf, process::Command};
+use std::{
+    env::current_exe,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 use anyhow::{Context, Result};
 
@@ -12,10 +16,12 @@ pub struct BattleLauncher {
     side: String,
     side_a_controls: Vec<String>,
     side_b_controls: Vec<String>,
+    rule_set: Option<String>,
 }
 
+#[allow(dead_code)]
 impl BattleLauncher {
-    pub fn new(map_name: &str, deployment: &PathBuf, side: &str) -> Result<Self> {
+    pub fn new(map_name: &str, deployment: &Path, side: &str) -> Result<Self> {
         let executable_path = current_exe().context("Retrieve current executable path")?;
         let executable_path = executable_path
             .parent()
@@ -23,13 +29,14 @@ impl BattleLauncher {
         Ok(Self {
             executable_path: executable_path.to_path_buf(),
             map_name: map_name.to_string(),
-            deployment: deployment.clone(),
+            deployment: deployment.to_path_buf(),
             embedded_server: true,
             server_rep_address: "tcp://0.0.0.0:4255".to_string(),
             server_bind_address: "tcp://0.0.0.0:4256".to_string(),
             side: side.to_string(),
             side_a_controls: vec![],
             side_b_controls: vec![],
+            rule_set: None,
         })
     }
 
@@ -78,6 +85,11 @@ impl BattleLauncher {
         self
     }
 
+    pub fn rule_set(mut self, value: String) -> Self {
+        self.rule_set = Some(value);
+        self
+    }
+
     pub fn launch(&self) -> Result<()> {
         let embedded_server = if self.embedded_server {
             vec!["--embedded-server"]
@@ -97,6 +109,11 @@ impl BattleLauncher {
             .iter()
             .map(|c| format!("--side-b-control={}", c))
             .collect::<Vec<String>>();
+        let rule_set = self
+            .rule_set
+            .iter()
+            .map(|r| format!("--rules={}", r))
+            .collect::<Vec<String>>();
 
         let mut command =
             Command::new(self.executable_path.join(self.battle_gui_executable_name()));
@@ -109,6 +126,7 @@ impl BattleLauncher {
             .arg(side)
             .args(side_a_control)
             .args(side_b_control)
+            .args(rule_set)
             .arg("--init-sync");
 
         let command_line = format!("{:?}", command);